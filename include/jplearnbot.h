@@ -0,0 +1,76 @@
+/*
+ * C bindings of jplearnbot for looking up words and generating questions.
+ * Build the library with
+ *   cargo rustc --lib --crate-type cdylib --no-default-features --features ffi
+ *
+ * Handles are opaque and freed with their _free function. Every string
+ * returned is JSON owned by the caller and freed with jp_string_free.
+ * Functions return NULL (or 0) on NULL or invalid arguments.
+ */
+
+#ifndef JPLEARNBOT_H
+#define JPLEARNBOT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Modes of jp_questions_new, named as what is shown to what is picked. */
+#define JP_MODE_ENG_TO_HIR 0
+#define JP_MODE_HIR_TO_ENG 1
+#define JP_MODE_HIR_TO_KAN 2
+#define JP_MODE_KAN_TO_HIR 3
+#define JP_MODE_KAN_TO_ENG 4
+#define JP_MODE_ENG_TO_KAN 5
+#define JP_MODE_MIXED 6
+/* The prompt is a reading to synthesize into speech for the player to hear. */
+#define JP_MODE_LISTEN_TO_HIR 7
+#define JP_MODE_LISTEN_TO_ENG 8
+/* Only words read in katakana, mostly loanwords, are asked about. */
+#define JP_MODE_ENG_TO_KAT 9
+#define JP_MODE_KAT_TO_ENG 10
+
+/* How closely the wrong options resemble the answer. */
+#define JP_DIFFICULTY_EASY 0
+#define JP_DIFFICULTY_NORMAL 1
+#define JP_DIFFICULTY_HARD 2
+
+/* Bits of the levels mask of jp_questions_new. 0 asks about every level. */
+#define JP_LEVEL_N1 (1u << 0)
+#define JP_LEVEL_N2 (1u << 1)
+#define JP_LEVEL_N3 (1u << 2)
+#define JP_LEVEL_N4 (1u << 3)
+#define JP_LEVEL_N5 (1u << 4)
+
+typedef struct JpDictionary JpDictionary;
+typedef struct JpQuestions JpQuestions;
+
+/* Creates a dictionary of the JSON lines in bytes. */
+JpDictionary *jp_dictionary_from_bytes(const uint8_t *bytes, size_t len);
+/* Frees a dictionary. Question streams created from it stay valid. */
+void jp_dictionary_free(JpDictionary *dictionary);
+size_t jp_dictionary_len(const JpDictionary *dictionary);
+
+/* Looks up a word by kanji, kana, romaji, or English. Returns a JSON array of
+ * at most limit entries, the closest matches first. */
+char *jp_lookup(const JpDictionary *dictionary, const char *word, size_t limit);
+
+/* Creates a stream of questions. The same arguments and seed ask the same
+ * questions. */
+JpQuestions *jp_questions_new(const JpDictionary *dictionary, uint32_t mode,
+                              uint32_t difficulty, uint32_t levels, uint64_t seed);
+size_t jp_questions_len(const JpQuestions *questions);
+/* Gets the next question as a JSON object, or NULL once there are none left. */
+char *jp_questions_next(JpQuestions *questions);
+void jp_questions_free(JpQuestions *questions);
+
+void jp_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
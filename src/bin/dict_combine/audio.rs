@@ -0,0 +1,67 @@
+use std::{cmp::Reverse, collections::HashSet, path::Path, process};
+
+use jplearnbot::{
+    Result,
+    dictionary::read_entries,
+    open_reader,
+    tts::{self, AudioCache},
+};
+
+/// Synthesizes the readings of the `count` most common words of the generated
+/// dictionary in `dir` into the bot's audio cache, so listening questions about
+/// them play without waiting on the text-to-speech service. Words of easier
+/// levels are taken as more common. Readings already cached are skipped.
+///
+/// The service and cache are configured by the same environment variables as
+/// the bot's, e.g. `VOICEVOX_URL` and `AUDIO_CACHE_PATH`.
+pub fn run(dir: &Path, count: usize) -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let Some(tts) = tts::backend_from_env() else {
+        eprintln!("No text-to-speech service configured, set VOICEVOX_URL or TTS_URL");
+        process::exit(-1);
+    };
+    let Some(cache) = AudioCache::from_env() else {
+        eprintln!("The audio cache is disabled, set AUDIO_CACHE_MB above 0");
+        process::exit(-1);
+    };
+
+    let mut entries = read_entries(open_reader(&dir.join("dictionary.jsonl"))?)?;
+    // Sorting is stable, so words of a level stay in the dictionary's order.
+    entries.retain(|entry| entry.is_annotated());
+    entries.sort_by_key(|entry| Reverse(entry.levels().last().copied()));
+
+    let mut seen = HashSet::new();
+    let readings: Vec<_> = entries
+        .iter()
+        .take(count)
+        .flat_map(|entry| entry.readings.iter().map(|r| r.text.as_str()))
+        .filter(|reading| seen.insert(*reading))
+        .collect();
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let (mut synthesized, mut failed) = (0, 0);
+    for (i, reading) in readings.iter().enumerate() {
+        if cache.get(&AudioCache::key(&tts.voice(), reading)).is_some() {
+            continue;
+        }
+
+        match runtime.block_on(tts::synthesize_cached(tts.as_ref(), Some(&cache), reading)) {
+            Ok(_) => synthesized += 1,
+            Err(e) => {
+                eprintln!("Failed to synthesize {reading}: {e}");
+                failed += 1;
+            }
+        }
+        if (i + 1) % 100 == 0 {
+            println!("{}/{} readings", i + 1, readings.len());
+        }
+    }
+
+    println!(
+        "Synthesized {synthesized} of {} readings, {failed} failed, the rest were cached",
+        readings.len()
+    );
+
+    Ok(())
+}
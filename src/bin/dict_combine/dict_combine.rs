@@ -1,113 +1,118 @@
 use std::{
-    cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::{File, OpenOptions},
     io::{BufWriter, Write},
     path::Path,
-    rc::Rc,
 };
 
-use jplearnbot::dictionary::{DictEntry, NLevel};
+use jplearnbot::{
+    Error, Result,
+    dictionary::{DictEntry, NLevel},
+};
 
 use crate::{
-    dictionary,
-    jlpt::{self, JlptEntry},
+    dictionary::{self, Format},
+    examples, jlpt, matching, pitch,
+    report::MatchReport,
 };
 
-pub fn run(dir: &Path, overwrite: bool) {
-    let entries = dict_entries(dir);
-    let mut writer = writer(dir, overwrite);
+/// Generates dictionary.jsonl in `dir` from the JMDict entries in `format` in
+/// `dir` that are on the JLPT lists in `dir`, annotated with their levels.
+///
+/// JMDict is streamed twice rather than held in memory: once to index the
+/// readings on the JLPT lists, and once to pick out the entries they match.
+/// Only those entries, a small part of JMDict, are held while their examples
+/// and pitch accents are attached.
+///
+/// If `report` is set, the entries of the JLPT lists that didn't match any
+/// JMDict entry are [written](MatchReport::write) to jlpt-report.tsv in `dir`.
+pub fn run(dir: &Path, overwrite: bool, format: Format, report: bool) -> Result<()> {
+    let jmdict = dir.join(format.file_name());
+    let (levels, matches) = jlpt_levels(dir, &jmdict, format)?;
+    println!("{}", matches.summary());
+    if report {
+        matches.write(dir)?;
+    }
+    let mut entries = annotated_entries(&jmdict, format, &levels)?;
+    examples::attach(dir, &mut entries)?;
+    pitch::attach(dir, &mut entries)?;
+    let mut writer = writer(dir, overwrite)?;
 
-    for entry in entries {
-        let mut str = serde_json::to_string(&*entry.borrow()).unwrap();
+    for entry in &entries {
+        let mut str = serde_json::to_string(entry)?;
         str.push('\n');
 
-        writer
-            .write_all(str.as_bytes())
-            .unwrap_or_else(|e| panic!("Failed to write to output:\n{e}"));
+        writer.write_all(str.as_bytes())?;
     }
 
-    writer.flush().expect("Failed to flush to output");
+    writer.flush()?;
+    println!("Wrote {} entries", entries.len());
+    Ok(())
 }
 
-fn dict_entries(dir: &Path) -> Vec<Rc<RefCell<DictEntry>>> {
-    let dict = annotated_dict(dir);
+const BLACKLIST_IDS: [u32; 1] = [1577100];
 
-    let mut set: HashMap<u32, _> = HashMap::new();
-    for entries in dict.into_values() {
-        for entry in entries {
-            if !entry.borrow().is_annotated() || BLACKLIST_IDS.contains(&entry.borrow().id) {
-                continue;
+/// Levels to annotate entries with. A key is the id of an entry and a value is
+/// a list of its readings, each with a level to annotate it with.
+type Levels = HashMap<u32, Vec<(String, NLevel)>>;
+
+/// Gets the levels to annotate the entries of the JMDict file `jmdict` in
+/// `format` with, from the JLPT lists in `dir`, [matching](matching::find)
+/// the entries of the lists to the entries of JMDict. Also gets how the
+/// entries of the lists matched.
+fn jlpt_levels(dir: &Path, jmdict: &Path, format: Format) -> Result<(Levels, MatchReport)> {
+    let mut pool = Vec::new();
+    for level in [NLevel::N1, NLevel::N2, NLevel::N3, NLevel::N4, NLevel::N5] {
+        pool.extend(jlpt::pool(dir, level)?);
+    }
+    let variants: Vec<_> = pool.iter().flat_map(matching::variants).collect();
+    let readings: HashSet<_> = variants.iter().map(|v| v.reading.as_str()).collect();
+    let index = dictionary::index(jmdict, format, &readings)?;
+
+    let mut levels: HashMap<u32, Vec<_>> = HashMap::new();
+    let mut report = MatchReport::default();
+    for entry in &pool {
+        match matching::find(entry, &index) {
+            Ok((reading, id)) => {
+                report.matched(entry);
+                levels.entry(id).or_default().push((reading, entry.level));
             }
-
-            entry.borrow_mut().trim();
-
-            let id = entry.borrow().id;
-            set.insert(id, entry);
+            Err(miss) => report.missed(entry, miss),
         }
     }
 
-    set.into_values().collect()
+    Ok((levels, report))
 }
 
-const BLACKLIST_IDS: [u32; 1] = [1577100];
-
-/// Gets a dictionary where a key is hiragana and a value
-/// is a list of [`DictEntry`]'s that contain that hiragana.
-fn annotated_dict(dir: &Path) -> HashMap<String, Vec<Rc<RefCell<DictEntry>>>> {
-    let dict = dictionary::dict(&dir.join("jmdict.jsonl"));
-
-    for pool in [NLevel::N1, NLevel::N2, NLevel::N3, NLevel::N4]
-        .into_iter()
-        .map(|lvl| jlpt::pool(dir, lvl))
-    {
-        for JlptEntry {
-            hiragana,
-            kanji,
-            level,
-        } in &pool
-        {
-            let Some(matches) = dict.get(hiragana) else {
-                continue;
-            };
-
-            // No definition ambiguity, mutate the exact match
-            if matches.len() == 1 {
-                matches[0].borrow_mut().add_level(hiragana, *level);
-                continue;
-            }
-
-            // Entry has kanji, mutate the only match with the same kanji, if it exists
-            if let Some(kanji) = kanji {
-                let matches: Vec<_> = matches
-                    .iter()
-                    .filter(|m| m.borrow().kanjis.iter().any(|k| k.text == *kanji))
-                    .collect();
-
-                if matches.len() == 1 {
-                    matches[0].borrow_mut().add_level(hiragana, *level);
-                }
-
-                continue;
-            }
-
-            // Entry has no kanji, mutate the only match without kanji too, if it exists
-            let matches: Vec<_> = matches
-                .iter()
-                .filter(|m| m.borrow().kanjis.is_empty())
-                .collect();
+/// Streams the entries of the JMDict file `jmdict` in `format`, keeping the
+/// ones annotated with `levels`, trimmed to their annotated kanjis and readings.
+fn annotated_entries(jmdict: &Path, format: Format, levels: &Levels) -> Result<Vec<DictEntry>> {
+    let mut annotated = Vec::new();
+    for entry in dictionary::entries(jmdict, format)? {
+        let mut entry = entry?;
+        let Some(levels) = levels.get(&entry.id) else {
+            continue;
+        };
+        if BLACKLIST_IDS.contains(&entry.id) {
+            continue;
+        }
 
-            if matches.len() == 1 {
-                matches[0].borrow_mut().add_level(hiragana, *level);
-            }
+        for (hiragana, level) in levels {
+            entry.add_level(hiragana, *level);
         }
+        if !entry.is_annotated() {
+            continue;
+        }
+
+        entry.trim();
+        annotated.push(entry);
     }
 
-    dict
+    Ok(annotated)
 }
 
 /// Open output file for writing
-fn writer(dir: &Path, overwrite: bool) -> BufWriter<File> {
+fn writer(dir: &Path, overwrite: bool) -> Result<BufWriter<File>> {
     let path = dir.join("dictionary.jsonl");
 
     let file = OpenOptions::new()
@@ -116,7 +121,10 @@ fn writer(dir: &Path, overwrite: bool) -> BufWriter<File> {
         .create(overwrite)
         .truncate(overwrite)
         .open(&path)
-        .unwrap_or_else(|e| panic!("Error writing output to {}:\n{e}", path.display()));
+        .map_err(|source| Error::File {
+            path: path.clone(),
+            source,
+        })?;
 
-    BufWriter::new(file)
+    Ok(BufWriter::new(file))
 }
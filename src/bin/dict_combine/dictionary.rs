@@ -1,43 +1,81 @@
-use std::{cell::RefCell, collections::HashMap, io::BufRead, path::Path, rc::Rc};
-
-use jplearnbot::{dictionary::DictEntry, open_reader};
-
-/// Gets a dictionary where a key is hiragana and a value
-/// is a list of [`DictEntry`]'s that contain that hiragana.
-/// [NLevel](`jplearnbot::dictionary::NLevel`) of kanjis and
-/// readings aren't annotated.
-pub fn dict(file: &Path) -> HashMap<String, Vec<Rc<RefCell<DictEntry>>>> {
-    let entries: Vec<_> = entries(file)
-        .into_iter()
-        .map(RefCell::new)
-        .map(Rc::new)
-        .collect();
-
-    let mut map: HashMap<String, Vec<_>> = HashMap::new();
-    for entry in entries {
-        for reading in &entry.borrow().readings {
-            map.entry(reading.text.clone())
-                .or_default()
-                .push(entry.clone());
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use clap::ValueEnum;
+use jplearnbot::{
+    Result,
+    dictionary::{self, DictEntry},
+    open_reader,
+};
+
+use crate::xml::JmdictXml;
+
+/// Formats JMDict is read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// The official XML, JMdict_e.xml
+    Xml,
+    /// A JSON Lines mirror, jmdict.jsonl
+    Jsonl,
+}
+
+impl Format {
+    /// Gets the name of the file JMDict is read from in this format.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Format::Xml => "JMdict_e.xml",
+            Format::Jsonl => "jmdict.jsonl",
         }
     }
-
-    map
 }
 
-/// Parses each line of a file into [`DictEntry`]'s
-fn entries(file: &Path) -> Vec<DictEntry> {
-    let reader = open_reader(file);
+/// The entries sharing a reading, each as its id and the text of its
+/// [kanjis](`DictEntry::kanjis`), which tell them apart.
+pub type Homophones = Vec<(u32, Vec<String>)>;
 
-    let mut entries = Vec::new();
-    for line in reader.lines() {
-        let line = line.unwrap_or_else(|e| panic!("Invalid byte read in dfile:\n{e}"));
+/// Indexes the entries of the JMDict file `file` in `format` by their readings,
+/// keeping only the ones in `readings` so the index stays small next to the file.
+pub fn index(
+    file: &Path,
+    format: Format,
+    readings: &HashSet<&str>,
+) -> Result<HashMap<String, Homophones>> {
+    let mut index: HashMap<String, Homophones> = HashMap::new();
+    for entry in entries(file, format)? {
+        let entry = entry?;
 
-        let entry =
-            serde_json::from_str(&line).unwrap_or_else(|e| panic!("JSON Parse error:\n{e}"));
+        for reading in &entry.readings {
+            if !readings.contains(reading.text.as_str()) {
+                continue;
+            }
 
-        entries.push(entry);
+            let kanjis = entry.kanjis.iter().map(|k| k.text.clone()).collect();
+            index
+                .entry(reading.text.clone())
+                .or_default()
+                .push((entry.id, kanjis));
+        }
     }
 
-    entries
+    Ok(index)
+}
+
+/// Parses a file in `format` into [`DictEntry`]'s, one at a time as they are
+/// iterated, with their senses numbered.
+pub fn entries(file: &Path, format: Format) -> Result<impl Iterator<Item = Result<DictEntry>>> {
+    let reader = open_reader(file)?;
+    let entries: Box<dyn Iterator<Item = Result<DictEntry>>> = match format {
+        Format::Xml => Box::new(JmdictXml::new(reader)),
+        Format::Jsonl => Box::new(dictionary::stream_entries(reader)),
+    };
+    let entries = entries.map(|entry| {
+        entry.map(|mut entry| {
+            entry.number_senses();
+            entry
+        })
+    });
+
+    Ok(entries)
 }
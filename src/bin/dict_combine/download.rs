@@ -0,0 +1,175 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use jplearnbot::{Error, Result};
+use reqwest::{Client, StatusCode, header::RANGE};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// Files to download, as listed in downloads.toml, e.g.
+///
+/// ```toml
+/// [[file]]
+/// name = "jmdict.jsonl"
+/// url = "https://example.com/jmdict.jsonl"
+/// sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// ```
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(rename = "file")]
+    files: Vec<Download>,
+}
+
+/// A file to download.
+#[derive(Debug, Deserialize)]
+struct Download {
+    /// Name the file is saved as, e.g. jmdict.jsonl.
+    name: String,
+    url: String,
+    /// The SHA-256 the file is expected to have, in hex.
+    sha256: String,
+}
+
+/// How often the progress of a download is printed, in bytes.
+const PROGRESS_INTERVAL: u64 = 16 * 1024 * 1024;
+
+/// Downloads the files listed in downloads.toml in `dir` into `dir`, e.g.
+/// jmdict.jsonl, checking each against the SHA-256 it is listed with. Files
+/// already there with that SHA-256 are skipped.
+///
+/// A file is downloaded to `<name>.part` and only renamed once it is checked,
+/// so an interrupted download is resumed where it stopped when run again.
+pub fn run(dir: &Path) -> Result<()> {
+    let path = dir.join("downloads.toml");
+    let text = fs::read_to_string(&path).map_err(|source| Error::File {
+        path: path.clone(),
+        source,
+    })?;
+    let manifest: Manifest = toml::from_str(&text)
+        .map_err(|e| Error::Other(format!("{}: {e}", path.display()).into()))?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let client = Client::new();
+    for download in &manifest.files {
+        let path = dir.join(&download.name);
+        if path.exists() && sha256_of(&path)? == download.sha256.to_lowercase() {
+            println!("{} is up to date", download.name);
+            continue;
+        }
+
+        runtime.block_on(fetch(&client, download, &path))?;
+    }
+
+    Ok(())
+}
+
+/// Downloads `download` to `path` through `<path>.part`, continuing the part
+/// already downloaded if the server allows it, then checks its SHA-256.
+///
+/// # Errors
+/// Fails if the download fails or the file doesn't have the SHA-256 listed,
+/// in which case the downloaded part is removed so it isn't resumed.
+async fn fetch(client: &Client, download: &Download, path: &Path) -> Result<()> {
+    let part = part_of(path);
+    let offset = fs::metadata(&part).map_or(0, |m| m.len());
+
+    let mut request = client.get(&download.url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    }
+    let mut response = request.send().await.map_err(|e| Error::Other(e.into()))?;
+
+    // The part was already downloaded in full if there is nothing past it.
+    if response.status() != StatusCode::RANGE_NOT_SATISFIABLE {
+        response = response
+            .error_for_status()
+            .map_err(|e| Error::Other(e.into()))?;
+
+        // Servers that don't support ranges send the whole file again.
+        let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+        let mut downloaded = if resumed { offset } else { 0 };
+        if resumed {
+            println!("Resuming {} at {}", download.name, megabytes(offset));
+        } else {
+            println!("Downloading {}", download.name);
+        }
+        let total = response.content_length().map(|len| downloaded + len);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(&part)
+            .map_err(|source| Error::File {
+                path: part.clone(),
+                source,
+            })?;
+        let mut reported = downloaded;
+        while let Some(chunk) = response.chunk().await.map_err(|e| Error::Other(e.into()))? {
+            file.write_all(&chunk)?;
+            downloaded += chunk.len() as u64;
+
+            if downloaded - reported >= PROGRESS_INTERVAL {
+                reported = downloaded;
+                match total {
+                    Some(total) => println!(
+                        "{}: {} of {} ({}%)",
+                        download.name,
+                        megabytes(downloaded),
+                        megabytes(total),
+                        downloaded * 100 / total.max(1)
+                    ),
+                    None => println!("{}: {}", download.name, megabytes(downloaded)),
+                }
+            }
+        }
+        file.flush()?;
+    }
+
+    let sha256 = sha256_of(&part)?;
+    let expected = download.sha256.to_lowercase();
+    if sha256 != expected {
+        fs::remove_file(&part)?;
+        return Err(Error::Other(
+            format!(
+                "{} has the SHA-256 {sha256} rather than {expected}, so it was removed",
+                download.name
+            )
+            .into(),
+        ));
+    }
+
+    fs::rename(&part, path)?;
+    println!("Downloaded {}", download.name);
+
+    Ok(())
+}
+
+/// Gets the file `path` is downloaded to until it is checked.
+fn part_of(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+
+    part.into()
+}
+
+/// Hashes the file at `path` with SHA-256, in lowercase hex.
+fn sha256_of(path: &Path) -> Result<String> {
+    let mut file = File::open(path).map_err(|source| Error::File {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Formats `bytes` in megabytes, e.g. `12.3 MB`.
+fn megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+}
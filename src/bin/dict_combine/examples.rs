@@ -0,0 +1,173 @@
+use std::{collections::HashMap, io::BufRead, path::Path};
+
+use jplearnbot::{
+    Result,
+    dictionary::{DictEntry, Example},
+    open_reader,
+};
+
+/// Most example sentences kept per entry.
+const MAX_EXAMPLES: usize = 3;
+
+/// Longest example sentence kept, in characters, so examples stay easy to
+/// read alongside an answer.
+const MAX_SENTENCE_CHARS: usize = 40;
+
+/// A word of a sentence as indexed on the `B:` line of the Tanaka corpus,
+/// e.g. `事(こと){こと}~`.
+struct IndexedWord<'a> {
+    /// The word as it is spelled in JMDict, e.g. `事`.
+    headword: &'a str,
+    /// The reading of [`Self::headword`], if it has several, e.g. `こと`.
+    reading: Option<&'a str>,
+    /// The word as it is written in the sentence, if it differs from
+    /// [`Self::headword`], e.g. `こと`.
+    form: Option<&'a str>,
+    /// Whether the sentence was checked to be a good example of the word.
+    checked: bool,
+}
+
+/// Adds the sentences of the Tanaka corpus, as distributed by Tatoeba in
+/// `examples.utf` in `dir`, to the `entries` they use. Each entry keeps up to
+/// [`MAX_EXAMPLES`] of them, checked and shorter sentences first.
+///
+/// Examples are skipped if there is no such file.
+pub fn attach(dir: &Path, entries: &mut [DictEntry]) -> Result<()> {
+    let path = dir.join("examples.utf");
+    if !path.exists() {
+        eprintln!(
+            "No example sentences at {}, skipping examples",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let mut by_spelling: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let kanjis = entry.kanjis.iter().map(|k| &k.text);
+        let readings = entry.readings.iter().map(|r| &r.text);
+
+        for spelling in kanjis.chain(readings) {
+            by_spelling.entry(spelling.clone()).or_default().push(i);
+        }
+    }
+
+    let mut found: HashMap<usize, Vec<(bool, Example)>> = HashMap::new();
+    let mut pair = None;
+    for line in open_reader(&path)?.lines() {
+        let line = line?;
+
+        if let Some(rest) = line.strip_prefix("A: ") {
+            pair = extract_pair(rest);
+            continue;
+        }
+        let (Some(words), Some((sentence, translation))) = (line.strip_prefix("B: "), pair.take())
+        else {
+            continue;
+        };
+        if sentence.chars().count() > MAX_SENTENCE_CHARS {
+            continue;
+        }
+
+        for word in words.split_whitespace().filter_map(extract_word) {
+            let written = word.form.unwrap_or(word.headword);
+            if !sentence.contains(written) {
+                continue;
+            }
+            let Some(i) = find_entry(entries, &by_spelling, &word) else {
+                continue;
+            };
+
+            let examples = found.entry(i).or_default();
+            if examples.iter().any(|(_, e)| e.sentence == sentence) {
+                continue;
+            }
+            examples.push((
+                word.checked,
+                Example {
+                    sentence: sentence.to_string(),
+                    translation: translation.to_string(),
+                    word: written.to_string(),
+                },
+            ));
+        }
+    }
+
+    let mut count = 0;
+    for (i, mut examples) in found {
+        examples.sort_by_key(|(checked, e)| (!checked, e.sentence.chars().count()));
+        examples.truncate(MAX_EXAMPLES);
+        count += examples.len();
+
+        entries[i].examples = examples.into_iter().map(|(_, e)| e).collect();
+    }
+
+    println!("Added {count} example sentences");
+    Ok(())
+}
+
+/// Finds the only one of `entries` that `word` can refer to, looked up by its
+/// spelling in `by_spelling`. Returns [`None`] if there is no such entry or
+/// more than one.
+fn find_entry(
+    entries: &[DictEntry],
+    by_spelling: &HashMap<String, Vec<usize>>,
+    word: &IndexedWord,
+) -> Option<usize> {
+    let matches: Vec<_> = by_spelling
+        .get(word.headword)?
+        .iter()
+        .copied()
+        .filter(|&i| match word.reading {
+            Some(reading) => entries[i].readings.iter().any(|r| r.text == reading),
+            None => true,
+        })
+        .collect();
+
+    match matches[..] {
+        [i] => Some(i),
+        _ => None,
+    }
+}
+
+/// Extracts the sentence and its translation from the rest of an `A:` line,
+/// e.g. `彼は学生です。\tHe is a student.#ID=1234_5678`.
+fn extract_pair(line: &str) -> Option<(String, String)> {
+    let (sentence, translation) = line.split_once('\t')?;
+    let translation = translation
+        .rsplit_once("#ID=")
+        .map_or(translation, |(left, _)| left);
+
+    let (sentence, translation) = (sentence.trim(), translation.trim());
+    if sentence.is_empty() || translation.is_empty() {
+        return None;
+    }
+
+    Some((sentence.to_string(), translation.to_string()))
+}
+
+/// Extracts a word from a space separated field of a `B:` line, e.g.
+/// `事(こと){こと}~`. A sense number in brackets, e.g. `[01]`, is ignored.
+fn extract_word(field: &str) -> Option<IndexedWord<'_>> {
+    let checked = field.contains('~');
+    let end = field.find(['(', '[', '{', '~']).unwrap_or(field.len());
+    let headword = &field[..end];
+    if headword.is_empty() {
+        return None;
+    }
+
+    Some(IndexedWord {
+        headword,
+        reading: enclosed(field, '(', ')'),
+        form: enclosed(field, '{', '}'),
+        checked,
+    })
+}
+
+/// Gets the text of `field` between `open` and `close`, if there is any.
+fn enclosed(field: &str, open: char, close: char) -> Option<&str> {
+    let (_, rest) = field.split_once(open)?;
+    let (text, _) = rest.split_once(close)?;
+
+    (!text.is_empty()).then_some(text)
+}
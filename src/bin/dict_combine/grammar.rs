@@ -0,0 +1,74 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufWriter, Write},
+    path::Path,
+};
+
+use jplearnbot::{Error, Result, dictionary::NLevel, grammar::GrammarPoint, open_reader};
+
+/// Generates grammar.jsonl in `dir` from the grammar points listed in
+/// grammar.tsv in `dir`, replacing any previously generated file.
+pub fn run(dir: &Path) -> Result<()> {
+    let reader = open_reader(&dir.join("grammar.tsv"))?;
+
+    let mut points = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if let Some(point) = extract_point(&line) {
+            points.push(point);
+        } else if !line.starts_with('#') && !line.trim().is_empty() {
+            return Err(Error::DictionaryFormat(format!(
+                "couldn't extract a grammar point on line {}: {line}",
+                i + 1
+            )));
+        }
+    }
+
+    let path = dir.join("grammar.jsonl");
+    let mut writer = BufWriter::new(File::create(&path).map_err(|source| Error::File {
+        path: path.clone(),
+        source,
+    })?);
+    for point in &points {
+        let mut str = serde_json::to_string(point)?;
+        str.push('\n');
+
+        writer.write_all(str.as_bytes())?;
+    }
+    writer.flush()?;
+
+    println!(
+        "Wrote {} grammar points to {}",
+        points.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Extracts a grammar point from a line of tab separated pattern, meaning,
+/// example, and level, e.g. `〜ばかりか`, `not only ~ but also`, an example
+/// sentence, and `N2`.
+///
+/// Returns [`None`] for comments, blank lines, and malformed lines.
+fn extract_point(line: &str) -> Option<GrammarPoint> {
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let fields: Vec<_> = line.split('\t').map(str::trim).collect();
+    let [pattern, meaning, example, level] = fields[..] else {
+        return None;
+    };
+    if [pattern, meaning, example].iter().any(|f| f.is_empty()) {
+        return None;
+    }
+
+    Some(GrammarPoint {
+        pattern: pattern.to_string(),
+        meaning: meaning.to_string(),
+        example: example.to_string(),
+        level: level.to_uppercase().parse::<NLevel>().ok()?,
+    })
+}
@@ -1,24 +1,38 @@
 use std::{io::BufRead, path::Path};
 
-use jplearnbot::{dictionary::NLevel, open_reader};
+use jplearnbot::{Error, Result, dictionary::NLevel, open_reader};
 
+#[derive(Clone)]
 pub struct JlptEntry {
     pub hiragana: String,
     pub kanji: Option<String>,
     pub level: NLevel,
 }
 
-/// Gets JLPT entries at `level`.
-pub fn pool(dir: &Path, level: NLevel) -> Vec<JlptEntry> {
+/// Gets JLPT entries at `level` from `jlpt-voc-<level>.utf.txt`, or from
+/// `jlpt-voc-<level>.csv` if there is no such file. The source material has
+/// no N5 list, so N5 is skipped if neither file exists.
+pub fn pool(dir: &Path, level: NLevel) -> Result<Vec<JlptEntry>> {
     let mut entries = Vec::new();
 
-    let path = dir.join(format!("jlpt-voc-{}.utf.txt", i32::from(level)));
-    let reader = open_reader(&path);
+    let txt = dir.join(format!("jlpt-voc-{}.utf.txt", i32::from(level)));
+    let csv = dir.join(format!("jlpt-voc-{}.csv", i32::from(level)));
+    let (path, extract): (_, fn(&str) -> _) = if txt.exists() {
+        (txt, extract_entry)
+    } else if csv.exists() {
+        (csv, |line| Ok(extract_csv_entry(line)))
+    } else if level == NLevel::N5 {
+        eprintln!("No N5 vocabulary at {}, skipping N5", csv.display());
+        return Ok(entries);
+    } else {
+        (txt, extract_entry)
+    };
+    let reader = open_reader(&path)?;
 
     for line in reader.lines() {
-        let line = line.unwrap_or_else(|e| panic!("Invalid byte read in jfile:\n{e}"));
+        let line = line?;
 
-        let Some((hiragana, kanji)) = extract_entry(&line) else {
+        let Some((hiragana, kanji)) = extract(&line)? else {
             continue;
         };
 
@@ -29,12 +43,12 @@ pub fn pool(dir: &Path, level: NLevel) -> Vec<JlptEntry> {
         });
     }
 
-    entries
+    Ok(entries)
 }
 
-fn extract_entry(line: &str) -> Option<(String, Option<String>)> {
+fn extract_entry(line: &str) -> Result<Option<(String, Option<String>)>> {
     if line.starts_with("#") || line.is_empty() || line.contains("~") {
-        return None;
+        return Ok(None);
     }
 
     // Remove parenthesized note
@@ -43,11 +57,31 @@ fn extract_entry(line: &str) -> Option<(String, Option<String>)> {
 
     match fields.len() {
         // Kanji isn't present, hiragana is first in line
-        1 => Some((fields[0].to_string(), None)),
+        1 => Ok(Some((fields[0].to_string(), None))),
         // Kanji is present, hiragana is second in line
-        2 => Some((fields[1].to_string(), Some(fields[0].to_string()))),
-        _ => panic!("Error extracting hiragana:\n\t{line}"),
+        2 => Ok(Some((fields[1].to_string(), Some(fields[0].to_string())))),
+        _ => Err(Error::DictionaryFormat(format!(
+            "couldn't extract hiragana from {line}"
+        ))),
+    }
+}
+
+/// Extracts an entry from a line of a CSV list whose first two columns are
+/// the word as written and its reading, e.g. `学校,がっこう,school`. Any other
+/// columns are ignored, as is a header starting with `expression`.
+fn extract_csv_entry(line: &str) -> Option<(String, Option<String>)> {
+    if line.starts_with("#") || line.is_empty() || line.starts_with("expression") {
+        return None;
     }
+
+    let mut fields = line.split(',').map(|f| f.trim().trim_matches('"'));
+    let written = fields.next().filter(|f| !f.is_empty())?;
+    let reading = fields.next().filter(|f| !f.is_empty()).unwrap_or(written);
+
+    // Words written in kana have the same text as their reading
+    let kanji = (written != reading).then(|| written.to_string());
+
+    Some((reading.to_string(), kanji))
 }
 
 // cat
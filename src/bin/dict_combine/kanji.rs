@@ -0,0 +1,85 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use jplearnbot::{
+    Error, Result,
+    dictionary::{NLevel, read_entries},
+    kanjidic, open_reader,
+};
+
+/// Generates kanji.jsonl in `dir` from KANJIDIC2's kanjidic2.xml in `dir`,
+/// replacing any previously generated file. Only the kanji taught in school
+/// or tested by the JLPT are kept.
+///
+/// KANJIDIC2 lists the levels of the JLPT before N3 was added, so kanji of the
+/// old level 2 are taken as N3 if a word of N3 or easier is spelled with them,
+/// as found in the generated dictionary.jsonl in `dir`. They are left at N2
+/// if it hasn't been generated.
+pub fn run(dir: &Path) -> Result<()> {
+    let mut characters = kanjidic::parse_kanjidic(open_reader(&dir.join("kanjidic2.xml"))?)?;
+    characters.retain(|c| c.grade.is_some() || c.level.is_some());
+
+    let levels = word_levels(dir)?;
+    for character in &mut characters {
+        let easiest = character
+            .literal
+            .chars()
+            .next()
+            .and_then(|c| levels.get(&c));
+        if character.level == Some(NLevel::N2) && easiest.is_some_and(|&l| l >= NLevel::N3) {
+            character.level = Some(NLevel::N3);
+        }
+    }
+
+    let path = dir.join("kanji.jsonl");
+    let mut writer = BufWriter::new(File::create(&path).map_err(|source| Error::File {
+        path: path.clone(),
+        source,
+    })?);
+    for character in &characters {
+        let mut str = serde_json::to_string(character)?;
+        str.push('\n');
+
+        writer.write_all(str.as_bytes())?;
+    }
+    writer.flush()?;
+
+    println!("Wrote {} kanji to {}", characters.len(), path.display());
+    Ok(())
+}
+
+/// Maps each kanji used to spell a word of the generated dictionary in `dir` to
+/// the easiest level of the words spelled with it. Empty if the dictionary
+/// hasn't been generated.
+fn word_levels(dir: &Path) -> Result<HashMap<char, NLevel>> {
+    let path = dir.join("dictionary.jsonl");
+    if !path.exists() {
+        eprintln!(
+            "No generated dictionary at {}, leaving old level 2 kanji at N2",
+            path.display()
+        );
+        return Ok(HashMap::new());
+    }
+
+    let entries = read_entries(open_reader(&path)?)?;
+
+    let mut levels = HashMap::new();
+    for entry in &entries {
+        for kanji in &entry.kanjis {
+            let Some(&easiest) = kanji.levels.iter().max() else {
+                continue;
+            };
+
+            for c in kanji.text.chars() {
+                let level = levels.entry(c).or_insert(easiest);
+                *level = (*level).max(easiest);
+            }
+        }
+    }
+
+    Ok(levels)
+}
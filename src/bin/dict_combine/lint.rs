@@ -0,0 +1,103 @@
+use std::{collections::BTreeSet, path::Path};
+
+use jplearnbot::{
+    Error, Result,
+    dictionary::{Dictionary, EntryRef, Pos},
+    open_reader,
+    question::{MAX_GLOSS_LEN, OPTIONS, Pairing},
+};
+use strum::IntoEnumIterator;
+
+/// Gets the modes of the bot asking about `pairing`, in both directions.
+fn modes(pairing: Pairing) -> &'static str {
+    match pairing {
+        Pairing::ReadingSense => "English ⇄ ひらがな",
+        Pairing::KanjiReading => "ひらがな ⇄ 漢字",
+        Pairing::KanjiSense => "漢字 ⇄ English",
+        Pairing::KatakanaSense => "English ⇄ カタカナ",
+    }
+}
+
+/// Explains why an entry `pairing` can't be extracted from can't be asked about.
+fn missing(pairing: Pairing) -> &'static str {
+    match pairing {
+        Pairing::ReadingSense => "no sense with a short enough gloss and a matching reading",
+        Pairing::KanjiReading => "no kanji with a matching reading",
+        Pairing::KanjiSense => "no kanji or no sense with a short enough gloss",
+        Pairing::KatakanaSense => "no katakana reading or no sense with a short enough gloss",
+    }
+}
+
+/// Reports the entries of the generated dictionary in `dir` that can never
+/// produce a valid question in any mode.
+///
+/// # Errors
+/// Fails if there are any, or if the dictionary can't be read.
+pub fn run(dir: &Path) -> Result<()> {
+    let dictionary = Dictionary::from_reader(open_reader(&dir.join("dictionary.jsonl"))?)?;
+    let poses: Vec<BTreeSet<Pos>> = dictionary
+        .iter()
+        .map(|entry| {
+            entry
+                .senses()
+                .flat_map(|s| s.pos().iter().copied())
+                .collect()
+        })
+        .collect();
+
+    let mut unaskable = 0;
+    for (entry, poses) in dictionary.iter().zip(&poses) {
+        let mut problems = Vec::new();
+
+        for pairing in Pairing::iter() {
+            let paired: Vec<_> = poses
+                .iter()
+                .filter(|&&pos| pairing.has_pair(entry, pos))
+                .collect();
+
+            if paired.is_empty() {
+                problems.push(format!("{}: {}", modes(pairing), missing(pairing)));
+            } else if paired
+                .iter()
+                .all(|&&pos| dictionary.count_paired(pairing, pos) < OPTIONS)
+            {
+                problems.push(format!(
+                    "{}: fewer than {} other words share a part of speech for distractors",
+                    modes(pairing),
+                    OPTIONS - 1
+                ));
+            }
+        }
+
+        if problems.len() == Pairing::iter().len() {
+            unaskable += 1;
+            println!("{} {}:", entry.id(), word(entry));
+            for problem in problems {
+                println!("\t{problem}");
+            }
+        }
+    }
+
+    println!(
+        "{unaskable} of {} entries can never be asked (glosses are limited to {MAX_GLOSS_LEN} characters)",
+        dictionary.len()
+    );
+
+    if unaskable > 0 {
+        return Err(Error::DictionaryFormat(format!(
+            "{unaskable} entries can never be asked"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Gets the first kanji and reading of `entry`, e.g. `日本 (にほん)`.
+fn word(entry: EntryRef<'_>) -> String {
+    let reading = entry.readings().next().map_or("", |r| r.text());
+
+    match entry.kanjis().next() {
+        Some(kanji) => format!("{} ({reading})", kanji.text()),
+        None => reading.to_string(),
+    }
+}
@@ -1,23 +1,104 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, process};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
+mod audio;
 mod dictionary;
+mod download;
+mod examples;
+mod grammar;
 mod jlpt;
+mod kanji;
 mod dict_combine;
+mod lint;
+mod matching;
+mod pitch;
+mod report;
+mod sqlite;
+mod xml;
 
 #[derive(Parser)]
-#[command()]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(long)]
     /// Overwrite generated entries file
     overwrite: bool,
 
-    /// Directory containing JMDict, JLPT files, and where to save generated file (default: working directory)
+    #[arg(long)]
+    /// Write the JLPT entries that matched no JMDict entry, or several, to jlpt-report.tsv
+    report: bool,
+
+    #[arg(long, value_enum, default_value_t = dictionary::Format::Jsonl)]
+    /// Format of JMDict, read from JMdict_e.xml or jmdict.jsonl
+    format: dictionary::Format,
+
+    /// Directory containing JMDict (see `download`), JLPT files, optionally Tatoeba's examples.utf and Kanjium's accents.txt, and where to save generated file (default: working directory)
     directory: Option<PathBuf>,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Report entries of the generated file that can never produce a valid question
+    Lint {
+        /// Directory containing the generated file (default: working directory)
+        directory: Option<PathBuf>,
+    },
+    /// Generate grammar.jsonl from the grammar points listed in grammar.tsv
+    Grammar {
+        /// Directory containing grammar.tsv and where to save the generated file (default: working directory)
+        directory: Option<PathBuf>,
+    },
+    /// Generate kanji.jsonl from the kanji of KANJIDIC2's kanjidic2.xml
+    Kanji {
+        /// Directory containing kanjidic2.xml, the generated file, and where to save kanji.jsonl (default: working directory)
+        directory: Option<PathBuf>,
+    },
+    /// Write the generated file to dictionary.sqlite, for the SQLite dictionary backend
+    Sqlite {
+        /// Directory containing the generated file (default: working directory)
+        directory: Option<PathBuf>,
+    },
+    /// Download the files listed in downloads.toml, e.g. JMDict, checking their SHA-256 and resuming interrupted downloads
+    Download {
+        /// Directory containing downloads.toml and where to save the files (default: working directory)
+        directory: Option<PathBuf>,
+    },
+    /// Synthesize the readings of the most common words into the bot's audio cache
+    Audio {
+        /// How many words to synthesize, easiest levels first
+        #[arg(long, default_value_t = 1000)]
+        count: usize,
+
+        /// Directory containing the generated file (default: working directory)
+        directory: Option<PathBuf>,
+    },
+}
+
 fn main() {
     let args = Args::parse();
-    dict_combine::run(&args.directory.unwrap_or(".".into()), args.overwrite);
+
+    let result = match args.command {
+        Some(Command::Lint { directory }) => lint::run(&directory.unwrap_or(".".into())),
+        Some(Command::Grammar { directory }) => grammar::run(&directory.unwrap_or(".".into())),
+        Some(Command::Kanji { directory }) => kanji::run(&directory.unwrap_or(".".into())),
+        Some(Command::Sqlite { directory }) => sqlite::run(&directory.unwrap_or(".".into())),
+        Some(Command::Download { directory }) => download::run(&directory.unwrap_or(".".into())),
+        Some(Command::Audio { count, directory }) => {
+            audio::run(&directory.unwrap_or(".".into()), count)
+        }
+        None => dict_combine::run(
+            &args.directory.unwrap_or(".".into()),
+            args.overwrite,
+            args.format,
+            args.report,
+        ),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use jplearnbot::kana;
+
+use crate::{dictionary::Homophones, jlpt::JlptEntry, report::Miss};
+
+/// A way an entry of the JLPT lists may be written in JMDict.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Variant {
+    /// The reading to look up, as JMDict may have it.
+    pub reading: String,
+    /// The kanji spellings it may have, none if the entry is written in kana.
+    pub kanjis: Vec<String>,
+}
+
+/// Gets the ways `entry` may be written in JMDict, the way it is written
+/// on the list first:
+///
+/// - Alternative kanji separated by `・`, e.g. `会う・遭う`, are each a
+///   spelling of the entry.
+/// - する verbs, e.g. `びっくりする`, may be in JMDict as their stem, e.g.
+///   `びっくり`, along with the stem of their kanji if they have any.
+/// - Readings given in katakana may be in JMDict in hiragana.
+pub fn variants(entry: &JlptEntry) -> Vec<Variant> {
+    let kanjis: Vec<String> = entry
+        .kanji
+        .iter()
+        .flat_map(|k| k.split('・'))
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let mut bases = vec![(entry.hiragana.clone(), kanjis.clone())];
+    if let Some(stem) = entry.hiragana.strip_suffix("する")
+        && !stem.is_empty()
+    {
+        let stems: Vec<_> = kanjis
+            .iter()
+            .filter_map(|k| k.strip_suffix("する"))
+            .filter(|k| !k.is_empty())
+            .map(str::to_string)
+            .collect();
+        // The kanji of a する verb ends with する too, e.g. 勉強する.
+        if stems.len() == kanjis.len() {
+            bases.push((stem.to_string(), stems));
+        }
+    }
+
+    let mut variants = Vec::new();
+    for (reading, kanjis) in bases {
+        let hiragana = kana::katakana_to_hiragana(&reading);
+        let converted = (hiragana != reading).then(|| Variant {
+            reading: hiragana,
+            kanjis: kanjis.clone(),
+        });
+
+        variants.push(Variant { reading, kanjis });
+        variants.extend(converted);
+    }
+
+    variants
+}
+
+/// Finds the JMDict entry `entry` is annotated on in `index`, looking up the
+/// first of its [`variants`] JMDict has the reading of. Later variants aren't
+/// tried once one is found, so e.g. ボタン isn't matched to ぼたん (牡丹)
+/// for being ambiguous. Gets the reading it matched as JMDict has it, along
+/// with the id of the entry.
+///
+/// # Errors
+/// Fails with why the variant didn't match exactly one entry, or
+/// [`Miss::NoReading`] if JMDict has none of the readings.
+pub fn find(entry: &JlptEntry, index: &HashMap<String, Homophones>) -> Result<(String, u32), Miss> {
+    let Some((Variant { reading, kanjis }, matches)) = variants(entry)
+        .into_iter()
+        .find_map(|v| index.get(&v.reading).map(|matches| (v, matches)))
+    else {
+        return Err(Miss::NoReading);
+    };
+
+    match candidates(matches, &kanjis)[..] {
+        [id] => Ok((reading, id)),
+        [] => Err(Miss::NoSpelling(ids(matches.iter()))),
+        ref candidates => Err(Miss::Ambiguous(candidates.to_vec())),
+    }
+}
+
+/// Gets the ids of the `matches` of a reading that an entry spelled as one
+/// of `kanjis`, or in kana if there are none, may be annotated on.
+///
+/// The only match is always a candidate, as there is no ambiguity. Otherwise,
+/// matches spelled exactly as one of `kanjis` are, or if there are none, the
+/// ones spelled with the same kanji but other okurigana, e.g. 受付 and 受け付け.
+/// An entry without kanji has the matches without kanji as candidates.
+fn candidates(matches: &Homophones, kanjis: &[String]) -> Vec<u32> {
+    if let [(id, _)] = matches[..] {
+        return vec![id];
+    }
+
+    if kanjis.is_empty() {
+        return ids(matches.iter().filter(|(_, spellings)| spellings.is_empty()));
+    }
+
+    let exact = ids(matches
+        .iter()
+        .filter(|(_, spellings)| spellings.iter().any(|s| kanjis.contains(s))));
+    if !exact.is_empty() {
+        return exact;
+    }
+
+    ids(matches.iter().filter(|(_, spellings)| {
+        spellings
+            .iter()
+            .any(|s| kanjis.iter().any(|k| kana::same_kanji(s, k)))
+    }))
+}
+
+/// Gets the ids of `matches`.
+fn ids<'a>(matches: impl Iterator<Item = &'a (u32, Vec<String>)>) -> Vec<u32> {
+    matches.map(|(id, _)| *id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use jplearnbot::dictionary::NLevel;
+
+    use super::*;
+
+    fn entry(hiragana: &str, kanji: Option<&str>) -> JlptEntry {
+        JlptEntry {
+            hiragana: hiragana.to_string(),
+            kanji: kanji.map(str::to_string),
+            level: NLevel::N3,
+        }
+    }
+
+    /// Entries sharing a reading, each as its id and its kanji.
+    type Matches<'a> = &'a [(u32, &'a [&'a str])];
+
+    fn index(readings: &[(&str, Matches)]) -> HashMap<String, Homophones> {
+        readings
+            .iter()
+            .map(|(reading, matches)| {
+                let matches = matches
+                    .iter()
+                    .map(|(id, kanjis)| (*id, kanjis.iter().map(|k| k.to_string()).collect()))
+                    .collect();
+                (reading.to_string(), matches)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_alternative_kanji() {
+        let index = index(&[("あう", &[(1, &["合う"]), (2, &["遭う", "遇う"])])]);
+
+        assert_eq!(
+            variants(&entry("あう", Some("会う・遭う"))),
+            [Variant {
+                reading: "あう".to_string(),
+                kanjis: vec!["会う".to_string(), "遭う".to_string()],
+            }]
+        );
+        assert_eq!(
+            find(&entry("あう", Some("会う・遭う")), &index).ok(),
+            Some(("あう".to_string(), 2))
+        );
+    }
+
+    #[test]
+    fn matches_other_okurigana() {
+        let index = index(&[("うけつけ", &[(1, &["受け付け"]), (2, &["請付"])])]);
+
+        assert_eq!(
+            find(&entry("うけつけ", Some("受付")), &index).ok(),
+            Some(("うけつけ".to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn prefers_exact_spelling_over_okurigana() {
+        let matches = index(&[("うけつけ", &[(1, &["受付"]), (2, &["受け付け"])])]);
+
+        assert_eq!(candidates(&matches["うけつけ"], &["受付".to_string()]), [1]);
+    }
+
+    #[test]
+    fn matches_suru_verb_stems() {
+        let index = index(&[
+            ("びっくり", &[(1, &["吃驚"])]),
+            ("べんきょう", &[(2, &["勉強"]), (3, &["勉彊"])]),
+        ]);
+
+        assert_eq!(
+            find(&entry("びっくりする", None), &index).ok(),
+            Some(("びっくり".to_string(), 1))
+        );
+        assert_eq!(
+            find(&entry("べんきょうする", Some("勉強する")), &index).ok(),
+            Some(("べんきょう".to_string(), 2))
+        );
+        // Verbs ending in する whose kanji doesn't aren't する verbs.
+        assert_eq!(variants(&entry("かする", Some("擦る"))).len(), 1);
+    }
+
+    #[test]
+    fn matches_katakana_to_hiragana() {
+        let index = index(&[("ばいばい", &[(1, &[])])]);
+
+        assert_eq!(
+            find(&entry("バイバイ", None), &index).ok(),
+            Some(("ばいばい".to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn keeps_katakana_found_in_jmdict() {
+        let index = index(&[
+            ("ボタン", &[(1, &["釦"]), (2, &["牡丹"])]),
+            ("ぼたん", &[(2, &["牡丹"])]),
+        ]);
+
+        assert!(matches!(
+            find(&entry("ボタン", None), &index),
+            Err(Miss::NoSpelling(ids)) if ids == [1, 2]
+        ));
+        assert!(matches!(
+            find(&entry("ないよみ", None), &index),
+            Err(Miss::NoReading)
+        ));
+    }
+}
@@ -0,0 +1,81 @@
+use std::{collections::HashMap, io::BufRead, path::Path};
+
+use jplearnbot::{Result, dictionary::DictEntry, open_reader};
+
+/// Annotates the readings of `entries` with their pitch accent from Kanjium's
+/// `accents.txt` in `dir`. A reading of a word spelled in kanji takes the
+/// accent listed for its first spelling that has one, since the same reading
+/// of different words can be accented differently.
+///
+/// Pitch accents are skipped if there is no such file.
+pub fn attach(dir: &Path, entries: &mut [DictEntry]) -> Result<()> {
+    let path = dir.join("accents.txt");
+    if !path.exists() {
+        eprintln!(
+            "No pitch accents at {}, skipping pitch accents",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let mut accents = HashMap::new();
+    for line in open_reader(&path)?.lines() {
+        let line = line?;
+
+        if let Some((word, reading, pitch)) = extract_accent(&line) {
+            accents.entry((word, reading)).or_insert(pitch);
+        }
+    }
+
+    let mut count = 0;
+    for entry in entries {
+        let kanjis: Vec<_> = entry.kanjis.iter().map(|k| k.text.clone()).collect();
+
+        for reading in &mut entry.readings {
+            let spellings = kanjis
+                .iter()
+                .filter(|k| reading.relevant_to.is_empty() || reading.relevant_to.contains(k));
+            let pitch = if kanjis.is_empty() {
+                accents.get(&(reading.text.clone(), reading.text.clone()))
+            } else {
+                spellings
+                    .filter_map(|k| accents.get(&(k.clone(), reading.text.clone())))
+                    .next()
+            };
+
+            if let Some(&pitch) = pitch {
+                reading.pitch = Some(pitch);
+                count += 1;
+            }
+        }
+    }
+
+    println!("Added {count} pitch accents");
+    Ok(())
+}
+
+/// Extracts a word, its reading, and the first of its pitch accents from a
+/// line of tab separated word, reading, and accents, e.g. `橋`, `はし`, and
+/// `2`. Words spelled in kana may leave their reading empty. Accents can be
+/// listed several to a word and labeled with parts of speech, e.g. `(名)0,(副)1`.
+///
+/// Returns [`None`] for malformed lines.
+fn extract_accent(line: &str) -> Option<(String, String, u8)> {
+    let fields: Vec<_> = line.split('\t').map(str::trim).collect();
+    let [word, reading, accents] = fields[..] else {
+        return None;
+    };
+    if word.is_empty() {
+        return None;
+    }
+    let reading = if reading.is_empty() { word } else { reading };
+
+    let first = accents.split(',').next()?;
+    let digits: String = first
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+
+    Some((word.to_string(), reading.to_string(), digits.parse().ok()?))
+}
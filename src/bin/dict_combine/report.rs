@@ -0,0 +1,147 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use jplearnbot::{Error, Result, dictionary::NLevel};
+
+use crate::jlpt::JlptEntry;
+
+/// Why an entry of the JLPT lists wasn't annotated on any JMDict entry.
+pub enum Miss {
+    /// No JMDict entry is read as it.
+    NoReading,
+    /// The JMDict entries read as it, none of which are spelled as it.
+    NoSpelling(Vec<u32>),
+    /// The JMDict entries read and spelled as it, which it can't be told apart from.
+    Ambiguous(Vec<u32>),
+}
+
+impl Miss {
+    /// Describes the miss in a column of the report.
+    fn reason(&self) -> &'static str {
+        match self {
+            Miss::NoReading => "no reading",
+            Miss::NoSpelling(_) => "no spelling",
+            Miss::Ambiguous(_) => "ambiguous",
+        }
+    }
+
+    /// Gets the ids of the JMDict entries that were candidates.
+    fn candidates(&self) -> &[u32] {
+        match self {
+            Miss::NoReading => &[],
+            Miss::NoSpelling(ids) | Miss::Ambiguous(ids) => ids,
+        }
+    }
+}
+
+/// How many entries of a JLPT list matched JMDict, and why the others didn't.
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    matched: usize,
+    no_reading: usize,
+    no_spelling: usize,
+    ambiguous: usize,
+}
+
+impl Tally {
+    fn total(&self) -> usize {
+        self.matched + self.no_reading + self.no_spelling + self.ambiguous
+    }
+}
+
+/// How the entries of the JLPT lists matched the entries of JMDict, so the
+/// matching can be audited.
+#[derive(Default)]
+pub struct MatchReport {
+    tallies: BTreeMap<NLevel, Tally>,
+    /// The entries that didn't match, in the order of the lists.
+    misses: Vec<(JlptEntry, Miss)>,
+}
+
+impl MatchReport {
+    /// Counts `entry` as annotated on a JMDict entry.
+    pub fn matched(&mut self, entry: &JlptEntry) {
+        self.tallies.entry(entry.level).or_default().matched += 1;
+    }
+
+    /// Keeps `entry`, which wasn't annotated on any JMDict entry due to `miss`.
+    pub fn missed(&mut self, entry: &JlptEntry, miss: Miss) {
+        let tally = self.tallies.entry(entry.level).or_default();
+        match miss {
+            Miss::NoReading => tally.no_reading += 1,
+            Miss::NoSpelling(_) => tally.no_spelling += 1,
+            Miss::Ambiguous(_) => tally.ambiguous += 1,
+        }
+        self.misses.push((entry.clone(), miss));
+    }
+
+    /// Summarizes how many entries matched in a line.
+    pub fn summary(&self) -> String {
+        let total = self
+            .tallies
+            .values()
+            .fold(Tally::default(), |sum, t| Tally {
+                matched: sum.matched + t.matched,
+                no_reading: sum.no_reading + t.no_reading,
+                no_spelling: sum.no_spelling + t.no_spelling,
+                ambiguous: sum.ambiguous + t.ambiguous,
+            });
+
+        format!(
+            "Matched {} of {} JLPT entries to JMDict",
+            total.matched,
+            total.total()
+        )
+    }
+
+    /// Writes the entries that didn't match to jlpt-report.tsv in `dir`, each
+    /// with its level, reading, kanji, why it didn't match, and the ids of the
+    /// JMDict entries that were candidates. Prints how many entries of each
+    /// level matched and why the others didn't.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        let path = dir.join("jlpt-report.tsv");
+        let mut writer = BufWriter::new(File::create(&path).map_err(|source| Error::File {
+            path: path.clone(),
+            source,
+        })?);
+
+        writeln!(writer, "level\treading\tkanji\treason\tcandidates")?;
+        for (entry, miss) in &self.misses {
+            let candidates: Vec<_> = miss.candidates().iter().map(u32::to_string).collect();
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}",
+                entry.level,
+                entry.hiragana,
+                entry.kanji.as_deref().unwrap_or_default(),
+                miss.reason(),
+                candidates.join(",")
+            )?;
+        }
+        writer.flush()?;
+
+        println!("Level\tEntries\tMatched\tNo reading\tNo spelling\tAmbiguous");
+        for (level, tally) in &self.tallies {
+            println!(
+                "{level}\t{}\t{} ({}%)\t{}\t{}\t{}",
+                tally.total(),
+                tally.matched,
+                tally.matched * 100 / tally.total().max(1),
+                tally.no_reading,
+                tally.no_spelling,
+                tally.ambiguous
+            );
+        }
+        println!(
+            "Wrote {} unmatched entries to {}",
+            self.misses.len(),
+            path.display()
+        );
+
+        Ok(())
+    }
+}
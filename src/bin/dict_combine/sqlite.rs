@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use jplearnbot::{
+    Result,
+    dictionary::{SqliteDictionary, read_entries},
+    open_reader,
+};
+
+/// Writes the entries of the generated dictionary in `dir` to dictionary.sqlite
+/// in `dir`, for hosts that page entries in with [`SqliteDictionary`].
+pub fn run(dir: &Path) -> Result<()> {
+    let entries = read_entries(open_reader(&dir.join("dictionary.jsonl"))?)?;
+
+    let path = dir.join("dictionary.sqlite");
+    SqliteDictionary::create(&path, &entries)?;
+
+    println!("Wrote {} entries to {}", entries.len(), path.display());
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use std::io::BufRead;
+
+use jplearnbot::{
+    Error, Result,
+    dictionary::{DictEntry, Gloss, KTag, Kanji, Pos, RTag, Reading, Sense},
+};
+use quick_xml::{
+    Reader,
+    escape::resolve_predefined_entity,
+    events::{BytesRef, BytesStart, Event},
+};
+use serde::de::{
+    DeserializeOwned,
+    value::{self, StrDeserializer},
+};
+
+/// Reads the entries of JMdict's XML, e.g. the official `JMdict_e.xml`, one at
+/// a time as they are iterated.
+///
+/// Tags written as entities, e.g. `<pos>&n;</pos>`, are kept as they are
+/// written, as in the JSON Lines mirror. Tags [`DictEntry`] doesn't know are
+/// skipped, as are glosses in languages other than English. Senses without
+/// parts of speech take the ones of the sense before them, as JMdict intends.
+pub struct JmdictXml<R> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    /// Whether the end of the file or an error was reached.
+    done: bool,
+}
+
+impl<R: BufRead> JmdictXml<R> {
+    pub fn new(reader: R) -> Self {
+        JmdictXml {
+            reader: Reader::from_reader(reader),
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Reads the next entry, or [`None`] if there are no more.
+    fn read_entry(&mut self) -> Result<Option<DictEntry>> {
+        let mut entry: Option<DictEntry> = None;
+        // Text of the innermost element, which holds text only in JMdict.
+        let mut text = String::new();
+        // Whether the gloss being read is in a language other than English.
+        let mut foreign = false;
+
+        loop {
+            self.buf.clear();
+            let event = self
+                .reader
+                .read_event_into(&mut self.buf)
+                .map_err(|e| malformed(&self.reader, e))?;
+
+            match event {
+                Event::Start(start) => {
+                    text.clear();
+                    match start.name().as_ref() {
+                        b"entry" => entry = Some(new_entry()),
+                        b"gloss" => foreign = !is_english(&start),
+                        _ => {}
+                    }
+                    if let Some(entry) = &mut entry {
+                        start_element(entry, start.name().as_ref());
+                    }
+                }
+                Event::Text(content) => {
+                    let content = content.decode().map_err(|e| malformed(&self.reader, e))?;
+                    text.push_str(&content);
+                }
+                Event::GeneralRef(reference) => {
+                    let reference = resolve(&reference).map_err(|e| malformed(&self.reader, e))?;
+                    text.push_str(&reference);
+                }
+                Event::End(end) => {
+                    let Some(current) = &mut entry else {
+                        continue;
+                    };
+                    let name = end.name();
+                    let text = std::mem::take(&mut text);
+                    match name.as_ref() {
+                        b"entry" => return Ok(entry),
+                        b"gloss" if foreign => {}
+                        name => end_element(current, name, text)
+                            .map_err(|e| malformed(&self.reader, e))?,
+                    }
+                }
+                Event::Eof => {
+                    return match entry {
+                        Some(_) => Err(malformed(&self.reader, "the file ended inside an entry")),
+                        None => Ok(None),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Describes a failure to read the XML at the position of `reader`.
+fn malformed<R>(reader: &Reader<R>, error: impl std::fmt::Display) -> Error {
+    Error::DictionaryFormat(format!(
+        "JMdict XML at byte {}: {error}",
+        reader.buffer_position()
+    ))
+}
+
+impl<R: BufRead> Iterator for JmdictXml<R> {
+    type Item = Result<DictEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let entry = self.read_entry().transpose();
+        self.done = !matches!(entry, Some(Ok(_)));
+        entry
+    }
+}
+
+/// Creates an entry to be filled in as its elements are read.
+fn new_entry() -> DictEntry {
+    DictEntry {
+        id: 0,
+        kanjis: Vec::new(),
+        readings: Vec::new(),
+        senses: Vec::new(),
+        examples: Vec::new(),
+    }
+}
+
+/// Adds the element named `name` that starts to `entry`, if it is one that
+/// holds others.
+fn start_element(entry: &mut DictEntry, name: &[u8]) {
+    match name {
+        b"k_ele" => entry.kanjis.push(Kanji {
+            text: String::new(),
+            levels: Vec::new(),
+            tags: Vec::new(),
+        }),
+        b"r_ele" => entry.readings.push(Reading {
+            text: String::new(),
+            relevant_to: Vec::new(),
+            levels: Vec::new(),
+            tags: Vec::new(),
+            pitch: None,
+        }),
+        b"sense" => entry.senses.push(Sense {
+            number: 0,
+            relevant_kanji: Vec::new(),
+            relevant_reading: Vec::new(),
+            pos: Vec::new(),
+            gloss: Vec::new(),
+        }),
+        _ => {}
+    }
+}
+
+/// Fills in `entry` with the `text` of the element named `name` that ends.
+/// Elements [`DictEntry`] doesn't model are skipped.
+fn end_element(
+    entry: &mut DictEntry,
+    name: &[u8],
+    text: String,
+) -> std::result::Result<(), String> {
+    let kanji = entry.kanjis.last_mut();
+    let reading = entry.readings.last_mut();
+    let (previous, sense) = match entry.senses.as_mut_slice() {
+        [.., previous, sense] => (Some(&*previous), Some(sense)),
+        [sense] => (None, Some(sense)),
+        [] => (None, None),
+    };
+
+    match (name, kanji, reading, sense) {
+        (b"ent_seq", ..) => {
+            entry.id = text
+                .trim()
+                .parse()
+                .map_err(|_| format!("{text} isn't an entry id"))?;
+        }
+        (b"keb", Some(kanji), ..) => kanji.text = text,
+        (b"ke_inf", Some(kanji), ..) => kanji.tags.extend(tag::<KTag>(&text)),
+        (b"reb", _, Some(reading), _) => reading.text = text,
+        (b"re_restr", _, Some(reading), _) => reading.relevant_to.push(text),
+        (b"re_inf", _, Some(reading), _) => reading.tags.extend(tag::<RTag>(&text)),
+        (b"stagk", .., Some(sense)) => sense.relevant_kanji.push(text),
+        (b"stagr", .., Some(sense)) => sense.relevant_reading.push(text),
+        (b"pos", .., Some(sense)) => sense.pos.extend(tag::<Pos>(&text)),
+        (b"gloss", .., Some(sense)) => sense.gloss.push(Gloss { content: text }),
+        (b"sense", .., Some(sense)) => {
+            if sense.pos.is_empty()
+                && let Some(previous) = previous
+            {
+                sense.pos = previous.pos.clone();
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Parses a tag written as an entity, e.g. `&n;`, or [`None`] if it isn't
+/// one of `T`.
+fn tag<T: DeserializeOwned>(text: &str) -> Option<T> {
+    T::deserialize(StrDeserializer::<value::Error>::new(text.trim())).ok()
+}
+
+/// Determines whether the gloss that starts with `start` is in English, as
+/// glosses without a language are.
+fn is_english(start: &BytesStart) -> bool {
+    start
+        .try_get_attribute("xml:lang")
+        .ok()
+        .flatten()
+        .is_none_or(|lang| lang.value.as_ref() == b"eng")
+}
+
+/// Resolves `reference`, e.g. `&amp;` or `&#x3042;`. JMdict's own entities,
+/// e.g. `&n;`, are kept as they are written.
+fn resolve(reference: &BytesRef) -> std::result::Result<String, String> {
+    if let Some(c) = reference.resolve_char_ref().map_err(|e| e.to_string())? {
+        return Ok(c.to_string());
+    }
+
+    let name = reference.decode().map_err(|e| e.to_string())?;
+    Ok(match resolve_predefined_entity(&name) {
+        Some(resolved) => resolved.to_string(),
+        None => format!("&{name};"),
+    })
+}
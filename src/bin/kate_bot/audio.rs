@@ -0,0 +1,23 @@
+use std::sync::LazyLock;
+
+use jplearnbot::tts::{self, AudioCache};
+pub use jplearnbot::tts::{Speech, TtsBackend, backend_from_env};
+
+use crate::{
+    Error,
+    perf::{self, Stage},
+};
+
+/// Speech synthesized before, configured by the environment. See [`AudioCache::from_env`].
+static CACHE: LazyLock<Option<AudioCache>> = LazyLock::new(AudioCache::from_env);
+
+/// Synthesizes `text` into speech with `tts`, reusing the cached speech if
+/// it was synthesized before.
+pub async fn speak(tts: &dyn TtsBackend, text: &str) -> Result<Speech, Error> {
+    perf::time_async(
+        Stage::Speech,
+        tts::synthesize_cached(tts, CACHE.as_ref(), text),
+    )
+    .await
+    .map_err(Error::from)
+}
@@ -4,5 +4,77 @@ pub use start::*;
 mod stop;
 pub use stop::*;
 
+mod pause;
+pub use pause::*;
+
 mod info;
 pub use info::*;
+
+mod replay;
+pub use replay::*;
+
+mod bookmarks;
+pub use bookmarks::*;
+
+mod flashcards;
+pub use flashcards::*;
+
+mod study;
+pub use study::*;
+
+mod mocktest;
+pub use mocktest::*;
+
+mod worksheet;
+pub use worksheet::*;
+
+mod stats;
+pub use stats::*;
+
+mod leaderboard;
+pub use leaderboard::*;
+
+mod rank;
+pub use rank::*;
+
+mod help;
+pub use help::*;
+
+mod report;
+pub use report::*;
+
+mod mnemonic;
+pub use mnemonic::*;
+
+mod daily;
+pub use daily::*;
+
+mod stroke_order;
+pub use stroke_order::*;
+
+mod admin;
+pub use admin::*;
+
+mod lookup;
+pub use lookup::*;
+
+mod entry;
+pub use entry::*;
+
+mod preview;
+pub use preview::*;
+
+mod grammar;
+pub use grammar::*;
+
+mod kanji;
+pub use kanji::*;
+
+mod resume;
+pub use resume::*;
+
+mod settings;
+pub use settings::*;
+
+mod wordlist;
+pub use wordlist::*;
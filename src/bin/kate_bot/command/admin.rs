@@ -0,0 +1,192 @@
+use poise::{
+    CreateReply,
+    serenity_prelude::{CreateEmbed, CreateEmbedFooter},
+};
+
+use jplearnbot::{
+    heap_size::format_bytes,
+    question::{MIN_AUDITED, OPTIONS, is_position_biased},
+};
+
+use crate::{
+    Context, Error,
+    db::{ALL_COMMANDS, CommandUsage},
+    perf,
+};
+
+/// Most commands listed by `/admin usage`.
+const MAX_COMMANDS_SHOWN: usize = 25;
+
+/// Tools for running the bot. Only for the bot's owners.
+#[poise::command(
+    slash_command,
+    owners_only,
+    hide_in_help,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("perf_stats", "memory", "usage"),
+    name_localized("ja", "管理"),
+    description_localized("ja", "ボットの管理ツール（ボットの所有者のみ）")
+)]
+pub async fn admin(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Shows the latencies of the bot's hot paths and where answers were placed.
+#[poise::command(
+    slash_command,
+    owners_only,
+    rename = "perf",
+    name_localized("ja", "性能"),
+    description_localized("ja", "ボットの処理時間と正解の位置を見る")
+)]
+pub async fn perf_stats(ctx: Context<'_>) -> Result<(), Error> {
+    let mut embed = CreateEmbed::new()
+        .title("Performance ・ 性能")
+        .footer(CreateEmbedFooter::new("Of the most recent runs of each"));
+    match perf::latencies() {
+        Some(latencies) if latencies.is_empty() => {
+            embed = embed.description("Nothing has run yet");
+        }
+        Some(latencies) => {
+            for latency in &latencies {
+                embed = embed.field(
+                    latency.stage.to_string(),
+                    format!(
+                        "p50 {:.1} ms ・ p95 {:.1} ms\n{} run(s)",
+                        latency.p50.as_secs_f64() * 1000.0,
+                        latency.p95.as_secs_f64() * 1000.0,
+                        latency.samples
+                    ),
+                    false,
+                );
+            }
+        }
+        None => {
+            embed = embed.description(
+                "Profiling is disabled. Build the bot with `--features perf` to enable it.",
+            );
+        }
+    }
+    embed = embed.field(
+        "Answer positions ・ 正解の位置",
+        answer_positions(perf::answer_positions()),
+        false,
+    );
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Lists how many questions had their answer at each of the `positions`,
+/// and whether they were placed evenly.
+fn answer_positions(positions: [u64; OPTIONS]) -> String {
+    let total: u64 = positions.iter().sum();
+    let counts = positions
+        .iter()
+        .enumerate()
+        .map(|(i, count)| format!("{}: {count}", i + 1))
+        .collect::<Vec<_>>()
+        .join(" ・ ");
+    let verdict = if total < MIN_AUDITED {
+        "Too few questions to tell"
+    } else if is_position_biased(&positions) {
+        "⚠️ Uneven, check how options are shuffled"
+    } else {
+        "Even"
+    };
+
+    format!("{counts}\n{verdict}")
+}
+
+/// Shows an estimate of how much memory the dictionary uses.
+#[poise::command(
+    slash_command,
+    owners_only,
+    name_localized("ja", "メモリ"),
+    description_localized("ja", "辞書のメモリ使用量を見る")
+)]
+pub async fn memory(ctx: Context<'_>) -> Result<(), Error> {
+    let dictionary = ctx.data().manager.dictionary();
+    let footprint = dictionary.footprint();
+
+    let embed = CreateEmbed::new()
+        .title("Dictionary Memory ・ 辞書のメモリ")
+        .description(format!(
+            "About **{}** for {} entries",
+            format_bytes(footprint.total()),
+            dictionary.len()
+        ))
+        .field("Tables", format_bytes(footprint.tables), true)
+        .field("Strings", format_bytes(footprint.strings), true)
+        .field("Indexes", format_bytes(footprint.indexes), true)
+        .footer(CreateEmbedFooter::new("Estimated heap usage"));
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Shows how much each command is used and how often it fails.
+#[poise::command(
+    slash_command,
+    owners_only,
+    name_localized("ja", "利用状況"),
+    description_localized("ja", "コマンドの利用状況を見る")
+)]
+pub async fn usage(
+    ctx: Context<'_>,
+    #[name_localized("ja", "日数")]
+    #[description = "Number of days to look back over, including today (default 30)"]
+    #[description_localized("ja", "今日を含めてさかのぼる日数（デフォルト30）")]
+    #[min = 1]
+    #[max = 365]
+    days: Option<u32>,
+) -> Result<(), Error> {
+    let days = days.unwrap_or(30);
+    let usage = ctx.data().db.command_usage(days)?;
+
+    let (all, commands): (Vec<_>, Vec<_>) = usage
+        .into_iter()
+        .partition(|usage| usage.command == ALL_COMMANDS);
+    let description = match all.first() {
+        Some(all) => {
+            let mut lines = vec![format!("**All commands** {}\n", usage_line(all))];
+            lines.extend(
+                commands
+                    .iter()
+                    .take(MAX_COMMANDS_SHOWN)
+                    .map(|usage| format!("`/{}` {}", usage.command, usage_line(usage))),
+            );
+            lines.join("\n")
+        }
+        None => "No commands were used".to_string(),
+    };
+    let embed = CreateEmbed::new()
+        .title("Command Usage ・ コマンドの利用状況")
+        .description(description)
+        .footer(CreateEmbedFooter::new(format!(
+            "Over the last {days} day(s) in UTC. Users and Servers are counted per day"
+        )));
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Summarizes `usage` in a line: its invocations, how many failed, and how
+/// many users and Servers used it on an average day it was used.
+fn usage_line(usage: &CommandUsage) -> String {
+    let days = usage.days.max(1) as f64;
+
+    format!(
+        "{} use(s) ・ {:.1}% failed ・ ~{:.0} user(s)/day ・ ~{:.0} Server(s)/day",
+        usage.invocations,
+        100.0 * usage.failures as f64 / usage.invocations.max(1) as f64,
+        usage.user_days as f64 / days,
+        usage.guild_days as f64 / days
+    )
+}
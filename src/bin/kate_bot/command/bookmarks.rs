@@ -0,0 +1,112 @@
+use jplearnbot::dictionary::DictEntry;
+use poise::serenity_prelude::{
+    self as serenity, ComponentInteraction, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+
+use crate::{Context, Data, Error, pagination};
+
+/// Prefix of the custom_id of save buttons. Followed by the entry id.
+const SAVE_PREFIX: &str = "bookmark,";
+
+/// How many bookmarks are listed per page.
+const PAGE_SIZE: usize = 10;
+
+/// Lists your bookmarked words.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "ブックマーク"),
+    description_localized("ja", "ブックマークした単語を見る")
+)]
+pub async fn bookmarks(ctx: Context<'_>) -> Result<(), Error> {
+    let ids = ctx.data().db.bookmarks(ctx.author().id)?;
+    let provider = ctx.data().manager.provider();
+
+    let lines: Vec<_> = ids
+        .into_iter()
+        .filter_map(|id| provider.by_id(id))
+        .map(|entry| format!("`{}` {}", entry.id, entry_summary(&entry)))
+        .collect();
+
+    if lines.is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content("You have no bookmarks. Save words with 🔖 after answering.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pages: Vec<_> = lines
+        .chunks(PAGE_SIZE)
+        .map(|chunk| chunk.join("\n"))
+        .collect();
+
+    pagination::paginate(ctx, "Bookmarks ・ ブックマーク", &pages, true).await
+}
+
+/// Summarizes `entry` in a single line.
+pub(super) fn entry_summary(entry: &DictEntry) -> String {
+    let reading = entry.readings.first().map_or("", |r| r.text.as_str());
+    let word = match entry.kanjis.first() {
+        Some(kanji) => format!("{} ({reading})", kanji.text),
+        None => reading.to_string(),
+    };
+    let gloss = entry
+        .senses
+        .iter()
+        .find_map(|s| s.gloss.first())
+        .map_or("", |g| g.content.as_str());
+
+    format!("**{word}** {gloss} {:?}", entry.levels())
+}
+
+/// Creates a button that bookmarks the entry with the id `entry_id`.
+pub fn save_button(entry_id: u32) -> CreateButton {
+    CreateButton::new(format!("{SAVE_PREFIX}{entry_id}")).label("🔖 Save")
+}
+
+/// Determines whether `interaction` is a press of a [`save_button`].
+pub fn is_save_button(interaction: &ComponentInteraction) -> bool {
+    interaction.data.custom_id.starts_with(SAVE_PREFIX)
+}
+
+/// Bookmarks the entry of a pressed [`save_button`] for the presser.
+pub async fn handle_save_button(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let entry = interaction
+        .data
+        .custom_id
+        .strip_prefix(SAVE_PREFIX)
+        .and_then(|id| id.parse().ok())
+        .and_then(|id| data.manager.provider().by_id(id));
+
+    let content = match entry {
+        Some(entry) => {
+            if data.db.add_bookmark(interaction.user.id, entry.id)? {
+                format!("Saved {} to your bookmarks", entry_summary(&entry))
+            } else {
+                "This word is already in your bookmarks".to_string()
+            }
+        }
+        None => "This word no longer exists".to_string(),
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        self as serenity, ComponentInteraction, CreateActionRow, CreateButton,
+        CreateInteractionResponse, CreateInteractionResponseMessage, GuildChannel,
+    },
+};
+
+use crate::{
+    Context, Data, Error, daily,
+    format::{self, TimestampStyle},
+};
+
+/// Prefix of the custom_id of daily quiz answer buttons. Followed by the day,
+/// the index of the question, and the index of the option, separated by commas.
+const ANSWER_PREFIX: &str = "daily,";
+
+/// Default hour of the day in UTC daily quizzes are posted at.
+const DEFAULT_HOUR: u32 = 9;
+
+/// Default number of questions of a daily quiz.
+const DEFAULT_QUESTIONS: u32 = 5;
+
+/// Most questions a daily quiz can have, as limited by `/daily set`.
+pub(super) const MAX_QUESTIONS: u32 = 10;
+
+/// Configures the server's daily quiz.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands("set_daily", "stop_daily", "daily_status"),
+    name_localized("ja", "デイリー"),
+    description_localized("ja", "サーバーの毎日のクイズを設定する")
+)]
+pub async fn daily(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Posts a quiz in a channel every day, along with the results of the last one.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "set",
+    name_localized("ja", "設定"),
+    description_localized("ja", "毎日チャンネルにクイズを投稿する")
+)]
+pub async fn set_daily(
+    ctx: Context<'_>,
+    #[name_localized("ja", "チャンネル")]
+    #[description = "Channel to post the quiz in. This channel by default"]
+    #[description_localized("ja", "クイズを投稿するチャンネル（デフォルトはこのチャンネル）")]
+    channel: Option<GuildChannel>,
+    #[name_localized("ja", "時間")]
+    #[description = "Hour of the day in UTC to post the quiz at (default 9)"]
+    #[description_localized("ja", "クイズを投稿する時間（UTC、デフォルト9時）")]
+    #[max = 23]
+    hour: Option<u32>,
+    #[name_localized("ja", "問題数")]
+    #[description = "Number of questions asked each day (default 5)"]
+    #[description_localized("ja", "毎日出題する問題の数（デフォルト5）")]
+    #[min = 1]
+    #[max = 10]
+    questions: Option<u32>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let channel_id = channel.map_or(ctx.channel_id(), |c| c.id);
+    let hour = hour.unwrap_or(DEFAULT_HOUR);
+    let questions = questions.unwrap_or(DEFAULT_QUESTIONS);
+
+    ctx.data()
+        .db
+        .set_daily_quiz(guild_id, channel_id, hour, questions)?;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "A daily quiz of {questions} question(s) will be posted in <#{channel_id}> every day at {}.",
+                format::timestamp(daily::next_occurrence(hour), TimestampStyle::ShortTime)
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Stops posting the daily quiz.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "off",
+    name_localized("ja", "停止"),
+    description_localized("ja", "毎日のクイズの投稿をやめる")
+)]
+pub async fn stop_daily(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let content = if ctx.data().db.remove_daily_quiz(guild_id)? {
+        "The daily quiz was stopped"
+    } else {
+        "There is no daily quiz to stop"
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Shows when and where the daily quiz is posted.
+#[poise::command(
+    slash_command,
+    guild_only,
+    rename = "status",
+    name_localized("ja", "状態"),
+    description_localized("ja", "毎日のクイズの設定を見る")
+)]
+pub async fn daily_status(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let content = match ctx.data().db.daily_quiz(guild_id)? {
+        Some(quiz) => format!(
+            "A daily quiz of {} question(s) is posted in <#{}> every day. The next one is {}.",
+            quiz.questions,
+            quiz.channel_id,
+            format::timestamp(daily::next_occurrence(quiz.hour), TimestampStyle::Relative)
+        ),
+        None => "There is no daily quiz. Start one with `/daily set`.".to_string(),
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Creates the answer buttons of the `question`th question of the daily quiz of `day`.
+pub fn daily_answer_components(
+    day: u64,
+    question: u32,
+    options: &[String],
+) -> Vec<CreateActionRow> {
+    let buttons = options
+        .iter()
+        .enumerate()
+        .map(|(i, text)| {
+            CreateButton::new(format!("{ANSWER_PREFIX}{day},{question},{i}")).label(text)
+        })
+        .collect();
+
+    vec![CreateActionRow::Buttons(buttons)]
+}
+
+/// Determines whether `interaction` is a press of a daily quiz answer button.
+pub fn is_daily_answer_button(interaction: &ComponentInteraction) -> bool {
+    interaction.data.custom_id.starts_with(ANSWER_PREFIX)
+}
+
+/// Stores the presser's answer to a daily quiz question and privately tells them
+/// whether it was correct. Only the first answer to each question counts.
+pub async fn handle_daily_answer_button(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let parsed = interaction
+        .data
+        .custom_id
+        .strip_prefix(ANSWER_PREFIX)
+        .and_then(parse_answer_id);
+    let (Some(guild_id), Some((day, question, choice))) = (interaction.guild_id, parsed) else {
+        return Ok(());
+    };
+
+    let content = if day != daily::now().0 {
+        "This quiz is over. Its results are posted with the next one"
+    } else {
+        match data.db.daily_question_answer(guild_id, day, question)? {
+            Some((answer, entry_id)) => {
+                // The quiz asks everyone the same questions, so it can't leave
+                // out entries they saw recently, but notes the ones they answer.
+                if let Some(entry_id) = entry_id {
+                    data.manager.recent().mark(interaction.user.id, entry_id);
+                }
+                let correct = choice == answer;
+                if !data.db.add_daily_answer(
+                    guild_id,
+                    day,
+                    question,
+                    interaction.user.id,
+                    correct,
+                )? {
+                    "You've already answered this question"
+                } else if correct {
+                    "✅ Correct!"
+                } else {
+                    "❌ Not quite. Better luck on the next one!"
+                }
+            }
+            None => "This question no longer exists",
+        }
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Parses the day, question, and option of an answer button's custom_id
+/// without its [`ANSWER_PREFIX`].
+fn parse_answer_id(id: &str) -> Option<(u64, u32, usize)> {
+    let mut parts = id.split(',');
+    let day = parts.next()?.parse().ok()?;
+    let question = parts.next()?.parse().ok()?;
+    let choice = parts.next()?.parse().ok()?;
+
+    Some((day, question, choice))
+}
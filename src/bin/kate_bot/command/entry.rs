@@ -0,0 +1,46 @@
+use poise::{
+    CreateReply,
+    serenity_prelude::{CreateActionRow, CreateEmbed},
+};
+
+use super::lookup;
+use crate::{Context, Error};
+
+/// Shows the dictionary entry with an id, as answers and lookups show it.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "見出し"),
+    description_localized("ja", "IDで辞書の見出しを表示する（正解や辞書の結果に表示されるもの）")
+)]
+pub async fn entry(
+    ctx: Context<'_>,
+    #[description = "Id of the entry, its sequence number in JMDict"]
+    #[description_localized("ja", "見出しのID（JMDictの番号）")]
+    id: u32,
+) -> Result<(), Error> {
+    let Some(entry) = ctx.data().manager.provider().by_id(id) else {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("There is no entry with the id {id}"))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title(format!("Entry ・ 見出し：{id}"))
+                    .description(lookup::describe(&entry)),
+            )
+            .components(vec![CreateActionRow::Buttons(lookup::entry_buttons(
+                &entry,
+            ))]),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,365 @@
+use std::{collections::HashSet, time::Duration};
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty, srs::Grade};
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateAttachment,
+        CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+        CreateInteractionResponseMessage, GuildId,
+    },
+};
+use rand::{SeedableRng, rngs::StdRng};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Context, Error, dictionary,
+    error::UserError,
+    game::{Filters, PosFilter, Question},
+    image,
+};
+
+use super::{goal_reached_message, save_button, start::autocomplete_mode};
+
+/// Most due cards reviewed in a single session.
+const MAX_DUE: usize = 100;
+
+/// Reviews flashcards at your own pace.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "フラッシュカード"),
+    description_localized("ja", "自分のペースで単語カードを復習する")
+)]
+pub async fn flashcards(
+    ctx: Context<'_>,
+    #[name_localized("ja", "モード")]
+    #[description = "Pick a game mode"]
+    #[description_localized("ja", "ゲームのモードを選んでください")]
+    #[autocomplete = "autocomplete_mode"]
+    mode: String,
+    #[name_localized("ja", "レベル")]
+    #[description = "Only show words of this NLevel"]
+    #[description_localized("ja", "このNレベルの単語だけを表示する")]
+    #[autocomplete = "autocomplete_level"]
+    level: Option<String>,
+    #[name_localized("ja", "復習")]
+    #[description = "Only show cards that are due for review"]
+    #[description_localized("ja", "復習の時期が来たカードだけを表示する")]
+    due: Option<bool>,
+    #[name_localized("ja", "練習")]
+    #[description = "Review without scheduling cards or counting towards your stats"]
+    #[description_localized("ja", "カードの予定や統計に記録せずに復習する")]
+    practice: Option<bool>,
+) -> Result<(), Error> {
+    let Some(source) = ctx.data().manager.source(&mode) else {
+        ctx.send(
+            CreateReply::default()
+                .content("Unknown game mode. Please pick one from the list.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let levels = match level.map(|l| l.parse::<NLevel>()) {
+        None => NLevel::iter().collect(),
+        Some(Ok(level)) => vec![level],
+        Some(Err(_)) => {
+            ctx.send(
+                CreateReply::default()
+                    .content("Unknown NLevel. Please pick one from the list.")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let entries = if due.unwrap_or(false) {
+        let ids: HashSet<_> = ctx
+            .data()
+            .db
+            .due_cards(ctx.author().id, MAX_DUE)?
+            .into_iter()
+            .collect();
+        if ids.is_empty() {
+            ctx.send(
+                CreateReply::default()
+                    .content("You have no cards due for review. Come back later!")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(ids)
+    } else {
+        None
+    };
+
+    let filters = Filters {
+        levels,
+        pos: PosFilter::iter().collect(),
+        entries,
+        distractors: Difficulty::default(),
+        grades: Vec::new(),
+        excluded: ctx.data().manager.recent().recent(ctx.author().id),
+        adaptive: None,
+    };
+    let mut questions = source.questions(&filters, StdRng::from_rng(&mut rand::rng()));
+    let total = questions.len();
+
+    let Some(mut question) = questions.next() else {
+        return Err(UserError::PoolEmpty.into());
+    };
+
+    let practice = practice.unwrap_or(false);
+    let ids = CardIds::new(ctx.id());
+    let mut card = 1;
+    let front = card_embed(&question, &mode, card, total, practice);
+    ctx.send(
+        CreateReply::default()
+            .attachment(prompt_attachment(&question, ctx.guild_id()))
+            .embed(front)
+            .components(front_components(&ids))
+            .ephemeral(true),
+    )
+    .await?;
+
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .filter({
+            let ids = ids.all();
+            move |press| ids.contains(&press.data.custom_id)
+        })
+        .timeout(Duration::from_secs(300))
+        .await
+    {
+        let id = &press.data.custom_id;
+        let mut finished = false;
+        let mut day = None;
+
+        let message = if *id == ids.reveal {
+            let answer = &question.options[question.answer];
+            let marked = match &question.entry {
+                Some(entry) => dictionary::mark_spelling(entry, answer),
+                None => answer.clone(),
+            };
+            let mut description = format!(
+                "**{marked}**\n[Definition ・ 意味]({})",
+                dictionary::jisho_url(answer)
+            );
+            if let (Some(entry), Some(sense)) = (&question.entry, question.sense) {
+                description.push_str(&format!("\n{}", dictionary::sense_label(entry, sense)));
+            }
+            if let Some(entry) = &question.entry {
+                description.push_str(&format!("\n{}", dictionary::entry_links(entry)));
+            }
+            let mut embed =
+                card_embed(&question, &mode, card, total, practice).description(description);
+            if let Some(entry) = &question.entry {
+                if let Some(footnotes) = dictionary::spelling_footnotes(entry) {
+                    embed = embed.field("Spelling Notes ・ 表記の注記", footnotes, false);
+                }
+
+                let mut pos: Vec<_> = entry.senses.iter().flat_map(|s| &s.pos).collect();
+                pos.sort_unstable();
+                pos.dedup();
+                if !pos.is_empty() {
+                    let pos = pos
+                        .iter()
+                        .map(|p| p.bilingual())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    embed = embed.field("Part of Speech ・ 品詞", pos, false);
+                }
+
+                embed = embed.footer(CreateEmbedFooter::new(format!("{:?}", entry.levels())));
+            }
+
+            CreateInteractionResponseMessage::new()
+                .files([prompt_attachment(&question, ctx.guild_id())])
+                .embed(embed)
+                .components(back_components(&ids, &question))
+        } else {
+            let Some(grade) = ids.grade(id) else {
+                continue;
+            };
+            if let Some(entry) = &question.entry {
+                ctx.data().manager.recent().mark(ctx.author().id, entry.id);
+            }
+            if !practice {
+                // Only cards of dictionary words are scheduled.
+                if let Some(entry) = &question.entry {
+                    ctx.data()
+                        .db
+                        .review_card(ctx.author().id, entry.id, grade)?;
+                }
+                ctx.data().db.add_answer(
+                    ctx.author().id,
+                    ctx.guild_id(),
+                    question.entry.as_ref().map(|entry| entry.id),
+                    question.mode.as_deref().unwrap_or(&mode),
+                    grade != Grade::Again,
+                    // Cards are graded by the players themselves, so they aren't worth points.
+                    0,
+                )?;
+            }
+            day = Some(ctx.data().db.record_answer(ctx.author().id)?);
+
+            match questions.next() {
+                Some(next) => {
+                    question = next;
+                    card += 1;
+
+                    CreateInteractionResponseMessage::new()
+                        .files([prompt_attachment(&question, ctx.guild_id())])
+                        .embed(card_embed(&question, &mode, card, total, practice))
+                        .components(front_components(&ids))
+                }
+                None => {
+                    finished = true;
+
+                    CreateInteractionResponseMessage::new()
+                        .content(format!("Finished reviewing {total} card(s)!"))
+                        .files([])
+                        .embeds(Vec::new())
+                        .components(Vec::new())
+                }
+            }
+        };
+
+        press
+            .create_response(ctx, CreateInteractionResponse::UpdateMessage(message))
+            .await?;
+
+        if let Some(day) = day.filter(|d| d.just_reached()) {
+            ctx.send(
+                CreateReply::default()
+                    .content(goal_reached_message(ctx.author().id, &day))
+                    .ephemeral(true),
+            )
+            .await?;
+        }
+
+        if finished {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the NLevels of the dictionary whose name contains `partial`.
+pub(super) async fn autocomplete_level(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+
+    ctx.data()
+        .manager
+        .dictionary()
+        .levels()
+        .iter()
+        .map(|lvl| lvl.to_string())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect()
+}
+
+/// Identifiers of the buttons of a flashcard session.
+struct CardIds {
+    reveal: String,
+    again: String,
+    good: String,
+    easy: String,
+}
+
+impl CardIds {
+    fn new(invocation_id: u64) -> Self {
+        CardIds {
+            reveal: format!("{invocation_id}-reveal"),
+            again: format!("{invocation_id}-again"),
+            good: format!("{invocation_id}-good"),
+            easy: format!("{invocation_id}-easy"),
+        }
+    }
+
+    fn all(&self) -> [String; 4] {
+        [
+            self.reveal.clone(),
+            self.again.clone(),
+            self.good.clone(),
+            self.easy.clone(),
+        ]
+    }
+
+    /// Gets the grade of the grade button with the identifier `id`.
+    fn grade(&self, id: &str) -> Option<Grade> {
+        if id == self.again {
+            Some(Grade::Again)
+        } else if id == self.good {
+            Some(Grade::Good)
+        } else if id == self.easy {
+            Some(Grade::Easy)
+        } else {
+            None
+        }
+    }
+}
+
+/// Creates the image of `question`'s prompt, in the font of `guild_id`.
+fn prompt_attachment(question: &Question, guild_id: Option<GuildId>) -> CreateAttachment {
+    CreateAttachment::bytes(
+        image::text_to_image(&question.prompt, guild_id),
+        "prompt.png",
+    )
+    .description(question.prompt_description())
+}
+
+/// Creates the embed of the `card`th card of `total` showing the front of `question`.
+/// The question's own mode is shown in place of `mode` if it has one, and
+/// `practice` cards are labeled.
+fn card_embed(
+    question: &Question,
+    mode: &str,
+    card: usize,
+    total: usize,
+    practice: bool,
+) -> CreateEmbed {
+    let title = if practice {
+        format!("Flashcard {card}/{total} ・ Practice 練習")
+    } else {
+        format!("Flashcard {card}/{total}")
+    };
+
+    CreateEmbed::new()
+        .title(title)
+        .field(question.mode.as_deref().unwrap_or(mode), "", false)
+        .attachment("prompt.png")
+}
+
+/// Creates the button that flips the card over.
+fn front_components(ids: &CardIds) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(&ids.reveal).label("Reveal ・ 答え"),
+    ])]
+}
+
+/// Creates the buttons that grade how well `question` was remembered, along with
+/// a button to bookmark its word.
+fn back_components(ids: &CardIds, question: &Question) -> Vec<CreateActionRow> {
+    let mut buttons = vec![
+        CreateButton::new(&ids.again)
+            .label("Again ・ もう一度")
+            .style(ButtonStyle::Danger),
+        CreateButton::new(&ids.good)
+            .label("Good ・ 正解")
+            .style(ButtonStyle::Primary),
+        CreateButton::new(&ids.easy)
+            .label("Easy ・ 簡単")
+            .style(ButtonStyle::Success),
+    ];
+    if let Some(entry) = &question.entry {
+        buttons.push(save_button(entry.id));
+    }
+
+    vec![CreateActionRow::Buttons(buttons)]
+}
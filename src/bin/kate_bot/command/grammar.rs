@@ -0,0 +1,89 @@
+use std::{collections::HashSet, time::Duration};
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty};
+use poise::CreateReply;
+use strum::IntoEnumIterator;
+
+use crate::{
+    Context, Error,
+    game::{Filters, GameOptions, GrammarSource, PosFilter},
+};
+
+use super::start::DEFAULT_READ_TIME_MS;
+
+/// Starts a game matching JLPT grammar points to their meanings.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "文法"),
+    description_localized("ja", "JLPTの文法の意味を当てるゲームを始める")
+)]
+pub async fn grammar(
+    ctx: Context<'_>,
+    #[name_localized("ja", "レベル")]
+    #[description = "Only ask grammar points of this NLevel"]
+    #[description_localized("ja", "このNレベルの文法だけを出題する")]
+    #[autocomplete = "autocomplete_grammar_level"]
+    level: Option<String>,
+) -> Result<(), Error> {
+    let levels = match level.map(|l| l.to_uppercase().parse::<NLevel>()) {
+        None => NLevel::iter().collect(),
+        Some(Ok(level)) => vec![level],
+        Some(Err(_)) => {
+            ctx.send(
+                CreateReply::default()
+                    .content("Unknown NLevel. Please pick one from the list.")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let Some(source) = ctx.data().manager.source(GrammarSource::NAME) else {
+        ctx.send(
+            CreateReply::default()
+                .content("Grammar questions aren't available right now.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let filters = Filters {
+        levels,
+        pos: PosFilter::iter().collect(),
+        entries: None,
+        distractors: Difficulty::default(),
+        grades: Vec::new(),
+        excluded: HashSet::new(),
+        adaptive: None,
+    };
+    let options = GameOptions {
+        min_read_time: Duration::from_millis(DEFAULT_READ_TIME_MS),
+        ..Default::default()
+    };
+
+    ctx.data()
+        .manager
+        .start_game(&ctx, source, filters, options)?;
+    ctx.send(
+        CreateReply::default()
+            .content("Starting a grammar game ・ 文法ゲームを始めます")
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the NLevels whose name contains `partial`. Unlike words, grammar
+/// points of every level are bundled.
+async fn autocomplete_grammar_level(_ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+
+    NLevel::iter()
+        .map(|lvl| lvl.to_string())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect()
+}
@@ -0,0 +1,136 @@
+use jplearnbot::{
+    dictionary::NLevel,
+    rating::{INITIAL_RATING, PROVISIONAL_ANSWERS},
+    scoring::{self, MAX_SPEED_BONUS, SPEED_WINDOW},
+};
+use poise::{CreateReply, serenity_prelude::CreateEmbed};
+use strum::IntoEnumIterator;
+
+use crate::{Context, Error};
+
+/// Explains how the bot works.
+#[poise::command(
+    slash_command,
+    subcommands("scoring", "rating"),
+    name_localized("ja", "ヘルプ"),
+    description_localized("ja", "ボットの使い方")
+)]
+pub async fn help(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Explains how points are earned.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "得点"),
+    description_localized("ja", "得点の計算方法")
+)]
+pub async fn scoring(ctx: Context<'_>) -> Result<(), Error> {
+    let levels = NLevel::iter()
+        .rev()
+        .map(|lvl| format!("{lvl} ×{}", scoring::level_weight(lvl)))
+        .collect::<Vec<_>>()
+        .join(" ・ ");
+
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title("Scoring ・ 得点")
+                    .description(format!(
+                        "A correct answer is worth {} points, multiplied by the weights below. Wrong answers and flashcards aren't worth points.",
+                        scoring::BASE_POINTS
+                    ))
+                    .field("JLPT Level", format!("· {levels}\n· Words of several levels use the easiest one."), false)
+                    .field(
+                        "Difficulty",
+                        "· +10% for every star after the first.",
+                        false,
+                    )
+                    .field(
+                        "Speed",
+                        format!(
+                            "· Up to +{}% for answering right away, decreasing to nothing after {} seconds.\n· Timed from when Discord receives your answer, so a slow connection doesn't cost you.",
+                            MAX_SPEED_BONUS * 100.0,
+                            SPEED_WINDOW.as_secs()
+                        ),
+                        false,
+                    ),
+            )
+            .embed(
+                CreateEmbed::new()
+                    .title("得点の計算")
+                    .description(format!(
+                        "正解は{}点で、下記の倍率がかけられます。不正解とフラッシュカードには得点がありません。",
+                        scoring::BASE_POINTS
+                    ))
+                    .field("JLPTレベル", format!("· {levels}\n· 複数のレベルがある単語は一番易しいレベルになります。"), false)
+                    .field("難易度", "· 星が一つ増えるごとに+10%。", false)
+                    .field(
+                        "速さ",
+                        format!(
+                            "· すぐに答えると最大+{}%、{}秒かけて0になります。",
+                            MAX_SPEED_BONUS * 100.0,
+                            SPEED_WINDOW.as_secs()
+                        ),
+                        false,
+                    ),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Explains how ratings change.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "レート"),
+    description_localized("ja", "レートの変動の仕組み")
+)]
+pub async fn rating(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.send(
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title("Rating ・ レート")
+                    .description(format!(
+                        "Every server keeps a rating of its players, starting at {INITIAL_RATING}. Your first answer to each question of a game moves it up if right or down if wrong. Practice games aren't rated."
+                    ))
+                    .field(
+                        "Questions",
+                        "· Questions are rated too, higher for harder words and for wrong options that look more like the answer.\n· Getting a question rated above you right raises your rating more, and getting one rated below you wrong lowers it more.",
+                        false,
+                    )
+                    .field(
+                        "New Players",
+                        format!("· Ratings move twice as fast for the first {PROVISIONAL_ANSWERS} answers, and are marked with a ?."),
+                        false,
+                    ),
+            )
+            .embed(
+                CreateEmbed::new()
+                    .title("レートの仕組み")
+                    .description(format!(
+                        "サーバーごとにプレイヤーのレートがあり、{INITIAL_RATING}から始まります。ゲームの各問題への最初の回答が正解なら上がり、不正解なら下がります。練習ゲームはレートに影響しません。"
+                    ))
+                    .field(
+                        "問題",
+                        "· 問題にもレートがあり、難しい単語や答えに似ている選択肢ほど高くなります。\n· 自分より高い問題に正解するほど大きく上がり、低い問題を間違えるほど大きく下がります。",
+                        false,
+                    )
+                    .field(
+                        "新しいプレイヤー",
+                        format!("· 最初の{PROVISIONAL_ANSWERS}回答まではレートの変動が2倍で、?が付きます。"),
+                        false,
+                    ),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -18,8 +18,8 @@ pub async fn info(ctx: Context<'_>) -> Result<(), Error> {
                     "· Displayed text will be presented as an image as the question.\n· Answer type is the displayed available answer buttons.",
                     false
                 )
-                .field("JLPT Levels - (N4 - N1)",
-                    "· N-Level measures the level of understanding of basic Japanese. Unfortunately the source material did not include N5.",
+                .field("JLPT Levels - (N5 - N1)",
+                    "· N-Level measures the level of understanding of basic Japanese. N5 words are only available if an N5 vocabulary list was supplied.",
                     false
                 )
                 .field("Part-of-speech Categories",
@@ -32,8 +32,8 @@ pub async fn info(ctx: Context<'_>) -> Result<(), Error> {
                     "· 表示テキストは、問題として画像で提示されます。\n· 回答タイプは、選択可能な回答ボタンとして表示されます。",
                     false
                 )
-                .field("JLPTレベル - (N4 - N1)",
-                    "· Nレベルは、基本的な日本語の理解度を測るものです。残念ながら、N5の教材は含まれていません。",
+                .field("JLPTレベル - (N5 - N1)",
+                    "· Nレベルは、基本的な日本語の理解度を測るものです。N5の単語は、N5の語彙リストが提供された場合のみ出題されます。",
                     false
                 )
                 .field("品詞カテゴリ",
@@ -0,0 +1,170 @@
+use std::{collections::HashSet, time::Duration};
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty};
+use poise::CreateReply;
+use strum::IntoEnumIterator;
+
+use crate::{
+    Context, Error,
+    game::{Filters, GameOptions, KanjiMode, PosFilter},
+};
+
+use super::start::DEFAULT_READ_TIME_MS;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum KanjiModeChoice {
+    #[name = "On'yomi"]
+    #[name_localized("ja", "音読み")]
+    On,
+    #[name = "Kun'yomi"]
+    #[name_localized("ja", "訓読み")]
+    Kun,
+    #[name = "Meaning"]
+    #[name_localized("ja", "意味")]
+    Meaning,
+}
+
+impl From<KanjiModeChoice> for KanjiMode {
+    fn from(value: KanjiModeChoice) -> Self {
+        match value {
+            KanjiModeChoice::On => KanjiMode::On,
+            KanjiModeChoice::Kun => KanjiMode::Kun,
+            KanjiModeChoice::Meaning => KanjiMode::Meaning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum GradeChoice {
+    #[name = "Grade 1"]
+    #[name_localized("ja", "小学1年")]
+    Grade1,
+    #[name = "Grade 2"]
+    #[name_localized("ja", "小学2年")]
+    Grade2,
+    #[name = "Grade 3"]
+    #[name_localized("ja", "小学3年")]
+    Grade3,
+    #[name = "Grade 4"]
+    #[name_localized("ja", "小学4年")]
+    Grade4,
+    #[name = "Grade 5"]
+    #[name_localized("ja", "小学5年")]
+    Grade5,
+    #[name = "Grade 6"]
+    #[name_localized("ja", "小学6年")]
+    Grade6,
+    #[name = "Secondary school"]
+    #[name_localized("ja", "中学以降")]
+    Secondary,
+}
+
+impl GradeChoice {
+    /// Gets the grade KANJIDIC2 lists the kanji taught in this grade as.
+    fn grade(self) -> u8 {
+        match self {
+            GradeChoice::Grade1 => 1,
+            GradeChoice::Grade2 => 2,
+            GradeChoice::Grade3 => 3,
+            GradeChoice::Grade4 => 4,
+            GradeChoice::Grade5 => 5,
+            GradeChoice::Grade6 => 6,
+            GradeChoice::Secondary => 8,
+        }
+    }
+}
+
+/// Starts a game asking the readings or meanings of single kanji.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "漢字"),
+    description_localized("ja", "漢字の読みや意味を当てるゲームを始める")
+)]
+pub async fn kanji(
+    ctx: Context<'_>,
+    #[name_localized("ja", "モード")]
+    #[description = "What to pick for each kanji"]
+    #[description_localized("ja", "漢字ごとに選ぶもの")]
+    mode: KanjiModeChoice,
+    #[name_localized("ja", "レベル")]
+    #[description = "Only ask kanji of this NLevel"]
+    #[description_localized("ja", "このNレベルの漢字だけを出題する")]
+    #[autocomplete = "autocomplete_kanji_level"]
+    level: Option<String>,
+    #[name_localized("ja", "学年")]
+    #[description = "Only ask kanji taught in this school grade, instead of by NLevel"]
+    #[description_localized("ja", "Nレベルの代わりに、この学年で習う漢字だけを出題する")]
+    grade: Option<GradeChoice>,
+) -> Result<(), Error> {
+    if level.is_some() && grade.is_some() {
+        ctx.send(
+            CreateReply::default()
+                .content("Please pick either an NLevel or a grade, not both.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let levels = match level.map(|l| l.to_uppercase().parse::<NLevel>()) {
+        None => NLevel::iter().collect(),
+        Some(Ok(level)) => vec![level],
+        Some(Err(_)) => {
+            ctx.send(
+                CreateReply::default()
+                    .content("Unknown NLevel. Please pick one from the list.")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let mode = KanjiMode::from(mode);
+    let Some(source) = ctx.data().manager.source(&mode.to_string()) else {
+        ctx.send(
+            CreateReply::default()
+                .content("Kanji questions aren't available right now.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let filters = Filters {
+        levels,
+        pos: PosFilter::iter().collect(),
+        entries: None,
+        distractors: Difficulty::default(),
+        grades: grade.map(|g| vec![g.grade()]).unwrap_or_default(),
+        excluded: HashSet::new(),
+        adaptive: None,
+    };
+    let options = GameOptions {
+        min_read_time: Duration::from_millis(DEFAULT_READ_TIME_MS),
+        ..Default::default()
+    };
+
+    ctx.data()
+        .manager
+        .start_game(&ctx, source, filters, options)?;
+    ctx.send(
+        CreateReply::default()
+            .content("Starting a kanji game ・ 漢字ゲームを始めます")
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the NLevels whose name contains `partial`.
+async fn autocomplete_kanji_level(_ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+
+    NLevel::iter()
+        .map(|lvl| lvl.to_string())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect()
+}
@@ -0,0 +1,218 @@
+use std::{cmp::Reverse, time::Duration};
+
+use poise::{ChoiceParameter, CreateReply, serenity_prelude::UserId};
+
+use crate::{Context, Error, db::AnswerStats, format, pagination};
+
+use super::rank::rating_label;
+
+/// How many players are ranked per page of the leaderboard.
+const PAGE_SIZE: usize = 10;
+
+/// Fewest answers a player needs to be ranked by accuracy, so a few lucky
+/// answers don't top the leaderboard.
+const MIN_ACCURACY_ANSWERS: u32 = 20;
+
+/// Choices of what players are ranked by.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum LeaderboardChoice {
+    #[name = "Points"]
+    #[name_localized("ja", "得点")]
+    Points,
+    #[name = "Correct answers"]
+    #[name_localized("ja", "正解数")]
+    Correct,
+    #[name = "Accuracy"]
+    #[name_localized("ja", "正解率")]
+    Accuracy,
+    #[name = "Best streak"]
+    #[name_localized("ja", "最高連続正解")]
+    Streak,
+    #[name = "Rating: this server"]
+    #[name_localized("ja", "レート：このサーバー")]
+    Rating,
+}
+
+/// Choices of how far back answers are counted.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum PeriodChoice {
+    #[name = "This week"]
+    #[name_localized("ja", "今週")]
+    Week,
+    #[name = "This month"]
+    #[name_localized("ja", "今月")]
+    Month,
+    #[name = "All time"]
+    #[name_localized("ja", "全期間")]
+    AllTime,
+}
+
+impl PeriodChoice {
+    /// How long ago the earliest counted answers can be, or [`None`] if there is no limit.
+    fn duration(self) -> Option<Duration> {
+        const DAY: u64 = 24 * 60 * 60;
+
+        match self {
+            PeriodChoice::Week => Some(Duration::from_secs(7 * DAY)),
+            PeriodChoice::Month => Some(Duration::from_secs(30 * DAY)),
+            PeriodChoice::AllTime => None,
+        }
+    }
+}
+
+/// Choices of whose answers are counted.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum ScopeChoice {
+    #[name = "This server"]
+    #[name_localized("ja", "このサーバー")]
+    Server,
+    #[name = "Everywhere"]
+    #[name_localized("ja", "全体")]
+    Global,
+}
+
+/// Ranks the players of the server or of everywhere.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "ランキング"),
+    description_localized("ja", "サーバーか全体のプレイヤーのランキング")
+)]
+pub async fn leaderboard(
+    ctx: Context<'_>,
+    #[name_localized("ja", "基準")]
+    #[description = "What players are ranked by. Points by default"]
+    #[description_localized("ja", "ランキングの基準（デフォルトは得点）")]
+    by: Option<LeaderboardChoice>,
+    #[name_localized("ja", "期間")]
+    #[description = "How far back answers are counted. All time by default"]
+    #[description_localized("ja", "回答を数える期間（デフォルトは全期間）")]
+    period: Option<PeriodChoice>,
+    #[name_localized("ja", "範囲")]
+    #[description = "Whose answers are counted. This server's by default, outside of one everyone's"]
+    #[description_localized(
+        "ja",
+        "数える回答の範囲（デフォルトはこのサーバー、サーバー外では全体）"
+    )]
+    scope: Option<ScopeChoice>,
+) -> Result<(), Error> {
+    let by = by.unwrap_or(LeaderboardChoice::Points);
+    let period = period.unwrap_or(PeriodChoice::AllTime);
+    let scope = match (scope, ctx.guild_id()) {
+        (Some(scope), _) => scope,
+        (None, Some(_)) => ScopeChoice::Server,
+        (None, None) => ScopeChoice::Global,
+    };
+
+    let guild_id = match scope {
+        ScopeChoice::Server => ctx.guild_id(),
+        ScopeChoice::Global => None,
+    };
+    let needs_server =
+        matches!(scope, ScopeChoice::Server) || matches!(by, LeaderboardChoice::Rating);
+    if needs_server && ctx.guild_id().is_none() {
+        ctx.send(
+            CreateReply::default()
+                .content("That leaderboard is only kept in servers")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let ranks: Vec<_> = match (by, ctx.guild_id()) {
+        // Ratings aren't counted from answers, so they are only ever of the server and current.
+        (LeaderboardChoice::Rating, Some(guild_id)) => ctx
+            .data()
+            .db
+            .rating_leaderboard(guild_id)?
+            .into_iter()
+            .map(|(user_id, rating)| (user_id, rating_label(&rating)))
+            .collect(),
+        _ => {
+            let mut players = ctx.data().db.player_stats(guild_id, period.duration())?;
+            players.retain(|(_, stats)| is_ranked(by, stats));
+            sort_players(by, &mut players);
+
+            players
+                .into_iter()
+                .map(|(user_id, stats)| (user_id, stat_label(by, &stats, ctx.locale())))
+                .collect()
+        }
+    };
+
+    if ranks.is_empty() {
+        ctx.say("Nobody has been ranked here yet.").await?;
+        return Ok(());
+    }
+
+    let lines: Vec<_> = ranks
+        .iter()
+        .enumerate()
+        .map(|(i, (user_id, score))| format!("{}. <@{user_id}> ・ {score}", i + 1))
+        .collect();
+    let pages: Vec<_> = lines
+        .chunks(PAGE_SIZE)
+        .map(|chunk| chunk.join("\n"))
+        .collect();
+
+    let title = match by {
+        LeaderboardChoice::Rating => format!("Leaderboard ・ ランキング: {}", by.name()),
+        _ => format!(
+            "Leaderboard ・ ランキング: {}, {}, {}",
+            by.name(),
+            period.name(),
+            scope.name()
+        ),
+    };
+
+    pagination::paginate(ctx, &title, &pages, false).await
+}
+
+/// Determines whether a player with `stats` is ranked by `by`. Players who
+/// haven't earned anything of it aren't.
+fn is_ranked(by: LeaderboardChoice, stats: &AnswerStats) -> bool {
+    match by {
+        LeaderboardChoice::Points => stats.points > 0,
+        LeaderboardChoice::Correct | LeaderboardChoice::Streak => stats.correct > 0,
+        LeaderboardChoice::Accuracy => stats.answered >= MIN_ACCURACY_ANSWERS,
+        LeaderboardChoice::Rating => true,
+    }
+}
+
+/// Sorts `players` by `by`, the best first.
+fn sort_players(by: LeaderboardChoice, players: &mut [(UserId, AnswerStats)]) {
+    match by {
+        LeaderboardChoice::Points => players.sort_by_key(|(_, stats)| Reverse(stats.points)),
+        LeaderboardChoice::Correct => players.sort_by_key(|(_, stats)| Reverse(stats.correct)),
+        LeaderboardChoice::Streak => players.sort_by_key(|(_, stats)| Reverse(stats.best_streak)),
+        // Compared as fractions, the one with more answers first when equal.
+        LeaderboardChoice::Accuracy => players.sort_by(|(_, a), (_, b)| {
+            (u64::from(b.correct) * u64::from(a.answered))
+                .cmp(&(u64::from(a.correct) * u64::from(b.answered)))
+                .then(b.answered.cmp(&a.answered))
+        }),
+        LeaderboardChoice::Rating => {}
+    }
+}
+
+/// Formats the stat of `stats` that players are ranked `by`, with the numbers
+/// of `locale`.
+fn stat_label(by: LeaderboardChoice, stats: &AnswerStats, locale: Option<&str>) -> String {
+    match by {
+        LeaderboardChoice::Points => format!("{} pts", format::number(stats.points, locale)),
+        LeaderboardChoice::Correct => {
+            format!("{} correct", format::number(stats.correct, locale))
+        }
+        LeaderboardChoice::Accuracy => format!(
+            "{}% ({}/{})",
+            stats.correct * 100 / stats.answered,
+            format::number(stats.correct, locale),
+            format::number(stats.answered, locale)
+        ),
+        LeaderboardChoice::Streak => {
+            format!("{} in a row", format::number(stats.best_streak, locale))
+        }
+        LeaderboardChoice::Rating => String::new(),
+    }
+}
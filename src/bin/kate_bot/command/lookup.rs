@@ -0,0 +1,84 @@
+use jplearnbot::dictionary::DictEntry;
+use poise::serenity_prelude::CreateButton;
+
+use super::{save_button, stroke_order_button};
+use crate::{Context, Error, dictionary, pagination};
+
+/// Most entries a lookup lists.
+const MAX_RESULTS: usize = 25;
+
+/// Longest description of an entry, within Discord's limit on embed descriptions.
+const MAX_DESCRIPTION_LEN: usize = 4000;
+
+/// Looks up a word in the dictionary by kanji, kana, or English meaning.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "辞書"),
+    description_localized("ja", "漢字・かな・英語の意味で単語を調べる")
+)]
+pub async fn lookup(
+    ctx: Context<'_>,
+    #[name_localized("ja", "単語")]
+    #[description = "Kanji, kana, romaji, or English"]
+    #[description_localized("ja", "漢字・かな・ローマ字・英語")]
+    #[max_length = 50]
+    word: String,
+) -> Result<(), Error> {
+    let word = word.trim();
+    let entries = ctx.data().manager.provider().lookup(word, MAX_RESULTS);
+    let pages: Vec<_> = entries.iter().map(describe).collect();
+
+    if pages.is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(format!("No words match `{word}`"))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    pagination::paginate_with(
+        ctx,
+        &format!("Lookup ・ 辞書：{word}"),
+        &pages,
+        true,
+        |page| entry_buttons(&entries[page]),
+    )
+    .await
+}
+
+/// Creates the buttons to save `entry` and to show the stroke order of its
+/// first spelling, if it has kanji with one.
+pub(super) fn entry_buttons(entry: &DictEntry) -> Vec<CreateButton> {
+    let mut buttons = vec![save_button(entry.id)];
+    buttons.extend(
+        entry
+            .kanjis
+            .first()
+            .and_then(|k| stroke_order_button(&k.text)),
+    );
+
+    buttons
+}
+
+/// Describes `entry` under a heading of its first spelling, which is
+/// [described](dictionary::describe_entry) in full below it, followed by
+/// [links](dictionary::entry_links) to it.
+pub(super) fn describe(entry: &DictEntry) -> String {
+    let spelling = entry
+        .kanjis
+        .first()
+        .map(|k| &k.text)
+        .or(entry.readings.first().map(|r| &r.text))
+        .map(|text| dictionary::mark_spelling(entry, text))
+        .unwrap_or_default();
+    let links = dictionary::entry_links(entry);
+    let description = format!("## {spelling}\n{}", dictionary::describe_entry(entry));
+
+    format!(
+        "{}\n\n{links}",
+        dictionary::truncate(&description, MAX_DESCRIPTION_LEN - links.len())
+    )
+}
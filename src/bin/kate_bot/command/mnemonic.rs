@@ -0,0 +1,188 @@
+use poise::CreateReply;
+
+use super::bookmarks::entry_summary;
+use crate::{Context, Error, pagination};
+
+/// Longest mnemonic that can be shared, in characters.
+const MAX_MNEMONIC_LEN: u32 = 300;
+
+/// How many pending mnemonics are listed per page.
+const PAGE_SIZE: usize = 5;
+
+/// Most pending mnemonics that are listed.
+const MAX_PENDING: usize = 100;
+
+/// Shares a mnemonic for a word with the server, shown once moderators approve it.
+#[poise::command(
+    slash_command,
+    guild_only,
+    user_cooldown = 10,
+    name_localized("ja", "覚え方"),
+    description_localized("ja", "単語の覚え方をサーバーで共有する（モデレーターの承認後に表示）")
+)]
+pub async fn mnemonic(
+    ctx: Context<'_>,
+    #[name_localized("ja", "単語")]
+    #[description = "Kanji, kana, romaji, or English"]
+    #[description_localized("ja", "漢字・かな・ローマ字・英語")]
+    #[max_length = 50]
+    word: String,
+    #[name_localized("ja", "覚え方")]
+    #[description = "How you remember the word"]
+    #[description_localized("ja", "単語の覚え方")]
+    #[max_length = 300]
+    text: String,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+
+    let text = text.trim();
+    let entry = ctx
+        .data()
+        .manager
+        .provider()
+        .lookup(word.trim(), 1)
+        .into_iter()
+        .next();
+    let content = if !db.has_mnemonic_pool(guild_id)? {
+        "This server doesn't share mnemonics. Moderators can start with `/settings mnemonics`"
+            .to_string()
+    } else if text.is_empty() || text.chars().count() > MAX_MNEMONIC_LEN as usize {
+        format!("Mnemonics can be 1 to {MAX_MNEMONIC_LEN} characters long")
+    } else if let Some(entry) = entry {
+        db.submit_mnemonic(guild_id, entry.id, ctx.author().id, text)?;
+        format!(
+            "Your mnemonic for {} will be shown once a moderator approves it ・ 承認待ちです",
+            entry_summary(&entry)
+        )
+    } else {
+        format!("No words match `{}`", word.trim())
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Reviews the mnemonics members shared with the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    subcommands("pending_mnemonics", "approve", "reject"),
+    name_localized("ja", "覚え方の管理"),
+    description_localized("ja", "メンバーが共有した覚え方を確認する")
+)]
+pub async fn mnemonics(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Lists the mnemonics waiting for approval, the oldest first.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    rename = "pending",
+    name_localized("ja", "承認待ち"),
+    description_localized("ja", "承認待ちの覚え方を見る")
+)]
+pub async fn pending_mnemonics(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let pending = ctx.data().db.pending_mnemonics(guild_id, MAX_PENDING)?;
+    let provider = ctx.data().manager.provider();
+
+    let lines: Vec<_> = pending
+        .iter()
+        .map(|mnemonic| {
+            let word = provider
+                .by_id(mnemonic.entry_id)
+                .map_or_else(|| "a removed word".to_string(), |e| entry_summary(&e));
+            format!(
+                "`{}` {word} ・ by <@{}>\n> {}",
+                mnemonic.id, mnemonic.user_id, mnemonic.text
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no mnemonics waiting for approval")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pages: Vec<_> = lines
+        .chunks(PAGE_SIZE)
+        .map(|chunk| chunk.join("\n\n"))
+        .collect();
+
+    pagination::paginate(ctx, "Pending Mnemonics ・ 承認待ちの覚え方", &pages, true).await
+}
+
+/// Shows a pending mnemonic to the server when its word is answered.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    name_localized("ja", "承認"),
+    description_localized("ja", "覚え方を承認して表示する")
+)]
+pub async fn approve(
+    ctx: Context<'_>,
+    #[description = "Id of the mnemonic, as listed by /mnemonics pending"]
+    #[description_localized("ja", "覚え方のid（/mnemonics pendingに表示されるもの）")]
+    id: i64,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let content = if ctx.data().db.approve_mnemonic(guild_id, id)? {
+        format!("Mnemonic {id} will be shown when its word is answered")
+    } else {
+        format!("There is no pending mnemonic with the id {id}")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Removes a mnemonic, whether pending or already approved.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    name_localized("ja", "却下"),
+    description_localized("ja", "覚え方を削除する")
+)]
+pub async fn reject(
+    ctx: Context<'_>,
+    #[description = "Id of the mnemonic"]
+    #[description_localized("ja", "覚え方のid")]
+    id: i64,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let content = if ctx.data().db.remove_mnemonic(guild_id, id)? {
+        format!("Removed mnemonic {id}")
+    } else {
+        format!("There is no mnemonic with the id {id}")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,344 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty, quiz::Mode, scoring};
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        ComponentInteractionCollector, CreateActionRow, CreateAttachment, CreateButton,
+        CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+        CreateInteractionResponseMessage, GuildId,
+    },
+};
+use rand::{SeedableRng, rngs::StdRng};
+use strum::IntoEnumIterator;
+use tokio::time::Instant;
+
+use crate::{
+    Context, Error,
+    db::MockTestResult,
+    dictionary,
+    format::{self, TimestampStyle},
+    game::{Filters, PosFilter, Question},
+    image,
+};
+
+use super::flashcards::autocomplete_level;
+
+/// How many questions a mock test asks.
+const QUESTION_COUNT: usize = 20;
+
+/// How long the vocabulary section of a mock test lasts.
+const SECTION_TIME: Duration = Duration::from_secs(10 * 60);
+
+/// Percentage of correct answers needed to pass a mock test.
+const PASS_PERCENT: u32 = 60;
+
+/// How many previous results are shown after a mock test.
+const HISTORY: usize = 5;
+
+/// Takes a timed JLPT-style vocabulary test.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "模擬試験"),
+    description_localized("ja", "JLPT形式の語彙の模擬試験を受ける")
+)]
+pub async fn mocktest(
+    ctx: Context<'_>,
+    #[name_localized("ja", "レベル")]
+    #[description = "Pick the NLevel to be tested on"]
+    #[description_localized("ja", "試験のNレベルを選んでください")]
+    #[autocomplete = "autocomplete_level"]
+    level: String,
+) -> Result<(), Error> {
+    let Ok(level) = level.to_uppercase().parse::<NLevel>() else {
+        ctx.send(
+            CreateReply::default()
+                .content("Unknown NLevel. Please pick one from the list.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    // Like the real test, questions come in every format.
+    let questions: Vec<_> = match ctx.data().manager.source(&Mode::Mixed.to_string()) {
+        Some(source) => source
+            .questions(
+                &Filters {
+                    levels: vec![level],
+                    pos: PosFilter::iter().collect(),
+                    entries: None,
+                    distractors: Difficulty::default(),
+                    grades: Vec::new(),
+                    excluded: ctx.data().manager.recent().recent(ctx.author().id),
+                    adaptive: None,
+                },
+                StdRng::from_rng(&mut rand::rng()),
+            )
+            .take(QUESTION_COUNT)
+            .collect(),
+        None => Vec::new(),
+    };
+    if questions.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no words to make a mock test from")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let ids: Vec<_> = (0..questions[0].options.len())
+        .map(|i| format!("{}-{i}", ctx.id()))
+        .collect();
+    let deadline = Instant::now() + SECTION_TIME;
+    let ends_at = (SystemTime::now() + SECTION_TIME)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let reply = ctx
+        .send(
+            CreateReply::default()
+                .attachment(prompt_attachment(&questions[0], ctx.guild_id()))
+                .embed(question_embed(level, &questions, 0, ends_at))
+                .components(answer_components(&ids, &questions[0]))
+                .ephemeral(true),
+        )
+        .await?;
+
+    // No feedback is given until the end, so only the choices and how long
+    // they took are kept.
+    let mut choices = Vec::new();
+    let mut response_times = Vec::new();
+    let mut shown_at = Instant::now();
+    while choices.len() < questions.len() {
+        let Some(press) = ComponentInteractionCollector::new(ctx)
+            .author_id(ctx.author().id)
+            .filter({
+                let ids = ids.clone();
+                move |press| ids.contains(&press.data.custom_id)
+            })
+            .timeout(deadline.saturating_duration_since(Instant::now()))
+            .await
+        else {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content("Time's up! ・ 時間切れ！")
+                        .components(Vec::new()),
+                )
+                .await?;
+            break;
+        };
+
+        let Some(choice) = ids.iter().position(|id| *id == press.data.custom_id) else {
+            continue;
+        };
+        choices.push(choice);
+        response_times.push(shown_at.elapsed());
+        shown_at = Instant::now();
+
+        let message = match questions.get(choices.len()) {
+            Some(next) => CreateInteractionResponseMessage::new()
+                .files([prompt_attachment(next, ctx.guild_id())])
+                .embed(question_embed(level, &questions, choices.len(), ends_at))
+                .components(answer_components(&ids, next)),
+            None => CreateInteractionResponseMessage::new()
+                .content("Section finished! ・ 終了！")
+                .files([])
+                .embeds(Vec::new())
+                .components(Vec::new()),
+        };
+        press
+            .create_response(ctx, CreateInteractionResponse::UpdateMessage(message))
+            .await?;
+    }
+
+    // The question after the last answer was shown too, unless time ran out.
+    let shown = questions.len().min(choices.len() + 1);
+    for entry in questions[..shown].iter().filter_map(|q| q.entry.as_ref()) {
+        ctx.data().manager.recent().mark(ctx.author().id, entry.id);
+    }
+
+    let total = questions.len() as u32;
+    let score = questions
+        .iter()
+        .zip(&choices)
+        .filter(|(q, choice)| q.answer == **choice)
+        .count() as u32;
+
+    for ((question, &choice), &response_time) in questions.iter().zip(&choices).zip(&response_times)
+    {
+        let correct = question.answer == choice;
+        ctx.data().db.add_answer(
+            ctx.author().id,
+            ctx.guild_id(),
+            question.entry.as_ref().map(|entry| entry.id),
+            question.mode.as_deref().unwrap_or_default(),
+            correct,
+            scoring::points(question.entry.as_deref(), response_time, correct),
+        )?;
+    }
+
+    let history = ctx.data().db.mock_tests(ctx.author().id, level, HISTORY)?;
+    ctx.data()
+        .db
+        .add_mock_test(ctx.author().id, level, score, total)?;
+
+    ctx.send(
+        CreateReply::default()
+            .embed(result_embed(
+                level, &questions, &choices, score, total, &history,
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Creates the image of `question`'s prompt, in the font of `guild_id`.
+fn prompt_attachment(question: &Question, guild_id: Option<GuildId>) -> CreateAttachment {
+    CreateAttachment::bytes(
+        image::text_to_image(&question.prompt, guild_id),
+        "prompt.png",
+    )
+    .description(question.prompt_description())
+}
+
+/// Creates the embed of the `index`th of `questions` in a test of `level` that ends
+/// at the Unix timestamp `ends_at`.
+fn question_embed(
+    level: NLevel,
+    questions: &[Question],
+    index: usize,
+    ends_at: u64,
+) -> CreateEmbed {
+    let question = &questions[index];
+
+    CreateEmbed::new()
+        .title(format!(
+            "{level} Mock Test ・ 模擬試験 — {}/{}",
+            index + 1,
+            questions.len()
+        ))
+        .description(format!(
+            "Time left ・ 残り時間: {}",
+            format::timestamp(ends_at, TimestampStyle::Relative)
+        ))
+        .field(
+            question.mode.as_deref().unwrap_or("Vocabulary ・ 語彙"),
+            "",
+            false,
+        )
+        .attachment("prompt.png")
+}
+
+/// Creates the answer buttons of `question`.
+fn answer_components(ids: &[String], question: &Question) -> Vec<CreateActionRow> {
+    let buttons = ids
+        .iter()
+        .zip(&question.options)
+        .map(|(id, text)| CreateButton::new(id).label(text))
+        .collect();
+
+    vec![CreateActionRow::Buttons(buttons)]
+}
+
+/// Creates the embed of the final score of a test of `level`, listing the missed
+/// `questions` and the previous results of `history`. Questions without a
+/// choice in `choices` weren't answered in time.
+fn result_embed(
+    level: NLevel,
+    questions: &[Question],
+    choices: &[usize],
+    score: u32,
+    total: u32,
+    history: &[MockTestResult],
+) -> CreateEmbed {
+    const MAX_MISSED: usize = 10;
+    // Discord's limit on the values of embed fields.
+    const MAX_FIELD_LEN: usize = 1024;
+
+    let percent = score * 100 / total.max(1);
+    let passed = percent >= PASS_PERCENT;
+
+    let missed: Vec<_> = questions
+        .iter()
+        .enumerate()
+        .filter(|(i, q)| choices.get(*i) != Some(&q.answer))
+        .map(|(i, q)| {
+            let choice = choices
+                .get(i)
+                .map_or("(no answer)", |&c| q.options[c].as_str());
+            let entry = q
+                .entry
+                .as_ref()
+                .map(|entry| format!(" `{}`", entry.id))
+                .unwrap_or_default();
+            format!(
+                "{} ▶ **{}** ~~{choice}~~{entry}",
+                q.prompt, q.options[q.answer]
+            )
+        })
+        .collect();
+    let mut missed_list = missed
+        .iter()
+        .take(MAX_MISSED)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if missed.len() > MAX_MISSED {
+        missed_list.push_str(&format!("\n…and {} more", missed.len() - MAX_MISSED));
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("{level} Mock Test Results ・ 模擬試験の結果"))
+        .field(
+            "Score ・ 得点",
+            format!("{score}/{total} ({percent}%)"),
+            true,
+        )
+        .field(
+            "Result ・ 結果",
+            if passed {
+                "✅ Pass ・ 合格"
+            } else {
+                "❌ Fail ・ 不合格"
+            },
+            true,
+        )
+        .footer(CreateEmbedFooter::new(format!(
+            "{PASS_PERCENT}% is needed to pass"
+        )));
+
+    if !missed.is_empty() {
+        embed = embed.field(
+            "Missed ・ 間違い",
+            dictionary::truncate(&missed_list, MAX_FIELD_LEN),
+            false,
+        );
+    }
+
+    if !history.is_empty() {
+        let history = history
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} {}/{}",
+                    format::timestamp(r.taken_at, TimestampStyle::ShortDate),
+                    r.score,
+                    r.total
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("Previous ・ 過去の結果", history, false);
+    }
+
+    embed
+}
@@ -0,0 +1,24 @@
+use crate::{Context, Error, game::SessionId};
+
+/// Pauses the active game in this channel until it is resumed with /resume.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "一時停止"),
+    description_localized("ja", "ゲームを一時停止する（/resumeで再開）")
+)]
+pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
+    let manager = &ctx.data().manager;
+    let session_id = SessionId::of(&ctx);
+
+    if !manager.is_active(session_id) {
+        ctx.say("There is no active game to pause.").await?;
+    } else if manager.pause(session_id, ctx.author().id).await {
+        ctx.say("Pausing game...").await?;
+    } else {
+        ctx.say("The game is already paused. Continue it with `/resume`.")
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty};
+use poise::{
+    CreateReply,
+    serenity_prelude::{CreateAttachment, CreateEmbed, GuildId},
+};
+use rand::{SeedableRng, rngs::StdRng};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Context, Error,
+    game::{Filters, PosFilter, Question},
+    image,
+};
+
+/// Most embeds Discord shows in a message.
+const MAX_EMBEDS: usize = 10;
+
+/// Shows how a word is asked about in every game mode, without starting a game.
+#[poise::command(
+    slash_command,
+    user_cooldown = 5,
+    name_localized("ja", "プレビュー"),
+    description_localized("ja", "ゲームを始めずに、単語の問題が各モードでどう出題されるかを見る")
+)]
+pub async fn preview(
+    ctx: Context<'_>,
+    #[name_localized("ja", "単語")]
+    #[description = "The id of an entry, or a word to look up"]
+    #[description_localized("ja", "エントリーのID、または調べる単語")]
+    #[max_length = 50]
+    entry: String,
+) -> Result<(), Error> {
+    let entry = entry.trim();
+    let manager = &ctx.data().manager;
+    let found = entry
+        .parse()
+        .ok()
+        .and_then(|id| manager.dictionary().entry(id))
+        .or_else(|| manager.dictionary().lookup(entry).next());
+    let Some(found) = found else {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("No words match `{entry}`"))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+    let id = found.id();
+
+    ctx.defer_ephemeral().await?;
+
+    let filters = Filters {
+        levels: NLevel::iter().collect(),
+        pos: PosFilter::iter().collect(),
+        entries: Some(HashSet::from([id])),
+        distractors: Difficulty::default(),
+        grades: Vec::new(),
+        excluded: HashSet::new(),
+        adaptive: None,
+    };
+    // Modes that don't ask about words of the dictionary ignore the entry, and
+    // mixed modes ask in one of the other modes, so both are left out.
+    let previews: Vec<_> = manager
+        .sources()
+        .iter()
+        .filter_map(|source| {
+            let question = source
+                .questions(&filters, StdRng::seed_from_u64(id.into()))
+                .next()?;
+            let about_entry = question.entry.as_ref().is_some_and(|e| e.id == id);
+
+            (about_entry && question.mode.is_none()).then(|| (source.name().to_string(), question))
+        })
+        .collect();
+
+    if previews.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "No game mode asks about entry {id}. It may be blocked or have no level."
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id();
+    for (chunk, previews) in previews.chunks(MAX_EMBEDS).enumerate() {
+        let mut reply = CreateReply::default().ephemeral(true);
+        if chunk == 0 {
+            reply = reply.content(format!(
+                "Questions about entry {id} ・ エントリー{id}の問題"
+            ));
+        }
+
+        for (i, (mode, question)) in previews.iter().enumerate() {
+            let (embed, attachment) = preview_embed(mode, question, i, guild_id);
+            reply = reply.embed(embed);
+            if let Some(attachment) = attachment {
+                reply = reply.attachment(attachment);
+            }
+        }
+
+        ctx.send(reply).await?;
+    }
+
+    Ok(())
+}
+
+/// Shows `question` as it would be asked in `mode`, with its options listed and
+/// the answer marked. The prompt is drawn as in games, as the `i`th attachment
+/// of the message, unless it is read aloud.
+fn preview_embed(
+    mode: &str,
+    question: &Question,
+    i: usize,
+    guild_id: Option<GuildId>,
+) -> (CreateEmbed, Option<CreateAttachment>) {
+    let mut description: Vec<_> = question
+        .options
+        .iter()
+        .enumerate()
+        .map(|(j, option)| {
+            if j == question.answer {
+                format!("✅ **{option}**")
+            } else {
+                format!("▫️ {option}")
+            }
+        })
+        .collect();
+    if let Some(note) = &question.note {
+        description.push(format!("\n{note}"));
+    }
+
+    let mut embed = CreateEmbed::new().title(mode);
+    if question.spoken {
+        description.insert(
+            0,
+            format!("🔊 Read aloud ・ 読み上げ: {}\n", question.prompt),
+        );
+        return (embed.description(description.join("\n")), None);
+    }
+
+    let name = format!("prompt-{i}.png");
+    let image = if question.single_kana {
+        image::kana_to_image(&question.prompt, guild_id)
+    } else {
+        image::text_to_image(&question.prompt, guild_id)
+    };
+    embed = embed
+        .description(description.join("\n"))
+        .image(format!("attachment://{name}"));
+
+    (embed, Some(CreateAttachment::bytes(image, name)))
+}
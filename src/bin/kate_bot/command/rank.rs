@@ -0,0 +1,68 @@
+use jplearnbot::rating::{PROVISIONAL_ANSWERS, Rating};
+use poise::{
+    CreateReply,
+    serenity_prelude::{CreateEmbed, CreateEmbedFooter, User},
+};
+
+use crate::{Context, Error};
+
+/// Formats `rating` as a whole number, marked with a `?` while it is provisional.
+pub(super) fn rating_label(rating: &Rating) -> String {
+    let mark = if rating.is_provisional() { "?" } else { "" };
+
+    format!("{:.0}{mark}", rating.value)
+}
+
+/// Shows a player's rating in the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    user_cooldown = 3,
+    name_localized("ja", "レート"),
+    description_localized("ja", "サーバーでのプレイヤーのレートを見る")
+)]
+pub async fn rank(
+    ctx: Context<'_>,
+    #[name_localized("ja", "ユーザー")]
+    #[description = "Whose rating to show. Yours by default"]
+    #[description_localized("ja", "レートを見るユーザー（デフォルトは自分）")]
+    user: Option<User>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let user = user.as_ref().unwrap_or(ctx.author());
+
+    let db = &ctx.data().db;
+    let (Some(rating), Some(place)) = (
+        db.rating(guild_id, user.id)?,
+        db.rating_rank(guild_id, user.id)?,
+    ) else {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "{} hasn't answered any questions in this server yet.",
+                    user.name
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("{}'s Rating ・ レート", user.name))
+        .thumbnail(user.face())
+        .field("Rating ・ レート", rating_label(&rating), true)
+        .field("Rank ・ 順位", format!("#{place}"), true)
+        .field("Answers ・ 回答数", rating.answers.to_string(), true);
+    if rating.is_provisional() {
+        embed = embed.footer(CreateEmbedFooter::new(format!(
+            "Provisional until {PROVISIONAL_ANSWERS} answers ・ {PROVISIONAL_ANSWERS}回答までは暫定"
+        )));
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, CreateMessage};
+
+use crate::{Context, Error, audio, game::SessionId, image};
+
+/// Re-posts the last game's questions in this channel with hidden answers.
+#[poise::command(
+    slash_command,
+    guild_cooldown = 60,
+    name_localized("ja", "リプレイ"),
+    description_localized("ja", "前回のゲームの問題をもう一度見る")
+)]
+pub async fn replay(ctx: Context<'_>) -> Result<(), Error> {
+    let session_id = SessionId::of(&ctx);
+    let manager = &ctx.data().manager;
+
+    if manager.is_active(session_id) {
+        ctx.say("Please wait for the active game to finish.")
+            .await?;
+        return Ok(());
+    }
+
+    let Some(records) = manager.replay(session_id) else {
+        ctx.say("There is no finished game to replay.").await?;
+        return Ok(());
+    };
+
+    ctx.say(format!(
+        "Replaying {} question(s). Reveal the answers at your own pace!",
+        records.len()
+    ))
+    .await?;
+
+    for (round, record) in records.iter().enumerate() {
+        let options = record
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, o)| format!("{}. {o}", i + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let entry = record
+            .entry_id
+            .map(|id| format!(" ・ `/entry {id}`"))
+            .unwrap_or_default();
+        let mut description = format!(
+            "{options}\n\nAnswer ・ 正解: ||{}. {}{entry}||",
+            record.answer + 1,
+            record.options[record.answer]
+        );
+
+        let mut embed = CreateEmbed::new()
+            .title(format!("Question {}", round + 1))
+            .field(&record.mode, "", false);
+        // Spoken prompts are the reading of the answer, so they are only heard.
+        let file = if record.spoken {
+            let speech = match manager.tts() {
+                Some(tts) => audio::speak(tts, &record.prompt)
+                    .await
+                    .inspect_err(|e| eprintln!("Failed to synthesize {}: {e}", record.prompt))
+                    .ok(),
+                None => None,
+            };
+            match speech {
+                Some(speech) => {
+                    description.insert_str(
+                        0,
+                        "🔊 Play the attached audio ・ 音声を再生してください\n\n",
+                    );
+                    Some(CreateAttachment::bytes(
+                        speech.bytes,
+                        format!("prompt.{}", speech.extension),
+                    ))
+                }
+                None => {
+                    description.insert_str(
+                        0,
+                        "🔊 The audio of this question is unavailable ・ この問題の音声は再生できません\n\n",
+                    );
+                    None
+                }
+            }
+        } else {
+            embed = embed.attachment("prompt.png");
+            Some(CreateAttachment::bytes(
+                image::text_to_image(&record.prompt, ctx.guild_id()),
+                "prompt.png",
+            ))
+        };
+
+        ctx.channel_id()
+            .send_message(
+                ctx,
+                CreateMessage::new()
+                    .embed(embed.description(description))
+                    .add_files(file),
+            )
+            .await?;
+
+        // Pace the messages so they don't arrive as a wall of text.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        self as serenity, ButtonStyle, ComponentInteraction, CreateButton, CreateEmbed,
+        CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    },
+};
+
+use super::bookmarks::entry_summary;
+use crate::{Context, Data, Error, game::RoundRecord, pagination};
+
+/// Prefix of the custom_id of report buttons. Followed by the id of the
+/// reported question's menu.
+const REPORT_PREFIX: &str = "report,";
+
+/// How many reported entries are listed per page.
+const PAGE_SIZE: usize = 10;
+
+/// Most reported entries that are listed.
+const MAX_REPORTED: usize = 100;
+
+/// Reviews reported questions. Only for the bot's owners.
+#[poise::command(
+    slash_command,
+    owners_only,
+    hide_in_help,
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands("list_reports", "block", "unblock"),
+    name_localized("ja", "報告"),
+    description_localized("ja", "報告された問題を確認する（ボットの所有者のみ）")
+)]
+pub async fn reports(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Lists the most reported entries that aren't blocked.
+#[poise::command(
+    slash_command,
+    owners_only,
+    rename = "list",
+    name_localized("ja", "一覧"),
+    description_localized("ja", "報告の多い単語を見る")
+)]
+pub async fn list_reports(ctx: Context<'_>) -> Result<(), Error> {
+    let reported = ctx.data().db.reported_entries(MAX_REPORTED)?;
+    let provider = ctx.data().manager.provider();
+
+    let lines: Vec<_> = reported
+        .into_iter()
+        .filter_map(|(id, count)| provider.by_id(id).map(|entry| (id, entry, count)))
+        .map(|(id, entry, count)| format!("`{id}` {} ・ reported {count}×", entry_summary(&entry)))
+        .collect();
+
+    if lines.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no reported words to review")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pages: Vec<_> = lines
+        .chunks(PAGE_SIZE)
+        .map(|chunk| chunk.join("\n"))
+        .collect();
+
+    pagination::paginate(ctx, "Reported Words ・ 報告された単語", &pages, true).await
+}
+
+/// Stops asking about an entry, e.g. because its data is wrong.
+#[poise::command(
+    slash_command,
+    owners_only,
+    name_localized("ja", "ブロック"),
+    description_localized("ja", "単語を出題しないようにする")
+)]
+pub async fn block(
+    ctx: Context<'_>,
+    #[name_localized("ja", "単語id")]
+    #[description = "Id of the entry, as listed by /reports list"]
+    #[description_localized("ja", "単語のid（/reports listに表示されるもの）")]
+    entry_id: u32,
+) -> Result<(), Error> {
+    let dictionary = ctx.data().manager.dictionary();
+
+    let content = match dictionary.entry(entry_id) {
+        Some(entry) => {
+            ctx.data().db.block_entry(entry_id, ctx.author().id)?;
+            dictionary.block(entry_id);
+            format!(
+                "{} will no longer be asked about",
+                entry_summary(&entry.to_entry())
+            )
+        }
+        None => format!("There is no entry with the id {entry_id}"),
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Asks about a blocked entry again.
+#[poise::command(
+    slash_command,
+    owners_only,
+    name_localized("ja", "ブロック解除"),
+    description_localized("ja", "ブロックした単語を再び出題する")
+)]
+pub async fn unblock(
+    ctx: Context<'_>,
+    #[name_localized("ja", "単語id")]
+    #[description = "Id of the blocked entry"]
+    #[description_localized("ja", "ブロックした単語のid")]
+    entry_id: u32,
+) -> Result<(), Error> {
+    let content = if ctx.data().db.unblock_entry(entry_id)? {
+        ctx.data().manager.dictionary().unblock(entry_id);
+        format!("Entry {entry_id} will be asked about again")
+    } else {
+        format!("Entry {entry_id} isn't blocked")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Creates a button that reports the question of the menu identified by `menu_id`.
+pub fn report_button(menu_id: &str) -> CreateButton {
+    CreateButton::new(format!("{REPORT_PREFIX}{menu_id}"))
+        .label("⚠️ Report")
+        .style(ButtonStyle::Secondary)
+}
+
+/// Determines whether `interaction` is a press of a [`report_button`].
+pub fn is_report_button(interaction: &ComponentInteraction) -> bool {
+    interaction.data.custom_id.starts_with(REPORT_PREFIX)
+}
+
+/// Stores the presser's report of the question of a pressed [`report_button`],
+/// and posts it to [`Data::report_channel`] if one is configured.
+pub async fn handle_report_button(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let round = interaction
+        .data
+        .custom_id
+        .strip_prefix(REPORT_PREFIX)
+        .and_then(|id| data.manager.round(id));
+
+    let content = match round {
+        Some(round) => {
+            if data.db.add_report(interaction.user.id, &round)? {
+                if let Some(channel) = data.report_channel
+                    && let Err(e) = channel
+                        .send_message(
+                            ctx,
+                            CreateMessage::new().embed(report_embed(interaction, &round)),
+                        )
+                        .await
+                {
+                    eprintln!("Failed to post report of {}: {e}", round.id);
+                }
+                "Thanks! The question was reported to the maintainers"
+            } else {
+                "You've already reported this question"
+            }
+        }
+        None => "This question is too old to report",
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Creates the embed of a report of `round` made with `interaction`, for maintainers.
+fn report_embed(interaction: &ComponentInteraction, round: &RoundRecord) -> CreateEmbed {
+    let options = round
+        .options
+        .iter()
+        .enumerate()
+        .map(|(i, o)| format!("{} {o}", if i == round.answer { "✅" } else { "▫️" }))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let entry = round.entry_id.map_or("None".to_string(), |id| {
+        format!("[{id}](https://www.edrdg.org/jmwsgi/entr.py?svc=jmdict&q={id})")
+    });
+
+    CreateEmbed::new()
+        .title("Question Reported ・ 問題の報告")
+        .field("Mode", &round.mode, true)
+        .field("Entry", entry, true)
+        .field("Prompt", &round.prompt, false)
+        .field("Options", options, false)
+        .field("Reported by", format!("<@{}>", interaction.user.id), false)
+}
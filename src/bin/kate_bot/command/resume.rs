@@ -0,0 +1,104 @@
+use poise::serenity_prelude::{
+    self as serenity, ComponentInteraction, CreateButton, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+};
+
+use crate::{Context, Data, Error, game::SessionId};
+
+/// Custom_id of resume buttons. The game resumed is the one saved in the
+/// channel of the button.
+const RESUME_ID: &str = "resume";
+
+/// Creates a button that resumes the game saved in the channel it is sent in.
+pub fn resume_button() -> CreateButton {
+    CreateButton::new(RESUME_ID).label("▶️ Resume")
+}
+
+/// Determines whether `interaction` is a press of a [`resume_button`].
+pub fn is_resume_button(interaction: &ComponentInteraction) -> bool {
+    interaction.data.custom_id == RESUME_ID
+}
+
+/// Resumes the game saved in the channel of a pressed [`resume_button`], and
+/// removes the button once it has.
+pub async fn handle_resume_button(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let session_id = SessionId::new(
+        interaction.guild_id,
+        interaction.user.id,
+        interaction.channel_id,
+    );
+
+    let error = resume_saved(data, session_id)?;
+
+    let response = match error {
+        Some(error) => CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(error)
+                .ephemeral(true),
+        ),
+        None => CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content(format!("{} resumed the game", interaction.user.name))
+                .components(Vec::new()),
+        ),
+    };
+    interaction.create_response(ctx, response).await?;
+
+    Ok(())
+}
+
+/// Resumes the paused game in this channel, or else the game interrupted by the
+/// bot restarting.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "再開"),
+    description_localized("ja", "一時停止または中断されたゲームを再開する")
+)]
+pub async fn resume(ctx: Context<'_>) -> Result<(), Error> {
+    let manager = &ctx.data().manager;
+    let session_id = SessionId::of(&ctx);
+
+    if manager.is_active(session_id) {
+        if manager.unpause(session_id, ctx.author().id).await {
+            ctx.say("Resuming game...").await?;
+        } else {
+            ctx.say("The game in this channel isn't paused.").await?;
+        }
+        return Ok(());
+    }
+
+    match resume_saved(ctx.data(), session_id)? {
+        Some(error) => ctx.say(error).await?,
+        None => ctx.say("Resuming game...").await?,
+    };
+
+    Ok(())
+}
+
+/// Resumes `session_id`'s game saved when it was interrupted.
+///
+/// Returns why it couldn't be resumed, if so.
+fn resume_saved(data: &Data, session_id: SessionId) -> Result<Option<String>, Error> {
+    let Some(saved) = data.db.saved_session(session_id)? else {
+        return Ok(Some(
+            "There is no paused or interrupted game to resume here".to_string(),
+        ));
+    };
+
+    match data.manager.source(&saved.mode) {
+        None => {
+            let message = format!("The game mode {} no longer exists", saved.mode);
+            data.db.remove_saved_session(session_id)?;
+            Ok(Some(message))
+        }
+        Some(source) => {
+            data.manager.resume_game(source, saved)?;
+            Ok(None)
+        }
+    }
+}
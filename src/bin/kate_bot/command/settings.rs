@@ -0,0 +1,763 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty};
+use poise::{
+    ChoiceParameter, CreateReply,
+    serenity_prelude::{Attachment, ChannelType, CreateAttachment, GuildChannel},
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Context, Error,
+    db::ChannelDefaults,
+    emote::{self, Emote},
+    feedback::{FeedbackSettings, Locale, Tone},
+    image,
+};
+
+use super::{
+    DistractorChoice, daily,
+    start::{ROUND_CHOICES, TIME_LIMIT_CHOICES},
+};
+
+/// Text shown in the preview of an uploaded font.
+const FONT_PREVIEW: &str = "ひらがな カタカナ 漢字 Romaji";
+
+/// Largest settings file that can be imported.
+const MAX_SETTINGS_SIZE: u32 = 64 * 1024;
+
+/// Settings of a server, as exported to and imported from TOML so a setup can
+/// be copied to other servers.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GuildSettings {
+    /// File name of the font question images are shown in, if not the default.
+    /// Fonts themselves aren't exported, so they have to be uploaded again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    font: Option<String>,
+    /// The daily quiz, if there is one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    daily: Option<DailySettings>,
+    /// Channels with defaults for `/start`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    channels: Vec<ChannelSettings>,
+    /// How players are told their answers are wrong, if not the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    feedback: Option<FeedbackSettings>,
+    /// Emotes of the server by their name, e.g. `wow`, if it set any.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    emotes: BTreeMap<String, String>,
+    /// Whether members share mnemonics with the server.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    mnemonics: bool,
+}
+
+/// Schedule of a server's daily quiz.
+#[derive(Debug, Serialize, Deserialize)]
+struct DailySettings {
+    /// Name of the channel the quiz is posted in, since channels are matched
+    /// by name across servers.
+    channel: String,
+    /// Hour of the day in UTC the quiz is posted at.
+    hour: u32,
+    /// How many questions are asked each day.
+    questions: u32,
+}
+
+/// Defaults of a channel for `/start`.
+#[derive(Debug, Serialize, Deserialize)]
+struct ChannelSettings {
+    /// Name of the channel, since channels are matched by name across servers.
+    channel: String,
+    #[serde(flatten)]
+    defaults: ChannelDefaults,
+}
+
+/// Configures the bot in the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands(
+        "font",
+        "reset_font",
+        "channel_defaults",
+        "reset_channel_defaults",
+        "feedback",
+        "emote",
+        "mnemonic_pool",
+        "export",
+        "import"
+    ),
+    name_localized("ja", "設定"),
+    description_localized("ja", "サーバーでのボットを設定する")
+)]
+pub async fn settings(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Renders the server's question images in an uploaded font.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("ja", "フォント"),
+    description_localized("ja", "問題の画像をアップロードしたフォントで表示する")
+)]
+pub async fn font(
+    ctx: Context<'_>,
+    #[name_localized("ja", "ファイル")]
+    #[description = "A .ttf or .otf font whose license allows it to be used by the bot"]
+    #[description_localized(
+        "ja",
+        "ボットでの使用がライセンスで認められている.ttfか.otfのフォント"
+    )]
+    file: Attachment,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let name = file.filename.to_lowercase();
+    let refusal = if !name.ends_with(".ttf") && !name.ends_with(".otf") {
+        Some("Please upload a .ttf or .otf file".to_string())
+    } else if file.size as usize > image::MAX_FONT_SIZE {
+        Some(format!(
+            "Fonts can be at most {} MB",
+            image::MAX_FONT_SIZE / (1024 * 1024)
+        ))
+    } else {
+        None
+    };
+    if let Some(refusal) = refusal {
+        ctx.send(CreateReply::default().content(refusal).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    let bytes = file.download().await?;
+    let font = match image::parse_font(bytes.clone()) {
+        Ok(font) => font,
+        Err(e) => {
+            ctx.send(CreateReply::default().content(e).ephemeral(true))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    ctx.data()
+        .db
+        .set_guild_font(guild_id, &file.filename, &bytes)?;
+    image::set_guild_font(guild_id, Some(font));
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Question images will be shown in {} ・ 問題の画像をこのフォントで表示します",
+                file.filename
+            ))
+            .attachment(CreateAttachment::bytes(
+                image::text_to_image(FONT_PREVIEW, Some(guild_id)),
+                "preview.png",
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Goes back to rendering the server's question images in the default font.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "font-reset",
+    name_localized("ja", "フォントリセット"),
+    description_localized("ja", "問題の画像をデフォルトのフォントに戻す")
+)]
+pub async fn reset_font(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let content = if ctx.data().db.remove_guild_font(guild_id)? {
+        image::set_guild_font(guild_id, None);
+        "Question images will be shown in the default font"
+    } else {
+        "The server already uses the default font"
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Sets what `/start` picks in a channel for the settings that aren't given.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "channel-defaults",
+    name_localized("ja", "チャンネルデフォルト"),
+    description_localized("ja", "チャンネルで/startのデフォルトの設定を決める")
+)]
+pub async fn channel_defaults(
+    ctx: Context<'_>,
+    #[name_localized("ja", "チャンネル")]
+    #[description = "Channel to set the defaults of. This channel by default"]
+    #[description_localized("ja", "デフォルトを決めるチャンネル（デフォルトはこのチャンネル）")]
+    channel: Option<GuildChannel>,
+    #[name_localized("ja", "レベル")]
+    #[description = "NLevels selected at first, e.g. N4 N5. All of them by default"]
+    #[description_localized("ja", "最初に選択されるNレベル、例：N4 N5（デフォルトは全て）")]
+    levels: Option<String>,
+    #[name_localized("ja", "選択肢")]
+    #[description = "How closely the wrong answers resemble the right one"]
+    #[description_localized("ja", "不正解の選択肢が正解にどれだけ似ているか")]
+    distractors: Option<DistractorChoice>,
+    #[name_localized("ja", "ラウンド数")]
+    #[description = "Rounds a game lasts: 10, 20, 30, or 50. Unlimited by default"]
+    #[description_localized("ja", "ゲームのラウンド数：10、20、30、50（デフォルトは無制限）")]
+    rounds: Option<usize>,
+    #[name_localized("ja", "制限時間")]
+    #[description = "Seconds per question: 10, 20, 30, or 60. No time limit by default"]
+    #[description_localized("ja", "一問の制限時間（秒）：10、20、30、60（デフォルトは無制限）")]
+    time_limit: Option<u64>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+    let channel_id = channel.map_or(ctx.channel_id(), |c| c.id);
+
+    let levels: Result<Vec<NLevel>, _> = levels
+        .iter()
+        .flat_map(|l| l.split(|c: char| c == ',' || c == '/' || c.is_whitespace()))
+        .filter(|l| !l.is_empty())
+        .map(|l| l.to_uppercase().parse())
+        .collect();
+    let refusal = match &levels {
+        Err(_) => Some("Unknown NLevel. Please list them as in N4 N5.".to_string()),
+        Ok(_) if rounds.is_some_and(|n| !ROUND_CHOICES.contains(&n)) => {
+            Some(format!("Games can last {ROUND_CHOICES:?} rounds"))
+        }
+        Ok(_) if time_limit.is_some_and(|secs| !TIME_LIMIT_CHOICES.contains(&secs)) => Some(
+            format!("Questions can have {TIME_LIMIT_CHOICES:?} second time limits"),
+        ),
+        Ok(_) => None,
+    };
+    if let Some(refusal) = refusal {
+        ctx.send(CreateReply::default().content(refusal).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let mut levels = levels.unwrap_or_default();
+    levels.sort_unstable();
+    levels.dedup();
+    let defaults = ChannelDefaults {
+        levels,
+        distractors: distractors.map(Difficulty::from),
+        rounds,
+        time_limit,
+    };
+    ctx.data()
+        .db
+        .set_channel_defaults(guild_id, channel_id, &defaults)?;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Games started in <#{channel_id}> will default to: {} ・ このチャンネルのデフォルトを設定しました",
+                defaults_label(&defaults)
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Goes back to the usual defaults of `/start` in a channel.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "channel-defaults-reset",
+    name_localized("ja", "チャンネルデフォルトリセット"),
+    description_localized("ja", "チャンネルで/startのデフォルトの設定を元に戻す")
+)]
+pub async fn reset_channel_defaults(
+    ctx: Context<'_>,
+    #[name_localized("ja", "チャンネル")]
+    #[description = "Channel to reset the defaults of. This channel by default"]
+    #[description_localized("ja", "デフォルトを元に戻すチャンネル（デフォルトはこのチャンネル）")]
+    channel: Option<GuildChannel>,
+) -> Result<(), Error> {
+    let channel_id = channel.map_or(ctx.channel_id(), |c| c.id);
+
+    let content = if ctx.data().db.remove_channel_defaults(channel_id)? {
+        format!("Games started in <#{channel_id}> will use the usual defaults")
+    } else {
+        format!("<#{channel_id}> already uses the usual defaults")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Describes the settings of `defaults`, e.g. `N4, N5 · Easy distractors`.
+fn defaults_label(defaults: &ChannelDefaults) -> String {
+    let mut parts = Vec::new();
+    if !defaults.levels.is_empty() {
+        parts.push(
+            defaults
+                .levels
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+    }
+    if let Some(distractors) = defaults.distractors {
+        parts.push(format!("{distractors:?} distractors"));
+    }
+    if let Some(rounds) = defaults.rounds {
+        parts.push(format!("{rounds} rounds"));
+    }
+    if let Some(secs) = defaults.time_limit {
+        parts.push(format!("{secs} seconds per question"));
+    }
+
+    if parts.is_empty() {
+        "the usual settings".to_string()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+/// Picks how players are told their answers are wrong in the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("ja", "フィードバック"),
+    description_localized("ja", "不正解のときのメッセージの口調と言語を選ぶ")
+)]
+pub async fn feedback(
+    ctx: Context<'_>,
+    #[name_localized("ja", "口調")]
+    #[description = "How the messages sound. Spicy by default"]
+    #[description_localized("ja", "メッセージの口調（デフォルトは辛口）")]
+    tone: Option<Tone>,
+    #[name_localized("ja", "言語")]
+    #[description = "Language of the messages. English by default"]
+    #[description_localized("ja", "メッセージの言語（デフォルトは英語）")]
+    language: Option<Locale>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let mut settings = ctx.data().db.guild_feedback(guild_id)?.unwrap_or_default();
+    let content = if tone.is_none() && language.is_none() {
+        format!(
+            "Wrong answers are met with: {} ・ 現在の設定",
+            feedback_label(settings)
+        )
+    } else {
+        settings.tone = tone.unwrap_or(settings.tone);
+        settings.locale = language.unwrap_or(settings.locale);
+        if settings == FeedbackSettings::default() {
+            ctx.data().db.remove_guild_feedback(guild_id)?;
+        } else {
+            ctx.data().db.set_guild_feedback(guild_id, &settings)?;
+        }
+        format!(
+            "Wrong answers will be met with: {} ・ 設定しました",
+            feedback_label(settings)
+        )
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Describes `settings`, e.g. `Friendly messages in Japanese`.
+fn feedback_label(settings: FeedbackSettings) -> String {
+    format!(
+        "{} messages in {}",
+        settings.tone.name(),
+        settings.locale.name()
+    )
+}
+
+/// Sets an emote the bot reacts to answers with in the server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("ja", "絵文字"),
+    description_localized("ja", "回答への反応に使う絵文字を設定する")
+)]
+pub async fn emote(
+    ctx: Context<'_>,
+    #[name_localized("ja", "種類")]
+    #[description = "Which of the bot's emotes to set"]
+    #[description_localized("ja", "設定するボットの絵文字")]
+    which: Emote,
+    #[name_localized("ja", "絵文字")]
+    #[description = "An emoji or an emote of the server. The usual one if not given"]
+    #[description_localized("ja", "絵文字かサーバーのカスタム絵文字（なければ元に戻す）")]
+    text: Option<String>,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let content = match text.as_deref().map(emote::parse_emote) {
+        Some(Err(e)) => e.to_string(),
+        Some(Ok(text)) => {
+            ctx.data().db.set_guild_emote(guild_id, which, &text)?;
+            emote::set_guild_emote(guild_id, which, Some(text.clone()));
+            format!("The bot will react with {text} ・ 絵文字を設定しました")
+        }
+        None => {
+            ctx.data().db.remove_guild_emote(guild_id, which)?;
+            emote::set_guild_emote(guild_id, which, None);
+            format!(
+                "The bot will react with {} again ・ 絵文字を元に戻しました",
+                emote::emotes_of(Some(guild_id)).get(which)
+            )
+        }
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Lets members share mnemonics, shown on answers once moderators approve them.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "mnemonics",
+    name_localized("ja", "覚え方"),
+    description_localized("ja", "メンバーが覚え方を共有できるようにする")
+)]
+pub async fn mnemonic_pool(
+    ctx: Context<'_>,
+    #[name_localized("ja", "有効")]
+    #[description = "Whether members share mnemonics, shown on answers once approved"]
+    #[description_localized("ja", "覚え方を共有するか（承認後に正解と一緒に表示）")]
+    enabled: bool,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    ctx.data().db.set_mnemonic_pool(guild_id, enabled)?;
+    let content = if enabled {
+        "Members can share mnemonics with `/mnemonic`. Review them with `/mnemonics pending` ・ 覚え方の共有を有効にしました"
+    } else {
+        "Mnemonics are no longer shared. Ones already shared are kept ・ 覚え方の共有を無効にしました"
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Saves the server's settings to a TOML file, which can be imported in other servers.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("ja", "エクスポート"),
+    description_localized("ja", "サーバーの設定をTOMLファイルに保存する")
+)]
+pub async fn export(ctx: Context<'_>) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    let daily = match ctx.data().db.daily_quiz(guild_id)? {
+        Some(quiz) => Some(DailySettings {
+            channel: quiz.channel_id.name(ctx).await?,
+            hour: quiz.hour,
+            questions: quiz.questions,
+        }),
+        None => None,
+    };
+    let mut channels = Vec::new();
+    for (channel_id, defaults) in ctx.data().db.guild_channel_defaults(guild_id)? {
+        channels.push(ChannelSettings {
+            channel: channel_id.name(ctx).await?,
+            defaults,
+        });
+    }
+    let settings = GuildSettings {
+        font: ctx.data().db.guild_font_name(guild_id)?,
+        daily,
+        channels,
+        feedback: ctx.data().db.guild_feedback(guild_id)?,
+        emotes: ctx
+            .data()
+            .db
+            .guild_emotes_of(guild_id)?
+            .into_iter()
+            .map(|(emote, text)| (emote.key().to_string(), text))
+            .collect(),
+        mnemonics: ctx.data().db.has_mnemonic_pool(guild_id)?,
+    };
+
+    let toml = format!(
+        "# Exported with /settings export. Import it in another server with /settings import.\n{}",
+        toml::to_string(&settings).map_err(|e| Error::Other(e.into()))?
+    );
+
+    ctx.send(
+        CreateReply::default()
+            .content("The server's settings ・ サーバーの設定")
+            .attachment(CreateAttachment::bytes(toml, "settings.toml"))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Replaces the server's settings with ones exported with `/settings export`.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("ja", "インポート"),
+    description_localized("ja", "エクスポートした設定でサーバーの設定を置き換える")
+)]
+pub async fn import(
+    ctx: Context<'_>,
+    #[name_localized("ja", "ファイル")]
+    #[description = "A settings.toml made with /settings export"]
+    #[description_localized("ja", "/settings exportで作ったsettings.toml")]
+    file: Attachment,
+) -> Result<(), Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(());
+    };
+
+    if file.size > MAX_SETTINGS_SIZE {
+        ctx.send(
+            CreateReply::default()
+                .content("That file is too large to be a settings file")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    let bytes = file.download().await?;
+    let settings = match String::from_utf8(bytes)
+        .map_err(|e| e.to_string())
+        .and_then(|text| toml::from_str::<GuildSettings>(&text).map_err(|e| e.to_string()))
+    {
+        Ok(settings) => settings,
+        Err(e) => {
+            ctx.send(
+                CreateReply::default()
+                    .content(format!("Couldn't read the settings:\n```\n{e}\n```"))
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+    if let Some(daily) = &settings.daily
+        && (daily.hour > 23 || !(1..=daily::MAX_QUESTIONS).contains(&daily.questions))
+    {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "The daily quiz must be at an hour from 0 to 23 and have 1 to {} questions",
+                    daily::MAX_QUESTIONS
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if settings.channels.iter().any(|c| {
+        c.defaults
+            .rounds
+            .is_some_and(|n| !ROUND_CHOICES.contains(&n))
+            || c.defaults
+                .time_limit
+                .is_some_and(|secs| !TIME_LIMIT_CHOICES.contains(&secs))
+    }) {
+        ctx.send(
+            CreateReply::default()
+                .content(format!(
+                    "Channel defaults must last {ROUND_CHOICES:?} rounds and have {TIME_LIMIT_CHOICES:?} second time limits"
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut changes = Vec::new();
+    match settings.daily {
+        Some(daily) => {
+            let channels = guild_id.channels(ctx).await?;
+            // Channels are matched by name, falling back to this one.
+            let matched = channels
+                .values()
+                .find(|c| c.kind == ChannelType::Text && c.name == daily.channel)
+                .map(|c| c.id);
+            let channel_id = matched.unwrap_or(ctx.channel_id());
+
+            ctx.data()
+                .db
+                .set_daily_quiz(guild_id, channel_id, daily.hour, daily.questions)?;
+            changes.push(format!(
+                "Daily quiz of {} question(s) posted in <#{channel_id}> at {}:00 UTC",
+                daily.questions, daily.hour
+            ));
+            if matched.is_none() {
+                changes.push(format!(
+                    "There is no channel named #{}, so the daily quiz is posted here",
+                    daily.channel
+                ));
+            }
+        }
+        None => {
+            if ctx.data().db.remove_daily_quiz(guild_id)? {
+                changes.push("Daily quiz stopped".to_string());
+            }
+        }
+    }
+    if ctx.data().db.remove_guild_channel_defaults(guild_id)? > 0 && settings.channels.is_empty() {
+        changes.push("Channel defaults removed".to_string());
+    }
+    if !settings.channels.is_empty() {
+        let channels: HashMap<_, _> = guild_id
+            .channels(ctx)
+            .await?
+            .into_values()
+            .map(|c| (c.name, c.id))
+            .collect();
+        for ChannelSettings { channel, defaults } in settings.channels {
+            match channels.get(&channel) {
+                Some(&channel_id) => {
+                    ctx.data()
+                        .db
+                        .set_channel_defaults(guild_id, channel_id, &defaults)?;
+                    changes.push(format!(
+                        "Games started in <#{channel_id}> default to: {}",
+                        defaults_label(&defaults)
+                    ));
+                }
+                None => changes.push(format!(
+                    "There is no channel named #{channel}, so its defaults are skipped"
+                )),
+            }
+        }
+    }
+    match settings.feedback {
+        Some(feedback) => {
+            ctx.data().db.set_guild_feedback(guild_id, &feedback)?;
+            changes.push(format!(
+                "Wrong answers are met with: {}",
+                feedback_label(feedback)
+            ));
+        }
+        None => {
+            if ctx.data().db.remove_guild_feedback(guild_id)? {
+                changes.push(format!(
+                    "Wrong answers are met with: {}",
+                    feedback_label(FeedbackSettings::default())
+                ));
+            }
+        }
+    }
+    for emote in Emote::iter() {
+        let text = settings.emotes.get(emote.key());
+        match text.map(|text| emote::parse_emote(text)) {
+            Some(Ok(text)) => {
+                ctx.data().db.set_guild_emote(guild_id, emote, &text)?;
+                emote::set_guild_emote(guild_id, emote, Some(text.clone()));
+                changes.push(format!("The bot reacts with {text} as {}", emote.key()));
+            }
+            Some(Err(e)) => changes.push(format!("Skipped the emote {}: {e}", emote.key())),
+            None => {
+                if ctx.data().db.remove_guild_emote(guild_id, emote)? {
+                    emote::set_guild_emote(guild_id, emote, None);
+                }
+            }
+        }
+    }
+    if let Some(name) = settings
+        .emotes
+        .keys()
+        .find(|name| Emote::from_str(name).is_err())
+    {
+        changes.push(format!("There is no emote named {name}, so it is skipped"));
+    }
+    if settings.mnemonics != ctx.data().db.has_mnemonic_pool(guild_id)? {
+        ctx.data()
+            .db
+            .set_mnemonic_pool(guild_id, settings.mnemonics)?;
+        changes.push(if settings.mnemonics {
+            "Members share mnemonics".to_string()
+        } else {
+            "Mnemonics are no longer shared".to_string()
+        });
+    }
+    match settings.font {
+        Some(font) => changes.push(format!(
+            "Fonts aren't exported, so upload {font} again with `/settings font`"
+        )),
+        None => {
+            if ctx.data().db.remove_guild_font(guild_id)? {
+                image::set_guild_font(guild_id, None);
+                changes.push("Question images will be shown in the default font".to_string());
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        changes.push("Nothing needed to change".to_string());
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Imported the settings ・ 設定をインポートしました\n{}",
+                changes
+                    .iter()
+                    .map(|change| format!("· {change}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,18 +1,37 @@
-use std::time::Duration;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use crate::{
     Context, Error,
-    game::{ModeChoice, PosFilter},
+    error::UserError,
+    game::{
+        Compatibility, Filters, GameOptions, KanjiTyping, PosFilter, QuestionSource, Resolution,
+    },
 };
-use jplearnbot::dictionary::{NLevel};
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty, kana::Tolerance};
 use poise::serenity_prelude::{
     ComponentInteractionCollector, ComponentInteractionDataKind, CreateActionRow, CreateButton,
     CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
-    CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse, futures::StreamExt,
+    CreateSelectMenuKind, CreateSelectMenuOption, futures::StreamExt,
 };
 use strum::IntoEnumIterator;
 
+use super::autocomplete_wordlist;
+
+/// Default of how many milliseconds after a question is posted answers are ignored.
+pub(super) const DEFAULT_READ_TIME_MS: u64 = 600;
+
+/// Round counts that can be picked for a game. A game is unlimited by default.
+pub(super) const ROUND_CHOICES: [usize; 4] = [10, 20, 30, 50];
+
+/// How many of the most missed words a game of them asks about.
+const MOST_MISSED_POOL: usize = 50;
+
+/// Per-question time limits in seconds that can be picked for a game. Questions
+/// have no time limit by default.
+pub(super) const TIME_LIMIT_CHOICES: [u64; 4] = [10, 20, 30, 60];
+
 /// Starts a new game.
+#[allow(clippy::too_many_arguments)]
 #[poise::command(
     slash_command,
     user_cooldown = 3,
@@ -24,9 +43,255 @@ pub async fn start(
     #[name_localized("ja", "モード")]
     #[description = "Pick a game mode"]
     #[description_localized("ja", "ゲームのモードを選んでください")]
-    mode: ModeChoice,
+    #[autocomplete = "autocomplete_mode"]
+    mode: String,
+    #[name_localized("ja", "不正防止")]
+    #[description = "Show every player their own order of answers"]
+    #[description_localized("ja", "プレイヤーごとに回答の順番を変える")]
+    anti_cheat: Option<bool>,
+    #[name_localized("ja", "ブックマーク")]
+    #[description = "Only ask words from your bookmarks"]
+    #[description_localized("ja", "ブックマークした単語だけを出題する")]
+    bookmarks: Option<bool>,
+    #[name_localized("ja", "苦手な単語")]
+    #[description = "Only ask the words missed the most, by you or in this Server"]
+    #[description_localized("ja", "自分またはこのサーバーで最も間違えた単語だけを出題する")]
+    most_missed: Option<MostMissedChoice>,
+    #[name_localized("ja", "単語リスト")]
+    #[description = "Only ask words from one of your imported word lists"]
+    #[description_localized("ja", "取り込んだ単語リストの単語だけを出題する")]
+    #[autocomplete = "autocomplete_wordlist"]
+    wordlist: Option<String>,
+    #[name_localized("ja", "決着")]
+    #[description = "Pick how each round ends"]
+    #[description_localized("ja", "各ラウンドの終わり方を選んでください")]
+    resolution: Option<Resolution>,
+    #[name_localized("ja", "読む時間")]
+    #[description = "Ignore answers made this many milliseconds after a question is posted (default 600)"]
+    #[description_localized("ja", "出題からこのミリ秒以内の回答を無視する（デフォルト600）")]
+    #[max = 5000]
+    read_time: Option<u64>,
+    #[name_localized("ja", "ハードモード")]
+    #[description = "Hide the level and difficulty of questions until answered"]
+    #[description_localized("ja", "回答されるまで問題のレベルと難易度を隠す")]
+    hard_mode: Option<bool>,
+    #[name_localized("ja", "練習")]
+    #[description = "Warm up without your answers counting towards your stats"]
+    #[description_localized("ja", "回答を統計に記録せずにウォーミングアップする")]
+    practice: Option<bool>,
+    #[name_localized("ja", "入力")]
+    #[description = "Type answers in the channel instead of pressing buttons"]
+    #[description_localized("ja", "ボタンの代わりに答えをチャンネルに入力する")]
+    typed: Option<bool>,
+    #[name_localized("ja", "入力の許容")]
+    #[description = "Pick how closely typed answers have to match (default Exact)"]
+    #[description_localized(
+        "ja",
+        "入力した答えがどれだけ正確である必要があるかを選んでください（デフォルトは完全一致）"
+    )]
+    tolerance: Option<ToleranceChoice>,
+    #[name_localized("ja", "漢字の入力")]
+    #[description = "Pick whether kanji answers are typed too, and if other okurigana count"]
+    #[description_localized(
+        "ja",
+        "漢字の答えも入力するか、送り仮名の違いを認めるかを選んでください"
+    )]
+    kanji_typing: Option<KanjiTyping>,
+    #[name_localized("ja", "スキップ票")]
+    #[description = "Number of players who have to vote to skip a question (default 3)"]
+    #[description_localized("ja", "問題をスキップするのに必要な票の数（デフォルト3）")]
+    #[min = 1]
+    #[max = 25]
+    skip_votes: Option<usize>,
+    #[name_localized("ja", "記録")]
+    #[description = "Get a transcript of the game in DMs once it ends"]
+    #[description_localized("ja", "ゲーム終了後に記録をDMで受け取る")]
+    transcript: Option<bool>,
+    #[name_localized("ja", "選択肢")]
+    #[description = "Pick how closely the wrong answers resemble the right one (default Normal)"]
+    #[description_localized(
+        "ja",
+        "不正解の選択肢が正解にどれだけ似ているかを選んでください（デフォルトは普通）"
+    )]
+    distractors: Option<DistractorChoice>,
+    #[name_localized("ja", "読み上げ")]
+    #[description = "Read prompts aloud in the voice channel you're in"]
+    #[description_localized("ja", "参加中のボイスチャンネルで問題を読み上げる")]
+    voice: Option<bool>,
+    #[name_localized("ja", "チーム戦")]
+    #[description = "Play in two teams racing for the first correct answer"]
+    #[description_localized("ja", "二つのチームに分かれて早押しで競う")]
+    teams: Option<bool>,
+    #[name_localized("ja", "参加受付")]
+    #[description = "Let players join for 30 seconds before the first question"]
+    #[description_localized("ja", "最初の問題の前に30秒間参加を受け付ける")]
+    join_phase: Option<bool>,
+    #[name_localized("ja", "適応")]
+    #[description = "Ask words near your rating, getting harder as you answer correctly"]
+    #[description_localized("ja", "レーティングに近い単語を出題し、正解するほど難しくする")]
+    adaptive: Option<bool>,
 ) -> Result<(), Error> {
-    let mut menu = FiltersMenu::new(&ctx, ctx.id(), mode);
+    let Some(source) = ctx.data().manager.source(&mode) else {
+        ctx.send(
+            poise::CreateReply::default()
+                .content("Unknown game mode. Please pick one from the list.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+    if ctx.data().manager.playable_levels(&mode).is_empty() {
+        ctx.send(
+            poise::CreateReply::default()
+                .content("This game mode has too few questions to play right now.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let word_sources = [
+        bookmarks.unwrap_or(false),
+        most_missed.is_some(),
+        wordlist.is_some(),
+    ];
+    if word_sources.into_iter().filter(|&picked| picked).count() > 1 {
+        ctx.send(
+            poise::CreateReply::default()
+                .content(
+                    "Please pick only one of your bookmarks, the most missed words, or a word list.",
+                )
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let entries = if let Some(most_missed) = most_missed {
+        let missed = match (most_missed, ctx.guild_id()) {
+            (MostMissedChoice::Mine, _) => ctx
+                .data()
+                .db
+                .most_missed(ctx.author().id, MOST_MISSED_POOL)?,
+            (MostMissedChoice::Server, Some(guild_id)) => ctx
+                .data()
+                .db
+                .guild_most_missed(guild_id, MOST_MISSED_POOL)?,
+            (MostMissedChoice::Server, None) => {
+                ctx.send(
+                    poise::CreateReply::default()
+                        .content("The most missed words of a Server can only be asked in one.")
+                        .ephemeral(true),
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+        if missed.is_empty() {
+            ctx.send(
+                poise::CreateReply::default()
+                    .content("No words were missed yet. Play a few games first!")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(missed.into_iter().map(|(id, _)| id).collect())
+    } else if let Some(name) = &wordlist {
+        let ids: HashSet<_> = ctx
+            .data()
+            .db
+            .wordlist(ctx.author().id, name)?
+            .into_iter()
+            .collect();
+        if ids.is_empty() {
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(format!(
+                        "You have no word list named **{name}**. Import one with `/wordlist import`."
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(ids)
+    } else if bookmarks.unwrap_or(false) {
+        let ids: HashSet<_> = ctx
+            .data()
+            .db
+            .bookmarks(ctx.author().id)?
+            .into_iter()
+            .collect();
+        if ids.is_empty() {
+            ctx.send(
+                poise::CreateReply::default()
+                    .content("You have no bookmarks. Save words with 🔖 after answering.")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(ids)
+    } else {
+        None
+    };
+
+    let voice_channel = if voice.unwrap_or(false) {
+        let Some(channel_id) = ctx.data().manager.voice_channel(&ctx) else {
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(
+                        "Join a voice channel of this Server first. If you're in one, \
+                        reading prompts aloud isn't set up for this bot.",
+                    )
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        };
+        Some(channel_id)
+    } else {
+        None
+    };
+
+    // Settings that aren't given fall back to the channel's defaults.
+    let defaults = ctx
+        .data()
+        .db
+        .channel_defaults(ctx.channel_id())?
+        .unwrap_or_default();
+
+    let options = GameOptions {
+        shuffle_per_player: anti_cheat.unwrap_or(false),
+        resolution: resolution.unwrap_or_default(),
+        min_read_time: Duration::from_millis(read_time.unwrap_or(DEFAULT_READ_TIME_MS)),
+        hard_mode: hard_mode.unwrap_or(false),
+        practice: practice.unwrap_or(false),
+        typed: typed.unwrap_or(kanji_typing.is_some_and(|k| k != KanjiTyping::Buttons)),
+        max_rounds: defaults.rounds,
+        time_limit: defaults.time_limit.map(Duration::from_secs),
+        voice_channel,
+        teams: teams.unwrap_or(false),
+        join_phase: join_phase.unwrap_or(false),
+        adaptive: adaptive.unwrap_or(false),
+        tolerance: tolerance.map(Tolerance::from).unwrap_or_default(),
+        skip_votes,
+        transcript: transcript.unwrap_or(false),
+        kanji_typing: kanji_typing.unwrap_or_default(),
+    };
+
+    let distractors =
+        distractors.map_or(defaults.distractors.unwrap_or_default(), Difficulty::from);
+    let mut menu = FiltersMenu::new(
+        &ctx,
+        ctx.id(),
+        source,
+        &defaults.levels,
+        entries,
+        distractors,
+        options,
+    );
 
     ctx.send(
         poise::CreateReply::default()
@@ -40,56 +305,218 @@ pub async fn start(
     Ok(())
 }
 
+/// Choices of how closely the wrong options of questions resemble the answer.
+/// Whose most missed words a game asks about.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum MostMissedChoice {
+    #[name = "Mine"]
+    #[name_localized("ja", "自分")]
+    Mine,
+    #[name = "This Server's"]
+    #[name_localized("ja", "このサーバー")]
+    Server,
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum DistractorChoice {
+    #[name = "Easy: random words"]
+    #[name_localized("ja", "簡単：ランダムな単語")]
+    Easy,
+    #[name = "Normal: similar words"]
+    #[name_localized("ja", "普通：似ている単語")]
+    Normal,
+    #[name = "Hard: the most similar words"]
+    #[name_localized("ja", "難しい：最も似ている単語")]
+    Hard,
+}
+
+impl From<DistractorChoice> for Difficulty {
+    fn from(choice: DistractorChoice) -> Self {
+        match choice {
+            DistractorChoice::Easy => Difficulty::Easy,
+            DistractorChoice::Normal => Difficulty::Normal,
+            DistractorChoice::Hard => Difficulty::Hard,
+        }
+    }
+}
+
+/// Choices of how closely typed answers have to match the answer.
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum ToleranceChoice {
+    #[name = "Exact: the kana must match"]
+    #[name_localized("ja", "完全一致：かなが一致する")]
+    Exact,
+    #[name = "Long vowels: may be typed short"]
+    #[name_localized("ja", "長音：省略してもよい")]
+    LongVowels,
+    #[name = "One typo: one kana may be wrong"]
+    #[name_localized("ja", "誤字一つ：一文字間違えてもよい")]
+    OneTypo,
+}
+
+impl From<ToleranceChoice> for Tolerance {
+    fn from(choice: ToleranceChoice) -> Self {
+        match choice {
+            ToleranceChoice::Exact => Tolerance::Exact,
+            ToleranceChoice::LongVowels => Tolerance::LongVowels,
+            ToleranceChoice::OneTypo => Tolerance::OneTypo,
+        }
+    }
+}
+
+/// Lists the playable game modes whose name contains `partial`.
+pub(super) async fn autocomplete_mode(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+    let manager = &ctx.data().manager;
+
+    manager
+        .sources()
+        .iter()
+        .map(|s| s.name().to_string())
+        .filter(|name| {
+            name.to_lowercase().contains(&partial) && !manager.playable_levels(name).is_empty()
+        })
+        .collect()
+}
+
 /// Manages the components of the create game form.
 struct FiltersMenu<'a> {
     ctx: &'a Context<'a>,
     /// Identifier for the NLevel filter menu.
     nlvls_id: String,
-    /// Currently selected NLevels. Initially all of them.
+    /// Currently selected NLevels. Initially the channel's default ones, or
+    /// all of the ones the mode can be played at.
     levels: Vec<NLevel>,
 
     /// Identifier for the parts of speech filter menu.
     pos_id: String,
-    /// Currently selected parts of speech. Initially all of them.
+    /// Currently selected parts of speech. Initially all of the ones the mode
+    /// has questions of at the selected levels.
     pos: Vec<PosFilter>,
 
+    /// Only entries with these ids are asked about, if set.
+    entries: Option<HashSet<u32>>,
+
+    /// How closely the wrong options resemble the answer.
+    distractors: Difficulty,
+
+    /// Identifier for the round count menu.
+    rounds_id: String,
+
+    /// Identifier for the time limit menu.
+    time_limit_id: String,
+
     /// Identifier for the submit button.
     submit_id: String,
 
     /// Mode of game to create.
-    mode: ModeChoice,
+    source: Arc<dyn QuestionSource>,
+
+    /// Options of game to create.
+    options: GameOptions,
 }
 
 impl<'a> FiltersMenu<'a> {
-    fn new(ctx: &'a Context<'_>, invocation_id: u64, mode: ModeChoice) -> Self {
+    /// Creates a menu with `default_levels` selected, or every playable level
+    /// if the mode can't be played at any of them.
+    fn new(
+        ctx: &'a Context<'_>,
+        invocation_id: u64,
+        source: Arc<dyn QuestionSource>,
+        default_levels: &[NLevel],
+        entries: Option<HashSet<u32>>,
+        distractors: Difficulty,
+        options: GameOptions,
+    ) -> Self {
         let id = invocation_id.to_string();
+        let playable = ctx.data().manager.playable_levels(source.name());
+        let mut levels: Vec<_> = playable
+            .iter()
+            .copied()
+            .filter(|l| default_levels.contains(l))
+            .collect();
+        if levels.is_empty() {
+            levels = playable.to_vec();
+        }
+        let pos = ctx.data().manager.compatibility(source.name()).map_or_else(
+            || PosFilter::iter().collect(),
+            |c| c.pos_at(&levels).into_iter().map(|(p, _)| p).collect(),
+        );
+
         FiltersMenu {
             ctx,
             nlvls_id: format!("{}-nlvls", id),
-            levels: NLevel::iter().collect(),
+            levels,
 
             pos_id: format!("{}-pos", id),
-            pos: PosFilter::iter().collect(),
+            pos,
+
+            entries,
+            distractors,
+
+            rounds_id: format!("{}-rounds", id),
+            time_limit_id: format!("{}-time-limit", id),
 
             submit_id: format!("{}-submit", id),
 
-            mode,
+            source,
+            options,
         }
     }
 
-    /// Create all of the components of this menu.
+    /// Gets how many questions the mode has at each level and part of speech.
+    fn compatibility(&self) -> &Compatibility {
+        self.ctx
+            .data()
+            .manager
+            .compatibility(self.source.name())
+            .expect("Game mode should be registered")
+    }
+
+    /// Create all of the components of this menu. The parts of speech menu is
+    /// left out for modes that don't ask about words.
     fn create_components(&self) -> Vec<CreateActionRow> {
-        vec![self.levels_menu(), self.pos_menu(), self.submit_button()]
+        let mut components = vec![self.levels_menu()];
+        if self.compatibility().uses_pos() {
+            components.push(self.pos_menu());
+        }
+        components.extend([
+            self.rounds_menu(),
+            self.time_limit_menu(),
+            self.submit_button(),
+        ]);
+
+        components
+    }
+
+    /// Selects `levels`, keeping only the selected parts of speech the mode
+    /// has questions of at them, or all of those if none of them are left.
+    fn select_levels(&mut self, levels: Vec<NLevel>) {
+        let offered: Vec<_> = self
+            .compatibility()
+            .pos_at(&levels)
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+        self.pos.retain(|p| offered.contains(p));
+        if self.pos.is_empty() {
+            self.pos = offered;
+        }
+        self.levels = levels;
     }
 
-    /// Creates a new menu for selecting NLevels. Used by [`Self::create_components`].
+    /// Creates a new menu for selecting NLevels, each with how many questions
+    /// the mode has at it. Used by [`Self::create_components`].
     fn levels_menu(&self) -> CreateActionRow {
-        let levels = self
-            .levels
+        let compatibility = self.compatibility();
+        let levels = compatibility
+            .playable_levels()
             .iter()
             .map(|lvl| {
+                let total = compatibility.total(*lvl);
                 CreateSelectMenuOption::new(lvl.to_string(), lvl.to_string())
-                    .default_selection(true)
+                    .description(format!("{total} questions ・ {total}問"))
+                    .default_selection(self.levels.contains(lvl))
             })
             .collect::<Vec<_>>();
         let levels_len = levels.len();
@@ -105,13 +532,18 @@ impl<'a> FiltersMenu<'a> {
         CreateActionRow::SelectMenu(menu)
     }
 
-    /// Creates a new menu for selecting parts of speech. Used by [`Self::create_components`].
+    /// Creates a new menu for selecting parts of speech, offering only the ones
+    /// the mode has questions of at the selected levels, each with how many.
+    /// Used by [`Self::create_components`].
     fn pos_menu(&self) -> CreateActionRow {
         let pos = self
-            .pos
-            .iter()
-            .map(|p| {
-                CreateSelectMenuOption::new(p.to_string(), p.to_string()).default_selection(true)
+            .compatibility()
+            .pos_at(&self.levels)
+            .into_iter()
+            .map(|(p, count)| {
+                CreateSelectMenuOption::new(p.to_string(), p.to_string())
+                    .description(format!("{count} questions ・ {count}問"))
+                    .default_selection(self.pos.contains(&p))
             })
             .collect::<Vec<_>>();
         let pos_len = pos.len();
@@ -125,6 +557,48 @@ impl<'a> FiltersMenu<'a> {
         CreateActionRow::SelectMenu(menu)
     }
 
+    /// Creates a new menu for selecting how many rounds the game lasts.
+    /// Used by [`Self::create_components`].
+    fn rounds_menu(&self) -> CreateActionRow {
+        let unlimited = CreateSelectMenuOption::new("Unlimited rounds", "0")
+            .default_selection(self.options.max_rounds.is_none());
+        let rounds = ROUND_CHOICES.iter().map(|&n| {
+            CreateSelectMenuOption::new(format!("{n} rounds"), n.to_string())
+                .default_selection(self.options.max_rounds == Some(n))
+        });
+
+        let menu = CreateSelectMenu::new(
+            &self.rounds_id,
+            CreateSelectMenuKind::String {
+                options: std::iter::once(unlimited).chain(rounds).collect(),
+            },
+        )
+        .placeholder("Select how many rounds to play");
+
+        CreateActionRow::SelectMenu(menu)
+    }
+
+    /// Creates a new menu for selecting how long each question can be answered.
+    /// Used by [`Self::create_components`].
+    fn time_limit_menu(&self) -> CreateActionRow {
+        let none = CreateSelectMenuOption::new("No time limit", "0")
+            .default_selection(self.options.time_limit.is_none());
+        let limits = TIME_LIMIT_CHOICES.iter().map(|&secs| {
+            CreateSelectMenuOption::new(format!("{secs} seconds per question"), secs.to_string())
+                .default_selection(self.options.time_limit == Some(Duration::from_secs(secs)))
+        });
+
+        let menu = CreateSelectMenu::new(
+            &self.time_limit_id,
+            CreateSelectMenuKind::String {
+                options: std::iter::once(none).chain(limits).collect(),
+            },
+        )
+        .placeholder("Select a time limit per question");
+
+        CreateActionRow::SelectMenu(menu)
+    }
+
     /// Creates a new submit button. Used by [`Self::create_components`].
     fn submit_button(&self) -> CreateActionRow {
         let button = CreateButton::new(&self.submit_id).label("Create Game");
@@ -145,6 +619,8 @@ impl<'a> FiltersMenu<'a> {
                 let ids = [
                     self.nlvls_id.clone(),
                     self.pos_id.clone(),
+                    self.rounds_id.clone(),
+                    self.time_limit_id.clone(),
                     self.submit_id.clone(),
                 ];
                 move |ci| ids.contains(&ci.data.custom_id)
@@ -157,14 +633,34 @@ impl<'a> FiltersMenu<'a> {
             match &ci.data.kind {
                 // Update filters
                 ComponentInteractionDataKind::StringSelect { values } => {
-                    if id != &self.nlvls_id && id != &self.pos_id {
-                        continue;
-                    }
-
                     if id == &self.nlvls_id {
-                        self.levels = values.iter().map(|v| v.parse().unwrap()).collect();
+                        self.select_levels(values.iter().map(|v| v.parse().unwrap()).collect());
+
+                        // Offer the parts of speech of the selected levels.
+                        ci.create_response(
+                            self.ctx,
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new()
+                                    .components(self.create_components()),
+                            ),
+                        )
+                        .await?;
+                        continue;
                     } else if id == &self.pos_id {
                         self.pos = values.iter().map(|v| v.parse().unwrap()).collect();
+                    } else if id == &self.rounds_id {
+                        self.options.max_rounds = values
+                            .first()
+                            .and_then(|v| v.parse().ok())
+                            .filter(|&n| n > 0);
+                    } else if id == &self.time_limit_id {
+                        self.options.time_limit = values
+                            .first()
+                            .and_then(|v| v.parse().ok())
+                            .filter(|&secs| secs > 0)
+                            .map(Duration::from_secs);
+                    } else {
+                        continue;
                     }
                     ci.create_response(self.ctx, CreateInteractionResponse::Acknowledge)
                         .await?;
@@ -181,7 +677,7 @@ impl<'a> FiltersMenu<'a> {
                             self.ctx,
                             CreateInteractionResponse::Message(
                                 CreateInteractionResponseMessage::new()
-                                    .content("Game has already been created")
+                                    .embed(UserError::SessionExists.embed())
                                     .ephemeral(true),
                             ),
                         )
@@ -200,24 +696,22 @@ impl<'a> FiltersMenu<'a> {
                     )
                     .await?;
 
-                    // Try start game
-                    if self
-                        .ctx
-                        .data()
-                        .manager
-                        .start_game(self.ctx, self.mode, self.levels.clone(), self.pos.clone())
-                        .is_err()
-                    {
-                        ci.edit_response(
-                            self.ctx,
-                            EditInteractionResponse::new().content(
-                                "Active game in progress. Please stop it.",
-                            ),
-                        )
-                        .await?;
-                    } else {
-                        ci.delete_response(self.ctx).await?;
-                    }
+                    let started = self.ctx.data().manager.start_game(
+                        self.ctx,
+                        Arc::clone(&self.source),
+                        Filters {
+                            levels: self.levels.clone(),
+                            pos: self.pos.clone(),
+                            entries: self.entries.clone(),
+                            distractors: self.distractors,
+                            grades: Vec::new(),
+                            excluded: HashSet::new(),
+                            adaptive: None,
+                        },
+                        self.options,
+                    );
+                    ci.delete_response(self.ctx).await?;
+                    started?;
                 }
                 _ => {}
             }
@@ -0,0 +1,85 @@
+use poise::{
+    CreateReply,
+    serenity_prelude::{CreateEmbed, User},
+};
+
+use crate::{Context, Error, format};
+
+use super::bookmarks::entry_summary;
+
+/// How many of the most missed words are listed.
+const MOST_MISSED: usize = 5;
+
+/// Shows long-term answering stats.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "統計"),
+    description_localized("ja", "長期的な回答の統計を見る")
+)]
+pub async fn stats(
+    ctx: Context<'_>,
+    #[name_localized("ja", "ユーザー")]
+    #[description = "Whose stats to show. Yours by default"]
+    #[description_localized("ja", "統計を見るユーザー（デフォルトは自分）")]
+    user: Option<User>,
+) -> Result<(), Error> {
+    let user = user.as_ref().unwrap_or(ctx.author());
+    let locale = ctx.locale();
+    let stats = ctx.data().db.answer_stats(user.id)?;
+
+    if stats.answered == 0 {
+        ctx.send(
+            CreateReply::default()
+                .content(format!("{} hasn't answered any questions yet.", user.name))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let provider = ctx.data().manager.provider();
+    let missed = ctx
+        .data()
+        .db
+        .most_missed(user.id, MOST_MISSED)?
+        .into_iter()
+        .filter_map(|(id, misses)| Some((provider.by_id(id)?, misses)))
+        .map(|(entry, misses)| format!("{} ・ missed {misses}×", entry_summary(&entry)))
+        .collect::<Vec<_>>();
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("{}'s Stats ・ 統計", user.name))
+        .thumbnail(user.face())
+        .field(
+            "Accuracy ・ 正解率",
+            format!(
+                "{}% ({}/{})",
+                stats.correct * 100 / stats.answered,
+                format::number(stats.correct, locale),
+                format::number(stats.answered, locale)
+            ),
+            false,
+        )
+        .field(
+            "Points ・ 得点",
+            format::number(stats.points, locale),
+            false,
+        )
+        .field(
+            "Streak ・ 連続正解",
+            format!(
+                "Current: {} ・ Best: {}",
+                format::number(stats.current_streak, locale),
+                format::number(stats.best_streak, locale)
+            ),
+            false,
+        );
+    if !missed.is_empty() {
+        embed = embed.field("Most Missed ・ よく間違える単語", missed.join("\n"), false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
-use crate::{Context, Error};
+use crate::{Context, Error, game::SessionId};
 
-/// Stops the active game, if any.
+/// Stops the active game in this channel, if any.
 #[poise::command(
     slash_command,
     user_cooldown = 3,
@@ -8,8 +8,7 @@ use crate::{Context, Error};
     description_localized("ja", "ゲームを止まる")
 )]
 pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
-    let session_id = ctx.guild_id().map(|g| g.get()).unwrap_or(ctx.author().id.get());
-    let stopped = ctx.data().manager.stop(session_id).await;
+    let stopped = ctx.data().manager.stop(SessionId::of(&ctx)).await;
 
     if stopped {
         ctx.say("Stopping game...").await?;
@@ -0,0 +1,52 @@
+use poise::serenity_prelude::{
+    self as serenity, ComponentInteraction, CreateAttachment, CreateButton,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::{Error, image};
+
+/// Prefix of the custom_id of stroke order buttons. Followed by the word.
+const STROKE_ORDER_PREFIX: &str = "strokes,";
+
+/// Creates a button that shows how the kanji of `word` are written, if any of
+/// them have a stroke order.
+pub fn stroke_order_button(word: &str) -> Option<CreateButton> {
+    word.chars()
+        .any(image::has_stroke_order)
+        .then(|| CreateButton::new(format!("{STROKE_ORDER_PREFIX}{word}")).label("✍️ Stroke Order"))
+}
+
+/// Determines whether `interaction` is a press of a [`stroke_order_button`].
+pub fn is_stroke_order_button(interaction: &ComponentInteraction) -> bool {
+    interaction.data.custom_id.starts_with(STROKE_ORDER_PREFIX)
+}
+
+/// Privately shows the presser the stroke order of the word of a pressed
+/// [`stroke_order_button`].
+pub async fn handle_stroke_order_button(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), Error> {
+    let word = interaction
+        .data
+        .custom_id
+        .strip_prefix(STROKE_ORDER_PREFIX)
+        .unwrap_or_default();
+
+    let message = match image::stroke_order_to_image(word) {
+        Some(image) => CreateInteractionResponseMessage::new()
+            .content(format!("How to write {word} ・ {word}の書き順"))
+            .add_file(CreateAttachment::bytes(image, "strokes.png")),
+        None => CreateInteractionResponseMessage::new()
+            .content("There is no stroke order for this word"),
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(message.ephemeral(true)),
+        )
+        .await?;
+
+    Ok(())
+}
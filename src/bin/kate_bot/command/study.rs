@@ -0,0 +1,96 @@
+use poise::{CreateReply, serenity_prelude::UserId};
+
+use crate::{Context, Error, db::StudyDay};
+
+/// Tracks your daily study goal.
+#[poise::command(
+    slash_command,
+    subcommands("goal", "progress"),
+    name_localized("ja", "勉強"),
+    description_localized("ja", "毎日の勉強目標")
+)]
+pub async fn study(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Sets how many questions you aim to answer today.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "目標"),
+    description_localized("ja", "今日答える問題の数を決める")
+)]
+pub async fn goal(
+    ctx: Context<'_>,
+    #[name_localized("ja", "問題数")]
+    #[description = "Number of questions to answer today"]
+    #[description_localized("ja", "今日答える問題の数")]
+    #[min = 1]
+    #[max = 1000]
+    count: u32,
+) -> Result<(), Error> {
+    let day = ctx.data().db.set_study_goal(ctx.author().id, count)?;
+
+    let content = if day.answered >= count {
+        format!(
+            "Today's goal is set to {count} question(s). You've already answered {}!",
+            day.answered
+        )
+    } else {
+        format!(
+            "Today's goal is set to {count} question(s). Answer questions in games or flashcards to make progress.\n{}",
+            progress_bar(&day)
+        )
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Shows your progress towards today's goal.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    name_localized("ja", "進捗"),
+    description_localized("ja", "今日の目標の進捗を見る")
+)]
+pub async fn progress(ctx: Context<'_>) -> Result<(), Error> {
+    let content = match ctx.data().db.study_day(ctx.author().id)? {
+        Some(day) if day.goal.is_some() => progress_bar(&day),
+        Some(day) => format!(
+            "You've answered {} question(s) today. Set a goal with `/study goal`.",
+            day.answered
+        ),
+        None => "You haven't studied today. Set a goal with `/study goal`.".to_string(),
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Renders the progress of `day` towards its goal as a bar.
+fn progress_bar(day: &StudyDay) -> String {
+    const WIDTH: u32 = 10;
+
+    let goal = day.goal.unwrap_or(0).max(1);
+    let filled = (day.answered.min(goal) * WIDTH / goal) as usize;
+
+    format!(
+        "`{}{}` {}/{goal}",
+        "█".repeat(filled),
+        "░".repeat(WIDTH as usize - filled),
+        day.answered
+    )
+}
+
+/// Creates the message congratulating `user_id` on reaching today's goal of `day`.
+pub fn goal_reached_message(user_id: UserId, day: &StudyDay) -> String {
+    format!(
+        "🎉 <@{user_id}> reached today's goal of answering {} question(s)!",
+        day.answered
+    )
+}
@@ -0,0 +1,197 @@
+use jplearnbot::wordlist;
+use poise::{CreateReply, serenity_prelude::Attachment};
+
+use crate::{Context, Error};
+
+/// Largest list that can be imported, in bytes.
+const MAX_FILE_SIZE: u32 = 1024 * 1024;
+
+/// Most unmatched rows listed after an import.
+const MAX_UNMATCHED_SHOWN: usize = 15;
+
+/// Manages your own lists of words to study.
+#[poise::command(
+    slash_command,
+    subcommands("import_wordlist", "list_wordlists", "remove_wordlist"),
+    name_localized("ja", "単語リスト"),
+    description_localized("ja", "自分の単語リストを管理する")
+)]
+pub async fn wordlist(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Imports a word list from a CSV file or an Anki export of notes in plain text.
+#[poise::command(
+    slash_command,
+    user_cooldown = 10,
+    rename = "import",
+    name_localized("ja", "インポート"),
+    description_localized(
+        "ja",
+        "CSVファイルかAnkiのテキスト形式の書き出しから単語リストを取り込む"
+    )
+)]
+pub async fn import_wordlist(
+    ctx: Context<'_>,
+    #[name_localized("ja", "ファイル")]
+    #[description = "A .csv, .tsv, or .txt file with a word in Japanese or an entry id on each row"]
+    #[description_localized(
+        "ja",
+        "各行に日本語の単語か見出しのIDがある.csv、.tsv、または.txtファイル"
+    )]
+    file: Attachment,
+    #[name_localized("ja", "名前")]
+    #[description = "Name of the list, replacing your list of the same name"]
+    #[description_localized("ja", "リストの名前（同じ名前のリストは置き換えられる）")]
+    #[max_length = 50]
+    name: String,
+) -> Result<(), Error> {
+    let name = name.trim();
+    let filename = file.filename.to_lowercase();
+    let refusal = if ![".csv", ".tsv", ".txt"]
+        .iter()
+        .any(|ext| filename.ends_with(ext))
+    {
+        Some("Please upload a .csv, .tsv, or .txt file".to_string())
+    } else if file.size > MAX_FILE_SIZE {
+        Some(format!(
+            "Word lists can be at most {} MB",
+            MAX_FILE_SIZE / (1024 * 1024)
+        ))
+    } else if name.is_empty() {
+        Some("Please give the list a name".to_string())
+    } else {
+        None
+    };
+    if let Some(refusal) = refusal {
+        ctx.send(CreateReply::default().content(refusal).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer_ephemeral().await?;
+
+    let bytes = file.download().await?;
+    let Ok(text) = String::from_utf8(bytes) else {
+        ctx.send(
+            CreateReply::default()
+                .content("The file isn't UTF-8 text. Please export it as UTF-8.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let import = wordlist::import(ctx.data().manager.dictionary(), &text);
+    if import.entries.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("No rows of the file matched a word of the dictionary")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+    ctx.data()
+        .db
+        .save_wordlist(ctx.author().id, name, &import.entries)?;
+
+    let mut content = format!(
+        "Imported {} word(s) into **{name}**. Play them with `/start` ・ 単語リストに{}語を取り込みました",
+        import.entries.len(),
+        import.entries.len()
+    );
+    if !import.unmatched.is_empty() {
+        content.push_str(&format!(
+            "\n\n{} row(s) didn't match a word ・ 一致しなかった行:",
+            import.unmatched.len()
+        ));
+        for row in import.unmatched.iter().take(MAX_UNMATCHED_SHOWN) {
+            let mut fields = row.fields.join(", ");
+            if fields.chars().count() > 60 {
+                fields = fields.chars().take(60).collect::<String>() + "…";
+            }
+            content.push_str(&format!("\nLine {}: {fields}", row.line));
+        }
+        if import.unmatched.len() > MAX_UNMATCHED_SHOWN {
+            content.push_str(&format!(
+                "\n…and {} more",
+                import.unmatched.len() - MAX_UNMATCHED_SHOWN
+            ));
+        }
+    }
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Lists your word lists.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    rename = "list",
+    name_localized("ja", "一覧"),
+    description_localized("ja", "自分の単語リストの一覧を見る")
+)]
+pub async fn list_wordlists(ctx: Context<'_>) -> Result<(), Error> {
+    let lists = ctx.data().db.wordlists(ctx.author().id)?;
+
+    let content = if lists.is_empty() {
+        "You have no word lists. Import one with `/wordlist import`".to_string()
+    } else {
+        lists
+            .iter()
+            .map(|(name, words)| format!("**{name}**: {words} word(s)"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Removes one of your word lists.
+#[poise::command(
+    slash_command,
+    user_cooldown = 3,
+    rename = "remove",
+    name_localized("ja", "削除"),
+    description_localized("ja", "自分の単語リストを削除する")
+)]
+pub async fn remove_wordlist(
+    ctx: Context<'_>,
+    #[name_localized("ja", "名前")]
+    #[description = "Name of the list"]
+    #[description_localized("ja", "リストの名前")]
+    #[autocomplete = "autocomplete_wordlist"]
+    name: String,
+) -> Result<(), Error> {
+    let content = if ctx.data().db.remove_wordlist(ctx.author().id, &name)? {
+        format!("Removed **{name}**")
+    } else {
+        format!("You have no word list named **{name}**")
+    };
+
+    ctx.send(CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Lists the names of the author's word lists containing `partial`.
+pub(super) async fn autocomplete_wordlist(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+
+    ctx.data()
+        .db
+        .wordlists(ctx.author().id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect()
+}
@@ -0,0 +1,190 @@
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty, quiz::Mode};
+use poise::{CreateReply, serenity_prelude::CreateAttachment};
+use rand::{SeedableRng, rngs::StdRng};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Context, Error,
+    game::{Filters, PosFilter, Question},
+    image,
+};
+
+use super::flashcards::autocomplete_level;
+
+/// Default of how many questions a worksheet has.
+const DEFAULT_QUESTIONS: usize = 20;
+
+/// Makes printable practice sheets.
+#[poise::command(
+    slash_command,
+    subcommands("generate"),
+    name_localized("ja", "プリント"),
+    description_localized("ja", "印刷用の練習問題を作る")
+)]
+pub async fn worksheet(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Makes a printable worksheet of quiz questions, with its answer key on the last page.
+#[poise::command(
+    slash_command,
+    user_cooldown = 10,
+    name_localized("ja", "作成"),
+    description_localized("ja", "印刷用の問題と最後のページに解答を作る")
+)]
+pub async fn generate(
+    ctx: Context<'_>,
+    #[name_localized("ja", "レベル")]
+    #[description = "Pick the NLevel of the words asked about"]
+    #[description_localized("ja", "出題する単語のNレベルを選んでください")]
+    #[autocomplete = "autocomplete_level"]
+    level: String,
+    #[name_localized("ja", "品詞")]
+    #[description = "Part of speech of the words asked about. Every one by default"]
+    #[description_localized("ja", "出題する単語の品詞（デフォルトは全て）")]
+    #[autocomplete = "autocomplete_pos"]
+    pos: Option<String>,
+    #[name_localized("ja", "問題数")]
+    #[description = "Number of questions (default 20)"]
+    #[description_localized("ja", "問題の数（デフォルト20）")]
+    #[min = 1]
+    #[max = 50]
+    questions: Option<usize>,
+) -> Result<(), Error> {
+    let Ok(level) = level.to_uppercase().parse::<NLevel>() else {
+        ctx.send(
+            CreateReply::default()
+                .content("Unknown NLevel. Please pick one from the list.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+    let pos = match pos {
+        Some(pos) => match parse_pos(&pos) {
+            Some(pos) => vec![pos],
+            None => {
+                ctx.send(
+                    CreateReply::default()
+                        .content("Unknown part of speech. Please pick one from the list.")
+                        .ephemeral(true),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+        None => PosFilter::iter().collect(),
+    };
+
+    let questions: Vec<_> = match ctx.data().manager.source(&Mode::Mixed.to_string()) {
+        Some(source) => source
+            .questions(
+                &Filters {
+                    levels: vec![level],
+                    pos,
+                    entries: None,
+                    distractors: Difficulty::default(),
+                    grades: Vec::new(),
+                    excluded: ctx.data().manager.recent().recent(ctx.author().id),
+                    adaptive: None,
+                },
+                StdRng::from_rng(&mut rand::rng()),
+            )
+            .take(questions.unwrap_or(DEFAULT_QUESTIONS))
+            .collect(),
+        None => Vec::new(),
+    };
+    if questions.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("There are no words to make a worksheet from")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    for entry in questions.iter().filter_map(|q| q.entry.as_ref()) {
+        ctx.data().manager.recent().mark(ctx.author().id, entry.id);
+    }
+
+    ctx.defer().await?;
+
+    let title = format!("{level} Worksheet ・ 練習問題");
+    let sheet = image::page_to_image(&title, &question_paragraphs(&questions), ctx.guild_id());
+    let key = image::page_to_image(
+        &format!("{level} Answer Key ・ 解答"),
+        &answer_paragraphs(&questions),
+        ctx.guild_id(),
+    );
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "{} questions about {level} words, ready to print ・ 印刷用の問題です",
+                questions.len()
+            ))
+            .attachment(CreateAttachment::bytes(sheet, "worksheet.png"))
+            .attachment(CreateAttachment::bytes(key, "answer-key.png")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the parts of speech whose name contains `partial`.
+async fn autocomplete_pos(_ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+
+    PosFilter::iter()
+        .map(|pos| pos.to_string())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect()
+}
+
+/// Gets the part of speech named `name`, or whose name starts with it, e.g. `nouns`.
+fn parse_pos(name: &str) -> Option<PosFilter> {
+    let name = name.trim().to_lowercase();
+
+    PosFilter::iter().find(|pos| pos.to_string().to_lowercase().starts_with(&name))
+}
+
+/// Writes each of `questions` with what it asks for, followed by its numbered
+/// options a line each.
+fn question_paragraphs(questions: &[Question]) -> Vec<String> {
+    questions
+        .iter()
+        .enumerate()
+        .map(|(i, question)| {
+            let options = question
+                .options
+                .iter()
+                .enumerate()
+                .map(|(j, option)| format!("\n      ({}) {option}", j + 1))
+                .collect::<String>();
+            let mode = question
+                .mode
+                .as_deref()
+                .map(|mode| format!("    [{mode}]"))
+                .unwrap_or_default();
+
+            format!("{}. {}{mode}{options}", i + 1, question.prompt)
+        })
+        .collect()
+}
+
+/// Writes the number and text of the answer of each of `questions`.
+fn answer_paragraphs(questions: &[Question]) -> Vec<String> {
+    questions
+        .iter()
+        .enumerate()
+        .map(|(i, question)| {
+            format!(
+                "{}. ({}) {}",
+                i + 1,
+                question.answer + 1,
+                question.options[question.answer]
+            )
+        })
+        .collect()
+}
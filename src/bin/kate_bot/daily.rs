@@ -0,0 +1,182 @@
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use jplearnbot::{distractor::Difficulty, quiz::Mode};
+use poise::serenity_prelude::{
+    CreateAttachment, CreateEmbed, CreateEmbedFooter, CreateMessage, UserId, http::Http,
+};
+use rand::{SeedableRng, rngs::StdRng};
+use strum::IntoEnumIterator;
+
+use crate::{
+    Error, command,
+    db::{DailyQuiz, Database},
+    format::{self, TimestampStyle},
+    game::{Filters, Manager, PosFilter},
+    image,
+};
+
+/// How often the scheduler checks for daily quizzes to post.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Length of a day in seconds.
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Posts every daily quiz that is due, checking every [`CHECK_INTERVAL`]. Runs forever.
+pub async fn schedule(http: Arc<Http>, db: Arc<Database>, manager: Arc<Manager>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let (day, hour) = now();
+        let quizzes = match db.due_daily_quizzes(day, hour) {
+            Ok(quizzes) => quizzes,
+            Err(e) => {
+                eprintln!("Failed to get due daily quizzes: {e}");
+                continue;
+            }
+        };
+
+        for quiz in quizzes {
+            if let Err(e) = post(&http, &db, &manager, &quiz, day).await {
+                eprintln!("Failed to post daily quiz of {}: {e}", quiz.guild_id);
+            }
+        }
+    }
+}
+
+/// Gets the current day since the Unix epoch and the hour of the day in UTC.
+pub fn now() -> (u64, u32) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    (secs / SECS_PER_DAY, (secs % SECS_PER_DAY / 3600) as u32)
+}
+
+/// Gets the next time it is `hour` UTC, in seconds since the Unix epoch.
+pub fn next_occurrence(hour: u32) -> u64 {
+    let (day, now) = now();
+    let day = if hour > now { day } else { day + 1 };
+
+    day * SECS_PER_DAY + u64::from(hour) * 3600
+}
+
+/// Posts the standings of the previous daily quiz of `quiz`'s server if anyone
+/// played it, then the questions of `day`.
+async fn post(
+    http: &Http,
+    db: &Database,
+    manager: &Manager,
+    quiz: &DailyQuiz,
+    day: u64,
+) -> Result<(), Error> {
+    // Marked first so a channel that can't be posted in isn't retried every minute.
+    db.mark_daily_quiz_posted(quiz.guild_id, day)?;
+
+    if let Some(last_day) = quiz.last_day {
+        let standings = db.daily_standings(quiz.guild_id, last_day)?;
+        if !standings.is_empty() {
+            quiz.channel_id
+                .send_message(
+                    http,
+                    CreateMessage::new().embed(standings_embed(last_day, &standings)),
+                )
+                .await?;
+        }
+    }
+
+    let Some(source) = manager.source(&Mode::Mixed.to_string()) else {
+        return Ok(());
+    };
+    // Seeded by the day so every server gets the same questions.
+    let questions: Vec<_> = source
+        .questions(
+            &Filters {
+                levels: manager.dictionary().levels().to_vec(),
+                pos: PosFilter::iter().collect(),
+                entries: None,
+                distractors: Difficulty::default(),
+                grades: Vec::new(),
+                excluded: HashSet::new(),
+                adaptive: None,
+            },
+            StdRng::seed_from_u64(day),
+        )
+        .take(quiz.questions as usize)
+        .collect();
+
+    quiz.channel_id
+        .send_message(
+            http,
+            CreateMessage::new().content(format!(
+                "📅 **Daily Quiz ・ 今日のクイズ** {}\nEveryone can answer each question once. The results are posted with the next quiz.",
+                format::timestamp(day * SECS_PER_DAY, TimestampStyle::LongDate)
+            )),
+        )
+        .await?;
+
+    for (i, question) in questions.iter().enumerate() {
+        db.add_daily_question(
+            quiz.guild_id,
+            day,
+            i as u32,
+            question.answer,
+            question.entry.as_ref().map(|entry| entry.id),
+        )?;
+
+        let embed = CreateEmbed::new()
+            .title(format!("Daily Question {}/{}", i + 1, questions.len()))
+            .field(question.mode.as_deref().unwrap_or(source.name()), "", false)
+            .attachment("prompt.png");
+        quiz.channel_id
+            .send_files(
+                http,
+                [CreateAttachment::bytes(
+                    image::text_to_image(&question.prompt, Some(quiz.guild_id)),
+                    "prompt.png",
+                )],
+                CreateMessage::new()
+                    .embed(embed)
+                    .components(command::daily_answer_components(
+                        day,
+                        i as u32,
+                        &question.options,
+                    )),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Creates the embed ranking the players of the daily quiz of `day`.
+fn standings_embed(day: u64, standings: &[(UserId, u32, u32)]) -> CreateEmbed {
+    const SIZE: usize = 10;
+
+    let ranks = standings
+        .iter()
+        .take(SIZE)
+        .enumerate()
+        .map(|(i, (user_id, correct, answered))| {
+            format!("{}. <@{user_id}> ・ {correct}/{answered}", i + 1)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    CreateEmbed::new()
+        .title("Daily Quiz Results ・ クイズの結果")
+        .description(format!(
+            "{}\n{ranks}",
+            format::timestamp(day * SECS_PER_DAY, TimestampStyle::LongDate)
+        ))
+        .footer(CreateEmbedFooter::new(format!(
+            "{} player(s) took part",
+            standings.len()
+        )))
+}
@@ -0,0 +1,105 @@
+use std::{
+    path::Path,
+    sync::{Mutex, MutexGuard},
+};
+
+use rusqlite::Connection;
+
+mod blocklist;
+mod bookmark;
+mod channel_defaults;
+mod daily;
+mod emote;
+mod feedback;
+mod font;
+mod mnemonic;
+mod mock_test;
+mod rating;
+mod report;
+mod session;
+mod srs;
+mod stats;
+mod study;
+mod usage;
+mod wordlist;
+
+pub use channel_defaults::ChannelDefaults;
+pub use daily::DailyQuiz;
+pub use mnemonic::Mnemonic;
+pub use mock_test::MockTestResult;
+pub use session::SavedSession;
+pub use stats::AnswerStats;
+pub use study::StudyDay;
+pub use usage::{ALL_COMMANDS, CommandUsage};
+
+/// Schemas of every feature's tables.
+const SCHEMAS: &[&str] = &[
+    bookmark::SCHEMA,
+    srs::SCHEMA,
+    study::SCHEMA,
+    mock_test::SCHEMA,
+    stats::SCHEMA,
+    report::SCHEMA,
+    daily::SCHEMA,
+    blocklist::SCHEMA,
+    session::SCHEMA,
+    font::SCHEMA,
+    rating::SCHEMA,
+    channel_defaults::SCHEMA,
+    wordlist::SCHEMA,
+    usage::SCHEMA,
+    feedback::SCHEMA,
+    emote::SCHEMA,
+    mnemonic::SCHEMA,
+];
+
+/// Columns added to tables after they were first released, as `(table, column, type)`.
+/// Databases made before then get them when opened.
+const ADDED_COLUMNS: &[(&str, &str, &str)] = &[
+    stats::GUILD_ID_COLUMN,
+    session::STARTER_COLUMN,
+    session::SKIPPED_COLUMN,
+    daily::ENTRY_ID_COLUMN,
+];
+
+/// Persistent storage of the bot, backed by SQLite.
+///
+/// Each feature keeps its queries in a submodule as an `impl Database` block
+/// along with the schema of its tables.
+pub struct Database {
+    conn: Mutex<Connection>,
+}
+
+impl Database {
+    /// Opens the database at `path`, creating it and any missing tables and
+    /// columns if needed.
+    ///
+    /// # Errors
+    /// Fails if the database can't be opened or the tables can't be created.
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+
+        for schema in SCHEMAS {
+            conn.execute_batch(schema)?;
+        }
+        for (table, column, kind) in ADDED_COLUMNS {
+            let exists: bool = conn.query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+                [table, column],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {kind}"))?;
+            }
+        }
+
+        Ok(Database {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Locks the connection for the duration of a query.
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,46 @@
+use poise::serenity_prelude::UserId;
+use rusqlite::params;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocked_entries (
+    entry_id INTEGER PRIMARY KEY,
+    blocked_by INTEGER NOT NULL,
+    blocked_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
+";
+
+impl Database {
+    /// Blocks `entry_id` from being asked about, on behalf of `user_id`.
+    ///
+    /// Returns false if the entry was already blocked.
+    pub fn block_entry(&self, entry_id: u32, user_id: UserId) -> rusqlite::Result<bool> {
+        let inserted = self.conn().execute(
+            "INSERT OR IGNORE INTO blocked_entries (entry_id, blocked_by) VALUES (?1, ?2)",
+            params![entry_id, user_id.get() as i64],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Allows `entry_id` to be asked about again.
+    ///
+    /// Returns false if the entry wasn't blocked.
+    pub fn unblock_entry(&self, entry_id: u32) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM blocked_entries WHERE entry_id = ?1",
+            params![entry_id],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Gets the ids of all the blocked entries.
+    pub fn blocked_entries(&self) -> rusqlite::Result<Vec<u32>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT entry_id FROM blocked_entries")?;
+
+        stmt.query_map([], |row| row.get(0))?.collect()
+    }
+}
@@ -0,0 +1,38 @@
+use poise::serenity_prelude::UserId;
+use rusqlite::params;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS bookmarks (
+    user_id INTEGER NOT NULL,
+    entry_id INTEGER NOT NULL,
+    created_at INTEGER NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY (user_id, entry_id)
+);
+";
+
+impl Database {
+    /// Adds `entry_id` to `user_id`'s bookmarks.
+    ///
+    /// Returns false if the entry was already bookmarked.
+    pub fn add_bookmark(&self, user_id: UserId, entry_id: u32) -> rusqlite::Result<bool> {
+        let inserted = self.conn().execute(
+            "INSERT OR IGNORE INTO bookmarks (user_id, entry_id) VALUES (?1, ?2)",
+            params![user_id.get() as i64, entry_id],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Gets the entry ids of `user_id`'s bookmarks, most recent first.
+    pub fn bookmarks(&self, user_id: UserId) -> rusqlite::Result<Vec<u32>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id FROM bookmarks WHERE user_id = ?1 ORDER BY created_at DESC, rowid DESC",
+        )?;
+
+        stmt.query_map(params![user_id.get() as i64], |row| row.get(0))?
+            .collect()
+    }
+}
@@ -0,0 +1,112 @@
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty};
+use poise::serenity_prelude::{ChannelId, GuildId};
+use rusqlite::{OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Database,
+    session::{from_json, to_json},
+};
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS channel_defaults (
+    channel_id INTEGER PRIMARY KEY,
+    guild_id INTEGER NOT NULL,
+    defaults TEXT NOT NULL
+);
+";
+
+/// Settings `/start` picks in a channel when they aren't given, e.g. only N4
+/// and N5 words with easy distractors in a beginners' channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelDefaults {
+    /// Levels selected at first, or every playable one if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<NLevel>,
+    /// How closely the wrong options resemble the answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distractors: Option<Difficulty>,
+    /// Rounds a game lasts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<usize>,
+    /// Seconds each question can be answered in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit: Option<u64>,
+}
+
+impl Database {
+    /// Stores `defaults` as the defaults of `channel_id` of `guild_id`, replacing
+    /// any previous ones. They are stored as JSON.
+    pub fn set_channel_defaults(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        defaults: &ChannelDefaults,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO channel_defaults (channel_id, guild_id, defaults)
+             VALUES (?1, ?2, ?3)",
+            params![
+                channel_id.get() as i64,
+                guild_id.get() as i64,
+                to_json(defaults)?
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Forgets the defaults of `channel_id`.
+    ///
+    /// Returns false if it had none.
+    pub fn remove_channel_defaults(&self, channel_id: ChannelId) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM channel_defaults WHERE channel_id = ?1",
+            params![channel_id.get() as i64],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Forgets the defaults of every channel of `guild_id`.
+    ///
+    /// Returns how many channels had defaults.
+    pub fn remove_guild_channel_defaults(&self, guild_id: GuildId) -> rusqlite::Result<usize> {
+        self.conn().execute(
+            "DELETE FROM channel_defaults WHERE guild_id = ?1",
+            params![guild_id.get() as i64],
+        )
+    }
+
+    /// Gets the defaults of `channel_id`, if it has any.
+    pub fn channel_defaults(
+        &self,
+        channel_id: ChannelId,
+    ) -> rusqlite::Result<Option<ChannelDefaults>> {
+        self.conn()
+            .query_row(
+                "SELECT defaults FROM channel_defaults WHERE channel_id = ?1",
+                params![channel_id.get() as i64],
+                |row| from_json(row, 0),
+            )
+            .optional()
+    }
+
+    /// Gets the defaults of every channel of `guild_id` that has any.
+    pub fn guild_channel_defaults(
+        &self,
+        guild_id: GuildId,
+    ) -> rusqlite::Result<Vec<(ChannelId, ChannelDefaults)>> {
+        let conn = self.conn();
+        let mut stmt =
+            conn.prepare("SELECT channel_id, defaults FROM channel_defaults WHERE guild_id = ?1")?;
+
+        stmt.query_map(params![guild_id.get() as i64], |row| {
+            Ok((
+                ChannelId::new(row.get::<_, i64>(0)? as u64),
+                from_json(row, 1)?,
+            ))
+        })?
+        .collect()
+    }
+}
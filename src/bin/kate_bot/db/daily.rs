@@ -0,0 +1,233 @@
+use poise::serenity_prelude::{ChannelId, GuildId, UserId};
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS daily_quizzes (
+    guild_id INTEGER PRIMARY KEY,
+    channel_id INTEGER NOT NULL,
+    hour INTEGER NOT NULL,
+    questions INTEGER NOT NULL,
+    last_day INTEGER
+);
+CREATE TABLE IF NOT EXISTS daily_questions (
+    guild_id INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    question INTEGER NOT NULL,
+    answer INTEGER NOT NULL,
+    entry_id INTEGER,
+    PRIMARY KEY (guild_id, day, question)
+);
+CREATE TABLE IF NOT EXISTS daily_answers (
+    guild_id INTEGER NOT NULL,
+    day INTEGER NOT NULL,
+    question INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    correct INTEGER NOT NULL,
+    answered_at INTEGER NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY (guild_id, day, question, user_id)
+);
+";
+
+/// The id of the entry a daily quiz question asks about, missing from
+/// databases made before players were kept from seeing entries again soon.
+/// Questions asked before then have none.
+pub(super) const ENTRY_ID_COLUMN: (&str, &str, &str) = ("daily_questions", "entry_id", "INTEGER");
+
+/// A server's daily quiz schedule. Days are counted since the Unix epoch.
+#[derive(Debug, Clone, Copy)]
+pub struct DailyQuiz {
+    pub guild_id: GuildId,
+    /// Channel the quiz is posted in.
+    pub channel_id: ChannelId,
+    /// Hour of the day in UTC the quiz is posted at.
+    pub hour: u32,
+    /// How many questions are asked each day.
+    pub questions: u32,
+    /// The last day the quiz was posted, if ever.
+    pub last_day: Option<u64>,
+}
+
+impl Database {
+    /// Schedules `guild_id`'s daily quiz of `questions` questions in `channel_id`
+    /// at `hour` UTC, replacing any previous schedule.
+    pub fn set_daily_quiz(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        hour: u32,
+        questions: u32,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT INTO daily_quizzes (guild_id, channel_id, hour, questions) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (guild_id) DO UPDATE SET
+                channel_id = excluded.channel_id, hour = excluded.hour, questions = excluded.questions",
+            params![
+                guild_id.get() as i64,
+                channel_id.get() as i64,
+                hour,
+                questions
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Unschedules `guild_id`'s daily quiz.
+    ///
+    /// Returns false if there was none.
+    pub fn remove_daily_quiz(&self, guild_id: GuildId) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM daily_quizzes WHERE guild_id = ?1",
+            params![guild_id.get() as i64],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Gets `guild_id`'s daily quiz schedule, if any.
+    pub fn daily_quiz(&self, guild_id: GuildId) -> rusqlite::Result<Option<DailyQuiz>> {
+        self.conn()
+            .query_row(
+                "SELECT guild_id, channel_id, hour, questions, last_day FROM daily_quizzes
+                 WHERE guild_id = ?1",
+                params![guild_id.get() as i64],
+                daily_quiz_row,
+            )
+            .optional()
+    }
+
+    /// Gets the daily quizzes that haven't been posted on `day` and are
+    /// scheduled at or before `hour`.
+    pub fn due_daily_quizzes(&self, day: u64, hour: u32) -> rusqlite::Result<Vec<DailyQuiz>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT guild_id, channel_id, hour, questions, last_day FROM daily_quizzes
+             WHERE (last_day IS NULL OR last_day < ?1) AND hour <= ?2",
+        )?;
+
+        stmt.query_map(params![day as i64, hour], daily_quiz_row)?
+            .collect()
+    }
+
+    /// Marks `guild_id`'s daily quiz as posted on `day`.
+    pub fn mark_daily_quiz_posted(&self, guild_id: GuildId, day: u64) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "UPDATE daily_quizzes SET last_day = ?2 WHERE guild_id = ?1",
+            params![guild_id.get() as i64, day as i64],
+        )?;
+
+        Ok(())
+    }
+
+    /// Stores the index of the correct option of the `question`th question of
+    /// `guild_id`'s daily quiz of `day`, along with the id of the entry it asks
+    /// about, if any.
+    pub fn add_daily_question(
+        &self,
+        guild_id: GuildId,
+        day: u64,
+        question: u32,
+        answer: usize,
+        entry_id: Option<u32>,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO daily_questions (guild_id, day, question, answer, entry_id)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                guild_id.get() as i64,
+                day as i64,
+                question,
+                answer as i64,
+                entry_id
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets the index of the correct option of the `question`th question of
+    /// `guild_id`'s daily quiz of `day`, if it was asked, along with the id of
+    /// the entry it asks about, if any.
+    pub fn daily_question_answer(
+        &self,
+        guild_id: GuildId,
+        day: u64,
+        question: u32,
+    ) -> rusqlite::Result<Option<(usize, Option<u32>)>> {
+        self.conn()
+            .query_row(
+                "SELECT answer, entry_id FROM daily_questions
+                 WHERE guild_id = ?1 AND day = ?2 AND question = ?3",
+                params![guild_id.get() as i64, day as i64, question],
+                |row| Ok((row.get::<_, i64>(0)? as usize, row.get(1)?)),
+            )
+            .optional()
+    }
+
+    /// Stores `user_id`'s answer to the `question`th question of `guild_id`'s
+    /// daily quiz of `day`.
+    ///
+    /// Returns false if the user already answered the question.
+    pub fn add_daily_answer(
+        &self,
+        guild_id: GuildId,
+        day: u64,
+        question: u32,
+        user_id: UserId,
+        correct: bool,
+    ) -> rusqlite::Result<bool> {
+        let inserted = self.conn().execute(
+            "INSERT OR IGNORE INTO daily_answers (guild_id, day, question, user_id, correct)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                guild_id.get() as i64,
+                day as i64,
+                question,
+                user_id.get() as i64,
+                correct
+            ],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Ranks the players of `guild_id`'s daily quiz of `day` by how many questions
+    /// they got right, then by who finished answering first.
+    ///
+    /// Returns each player with their correct and total answers.
+    pub fn daily_standings(
+        &self,
+        guild_id: GuildId,
+        day: u64,
+    ) -> rusqlite::Result<Vec<(UserId, u32, u32)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT user_id, SUM(correct), COUNT(*) FROM daily_answers
+             WHERE guild_id = ?1 AND day = ?2
+             GROUP BY user_id
+             ORDER BY SUM(correct) DESC, MAX(answered_at) ASC",
+        )?;
+
+        stmt.query_map(params![guild_id.get() as i64, day as i64], |row| {
+            Ok((
+                UserId::new(row.get::<_, i64>(0)? as u64),
+                row.get(1)?,
+                row.get(2)?,
+            ))
+        })?
+        .collect()
+    }
+}
+
+/// Reads a [`DailyQuiz`] from a row of `daily_quizzes`.
+fn daily_quiz_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DailyQuiz> {
+    Ok(DailyQuiz {
+        guild_id: GuildId::new(row.get::<_, i64>(0)? as u64),
+        channel_id: ChannelId::new(row.get::<_, i64>(1)? as u64),
+        hour: row.get(2)?,
+        questions: row.get(3)?,
+        last_day: row.get::<_, Option<i64>>(4)?.map(|day| day as u64),
+    })
+}
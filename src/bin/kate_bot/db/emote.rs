@@ -0,0 +1,79 @@
+use std::str::FromStr;
+
+use poise::serenity_prelude::GuildId;
+use rusqlite::params;
+
+use crate::emote::Emote;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS guild_emotes (
+    guild_id INTEGER NOT NULL,
+    emote TEXT NOT NULL,
+    text TEXT NOT NULL,
+    PRIMARY KEY (guild_id, emote)
+);
+";
+
+impl Database {
+    /// Stores `text` as `guild_id`'s `emote`, replacing any previous one.
+    pub fn set_guild_emote(
+        &self,
+        guild_id: GuildId,
+        emote: Emote,
+        text: &str,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO guild_emotes (guild_id, emote, text) VALUES (?1, ?2, ?3)",
+            params![guild_id.get() as i64, emote.key(), text],
+        )?;
+
+        Ok(())
+    }
+
+    /// Forgets `guild_id`'s `emote`.
+    ///
+    /// Returns false if it had none.
+    pub fn remove_guild_emote(&self, guild_id: GuildId, emote: Emote) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM guild_emotes WHERE guild_id = ?1 AND emote = ?2",
+            params![guild_id.get() as i64, emote.key()],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Gets the emotes `guild_id` set. Ones no longer known are skipped.
+    pub fn guild_emotes_of(&self, guild_id: GuildId) -> rusqlite::Result<Vec<(Emote, String)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT emote, text FROM guild_emotes WHERE guild_id = ?1")?;
+
+        let emotes = stmt
+            .query_map(params![guild_id.get() as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(emotes
+            .into_iter()
+            .filter_map(|(name, text)| Some((Emote::from_str(&name).ok()?, text)))
+            .collect())
+    }
+
+    /// Gets the emotes of every Server that set any, as the Server, the name of
+    /// the emote, and the emote.
+    pub fn guild_emotes(&self) -> rusqlite::Result<Vec<(GuildId, String, String)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT guild_id, emote, text FROM guild_emotes")?;
+
+        stmt.query_map([], |row| {
+            Ok((
+                GuildId::new(row.get::<_, i64>(0)? as u64),
+                row.get(1)?,
+                row.get(2)?,
+            ))
+        })?
+        .collect()
+    }
+}
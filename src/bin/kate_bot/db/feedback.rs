@@ -0,0 +1,57 @@
+use poise::serenity_prelude::GuildId;
+use rusqlite::{OptionalExtension, params};
+
+use crate::feedback::FeedbackSettings;
+
+use super::{
+    Database,
+    session::{from_json, to_json},
+};
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS guild_feedback (
+    guild_id INTEGER PRIMARY KEY,
+    settings TEXT NOT NULL
+);
+";
+
+impl Database {
+    /// Stores `settings` as how players of `guild_id` are told their answers are
+    /// wrong, replacing any previous ones. They are stored as JSON.
+    pub fn set_guild_feedback(
+        &self,
+        guild_id: GuildId,
+        settings: &FeedbackSettings,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO guild_feedback (guild_id, settings) VALUES (?1, ?2)",
+            params![guild_id.get() as i64, to_json(settings)?],
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets how players of `guild_id` are told their answers are wrong, if it
+    /// was ever set.
+    pub fn guild_feedback(&self, guild_id: GuildId) -> rusqlite::Result<Option<FeedbackSettings>> {
+        self.conn()
+            .query_row(
+                "SELECT settings FROM guild_feedback WHERE guild_id = ?1",
+                params![guild_id.get() as i64],
+                |row| from_json(row, 0),
+            )
+            .optional()
+    }
+
+    /// Forgets how players of `guild_id` are told their answers are wrong.
+    ///
+    /// Returns false if it was never set.
+    pub fn remove_guild_feedback(&self, guild_id: GuildId) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM guild_feedback WHERE guild_id = ?1",
+            params![guild_id.get() as i64],
+        )?;
+
+        Ok(removed > 0)
+    }
+}
@@ -0,0 +1,64 @@
+use poise::serenity_prelude::GuildId;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS guild_fonts (
+    guild_id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    data BLOB NOT NULL
+);
+";
+
+impl Database {
+    /// Stores the font file `data` named `name` as `guild_id`'s font, replacing
+    /// any previous one.
+    pub fn set_guild_font(
+        &self,
+        guild_id: GuildId,
+        name: &str,
+        data: &[u8],
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO guild_fonts (guild_id, name, data) VALUES (?1, ?2, ?3)",
+            params![guild_id.get() as i64, name, data],
+        )?;
+
+        Ok(())
+    }
+
+    /// Forgets `guild_id`'s font.
+    ///
+    /// Returns false if it had none.
+    pub fn remove_guild_font(&self, guild_id: GuildId) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM guild_fonts WHERE guild_id = ?1",
+            params![guild_id.get() as i64],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Gets the file name of `guild_id`'s font, if it has one.
+    pub fn guild_font_name(&self, guild_id: GuildId) -> rusqlite::Result<Option<String>> {
+        self.conn()
+            .query_row(
+                "SELECT name FROM guild_fonts WHERE guild_id = ?1",
+                params![guild_id.get() as i64],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// Gets the font files of every Server that has one.
+    pub fn guild_fonts(&self) -> rusqlite::Result<Vec<(GuildId, Vec<u8>)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT guild_id, data FROM guild_fonts")?;
+
+        stmt.query_map([], |row| {
+            Ok((GuildId::new(row.get::<_, i64>(0)? as u64), row.get(1)?))
+        })?
+        .collect()
+    }
+}
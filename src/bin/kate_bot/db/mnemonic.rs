@@ -0,0 +1,152 @@
+use poise::serenity_prelude::{GuildId, UserId};
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS mnemonic_pools (
+    guild_id INTEGER PRIMARY KEY
+);
+CREATE TABLE IF NOT EXISTS mnemonics (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id INTEGER NOT NULL,
+    entry_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    approved INTEGER NOT NULL DEFAULT 0,
+    submitted_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
+CREATE INDEX IF NOT EXISTS mnemonics_by_entry ON mnemonics (guild_id, entry_id);
+";
+
+/// A mnemonic a member shared with their Server.
+#[derive(Debug, Clone)]
+pub struct Mnemonic {
+    pub id: i64,
+    /// Id of the dictionary entry it helps remember.
+    pub entry_id: u32,
+    /// The member who shared it.
+    pub user_id: UserId,
+    pub text: String,
+}
+
+impl Database {
+    /// Opens or closes `guild_id`'s shared pool of mnemonics. Mnemonics already
+    /// shared are kept while it is closed.
+    pub fn set_mnemonic_pool(&self, guild_id: GuildId, open: bool) -> rusqlite::Result<()> {
+        let sql = if open {
+            "INSERT OR IGNORE INTO mnemonic_pools (guild_id) VALUES (?1)"
+        } else {
+            "DELETE FROM mnemonic_pools WHERE guild_id = ?1"
+        };
+        self.conn().execute(sql, params![guild_id.get() as i64])?;
+
+        Ok(())
+    }
+
+    /// Determines whether `guild_id` opened a shared pool of mnemonics.
+    pub fn has_mnemonic_pool(&self, guild_id: GuildId) -> rusqlite::Result<bool> {
+        self.conn().query_row(
+            "SELECT COUNT(*) > 0 FROM mnemonic_pools WHERE guild_id = ?1",
+            params![guild_id.get() as i64],
+            |row| row.get(0),
+        )
+    }
+
+    /// Queues `user_id`'s mnemonic `text` for `entry_id` in `guild_id` until a
+    /// moderator approves it.
+    ///
+    /// Returns the id of the mnemonic.
+    pub fn submit_mnemonic(
+        &self,
+        guild_id: GuildId,
+        entry_id: u32,
+        user_id: UserId,
+        text: &str,
+    ) -> rusqlite::Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO mnemonics (guild_id, entry_id, user_id, text) VALUES (?1, ?2, ?3, ?4)",
+            params![guild_id.get() as i64, entry_id, user_id.get() as i64, text],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Gets up to `limit` of `guild_id`'s mnemonics waiting for approval, the
+    /// oldest first.
+    pub fn pending_mnemonics(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<Mnemonic>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, entry_id, user_id, text FROM mnemonics
+             WHERE guild_id = ?1 AND NOT approved
+             ORDER BY id
+             LIMIT ?2",
+        )?;
+
+        stmt.query_map(params![guild_id.get() as i64, limit as i64], |row| {
+            Ok(Mnemonic {
+                id: row.get(0)?,
+                entry_id: row.get(1)?,
+                user_id: UserId::new(row.get::<_, i64>(2)? as u64),
+                text: row.get(3)?,
+            })
+        })?
+        .collect()
+    }
+
+    /// Approves `guild_id`'s pending mnemonic `id`, showing it to the Server.
+    ///
+    /// Returns false if it has no such pending mnemonic.
+    pub fn approve_mnemonic(&self, guild_id: GuildId, id: i64) -> rusqlite::Result<bool> {
+        let updated = self.conn().execute(
+            "UPDATE mnemonics SET approved = 1 WHERE guild_id = ?1 AND id = ?2 AND NOT approved",
+            params![guild_id.get() as i64, id],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    /// Removes `guild_id`'s mnemonic `id`, whether or not it was approved.
+    ///
+    /// Returns false if it has no such mnemonic.
+    pub fn remove_mnemonic(&self, guild_id: GuildId, id: i64) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM mnemonics WHERE guild_id = ?1 AND id = ?2",
+            params![guild_id.get() as i64, id],
+        )?;
+
+        Ok(removed > 0)
+    }
+
+    /// Gets the latest approved mnemonic for `entry_id` in `guild_id`, if its
+    /// pool is open and has one.
+    pub fn approved_mnemonic(
+        &self,
+        guild_id: GuildId,
+        entry_id: u32,
+    ) -> rusqlite::Result<Option<Mnemonic>> {
+        self.conn()
+            .query_row(
+                "SELECT id, entry_id, user_id, text FROM mnemonics
+                 WHERE guild_id = ?1 AND entry_id = ?2 AND approved
+                    AND guild_id IN (SELECT guild_id FROM mnemonic_pools)
+                 ORDER BY id DESC
+                 LIMIT 1",
+                params![guild_id.get() as i64, entry_id],
+                |row| {
+                    Ok(Mnemonic {
+                        id: row.get(0)?,
+                        entry_id: row.get(1)?,
+                        user_id: UserId::new(row.get::<_, i64>(2)? as u64),
+                        text: row.get(3)?,
+                    })
+                },
+            )
+            .optional()
+    }
+}
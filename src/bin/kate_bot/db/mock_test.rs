@@ -0,0 +1,70 @@
+use jplearnbot::dictionary::NLevel;
+use poise::serenity_prelude::UserId;
+use rusqlite::params;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS mock_tests (
+    user_id INTEGER NOT NULL,
+    level TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    total INTEGER NOT NULL,
+    taken_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
+";
+
+/// The result of a mock test.
+#[derive(Debug, Clone, Copy)]
+pub struct MockTestResult {
+    /// How many questions were answered correctly.
+    pub score: u32,
+    /// How many questions were asked.
+    pub total: u32,
+    /// When the test was taken, in seconds since the Unix epoch.
+    pub taken_at: i64,
+}
+
+impl Database {
+    /// Stores `user_id`'s result of a mock test of `level`.
+    pub fn add_mock_test(
+        &self,
+        user_id: UserId,
+        level: NLevel,
+        score: u32,
+        total: u32,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT INTO mock_tests (user_id, level, score, total) VALUES (?1, ?2, ?3, ?4)",
+            params![user_id.get() as i64, level.to_string(), score, total],
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets up to `limit` of `user_id`'s results of mock tests of `level`, most recent first.
+    pub fn mock_tests(
+        &self,
+        user_id: UserId,
+        level: NLevel,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<MockTestResult>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT score, total, taken_at FROM mock_tests WHERE user_id = ?1 AND level = ?2
+             ORDER BY taken_at DESC, rowid DESC LIMIT ?3",
+        )?;
+
+        stmt.query_map(
+            params![user_id.get() as i64, level.to_string(), limit as i64],
+            |row| {
+                Ok(MockTestResult {
+                    score: row.get(0)?,
+                    total: row.get(1)?,
+                    taken_at: row.get(2)?,
+                })
+            },
+        )?
+        .collect()
+    }
+}
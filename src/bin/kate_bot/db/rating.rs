@@ -0,0 +1,103 @@
+use jplearnbot::rating::Rating;
+use poise::serenity_prelude::{GuildId, UserId};
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS ratings (
+    guild_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    rating REAL NOT NULL,
+    answers INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+";
+
+impl Database {
+    /// Gets `user_id`'s rating in `guild_id`, if they ever answered a question there.
+    pub fn rating(&self, guild_id: GuildId, user_id: UserId) -> rusqlite::Result<Option<Rating>> {
+        self.conn()
+            .query_row(
+                "SELECT rating, answers FROM ratings WHERE guild_id = ?1 AND user_id = ?2",
+                params![guild_id.get() as i64, user_id.get() as i64],
+                |row| {
+                    Ok(Rating {
+                        value: row.get(0)?,
+                        answers: row.get(1)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Adjusts `user_id`'s rating in `guild_id` for answering a question rated
+    /// `question`. See [`jplearnbot::rating::question_rating`].
+    ///
+    /// Returns the new rating.
+    pub fn rate_answer(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        question: f64,
+        correct: bool,
+    ) -> rusqlite::Result<Rating> {
+        let rating = self
+            .rating(guild_id, user_id)?
+            .unwrap_or_default()
+            .answer(question, correct);
+
+        self.conn().execute(
+            "INSERT OR REPLACE INTO ratings (guild_id, user_id, rating, answers)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                guild_id.get() as i64,
+                user_id.get() as i64,
+                rating.value,
+                rating.answers
+            ],
+        )?;
+
+        Ok(rating)
+    }
+
+    /// Gets the place of `user_id` among the rated players of `guild_id`,
+    /// starting at 1, if they are rated there.
+    pub fn rating_rank(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> rusqlite::Result<Option<usize>> {
+        let Some(rating) = self.rating(guild_id, user_id)? else {
+            return Ok(None);
+        };
+
+        let higher: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM ratings WHERE guild_id = ?1 AND rating > ?2",
+            params![guild_id.get() as i64, rating.value],
+            |row| row.get(0),
+        )?;
+
+        Ok(Some(higher as usize + 1))
+    }
+
+    /// Gets the rated players of `guild_id`, the best first.
+    pub fn rating_leaderboard(&self, guild_id: GuildId) -> rusqlite::Result<Vec<(UserId, Rating)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT user_id, rating, answers FROM ratings WHERE guild_id = ?1
+             ORDER BY rating DESC",
+        )?;
+
+        stmt.query_map(params![guild_id.get() as i64], |row| {
+            Ok((
+                UserId::new(row.get::<_, i64>(0)? as u64),
+                Rating {
+                    value: row.get(1)?,
+                    answers: row.get(2)?,
+                },
+            ))
+        })?
+        .collect()
+    }
+}
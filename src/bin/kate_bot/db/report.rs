@@ -0,0 +1,65 @@
+use poise::serenity_prelude::UserId;
+use rusqlite::params;
+
+use crate::game::RoundRecord;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS reports (
+    user_id INTEGER NOT NULL,
+    question_id TEXT NOT NULL,
+    entry_id INTEGER,
+    mode TEXT NOT NULL,
+    prompt TEXT NOT NULL,
+    options TEXT NOT NULL,
+    answer INTEGER NOT NULL,
+    reported_at INTEGER NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY (user_id, question_id)
+);
+";
+
+impl Database {
+    /// Stores `user_id`'s report of the question asked in `round`. The options
+    /// are stored as a JSON array.
+    ///
+    /// Returns false if the user already reported the question.
+    pub fn add_report(&self, user_id: UserId, round: &RoundRecord) -> rusqlite::Result<bool> {
+        let options = serde_json::to_string(&round.options)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+
+        let inserted = self.conn().execute(
+            "INSERT OR IGNORE INTO reports
+             (user_id, question_id, entry_id, mode, prompt, options, answer)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                user_id.get() as i64,
+                round.id,
+                round.entry_id,
+                round.mode,
+                round.prompt,
+                options,
+                round.answer as i64,
+            ],
+        )?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Gets up to `limit` of the reported entries that aren't blocked, along with
+    /// how many times each was reported, most reported first.
+    pub fn reported_entries(&self, limit: usize) -> rusqlite::Result<Vec<(u32, u32)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id, COUNT(*) FROM reports
+             WHERE entry_id IS NOT NULL
+                AND entry_id NOT IN (SELECT entry_id FROM blocked_entries)
+             GROUP BY entry_id
+             ORDER BY COUNT(*) DESC, MAX(reported_at) DESC
+             LIMIT ?1",
+        )?;
+
+        stmt.query_map(params![limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect()
+    }
+}
@@ -0,0 +1,177 @@
+use std::{collections::HashMap, time::Duration};
+
+use poise::serenity_prelude::UserId;
+use rusqlite::{OptionalExtension, Row, params, types::Type};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::game::{Filters, GameOptions, SessionId};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS saved_sessions (
+    guild_id INTEGER NOT NULL,
+    channel_id INTEGER NOT NULL,
+    mode TEXT NOT NULL,
+    filters TEXT NOT NULL,
+    options TEXT NOT NULL,
+    seed INTEGER NOT NULL,
+    round INTEGER NOT NULL,
+    scores TEXT NOT NULL,
+    saved_at INTEGER NOT NULL DEFAULT (unixepoch()),
+    starter INTEGER,
+    skipped INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, channel_id)
+);
+";
+
+/// The player who started a saved game, missing from databases made before
+/// games could be re-rolled. Games saved before then have none.
+pub(super) const STARTER_COLUMN: (&str, &str, &str) = ("saved_sessions", "starter", "INTEGER");
+
+/// How many rounds of a saved game were skipped, missing from databases made
+/// before games could be re-rolled.
+pub(super) const SKIPPED_COLUMN: (&str, &str, &str) =
+    ("saved_sessions", "skipped", "INTEGER NOT NULL DEFAULT 0");
+
+/// The state of a game saved as it is played, so it can be resumed if the bot
+/// restarts in the middle of it.
+#[derive(Debug, Clone)]
+pub struct SavedSession {
+    pub session_id: SessionId,
+    /// Name of the game mode.
+    pub mode: String,
+    pub filters: Filters,
+    pub options: GameOptions,
+    /// Seed the session draws its randomness from, which reproduces its questions.
+    pub seed: u64,
+    /// The round the game resumes at, counted from zero.
+    pub round: usize,
+    /// How many questions each player answered correctly, or the points they
+    /// earned in [`Resolution::Race`](crate::game::Resolution::Race) games.
+    pub scores: HashMap<UserId, usize>,
+    /// The player who started the game, who can re-roll its questions, if known.
+    pub starter: Option<UserId>,
+    /// How many rounds were re-rolled, which don't count towards
+    /// [`GameOptions::max_rounds`].
+    pub skipped: usize,
+}
+
+impl Database {
+    /// Saves `session`, replacing any previously saved session of its channel.
+    /// The filters, options, and scores are stored as JSON.
+    pub fn save_session(&self, session: &SavedSession) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO saved_sessions
+             (guild_id, channel_id, mode, filters, options, seed, round, scores, starter, skipped)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                session.session_id.guild as i64,
+                session.session_id.channel as i64,
+                session.mode,
+                to_json(&session.filters)?,
+                to_json(&session.options)?,
+                session.seed as i64,
+                session.round as i64,
+                to_json(&session.scores)?,
+                session.starter.map(|user_id| user_id.get() as i64),
+                session.skipped as i64,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Updates the round `session_id`'s saved game resumes at, how many of its
+    /// rounds were skipped, and its scores.
+    pub fn save_session_progress(
+        &self,
+        session_id: SessionId,
+        round: usize,
+        skipped: usize,
+        scores: &HashMap<UserId, usize>,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "UPDATE saved_sessions SET round = ?3, skipped = ?4, scores = ?5, saved_at = unixepoch()
+             WHERE guild_id = ?1 AND channel_id = ?2",
+            params![
+                session_id.guild as i64,
+                session_id.channel as i64,
+                round as i64,
+                skipped as i64,
+                to_json(scores)?,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Forgets `session_id`'s saved game, e.g. once it has finished.
+    pub fn remove_saved_session(&self, session_id: SessionId) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "DELETE FROM saved_sessions WHERE guild_id = ?1 AND channel_id = ?2",
+            params![session_id.guild as i64, session_id.channel as i64],
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets `session_id`'s saved game, if any.
+    pub fn saved_session(&self, session_id: SessionId) -> rusqlite::Result<Option<SavedSession>> {
+        self.conn()
+            .query_row(
+                "SELECT guild_id, channel_id, mode, filters, options, seed, round, scores,
+                    starter, skipped
+                 FROM saved_sessions WHERE guild_id = ?1 AND channel_id = ?2",
+                params![session_id.guild as i64, session_id.channel as i64],
+                saved_session_row,
+            )
+            .optional()
+    }
+
+    /// Gets the games saved within `max_age`, forgetting older ones.
+    pub fn saved_sessions(&self, max_age: Duration) -> rusqlite::Result<Vec<SavedSession>> {
+        let conn = self.conn();
+        conn.execute(
+            "DELETE FROM saved_sessions WHERE saved_at < unixepoch() - ?1",
+            params![max_age.as_secs() as i64],
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT guild_id, channel_id, mode, filters, options, seed, round, scores,
+                starter, skipped
+             FROM saved_sessions",
+        )?;
+
+        stmt.query_map([], saved_session_row)?.collect()
+    }
+}
+
+fn saved_session_row(row: &Row<'_>) -> rusqlite::Result<SavedSession> {
+    Ok(SavedSession {
+        session_id: SessionId {
+            guild: row.get::<_, i64>(0)? as u64,
+            channel: row.get::<_, i64>(1)? as u64,
+        },
+        mode: row.get(2)?,
+        filters: from_json(row, 3)?,
+        options: from_json(row, 4)?,
+        seed: row.get::<_, i64>(5)? as u64,
+        round: row.get::<_, i64>(6)? as usize,
+        scores: from_json(row, 7)?,
+        starter: row
+            .get::<_, Option<i64>>(8)?
+            .map(|user_id| UserId::new(user_id as u64)),
+        skipped: row.get::<_, i64>(9)? as usize,
+    })
+}
+
+pub(super) fn to_json(value: &impl Serialize) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))
+}
+
+pub(super) fn from_json<T: DeserializeOwned>(row: &Row<'_>, idx: usize) -> rusqlite::Result<T> {
+    let json: String = row.get(idx)?;
+    serde_json::from_str(&json)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, e.into()))
+}
@@ -0,0 +1,81 @@
+use jplearnbot::srs::{Card, Grade};
+use poise::serenity_prelude::UserId;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS srs_cards (
+    user_id INTEGER NOT NULL,
+    entry_id INTEGER NOT NULL,
+    interval INTEGER NOT NULL,
+    ease REAL NOT NULL,
+    reps INTEGER NOT NULL,
+    due_at INTEGER NOT NULL,
+    PRIMARY KEY (user_id, entry_id)
+);
+";
+
+impl Database {
+    /// Gets `user_id`'s card of `entry_id`, if it was ever reviewed.
+    pub fn card(&self, user_id: UserId, entry_id: u32) -> rusqlite::Result<Option<Card>> {
+        self.conn()
+            .query_row(
+                "SELECT interval, ease, reps FROM srs_cards WHERE user_id = ?1 AND entry_id = ?2",
+                params![user_id.get() as i64, entry_id],
+                |row| {
+                    Ok(Card {
+                        interval: row.get(0)?,
+                        ease: row.get(1)?,
+                        reps: row.get(2)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Reviews `user_id`'s card of `entry_id` with `grade` and schedules its next review.
+    ///
+    /// Returns the card's new state.
+    pub fn review_card(
+        &self,
+        user_id: UserId,
+        entry_id: u32,
+        grade: Grade,
+    ) -> rusqlite::Result<Card> {
+        let card = self
+            .card(user_id, entry_id)?
+            .unwrap_or_default()
+            .review(grade);
+
+        self.conn().execute(
+            "INSERT OR REPLACE INTO srs_cards (user_id, entry_id, interval, ease, reps, due_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, unixepoch() + ?6)",
+            params![
+                user_id.get() as i64,
+                entry_id,
+                card.interval,
+                card.ease,
+                card.reps,
+                card.due_after().as_secs() as i64
+            ],
+        )?;
+
+        Ok(card)
+    }
+
+    /// Gets up to `limit` entry ids of `user_id`'s cards that are due, most overdue first.
+    pub fn due_cards(&self, user_id: UserId, limit: usize) -> rusqlite::Result<Vec<u32>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id FROM srs_cards
+             WHERE user_id = ?1 AND due_at <= unixepoch()
+             ORDER BY due_at LIMIT ?2",
+        )?;
+
+        stmt.query_map(params![user_id.get() as i64, limit as i64], |row| {
+            row.get(0)
+        })?
+        .collect()
+    }
+}
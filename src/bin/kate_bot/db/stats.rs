@@ -0,0 +1,195 @@
+use std::{collections::HashMap, time::Duration};
+
+use poise::serenity_prelude::{GuildId, UserId};
+use rusqlite::params;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS answers (
+    user_id INTEGER NOT NULL,
+    guild_id INTEGER,
+    entry_id INTEGER,
+    mode TEXT NOT NULL,
+    correct INTEGER NOT NULL,
+    points INTEGER NOT NULL DEFAULT 0,
+    answered_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
+CREATE INDEX IF NOT EXISTS answers_user_id ON answers (user_id);
+";
+
+/// The Server an answer was made in, missing from databases made before
+/// answers were kept per Server. Answers made outside of one, or before, have none.
+pub(super) const GUILD_ID_COLUMN: (&str, &str, &str) = ("answers", "guild_id", "INTEGER");
+
+/// A player's long-term answering stats.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnswerStats {
+    /// How many questions were answered.
+    pub answered: u32,
+    /// How many questions were answered correctly.
+    pub correct: u32,
+    /// How many of the latest answers in a row were correct.
+    pub current_streak: u32,
+    /// The most answers in a row that were correct.
+    pub best_streak: u32,
+    /// Total points earned. See [`jplearnbot::scoring::points`].
+    pub points: u64,
+}
+
+impl AnswerStats {
+    /// Counts the answer made after the ones already counted.
+    fn add(&mut self, correct: bool, points: u32) {
+        self.answered += 1;
+        self.points += u64::from(points);
+        if correct {
+            self.correct += 1;
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+        } else {
+            self.current_streak = 0;
+        }
+    }
+}
+
+impl Database {
+    /// Stores an answer by `user_id` in `guild_id`, if it was made in a Server,
+    /// worth `points` to a question in `mode` about the entry with the id
+    /// `entry_id`, if the question was made from one.
+    pub fn add_answer(
+        &self,
+        user_id: UserId,
+        guild_id: Option<GuildId>,
+        entry_id: Option<u32>,
+        mode: &str,
+        correct: bool,
+        points: u32,
+    ) -> rusqlite::Result<()> {
+        self.conn().execute(
+            "INSERT INTO answers (user_id, guild_id, entry_id, mode, correct, points)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                user_id.get() as i64,
+                guild_id.map(|g| g.get() as i64),
+                entry_id,
+                mode,
+                correct,
+                points
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Gets the stats of every answer `user_id` has made.
+    pub fn answer_stats(&self, user_id: UserId) -> rusqlite::Result<AnswerStats> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT correct, points FROM answers WHERE user_id = ?1 ORDER BY answered_at, rowid",
+        )?;
+        let answers = stmt.query_map(params![user_id.get() as i64], |row| {
+            Ok((row.get::<_, bool>(0)?, row.get::<_, u32>(1)?))
+        })?;
+
+        let mut stats = AnswerStats::default();
+        for answer in answers {
+            let (correct, points) = answer?;
+            stats.add(correct, points);
+        }
+
+        Ok(stats)
+    }
+
+    /// Gets up to `limit` ids of the entries `user_id` answered incorrectly the most,
+    /// along with how many times they were missed.
+    pub fn most_missed(&self, user_id: UserId, limit: usize) -> rusqlite::Result<Vec<(u32, u32)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id, COUNT(*) AS misses FROM answers
+             WHERE user_id = ?1 AND entry_id IS NOT NULL AND NOT correct
+             GROUP BY entry_id ORDER BY misses DESC, MAX(answered_at) DESC LIMIT ?2",
+        )?;
+
+        stmt.query_map(params![user_id.get() as i64, limit as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect()
+    }
+
+    /// Gets up to `limit` ids of the entries players answered incorrectly the most
+    /// in `guild_id`, along with how many times they were missed.
+    pub fn guild_most_missed(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<(u32, u32)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id, COUNT(*) AS misses FROM answers
+             WHERE guild_id = ?1 AND entry_id IS NOT NULL AND NOT correct
+             GROUP BY entry_id ORDER BY misses DESC, MAX(answered_at) DESC LIMIT ?2",
+        )?;
+
+        stmt.query_map(params![guild_id.get() as i64, limit as i64], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect()
+    }
+
+    /// Gets how many times the entries asked about in any Server or DM were
+    /// answered correctly and how many times they were answered, by entry id.
+    pub fn entry_accuracy(&self) -> rusqlite::Result<HashMap<u32, (u32, u32)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id, SUM(correct), COUNT(*) FROM answers
+             WHERE entry_id IS NOT NULL GROUP BY entry_id",
+        )?;
+
+        stmt.query_map([], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?
+            .collect()
+    }
+
+    /// Gets the stats of every player who answered in `guild_id` within the
+    /// last `within`, or in any Server or DM and at any time if [`None`].
+    pub fn player_stats(
+        &self,
+        guild_id: Option<GuildId>,
+        within: Option<Duration>,
+    ) -> rusqlite::Result<Vec<(UserId, AnswerStats)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT user_id, correct, points FROM answers
+             WHERE (?1 IS NULL OR guild_id = ?1) AND (?2 IS NULL OR answered_at >= unixepoch() - ?2)
+             ORDER BY user_id, answered_at, rowid",
+        )?;
+        let answers = stmt.query_map(
+            params![
+                guild_id.map(|g| g.get() as i64),
+                within.map(|w| w.as_secs() as i64)
+            ],
+            |row| {
+                Ok((
+                    UserId::new(row.get::<_, i64>(0)? as u64),
+                    row.get::<_, bool>(1)?,
+                    row.get::<_, u32>(2)?,
+                ))
+            },
+        )?;
+
+        let mut players: Vec<(UserId, AnswerStats)> = Vec::new();
+        for answer in answers {
+            let (user_id, correct, points) = answer?;
+            // Answers are grouped by player, so a new one starts at their first answer.
+            match players.last_mut() {
+                Some((last, stats)) if *last == user_id => stats.add(correct, points),
+                _ => {
+                    let mut stats = AnswerStats::default();
+                    stats.add(correct, points);
+                    players.push((user_id, stats));
+                }
+            }
+        }
+
+        Ok(players)
+    }
+}
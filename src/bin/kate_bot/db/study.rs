@@ -0,0 +1,84 @@
+use poise::serenity_prelude::UserId;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS study_days (
+    user_id INTEGER NOT NULL,
+    day TEXT NOT NULL,
+    goal INTEGER,
+    answered INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day)
+);
+";
+
+/// A user's study progress of a day.
+#[derive(Debug, Clone, Copy)]
+pub struct StudyDay {
+    /// How many questions the user aims to answer, if set.
+    pub goal: Option<u32>,
+    /// How many questions the user answered.
+    pub answered: u32,
+}
+
+impl StudyDay {
+    /// Determines whether the goal was reached by the last answer.
+    pub fn just_reached(&self) -> bool {
+        self.goal == Some(self.answered)
+    }
+}
+
+impl Database {
+    /// Sets `user_id`'s goal of today to answering `goal` questions.
+    ///
+    /// Returns today's progress.
+    pub fn set_study_goal(&self, user_id: UserId, goal: u32) -> rusqlite::Result<StudyDay> {
+        self.conn().query_row(
+            "INSERT INTO study_days (user_id, day, goal) VALUES (?1, date('now'), ?2)
+             ON CONFLICT (user_id, day) DO UPDATE SET goal = excluded.goal
+             RETURNING goal, answered",
+            params![user_id.get() as i64, goal],
+            |row| {
+                Ok(StudyDay {
+                    goal: row.get(0)?,
+                    answered: row.get(1)?,
+                })
+            },
+        )
+    }
+
+    /// Gets `user_id`'s progress of today, if they studied or set a goal today.
+    pub fn study_day(&self, user_id: UserId) -> rusqlite::Result<Option<StudyDay>> {
+        self.conn()
+            .query_row(
+                "SELECT goal, answered FROM study_days WHERE user_id = ?1 AND day = date('now')",
+                params![user_id.get() as i64],
+                |row| {
+                    Ok(StudyDay {
+                        goal: row.get(0)?,
+                        answered: row.get(1)?,
+                    })
+                },
+            )
+            .optional()
+    }
+
+    /// Counts a question answered by `user_id` towards today's progress.
+    ///
+    /// Returns today's progress.
+    pub fn record_answer(&self, user_id: UserId) -> rusqlite::Result<StudyDay> {
+        self.conn().query_row(
+            "INSERT INTO study_days (user_id, day, answered) VALUES (?1, date('now'), 1)
+             ON CONFLICT (user_id, day) DO UPDATE SET answered = answered + 1
+             RETURNING goal, answered",
+            params![user_id.get() as i64],
+            |row| {
+                Ok(StudyDay {
+                    goal: row.get(0)?,
+                    answered: row.get(1)?,
+                })
+            },
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use poise::serenity_prelude::{GuildId, UserId};
+use rusqlite::{Transaction, params};
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS command_usage (
+    day INTEGER NOT NULL,
+    command TEXT NOT NULL,
+    invocations INTEGER NOT NULL DEFAULT 0,
+    failures INTEGER NOT NULL DEFAULT 0,
+    users INTEGER NOT NULL DEFAULT 0,
+    guilds INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, command)
+);
+CREATE TABLE IF NOT EXISTS command_usage_seen (
+    day INTEGER NOT NULL,
+    command TEXT NOT NULL,
+    seen INTEGER NOT NULL,
+    PRIMARY KEY (day, command, seen)
+);
+";
+
+/// Name the usage of every command together is recorded under, to count the
+/// users and Servers of the bot as a whole.
+pub const ALL_COMMANDS: &str = "*";
+
+/// How a command was used over some days. Users and Servers are counted once
+/// per day they used it, since who used it is forgotten after each day.
+#[derive(Debug, Clone)]
+pub struct CommandUsage {
+    /// Qualified name of the command, or [`ALL_COMMANDS`].
+    pub command: String,
+    pub invocations: u32,
+    /// How many of the invocations failed.
+    pub failures: u32,
+    /// Sum over the days of how many users used the command that day.
+    pub user_days: u32,
+    /// Sum over the days of how many Servers the command was used in that day.
+    pub guild_days: u32,
+    /// How many days the command was used on.
+    pub days: u32,
+}
+
+impl Database {
+    /// Counts an invocation of `command` by `user_id` in `guild_id`, today in
+    /// UTC, and whether they are a new user or Server of it today. Only hashes
+    /// of who used it are stored, and only until the day is over.
+    pub fn record_command(
+        &self,
+        command: &str,
+        user_id: UserId,
+        guild_id: Option<GuildId>,
+    ) -> rusqlite::Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM command_usage_seen WHERE day < unixepoch() / 86400",
+            [],
+        )?;
+        for command in [command, ALL_COMMANDS] {
+            tx.execute(
+                "INSERT INTO command_usage (day, command, invocations) VALUES (unixepoch() / 86400, ?1, 1)
+                 ON CONFLICT (day, command) DO UPDATE SET invocations = invocations + 1",
+                params![command],
+            )?;
+            if first_seen(&tx, command, ("user", user_id.get()))? {
+                tx.execute(
+                    "UPDATE command_usage SET users = users + 1
+                     WHERE day = unixepoch() / 86400 AND command = ?1",
+                    params![command],
+                )?;
+            }
+            if let Some(guild_id) = guild_id
+                && first_seen(&tx, command, ("guild", guild_id.get()))?
+            {
+                tx.execute(
+                    "UPDATE command_usage SET guilds = guilds + 1
+                     WHERE day = unixepoch() / 86400 AND command = ?1",
+                    params![command],
+                )?;
+            }
+        }
+
+        tx.commit()
+    }
+
+    /// Counts a failure of an invocation of `command` today in UTC.
+    pub fn record_command_failure(&self, command: &str) -> rusqlite::Result<()> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "INSERT INTO command_usage (day, command, failures) VALUES (unixepoch() / 86400, ?1, 1)
+             ON CONFLICT (day, command) DO UPDATE SET failures = failures + 1",
+        )?;
+        for command in [command, ALL_COMMANDS] {
+            stmt.execute(params![command])?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets how each command was used over the last `days` days including
+    /// today, the most invoked first.
+    pub fn command_usage(&self, days: u32) -> rusqlite::Result<Vec<CommandUsage>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT command, SUM(invocations), SUM(failures), SUM(users), SUM(guilds), COUNT(*)
+             FROM command_usage
+             WHERE day > unixepoch() / 86400 - ?1
+             GROUP BY command
+             ORDER BY SUM(invocations) DESC, command",
+        )?;
+
+        stmt.query_map(params![days], |row| {
+            Ok(CommandUsage {
+                command: row.get(0)?,
+                invocations: row.get(1)?,
+                failures: row.get(2)?,
+                user_days: row.get(3)?,
+                guild_days: row.get(4)?,
+                days: row.get(5)?,
+            })
+        })?
+        .collect()
+    }
+}
+
+/// Notes that `who`, a kind of id along with the id, used `command` today.
+/// The id is stored hashed along with the day.
+///
+/// Returns false if they already used it today.
+fn first_seen(tx: &Transaction, command: &str, who: (&str, u64)) -> rusqlite::Result<bool> {
+    let day: i64 = tx.query_row("SELECT unixepoch() / 86400", [], |row| row.get(0))?;
+    let mut hasher = DefaultHasher::new();
+    (day, who).hash(&mut hasher);
+
+    let inserted = tx.execute(
+        "INSERT OR IGNORE INTO command_usage_seen (day, command, seen) VALUES (?1, ?2, ?3)",
+        params![day, command, hasher.finish() as i64],
+    )?;
+
+    Ok(inserted > 0)
+}
@@ -0,0 +1,86 @@
+use poise::serenity_prelude::UserId;
+use rusqlite::params;
+
+use super::Database;
+
+pub(super) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS wordlists (
+    user_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    entry_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (user_id, name, entry_id)
+);
+";
+
+impl Database {
+    /// Stores `entry_ids` as `user_id`'s word list named `name`, replacing the
+    /// list they had by that name.
+    pub fn save_wordlist(
+        &self,
+        user_id: UserId,
+        name: &str,
+        entry_ids: &[u32],
+    ) -> rusqlite::Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM wordlists WHERE user_id = ?1 AND name = ?2",
+            params![user_id.get() as i64, name],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO wordlists (user_id, name, entry_id, position)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (position, &entry_id) in entry_ids.iter().enumerate() {
+                stmt.execute(params![
+                    user_id.get() as i64,
+                    name,
+                    entry_id,
+                    position as i64
+                ])?;
+            }
+        }
+
+        tx.commit()
+    }
+
+    /// Gets the entry ids of `user_id`'s word list named `name`, in the order
+    /// they were imported. Empty if they have no such list.
+    pub fn wordlist(&self, user_id: UserId, name: &str) -> rusqlite::Result<Vec<u32>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT entry_id FROM wordlists WHERE user_id = ?1 AND name = ?2 ORDER BY position",
+        )?;
+
+        stmt.query_map(params![user_id.get() as i64, name], |row| row.get(0))?
+            .collect()
+    }
+
+    /// Gets the names of `user_id`'s word lists along with how many words they
+    /// have, by name.
+    pub fn wordlists(&self, user_id: UserId) -> rusqlite::Result<Vec<(String, usize)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT name, COUNT(*) FROM wordlists WHERE user_id = ?1 GROUP BY name ORDER BY name",
+        )?;
+
+        stmt.query_map(params![user_id.get() as i64], |row| {
+            Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+        })?
+        .collect()
+    }
+
+    /// Removes `user_id`'s word list named `name`.
+    ///
+    /// Returns false if they had no such list.
+    pub fn remove_wordlist(&self, user_id: UserId, name: &str) -> rusqlite::Result<bool> {
+        let removed = self.conn().execute(
+            "DELETE FROM wordlists WHERE user_id = ?1 AND name = ?2",
+            params![user_id.get() as i64, name],
+        )?;
+
+        Ok(removed > 0)
+    }
+}
@@ -1,62 +1,186 @@
-use std::{
-    io::{BufRead, Cursor},
-    sync::Arc,
+use jplearnbot::{
+    dictionary::{DictEntry, Dictionary},
+    kana,
 };
 
-use jplearnbot::dictionary::{DictEntry, NLevel, Pos};
-use rand::seq::SliceRandom;
+use crate::db::Database;
 
-/// Contains [`DictEntry`]'s.
-pub struct Dictionary {
-    /// Contains all of the entries.
-    pub entries: Vec<Arc<DictEntry>>,
+/// Loads the dictionary generated by dict_combine that is bundled with the bot.
+pub fn bundled() -> Dictionary {
+    static DICT_FILE: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/content/dictionary.jsonl"
+    ));
+
+    Dictionary::from_bytes(DICT_FILE).expect("failed to deserialize entries")
 }
 
-impl Default for Dictionary {
-    fn default() -> Self {
-        let mut dict = Dictionary {
-            entries: Vec::new(),
-        };
+/// Loads the [bundled] dictionary with the entries blocked in `db` left out of
+/// its samples.
+pub fn load(db: &Database) -> Dictionary {
+    let dictionary = bundled();
+    match db.blocked_entries() {
+        Ok(ids) => {
+            for id in ids {
+                dictionary.block(id);
+            }
+        }
+        Err(e) => eprintln!("Failed to get blocked entries: {e}"),
+    }
 
-        static DICT_FILE: &[u8] = include_bytes!(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/content/dictionary.jsonl"
-        ));
+    dictionary
+}
 
-        // Deserialize entries and append
-        for line in Cursor::new(DICT_FILE).lines() {
-            let entry: DictEntry = serde_json::from_str(&line.expect("failed to read line"))
-                .expect("failed to deserialize entry");
+/// Links to the search for `text` on jisho.org.
+pub fn jisho_url(text: &str) -> String {
+    format!("https://jisho.org/search/{}", urlencoding::encode(text))
+}
 
-            dict.entries.push(entry.into());
-        }
+/// References `entry` by its id, its sequence number in JMDict, e.g.
+/// ``Entry ・ 見出し: `/entry 1358280` ・ [jisho.org](…)``, so it can be looked
+/// up again exactly with `/entry` or found on jisho.org.
+pub fn entry_links(entry: &DictEntry) -> String {
+    let spelling = entry
+        .kanjis
+        .first()
+        .map(|k| &k.text)
+        .or(entry.readings.first().map(|r| &r.text))
+        .map_or("", String::as_str);
+
+    format!(
+        "Entry ・ 見出し: `/entry {}` ・ [jisho.org]({})",
+        entry.id,
+        jisho_url(spelling)
+    )
+}
+
+/// Labels the sense numbered `number` of `entry` with its position in JMDict,
+/// e.g. `Sense 3 of 7 ・ 語義 3/7`, so it can be cross-referenced.
+pub fn sense_label(entry: &DictEntry, number: u32) -> String {
+    let total = entry.senses.len();
+
+    format!("Sense {number} of {total} ・ 語義 {number}/{total}")
+}
+
+/// Shows the first [example](`DictEntry::examples`) of `entry`, with the word
+/// it uses in bold, above its translation. Returns [`None`] if it has none.
+pub fn example_label(entry: &DictEntry) -> Option<String> {
+    let example = entry.examples.first()?;
+    let sentence = example
+        .sentence
+        .replacen(&example.word, &format!("**{}**", example.word), 1);
+
+    Some(format!(
+        "Example ・ 例文: {sentence}\n*{}*",
+        example.translation
+    ))
+}
+
+/// Most readings whose pitch accent is shown.
+const MAX_PITCH_READINGS: usize = 3;
+
+/// Shows the pitch accent of the readings of `entry` that have one, e.g.
+/// `Pitch ・ アクセント: は↓し [1]`. Returns [`None`] if none of them do.
+pub fn pitch_label(entry: &DictEntry) -> Option<String> {
+    let accents: Vec<_> = entry
+        .readings
+        .iter()
+        .filter_map(|r| {
+            let pitch = r.pitch?;
+            Some(format!("{} [{pitch}]", kana::mark_downstep(&r.text, pitch)))
+        })
+        .take(MAX_PITCH_READINGS)
+        .collect();
+
+    (!accents.is_empty()).then(|| format!("Pitch ・ アクセント: {}", accents.join("、")))
+}
+
+/// Superscript markers of footnotes, in order.
+const FOOTNOTE_MARKERS: [&str; 9] = ["¹", "²", "³", "⁴", "⁵", "⁶", "⁷", "⁸", "⁹"];
+
+/// Appends the footnote marker of `text` if it is one of the
+/// [unusual spellings](`DictEntry::unusual_spellings`) of `entry`.
+pub fn mark_spelling(entry: &DictEntry, text: &str) -> String {
+    let marker = entry
+        .unusual_spellings()
+        .iter()
+        .position(|(spelling, _)| *spelling == text)
+        .and_then(|i| FOOTNOTE_MARKERS.get(i));
 
-        dict
+    match marker {
+        Some(marker) => format!("{text}{marker}"),
+        None => text.to_string(),
     }
 }
 
-impl Dictionary {
-    pub fn new() -> Self {
-        Dictionary::default()
+/// Creates the footnotes explaining why the spellings of `entry` look unusual,
+/// one per line. Returns [`None`] if none of them do.
+pub fn spelling_footnotes(entry: &DictEntry) -> Option<String> {
+    let footnotes: Vec<_> = entry
+        .unusual_spellings()
+        .into_iter()
+        .zip(FOOTNOTE_MARKERS)
+        .map(|((spelling, descs), marker)| format!("{marker} {spelling}: {}", descs.join(", ")))
+        .collect();
+
+    (!footnotes.is_empty()).then(|| footnotes.join("\n"))
+}
+
+/// Describes `entry` in full: its spellings along with their JLPT levels, its
+/// numbered senses with the parts of speech and glosses of each, and the
+/// footnotes of its [unusual spellings](spelling_footnotes), e.g. ateji or rare
+/// kanji.
+pub fn describe_entry(entry: &DictEntry) -> String {
+    let kanjis: Vec<_> = entry
+        .kanjis
+        .iter()
+        .map(|k| mark_spelling(entry, &k.text))
+        .collect();
+    let readings: Vec<_> = entry
+        .readings
+        .iter()
+        .map(|r| mark_spelling(entry, &r.text))
+        .collect();
+
+    let mut description = String::new();
+    if !kanjis.is_empty() {
+        description.push_str(&format!("Spellings ・ 表記: {}\n", kanjis.join("、")));
     }
+    description.push_str(&format!("Readings ・ 読み: {}\n", readings.join("、")));
 
-    /// Creates a randomized subset of the entries based on the parameter filters.
-    pub async fn sample(&self, levels: &[NLevel], pos: &[Pos]) -> Vec<Arc<DictEntry>> {
-        let mut sample = Vec::new();
-
-        for entry in &self.entries {
-            // Add only if at least one matching NLevel or part of speech.
-            if entry.levels().iter().any(|lvl| levels.contains(lvl))
-                && entry
-                    .senses
-                    .iter()
-                    .any(|sense| sense.pos.iter().any(|p| pos.contains(p)))
-            {
-                sample.push(entry.clone());
-            }
+    let levels = entry.levels();
+    if !levels.is_empty() {
+        let levels: Vec<_> = levels.iter().map(|lvl| lvl.to_string()).collect();
+        description.push_str(&format!("JLPT: {}\n", levels.join(", ")));
+    }
+
+    for sense in &entry.senses {
+        let pos: Vec<_> = sense.pos.iter().map(|p| p.bilingual()).collect();
+        let glosses: Vec<_> = sense.gloss.iter().map(|g| g.content.as_str()).collect();
+
+        description.push_str(&format!("\n**{}.** ", sense.number));
+        if !pos.is_empty() {
+            description.push_str(&format!("*{}*\n", pos.join(", ")));
         }
-        sample.shuffle(&mut rand::rng());
+        description.push_str(&glosses.join("; "));
+    }
 
-        sample
+    if let Some(footnotes) = spelling_footnotes(entry) {
+        description.push_str(&format!("\n\n{footnotes}"));
     }
+
+    description
+}
+
+/// Shortens `text` to at most `max_len` characters, ending it with … if it was
+/// longer, e.g. to fit Discord's limits on embeds.
+pub fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(max_len.saturating_sub(1)).collect();
+    truncated.push('…');
+
+    truncated
 }
@@ -1,11 +1,215 @@
-use std::env;
+use std::{
+    collections::HashMap,
+    env,
+    str::FromStr,
+    sync::{Arc, LazyLock, RwLock},
+};
 
-use lazy_static::lazy_static;
+use poise::serenity_prelude::GuildId;
+use regex::Regex;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
 
-lazy_static! {
-    pub static ref WOW: String = env::var("EMOTE_WOW").unwrap();
-    pub static ref FUBU_LAUGH: String = env::var("EMOTE_FUBU_LAUGH").unwrap();
-    pub static ref SCRAJJ: String = env::var("EMOTE_SCRAJJ").unwrap();
-    pub static ref ANW: String = env::var("EMOTE_ANW").unwrap();
-    pub static ref WAT: String = env::var("EMOTE_WAT").unwrap();
+use crate::db::Database;
+
+/// Emotes the bot reacts to answers with.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    EnumString,
+    IntoStaticStr,
+    poise::ChoiceParameter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Emote {
+    /// Cheers correct answers.
+    #[name = "Wow"]
+    Wow,
+    /// Laughs at wrong answers.
+    #[name = "Fubu laugh"]
+    FubuLaugh,
+    /// Cringes at wrong answers.
+    #[name = "Scrajj"]
+    Scrajj,
+    /// Pities wrong answers.
+    #[name = "Anw"]
+    Anw,
+    /// Doubts wrong answers, and rounds nobody answered right.
+    #[name = "Wat"]
+    Wat,
+}
+
+impl Emote {
+    /// Gets the name of the emote, e.g. `fubu_laugh`, as in settings files and
+    /// in the placeholders of feedback messages.
+    pub fn key(self) -> &'static str {
+        self.into()
+    }
+
+    /// Gets the environment variable setting the emote everywhere, e.g. `EMOTE_WOW`.
+    fn var(self) -> String {
+        format!("EMOTE_{}", self.key().to_uppercase())
+    }
+
+    /// Gets the Unicode emoji used when the emote isn't set.
+    fn fallback(self) -> &'static str {
+        match self {
+            Emote::Wow => "😮",
+            Emote::FubuLaugh => "🤣",
+            Emote::Scrajj => "😬",
+            Emote::Anw => "🥲",
+            Emote::Wat => "🤨",
+        }
+    }
+}
+
+/// The emotes used in a Server, or everywhere.
+#[derive(Debug, Clone, Default)]
+pub struct EmoteSet {
+    /// The emotes set, each as it is written in messages. Emotes that aren't
+    /// set are their [fallback](Emote::fallback).
+    emotes: HashMap<Emote, String>,
+}
+
+impl EmoteSet {
+    /// Reads the emotes set with `EMOTE_*` environment variables, e.g.
+    /// `EMOTE_WOW`. Emotes whose variable is missing or malformed aren't set.
+    fn from_env() -> Self {
+        let mut emotes = HashMap::new();
+        for emote in Emote::iter() {
+            let Ok(text) = env::var(emote.var()) else {
+                continue;
+            };
+            match parse_emote(&text) {
+                Ok(text) => {
+                    emotes.insert(emote, text);
+                }
+                Err(e) => eprintln!("Ignoring {}: {e}", emote.var()),
+            }
+        }
+
+        EmoteSet { emotes }
+    }
+
+    /// Gets `emote` as it is written in messages.
+    pub fn get(&self, emote: Emote) -> &str {
+        self.emotes
+            .get(&emote)
+            .map_or(emote.fallback(), String::as_str)
+    }
+
+    /// Fills in the names of emotes in braces in `text`, e.g. `{wat}`.
+    pub fn fill(&self, text: &str) -> String {
+        Emote::iter().fold(text.to_string(), |text, emote| {
+            text.replace(&format!("{{{}}}", emote.key()), self.get(emote))
+        })
+    }
+}
+
+/// Emotes used everywhere, unless a Server has its own.
+static EMOTES: LazyLock<Arc<EmoteSet>> = LazyLock::new(|| EmoteSet::from_env().into());
+
+/// Emotes Servers set with `/settings emote`, which replace [`EMOTES`] in them.
+static GUILD_EMOTES: LazyLock<RwLock<HashMap<GuildId, Arc<EmoteSet>>>> =
+    LazyLock::new(Default::default);
+
+/// Checks that `text` is a well-formed emote, either a custom emote as Discord
+/// writes it, e.g. `<:wat:123456789012345678>` or `<a:wat:123...>`, or a Unicode
+/// emoji.
+///
+/// # Errors
+/// Fails with the reason to show the user if it is neither.
+pub fn parse_emote(text: &str) -> Result<String, &'static str> {
+    static CUSTOM: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^<a?:\w{2,32}:\d{17,20}>$").unwrap());
+
+    let text = text.trim();
+    if text.starts_with('<') {
+        return if CUSTOM.is_match(text) {
+            Ok(text.to_string())
+        } else {
+            Err("Custom emotes are written as <:name:id>, e.g. by sending \\:name: in Discord")
+        };
+    }
+
+    // Emoji are a few code points, e.g. with skin tones or joined with ZWJs,
+    // and never ASCII letters, spaces, or punctuation, barring keycaps.
+    let unicode = !text.is_empty()
+        && text.chars().count() <= 10
+        && text
+            .chars()
+            .all(|c| !c.is_ascii() || c.is_ascii_digit() || c == '#' || c == '*');
+    if unicode && !text.is_ascii() {
+        Ok(text.to_string())
+    } else {
+        Err("That isn't an emoji or a custom emote")
+    }
+}
+
+/// Loads the emotes Servers set from `db`.
+///
+/// Returns how many Servers have their own. Emotes that are no longer
+/// well-formed are skipped.
+pub fn load_guild_emotes(db: &Database) -> usize {
+    let emotes = match db.guild_emotes() {
+        Ok(emotes) => emotes,
+        Err(e) => {
+            eprintln!("Failed to get emotes of Servers: {e}");
+            return 0;
+        }
+    };
+
+    let mut sets: HashMap<GuildId, EmoteSet> = HashMap::new();
+    for (guild_id, name, text) in emotes {
+        match (Emote::from_str(&name), parse_emote(&text)) {
+            (Ok(emote), Ok(text)) => {
+                sets.entry(guild_id)
+                    .or_insert_with(|| (**EMOTES).clone())
+                    .emotes
+                    .insert(emote, text);
+            }
+            _ => eprintln!("Failed to load emote {name} of {guild_id}"),
+        }
+    }
+
+    let mut guild_emotes = GUILD_EMOTES.write().unwrap_or_else(|e| e.into_inner());
+    for (guild_id, set) in sets {
+        guild_emotes.insert(guild_id, set.into());
+    }
+
+    guild_emotes.len()
+}
+
+/// Uses `text` as `emote` in `guild_id`, or the emote used everywhere if [`None`].
+pub fn set_guild_emote(guild_id: GuildId, emote: Emote, text: Option<String>) {
+    let mut guild_emotes = GUILD_EMOTES.write().unwrap_or_else(|e| e.into_inner());
+    let mut set = guild_emotes
+        .get(&guild_id)
+        .map_or_else(|| (**EMOTES).clone(), |set| (**set).clone());
+    match text {
+        Some(text) => set.emotes.insert(emote, text),
+        None => match EMOTES.emotes.get(&emote) {
+            Some(text) => set.emotes.insert(emote, text.clone()),
+            None => set.emotes.remove(&emote),
+        },
+    };
+    guild_emotes.insert(guild_id, set.into());
+}
+
+/// Gets the emotes used in `guild_id`, or everywhere if [`None`].
+pub fn emotes_of(guild_id: Option<GuildId>) -> Arc<EmoteSet> {
+    guild_id
+        .and_then(|guild_id| {
+            GUILD_EMOTES
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&guild_id)
+                .cloned()
+        })
+        .unwrap_or_else(|| Arc::clone(&EMOTES))
 }
@@ -0,0 +1,254 @@
+use std::{fmt::Display, time::Duration};
+
+use poise::{
+    CreateReply, FrameworkError,
+    serenity_prelude::{
+        self as serenity, ComponentInteraction, CreateEmbed, CreateEmbedFooter,
+        CreateInteractionResponse, CreateInteractionResponseFollowup,
+        CreateInteractionResponseMessage,
+    },
+};
+
+use crate::{Data, Error, game::SessionAlreadyCreated};
+
+/// Failures shown to users, each with a short code they can give when asking
+/// for help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserError {
+    /// The channel already has an active game.
+    SessionExists,
+    /// No questions can be asked with the mode and filters picked.
+    PoolEmpty,
+    /// The user lacks the permissions the command needs.
+    MissingPermissions,
+    /// The bot lacks the permissions the command needs.
+    BotMissingPermissions,
+    /// The command can only be used in a Server.
+    GuildOnly,
+    /// The command can only be used by the bot's owners.
+    OwnersOnly,
+    /// The user used the command too recently, and can again after this long.
+    Cooldown(Duration),
+    /// An option of the command couldn't be understood.
+    BadArgument,
+    /// The database couldn't be read or written.
+    DataStore,
+    /// Discord couldn't be reached or refused a request.
+    Discord,
+    /// Anything else, e.g. a bug.
+    Internal,
+}
+
+impl UserError {
+    /// Classifies `error`, a failure of a command or of handling an interaction.
+    pub fn of(error: &Error) -> Self {
+        match error {
+            Error::Sqlite(_) => UserError::DataStore,
+            Error::Discord(_) => UserError::Discord,
+            Error::Other(error) => {
+                if let Some(&error) = error.downcast_ref::<UserError>() {
+                    error
+                } else if error.is::<SessionAlreadyCreated>() {
+                    UserError::SessionExists
+                } else {
+                    UserError::Internal
+                }
+            }
+            _ => UserError::Internal,
+        }
+    }
+
+    /// Gets the short code of the failure, e.g. `E-POOL`.
+    pub fn code(self) -> &'static str {
+        match self {
+            UserError::SessionExists => "E-SESSION",
+            UserError::PoolEmpty => "E-POOL",
+            UserError::MissingPermissions => "E-PERM",
+            UserError::BotMissingPermissions => "E-BOTPERM",
+            UserError::GuildOnly => "E-GUILD",
+            UserError::OwnersOnly => "E-OWNER",
+            UserError::Cooldown(_) => "E-COOLDOWN",
+            UserError::BadArgument => "E-ARG",
+            UserError::DataStore => "E-DATA",
+            UserError::Discord => "E-NET",
+            UserError::Internal => "E-INTERNAL",
+        }
+    }
+
+    /// Explains the failure to users, in English and in Japanese.
+    fn message(self) -> (String, String) {
+        let (en, ja) = match self {
+            UserError::SessionExists => (
+                "A game is already running in this channel. Stop it with `/stop` first.",
+                "このチャンネルではゲームが進行中です。先に`/stop`で終了してください。",
+            ),
+            UserError::PoolEmpty => (
+                "There are no words in the pool for this mode and filters.",
+                "このモードと条件に合う単語がありません。",
+            ),
+            UserError::MissingPermissions => (
+                "You don't have the permissions needed to use this command.",
+                "このコマンドを使う権限がありません。",
+            ),
+            UserError::BotMissingPermissions => (
+                "The bot is missing permissions it needs in this channel.",
+                "ボットにこのチャンネルで必要な権限がありません。",
+            ),
+            UserError::GuildOnly => (
+                "This command can only be used in a Server.",
+                "このコマンドはサーバー内でのみ使えます。",
+            ),
+            UserError::OwnersOnly => (
+                "This command can only be used by the bot's maintainers.",
+                "このコマンドはボットの管理者のみ使えます。",
+            ),
+            UserError::Cooldown(remaining) => {
+                let seconds = remaining.as_secs() + 1;
+                return (
+                    format!("Please wait {seconds} second(s) before using this command again."),
+                    format!("{seconds}秒後にもう一度お試しください。"),
+                );
+            }
+            UserError::BadArgument => (
+                "An option couldn't be understood. Please check what you entered.",
+                "オプションを理解できませんでした。入力内容を確認してください。",
+            ),
+            UserError::DataStore => (
+                "Your progress couldn't be loaded or saved right now. Please try again later.",
+                "データの読み込みまたは保存ができませんでした。後でもう一度お試しください。",
+            ),
+            UserError::Discord => (
+                "Discord couldn't be reached. Please try again.",
+                "Discordに接続できませんでした。もう一度お試しください。",
+            ),
+            UserError::Internal => (
+                "Something went wrong on our side. Please try again later.",
+                "内部エラーが発生しました。後でもう一度お試しください。",
+            ),
+        };
+
+        (en.to_string(), ja.to_string())
+    }
+
+    /// Creates the embed explaining the failure to users.
+    pub fn embed(self) -> CreateEmbed {
+        let (en, ja) = self.message();
+
+        CreateEmbed::new()
+            .title("Something went wrong ・ エラー")
+            .description(format!("{en}\n{ja}"))
+            .footer(CreateEmbedFooter::new(format!(
+                "Error code ・ エラーコード: {}",
+                self.code()
+            )))
+    }
+}
+
+impl Display for UserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message().0)
+    }
+}
+
+impl std::error::Error for UserError {}
+
+impl From<UserError> for Error {
+    fn from(error: UserError) -> Self {
+        Error::Other(Box::new(error))
+    }
+}
+
+/// Shows users the failures of commands in an ephemeral embed with their code,
+/// logs the ones that aren't their doing, and counts the failures of commands
+/// that ran in their usage. Failures without a user to show them to are left
+/// to poise.
+pub async fn on_error(error: FrameworkError<'_, Data, Error>) {
+    // Whether the command ran and failed, rather than being refused.
+    let failed = matches!(
+        error,
+        FrameworkError::Command { .. } | FrameworkError::CommandPanic { .. }
+    );
+    let (ctx, user_error) = match error {
+        FrameworkError::Command { error, ctx, .. } => {
+            let user_error = UserError::of(&error);
+            if matches!(
+                user_error,
+                UserError::DataStore | UserError::Discord | UserError::Internal
+            ) {
+                eprintln!(
+                    "[{}] Command /{} failed: {error}",
+                    user_error.code(),
+                    ctx.command().qualified_name
+                );
+            }
+            (ctx, user_error)
+        }
+        FrameworkError::CommandPanic { ctx, payload, .. } => {
+            eprintln!(
+                "[E-INTERNAL] Command /{} panicked: {}",
+                ctx.command().qualified_name,
+                payload.as_deref().unwrap_or("no message")
+            );
+            (ctx, UserError::Internal)
+        }
+        FrameworkError::CooldownHit {
+            remaining_cooldown,
+            ctx,
+            ..
+        } => (ctx, UserError::Cooldown(remaining_cooldown)),
+        FrameworkError::MissingUserPermissions { ctx, .. } => (ctx, UserError::MissingPermissions),
+        FrameworkError::MissingBotPermissions { ctx, .. } => {
+            (ctx, UserError::BotMissingPermissions)
+        }
+        FrameworkError::GuildOnly { ctx, .. } => (ctx, UserError::GuildOnly),
+        FrameworkError::NotAnOwner { ctx, .. } => (ctx, UserError::OwnersOnly),
+        FrameworkError::ArgumentParse { ctx, .. } => (ctx, UserError::BadArgument),
+        error => {
+            if let Err(e) = poise::builtins::on_error(error).await {
+                eprintln!("Failed to handle error: {e}");
+            }
+            return;
+        }
+    };
+
+    if failed {
+        let command = &ctx.command().qualified_name;
+        if let Err(e) = ctx.data().db.record_command_failure(command) {
+            eprintln!("Failed to record failure of /{command}: {e}");
+        }
+    }
+
+    let reply = CreateReply::default()
+        .embed(user_error.embed())
+        .ephemeral(true);
+    if let Err(e) = ctx.send(reply).await {
+        eprintln!("Failed to show error {}: {e}", user_error.code());
+    }
+}
+
+/// Shows the user of `interaction` the failure `error` of handling it, in an
+/// ephemeral embed with its code, whether or not it was responded to already.
+pub async fn respond_to_component(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    error: &Error,
+) {
+    let user_error = UserError::of(error);
+    eprintln!(
+        "[{}] Handling interaction {} failed: {error}",
+        user_error.code(),
+        interaction.data.custom_id
+    );
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(user_error.embed())
+            .ephemeral(true),
+    );
+    if interaction.create_response(ctx, response).await.is_err() {
+        let followup = CreateInteractionResponseFollowup::new()
+            .embed(user_error.embed())
+            .ephemeral(true);
+        interaction.create_followup(ctx, followup).await.ok();
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    path::Path,
+    sync::{Arc, LazyLock},
+};
+
+use poise::serenity_prelude::UserId;
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    emote::{Emote, EmoteSet},
+};
+
+/// How the bot talks to players who answer wrong.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Tone {
+    /// Encouraging.
+    #[name = "Friendly"]
+    #[name_localized("ja", "やさしい")]
+    Friendly,
+    /// Only says the answer was wrong.
+    #[name = "Neutral"]
+    #[name_localized("ja", "ふつう")]
+    Neutral,
+    /// Teasing, with the bot's emotes, as it always used to be.
+    #[default]
+    #[name = "Spicy"]
+    #[name_localized("ja", "辛口")]
+    Spicy,
+}
+
+/// Languages the bot talks to players who answer wrong in.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    #[name = "English"]
+    #[name_localized("ja", "英語")]
+    En,
+    #[name = "Japanese"]
+    #[name_localized("ja", "日本語")]
+    Ja,
+}
+
+/// How a Server's players are told their answers are wrong.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackSettings {
+    #[serde(default)]
+    pub tone: Tone,
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+/// Messages of each tone a bot tells players their answers are wrong with, as
+/// in `content/feedback.toml`.
+#[derive(Debug, Deserialize)]
+pub struct MessagePacks {
+    friendly: MessagePack,
+    neutral: MessagePack,
+    spicy: MessagePack,
+}
+
+/// Messages of a tone in each language.
+#[derive(Debug, Deserialize)]
+struct MessagePack {
+    en: Vec<String>,
+    ja: Vec<String>,
+}
+
+impl MessagePacks {
+    /// Loads the message packs bundled with the bot.
+    pub fn bundled() -> Arc<MessagePacks> {
+        static PACKS: LazyLock<Arc<MessagePacks>> = LazyLock::new(|| {
+            static FEEDBACK_FILE: &str = include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/content/feedback.toml"
+            ));
+
+            toml::from_str::<MessagePacks>(FEEDBACK_FILE)
+                .expect("failed to deserialize feedback messages")
+                .into()
+        });
+
+        Arc::clone(&PACKS)
+    }
+
+    /// Loads message packs written like `content/feedback.toml` from `path`,
+    /// e.g. for a bot with another personality.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path).map_err(|source| Error::File {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        toml::from_str(&text).map_err(|e| Error::Other(e.into()))
+    }
+}
+
+/// Creates a randomized message from `packs` in the tone and language of
+/// `settings` telling `user_id` their answer `choice` is wrong, with the
+/// `emotes` of the Server.
+pub fn wrong_answer<R: Rng + ?Sized>(
+    packs: &MessagePacks,
+    settings: FeedbackSettings,
+    emotes: &EmoteSet,
+    user_id: UserId,
+    choice: &str,
+    rng: &mut R,
+) -> String {
+    let pack = match settings.tone {
+        Tone::Friendly => &packs.friendly,
+        Tone::Neutral => &packs.neutral,
+        Tone::Spicy => &packs.spicy,
+    };
+    let messages = match settings.locale {
+        Locale::En => &pack.en,
+        Locale::Ja => &pack.ja,
+    };
+    let message = match messages.choose(rng) {
+        Some(message) => emotes.fill(message),
+        None => emotes.get(Emote::Wat).to_string(),
+    };
+
+    format!("{message} <@{user_id}> ({choice})")
+}
@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+/// Styles of Discord timestamps, which Discord shows in each viewer's own
+/// time zone and language.
+///
+/// See <https://discord.com/developers/docs/reference#message-formatting-timestamp-styles>
+#[derive(Debug, Clone, Copy)]
+pub enum TimestampStyle {
+    /// e.g. `16:20`.
+    ShortTime,
+    /// e.g. `20/04/2021`.
+    ShortDate,
+    /// e.g. `20 April 2021`.
+    LongDate,
+    /// e.g. `2 months ago`.
+    Relative,
+}
+
+impl TimestampStyle {
+    fn flag(self) -> char {
+        match self {
+            TimestampStyle::ShortTime => 't',
+            TimestampStyle::ShortDate => 'd',
+            TimestampStyle::LongDate => 'D',
+            TimestampStyle::Relative => 'R',
+        }
+    }
+}
+
+/// Formats `secs` since the Unix epoch as a Discord timestamp in `style`.
+pub fn timestamp(secs: impl Display, style: TimestampStyle) -> String {
+    format!("<t:{secs}:{}>", style.flag())
+}
+
+/// Formats `n` with the thousands separator of `locale`, a Discord locale
+/// such as `en-US` or `de`, e.g. `12,345` or `12.345`. Separates with commas
+/// if `locale` isn't set.
+pub fn number(n: impl Into<u64>, locale: Option<&str>) -> String {
+    let digits = n.into().to_string();
+    let separator = thousands_separator(locale.unwrap_or_default());
+
+    let mut formatted = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(separator);
+        }
+        formatted.push(digit);
+    }
+
+    formatted
+}
+
+/// Gets the character grouping the thousands of numbers in `locale`.
+fn thousands_separator(locale: &str) -> char {
+    // Latin American Spanish groups with commas, unlike Spain's.
+    if locale == "es-419" {
+        return ',';
+    }
+
+    let language = locale.split('-').next().unwrap_or_default();
+    match language {
+        "da" | "de" | "el" | "es" | "hr" | "id" | "it" | "nl" | "pt" | "ro" | "tr" | "vi" => '.',
+        // A no-break space, so numbers aren't split across lines.
+        "bg" | "cs" | "fi" | "fr" | "hu" | "lt" | "no" | "pl" | "ru" | "sv" | "uk" => '\u{a0}',
+        _ => ',',
+    }
+}
@@ -1,59 +1,108 @@
 use std::{
+    collections::{HashMap, HashSet},
     fmt::Display,
     sync::{Arc, LazyLock},
     time::Duration,
 };
 
-use dashmap::DashMap;
-use jplearnbot::dictionary::{DictEntry, Kanji, NLevel, Pos, Reading, Sense};
-use lazy_static::lazy_static;
-use poise::{
-    ChoiceParameter,
-    serenity_prelude::{
-        ComponentInteraction, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed,
-        CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, EditMessage,
-        UserId, http::Http,
-    },
+use dashmap::{DashMap, DashSet};
+use jplearnbot::{
+    dictionary::{Dictionary, DictionaryProvider, NLevel, Pos},
+    kana::Tolerance,
+    question::{OPTIONS, is_position_biased},
+    quiz::Mode,
+    rating,
 };
-use rand::{
-    rng,
-    seq::{IndexedRandom, IteratorRandom, SliceRandom},
+use poise::serenity_prelude::{
+    ChannelId, ComponentInteraction, CreateActionRow, CreateEmbed, CreateMessage, GuildId, Message,
+    UserId, http::Http,
 };
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "voice")]
+use songbird::Songbird;
+use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumString};
-use tokio::{
-    sync::mpsc::{self, Receiver, Sender},
-    time::timeout,
-};
+use tokio::sync::mpsc::{self, Sender};
 use uuid::Uuid;
 
-use crate::{Context, dictionary::Dictionary, emote, image};
-
-/// Game modes
-#[derive(Debug, poise::ChoiceParameter, Clone, Copy)]
-pub enum ModeChoice {
-    #[name = "English ▶ ひらがな"]
-    EngToHir,
-    #[name = "ひらがな ▶ English"]
-    HirToEng,
-    #[name = "ひらがな ▶ 漢字"]
-    HirToKan,
-    #[name = "漢字 ▶ ひらがな"]
-    KanToHir,
-    #[name = "漢字 ▶ English"]
-    KanToEng,
-    #[name = "English ▶ 漢字"]
-    EngToKan,
-}
+#[cfg(feature = "voice")]
+use crate::voice::{self, VoiceHost};
+use crate::{
+    Context, Error,
+    audio::TtsBackend,
+    command,
+    db::{Database, SavedSession},
+    feedback::MessagePacks,
+    perf,
+};
+
+mod adaptive;
+mod compatibility;
+mod conjugation;
+mod counters;
+mod grammar;
+mod jmdict;
+mod kana_chart;
+mod kanji;
+mod lobby;
+mod menu;
+mod numbers;
+mod particles;
+mod question;
+mod recent;
+mod record;
+mod team;
+mod transcript;
+mod turn;
+
+pub use adaptive::*;
+pub use compatibility::*;
+pub use conjugation::*;
+pub use counters::*;
+pub use grammar::*;
+pub use jmdict::*;
+pub use kana_chart::*;
+pub use kanji::*;
+pub use numbers::*;
+pub use particles::*;
+pub use question::*;
+pub use recent::*;
+pub use record::*;
+pub use team::*;
+pub use turn::*;
+
+use menu::Menu;
 
 pub enum GameMessage {
     /// A component interaction.
-    Interaction(ComponentInteraction),
+    Interaction(Box<ComponentInteraction>),
+    /// A message sent in a channel, which may be a typed answer.
+    Message(Box<Message>),
     /// Indicates game should close.
     Close,
+    /// Indicates game should close because the bot is shutting down.
+    Shutdown,
+    /// Indicates game should wait until it is resumed, paused by the user.
+    Pause(UserId),
+    /// Indicates a paused game should continue, resumed by the user.
+    Resume(UserId),
 }
 
-#[derive(Debug, Clone, Copy, EnumString, EnumIter, strum_macros::Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    EnumIter,
+    strum_macros::Display,
+    Serialize,
+    Deserialize,
+)]
 pub enum PosFilter {
     #[strum(to_string = "Nouns 名詞")]
     Nouns,
@@ -182,83 +231,635 @@ impl PosFilter {
     }
 }
 
+/// Options chosen when creating a game.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct GameOptions {
+    /// Whether every player is privately shown their own order of the answer buttons,
+    /// so answers can't be called out by position.
+    pub shuffle_per_player: bool,
+    /// How each round ends.
+    pub resolution: Resolution,
+    /// Answers made sooner than this after a question is sent are ignored,
+    /// to discourage answering without reading the question.
+    pub min_read_time: Duration,
+    /// Whether the level and difficulty of questions are hidden until they are answered.
+    pub hard_mode: bool,
+    /// Whether answers are left out of the players' stats and the leaderboard.
+    pub practice: bool,
+    /// Whether questions with kana answers, and with kanji answers as
+    /// [`Self::kanji_typing`] says, are answered by typing the answer in the
+    /// channel instead of with buttons. Rounds of typed questions end at the
+    /// first correct answer regardless of [`Self::resolution`], unless players
+    /// take [turns](Resolution::Turns).
+    pub typed: bool,
+    /// The game ends after this many rounds, if set.
+    pub max_rounds: Option<usize>,
+    /// Rounds end with the answer revealed if it isn't found within this long
+    /// of the question being sent, if set. Replaces the timer of
+    /// [`Resolution::FixedTimer`] rounds.
+    pub time_limit: Option<Duration>,
+    /// Voice channel the prompts are read aloud in, if set.
+    pub voice_channel: Option<ChannelId>,
+    /// Whether players play in two [`Team`]s racing for the first correct answer
+    /// of each round. Every player answers once per round, and a wrong answer
+    /// locks the other team out for a few seconds. Replaces [`Self::resolution`]
+    /// and [`Self::typed`].
+    pub teams: bool,
+    /// Whether players join during a [join phase](lobby::JOIN_PHASE) before the first
+    /// question, which starts them on the scoreboard and splits them evenly
+    /// between the teams of team games. The starter can give players handicaps
+    /// then. [Turn-based](Resolution::Turns) games always have one.
+    #[serde(default)]
+    pub join_phase: bool,
+    /// Whether the words asked about are picked near the [`Adaptive`] ability of
+    /// the players, which starts at the starter's rating. Only game modes asking
+    /// about the words of the dictionary in a [`Mode`](jplearnbot::quiz::Mode)
+    /// adapt. Resumed games adapt anew, so they may ask other words than they
+    /// would have.
+    #[serde(default)]
+    pub adaptive: bool,
+    /// How closely [typed](Self::typed) answers have to match the answer.
+    #[serde(default)]
+    pub tolerance: Tolerance,
+    /// How many players have to vote to skip a question, if not the default.
+    /// The starter can skip one alone.
+    #[serde(default)]
+    pub skip_votes: Option<usize>,
+    /// Whether the starter is sent a transcript of the game in DMs once it ends.
+    #[serde(default)]
+    pub transcript: bool,
+    /// How questions with kanji answers are answered in [typed](Self::typed) games.
+    #[serde(default)]
+    pub kanji_typing: KanjiTyping,
+}
+
+/// Ways questions with kanji answers can be answered in typed games.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum KanjiTyping {
+    /// With buttons, as in games that aren't typed.
+    #[default]
+    #[name = "Buttons"]
+    #[name_localized("ja", "ボタン")]
+    Buttons,
+    /// By typing the answer exactly as it is spelled.
+    #[name = "Typed, spelled exactly"]
+    #[name_localized("ja", "入力：表記どおり")]
+    Exact,
+    /// By typing the answer or another spelling of its word with the same
+    /// kanji recorded in JMDict, e.g. 受付 for 受け付け.
+    #[name = "Typed, any okurigana"]
+    #[name_localized("ja", "入力：送り仮名は問わない")]
+    AnyOkurigana,
+}
+
+/// Policies of how a round of a game ends.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum Resolution {
+    /// The round ends at the first correct answer.
+    #[default]
+    #[name = "First correct wins"]
+    #[name_localized("ja", "早い者勝ち")]
+    FirstCorrect,
+    /// Every player answers once. The answer is revealed along with how many
+    /// players picked each option once no one new has answered for a while.
+    #[name = "Everyone answers once, then reveal"]
+    #[name_localized("ja", "全員が一回答えてから発表")]
+    EveryoneOnce,
+    /// Every player answers once. The answer is revealed along with how many
+    /// players picked each option when a timer set at the start of the round ends.
+    #[name = "Fixed timer, then reveal"]
+    #[name_localized("ja", "時間切れで発表")]
+    FixedTimer,
+    /// The round ends at the first correct answer. Every player's first answer
+    /// is final, so a wrong one locks them out of the round, and the winner
+    /// scores more points the faster they answered.
+    #[name = "Race: one try each, faster scores more"]
+    #[name_localized("ja", "早押し：一回勝負、速いほど高得点")]
+    Race,
+    /// Players who joined are called on in the order they joined, one a round.
+    /// Only their answer counts, and the round ends with it. Their turn is
+    /// skipped if they don't answer in time. Questions aren't typed.
+    #[name = "Take turns answering"]
+    #[name_localized("ja", "順番に回答")]
+    Turns,
+}
+
+/// Fewest questions a game mode must have at a level for it to be playable there,
+/// so games don't end right after they start.
+const MIN_POOL_SIZE: usize = 10;
+
+/// How long after its last round an interrupted game can be resumed.
+const RESUME_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// Manages all game sessions.
 pub struct Manager {
     /// Handle to serenity client.
     http: Arc<Http>,
+    /// Storage for progress made by players.
+    db: Arc<Database>,
     /// Dictionary for getting randomized samples and entries.
     dictionary: Arc<Dictionary>,
-    /// Stores transmitters to game sessions. A Server/DM may only have
+    /// Messages telling players their answers are wrong.
+    feedback: Arc<MessagePacks>,
+    /// Service reading the prompts of listening questions aloud, if configured.
+    tts: Option<Arc<dyn TtsBackend>>,
+    /// Voice client games read their prompts aloud with, if registered.
+    #[cfg(feature = "voice")]
+    songbird: Option<Arc<Songbird>>,
+    /// Game modes that can be played, in the order they are listed.
+    sources: Vec<Arc<dyn QuestionSource>>,
+    /// How many questions each game mode has at each level and part of
+    /// speech, by name.
+    compatibility: HashMap<String, Compatibility>,
+    /// Stores transmitters to game sessions. A channel may only have
     /// one active game session.
-    sessions: Arc<DashMap<u64, Sender<GameMessage>>>,
+    sessions: Arc<DashMap<SessionId, Sender<GameMessage>>>,
+    /// Sessions whose game was paused and not resumed since.
+    paused: Arc<DashSet<SessionId>>,
+    /// Stores the rounds of the last finished game of each channel.
+    replays: Arc<DashMap<SessionId, Arc<Vec<RoundRecord>>>>,
+    /// Stores the rounds asked so far in the current or last game of each
+    /// channel, so their questions can be reported.
+    rounds: Arc<DashMap<SessionId, Vec<RoundRecord>>>,
+    /// Entries shown to each player recently, which their pools leave out.
+    recent: Arc<RecentEntries>,
 }
 
 impl Manager {
-    pub fn new(http: Arc<Http>) -> Self {
-        Manager {
+    /// Creates a manager with all of the built-in [`QuestionSource`]'s registered,
+    /// telling players their answers are wrong with the bundled messages.
+    /// `dictionary` may be shared with the managers of other bots. Listening
+    /// modes are only registered if `tts` is set to read their prompts aloud.
+    pub fn new(
+        http: Arc<Http>,
+        db: Arc<Database>,
+        dictionary: Arc<Dictionary>,
+        tts: Option<Arc<dyn TtsBackend>>,
+    ) -> Self {
+        let mut manager = Manager {
             http,
-            dictionary: Dictionary::new().into(),
+            db,
+            dictionary,
+            feedback: MessagePacks::bundled(),
+            tts,
+            #[cfg(feature = "voice")]
+            songbird: None,
+            sources: Vec::new(),
+            compatibility: HashMap::new(),
             sessions: DashMap::new().into(),
+            paused: DashSet::new().into(),
+            replays: DashMap::new().into(),
+            rounds: DashMap::new().into(),
+            recent: Arc::default(),
+        };
+
+        let can_speak = manager.tts.is_some();
+        for mode in Mode::iter().filter(|m| !m.is_spoken() || can_speak) {
+            manager.register(JmdictSource::new(mode, Arc::clone(&manager.dictionary)));
+        }
+        manager.register(KanaSource::hiragana());
+        manager.register(KanaSource::katakana());
+        manager.register(NumbersSource);
+        manager.register(CountersSource);
+        manager.register(ConjugationSource::new(Arc::clone(&manager.dictionary)));
+        let grammar = bundled_grammar();
+        manager.register(GrammarSource::new(Arc::clone(&grammar)));
+        manager.register(ParticlesSource::new(&grammar));
+        if let Some(kanji) = load_kanji() {
+            for mode in KanjiMode::iter() {
+                manager.register(KanjiSource::new(mode, Arc::clone(&kanji)));
+            }
+        }
+
+        manager
+    }
+
+    /// Lets games read their prompts aloud in voice channels with `songbird`,
+    /// the voice client registered with serenity.
+    #[cfg(feature = "voice")]
+    pub fn with_songbird(self, songbird: Option<Arc<Songbird>>) -> Self {
+        Manager { songbird, ..self }
+    }
+
+    /// Tells players their answers are wrong with the messages of `feedback`
+    /// rather than the bundled ones.
+    pub fn with_feedback(self, feedback: Arc<MessagePacks>) -> Self {
+        Manager { feedback, ..self }
+    }
+
+    /// Adds `source` to the playable game modes. Warns about the levels it has
+    /// too few questions at, which won't be playable.
+    ///
+    /// # Panics
+    /// Will panic if a source with the same name is already registered.
+    pub fn register(&mut self, source: impl QuestionSource + 'static) {
+        assert!(
+            self.source(source.name()).is_none(),
+            "Game mode {} is already registered",
+            source.name()
+        );
+
+        let compatibility = Compatibility::count(&source, self.dictionary.levels());
+        self.compatibility
+            .insert(source.name().to_string(), compatibility);
+        self.sources.push(Arc::new(source));
+    }
+
+    /// Gets the dictionary shared by the game modes.
+    pub fn dictionary(&self) -> &Arc<Dictionary> {
+        &self.dictionary
+    }
+
+    /// Gets the entries of the dictionary for commands that only look them up,
+    /// so they work with any [`DictionaryProvider`].
+    pub fn provider(&self) -> &dyn DictionaryProvider {
+        &*self.dictionary
+    }
+
+    /// Gets the service reading the prompts of listening questions aloud, if
+    /// configured.
+    pub fn tts(&self) -> Option<&dyn TtsBackend> {
+        self.tts.as_deref()
+    }
+
+    /// Gets all of the playable game modes.
+    pub fn sources(&self) -> &[Arc<dyn QuestionSource>] {
+        &self.sources
+    }
+
+    /// Gets the entries shown to each player recently.
+    pub fn recent(&self) -> &RecentEntries {
+        &self.recent
+    }
+
+    /// Gets the levels the game mode named `name` can be played at. Empty if
+    /// it can't be played at any or doesn't exist.
+    pub fn playable_levels(&self, name: &str) -> &[NLevel] {
+        self.compatibility
+            .get(name)
+            .map_or(&[], Compatibility::playable_levels)
+    }
+
+    /// Gets how many questions the game mode named `name` has at each level
+    /// and part of speech, if it exists.
+    pub fn compatibility(&self, name: &str) -> Option<&Compatibility> {
+        self.compatibility.get(name)
+    }
+
+    /// Gets the game mode named `name`, if it exists.
+    pub fn source(&self, name: &str) -> Option<Arc<dyn QuestionSource>> {
+        self.sources.iter().find(|s| s.name() == name).cloned()
+    }
+
+    /// Gets the voice channel the author of `ctx` is in, if games can read their
+    /// prompts aloud there. They can't without a text-to-speech service, or if
+    /// the bot isn't built with the `voice` feature.
+    pub fn voice_channel(&self, ctx: &Context<'_>) -> Option<ChannelId> {
+        #[cfg(feature = "voice")]
+        {
+            self.songbird.as_ref()?;
+            self.tts.as_ref()?;
+            voice::author_channel(ctx)
+        }
+        #[cfg(not(feature = "voice"))]
+        {
+            _ = ctx;
+            None
         }
     }
 
-    /// Starts a new game session with the selected `mode`, `levels`, and `pos`.
+    /// Starts a new game session asking questions from `source` with the selected `filters`
+    /// and `options`.
     /// A separate task is created for game interaction handling. A [`Sender`]
     /// to the session is stored in [`Self::sessions`] for the duration of the game.
-    /// The sessions exists while there are words in the pool and user interaction
-    /// doesn't timeout from inactivity. A session can be stopped prematurely by sending
+    /// The sessions exists while there are questions left, [`GameOptions::max_rounds`]
+    /// isn't reached, and user interaction doesn't timeout from inactivity. A session can be stopped prematurely by sending
     /// a [`GameMessage::Close`] through the sender.
     ///
+    /// The state of the session is saved after every round, so it can be resumed
+    /// with [`Self::resume_game`] if the bot restarts before it ends.
+    ///
     /// # Errors
-    /// Fails if user already has an active game.
+    /// Fails if the channel already has an active game.
     pub fn start_game(
         &self,
         ctx: &Context<'_>,
-        mode: ModeChoice,
-        levels: Vec<NLevel>,
-        filters: Vec<PosFilter>,
+        source: Arc<dyn QuestionSource>,
+        mut filters: Filters,
+        options: GameOptions,
     ) -> Result<(), SessionAlreadyCreated> {
-        let session_id = ctx
-            .guild_id()
-            .map(|g| g.get())
-            .unwrap_or(ctx.author().id.get());
+        // The starter isn't asked about words they saw recently. They are saved
+        // along with the filters, so resumed games ask the same questions.
+        filters.excluded.extend(self.recent.recent(ctx.author().id));
+
+        // Every random decision of the session is drawn from a single seeded source,
+        // so a session can be reproduced from its seed.
+        let seed: u64 = rand::rng().random();
+
+        let saved = SavedSession {
+            session_id: SessionId::of(ctx),
+            mode: source.name().to_string(),
+            filters,
+            options,
+            seed,
+            round: 0,
+            scores: HashMap::new(),
+            starter: Some(ctx.author().id),
+            skipped: 0,
+        };
+        self.spawn_session(source, saved)
+    }
+
+    /// Resumes the game `saved` from the round and with the scores it was
+    /// saved at, asking the same questions it would have asked. `source` is
+    /// the game mode named [`SavedSession::mode`].
+    ///
+    /// # Errors
+    /// Fails if the channel already has an active game.
+    pub fn resume_game(
+        &self,
+        source: Arc<dyn QuestionSource>,
+        saved: SavedSession,
+    ) -> Result<(), SessionAlreadyCreated> {
+        self.spawn_session(source, saved)
+    }
+
+    /// Offers to resume the games saved within [`RESUME_WINDOW`] in their
+    /// channels, e.g. the ones stopped by the bot restarting. Older saved games
+    /// are forgotten.
+    pub async fn offer_resumes(&self) {
+        let saved = match self.db.saved_sessions(RESUME_WINDOW) {
+            Ok(saved) => saved,
+            Err(e) => {
+                eprintln!("Failed to get saved games: {e}");
+                return;
+            }
+        };
+
+        for session in saved {
+            let message = CreateMessage::new()
+                .content(format!(
+                    "A game of {} was interrupted after {} round(s). Resume it?",
+                    session.mode, session.round
+                ))
+                .components(vec![CreateActionRow::Buttons(vec![
+                    command::resume_button(),
+                ])]);
+
+            if let Err(e) = ChannelId::new(session.session_id.channel)
+                .send_message(&self.http, message)
+                .await
+            {
+                eprintln!(
+                    "Failed to offer to resume session {}: {e}",
+                    session.session_id
+                );
+            }
+        }
+    }
+
+    /// Spawns the task of the session `saved`, starting at [`SavedSession::round`].
+    fn spawn_session(
+        &self,
+        source: Arc<dyn QuestionSource>,
+        saved: SavedSession,
+    ) -> Result<(), SessionAlreadyCreated> {
+        let session_id = saved.session_id;
 
         if self.sessions.contains_key(&session_id) {
             return Err(SessionAlreadyCreated);
         }
 
-        let channel_id = ctx.channel_id();
+        let channel_id = ChannelId::new(session_id.channel);
 
         let http = Arc::clone(&self.http);
+        let db = Arc::clone(&self.db);
+        let feedback = Arc::clone(&self.feedback);
+        let tts = self.tts.clone();
+        #[cfg(feature = "voice")]
+        let songbird = self.songbird.clone();
         let sessions = Arc::clone(&self.sessions);
-        let dictionary = Arc::clone(&self.dictionary);
-
-        let mut pos = pos_filters_to_pos(filters);
+        let paused = Arc::clone(&self.paused);
+        let replays = Arc::clone(&self.replays);
+        let rounds = Arc::clone(&self.rounds);
+        let recent = Arc::clone(&self.recent);
 
         let (tx, mut rx) = mpsc::channel(10);
         self.sessions.insert(session_id, tx);
 
+        let seed = saved.seed;
+        let resumed_at = saved.round;
+        if resumed_at == 0 {
+            println!("Starting session {session_id} with seed {seed}");
+        } else {
+            println!("Resuming session {session_id} with seed {seed} at round {resumed_at}");
+        }
+
         tokio::spawn(async move {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let options = saved.options;
+
             // Natural expected exit reason, reason may change from interactions or lack thereof.
             let mut exit_reason = InteractionExitReason::PoolExhausted;
 
-            for (round, entry) in dictionary.sample(&levels, &pos).await.iter().enumerate() {
-                pos.shuffle(&mut rng());
-                let Some(question) = pos
-                    .iter()
-                    .find_map(|&p| Question::new(entry, mode, p, &dictionary))
-                else {
-                    continue;
+            // Adaptive games start at the starter's rating in the Server.
+            let adaptive = options.adaptive.then(|| {
+                let rating = saved
+                    .starter
+                    .and_then(|starter| {
+                        db.rating(GuildId::new(session_id.guild), starter)
+                            .inspect_err(|e| eprintln!("Failed to get rating of {starter}: {e}"))
+                            .ok()
+                            .flatten()
+                    })
+                    .unwrap_or_default();
+                let accuracy = db.entry_accuracy().unwrap_or_else(|e| {
+                    eprintln!("Failed to get accuracy of entries: {e}");
+                    HashMap::new()
+                });
+                Adaptive::new(rating.value, accuracy, saved.filters.distractors)
+            });
+            let filters = Filters {
+                adaptive: adaptive.clone(),
+                ..saved.filters.clone()
+            };
+            let questions = source.questions(&filters, StdRng::from_rng(&mut rng));
+
+            let size = questions.len().saturating_sub(resumed_at);
+            let mut pool_message = match (resumed_at, size) {
+                (_, 0) if !filters.excluded.is_empty() => format!(
+                    "There are no words in the pool for this mode and filters that weren't shown in the last {} hours",
+                    REPEAT_COOLDOWN.as_secs() / 3600
+                ),
+                (_, 0) => "There are no words in the pool for this mode and filters".to_string(),
+                (0, 1) => "Starting game with 1 word in the pool".to_string(),
+                (0, n) => format!("Starting game with {n} words in the pool"),
+                (round, n) => format!(
+                    "Resuming game at round {} with {n} word(s) left in the pool",
+                    round + 1
+                ),
+            };
+            if let Some(max_rounds) = options.max_rounds
+                && resumed_at + size > max_rounds
+            {
+                pool_message.push_str(&format!(", playing {max_rounds} rounds"));
+            }
+            if channel_id
+                .send_message(&http, CreateMessage::new().content(pool_message))
+                .await
+                .is_err()
+                || size == 0
+            {
+                db.remove_saved_session(session_id).ok();
+                sessions.remove(&session_id);
+                paused.remove(&session_id);
+                return;
+            }
+            if let Err(e) = db.save_session(&saved) {
+                eprintln!("Failed to save session {session_id}: {e}");
+            }
+
+            // Prompts are read aloud in the voice channel, if the game is played
+            // in one. Failing to join it doesn't stop the game.
+            #[cfg(feature = "voice")]
+            let voice = match (options.voice_channel, songbird) {
+                (Some(voice_channel), Some(songbird)) => {
+                    let guild_id = GuildId::new(session_id.guild);
+                    match VoiceHost::join(songbird, guild_id, voice_channel).await {
+                        Ok(voice) => Some(voice),
+                        Err(e) => {
+                            eprintln!("Failed to join voice channel of session {session_id}: {e}");
+                            let message =
+                                "Couldn't join the voice channel, so prompts won't be read aloud";
+                            channel_id
+                                .send_message(&http, CreateMessage::new().content(message))
+                                .await
+                                .ok();
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            };
+
+            rounds.insert(session_id, Vec::new());
+            let mut records = Vec::new();
+            // How many questions each player answered correctly, or the points
+            // they earned in race games.
+            let mut scores = saved.scores;
+            // Rounds re-rolled by the starter aren't counted.
+            let mut skipped = saved.skipped;
+
+            // Players join before the first question, if the game has a join phase.
+            let mut teams = Teams::default();
+            let mut turns = Turns::default();
+            let mut handicaps = HashMap::new();
+            let taking_turns = options.resolution == Resolution::Turns && !options.teams;
+            let mut join_error = None;
+            if taking_turns || options.join_phase {
+                let rules = if taking_turns {
+                    "Players answer in turns, in the order they joined"
+                } else if options.teams {
+                    "Players who join are split evenly between the teams"
+                } else {
+                    "Players who join start on the scoreboard"
                 };
+                match lobby::join_phase(
+                    &http,
+                    session_id,
+                    &mut rx,
+                    saved.starter,
+                    rules,
+                    taking_turns,
+                )
+                .await
+                {
+                    Ok(joined) => {
+                        for &player in &joined.players {
+                            scores.entry(player).or_default();
+                            if options.teams {
+                                teams.assign(player);
+                            }
+                        }
+                        if taking_turns && joined.players.is_empty() {
+                            join_error = Some(InteractionExitReason::Timeout);
+                        }
+                        turns = Turns::new(joined.players);
+                        handicaps = joined.handicaps;
+                    }
+                    Err(reason) => join_error = Some(reason),
+                }
+            }
+
+            if options.teams {
+                let message = CreateMessage::new()
+                    .embed(
+                        CreateEmbed::new()
+                            .title("Pick a team ・ チームを選んでください")
+                            .description(
+                                "Players who answer without picking one join the smaller team",
+                            ),
+                    )
+                    .components(vec![CreateActionRow::Buttons(
+                        Team::iter().map(|team| team.button(session_id)).collect(),
+                    )]);
+                channel_id.send_message(&http, message).await.ok();
+            }
+
+            // Everyone who played so far. The pool only leaves out the words the
+            // starter saw recently, so the ones any other player saw are skipped
+            // as they come up.
+            let mut players: HashSet<_> = saved
+                .starter
+                .into_iter()
+                .chain(scores.keys().copied())
+                .collect();
+
+            for (round, question) in questions.enumerate().skip(resumed_at) {
+                if let Some(reason) = join_error.take() {
+                    exit_reason = reason;
+                    break;
+                }
+                if options
+                    .max_rounds
+                    .is_some_and(|max_rounds| round - skipped >= max_rounds)
+                {
+                    exit_reason = InteractionExitReason::RoundLimitReached;
+                    break;
+                }
 
+                let entry_id = question.entry.as_ref().map(|entry| entry.id);
+                if let Some(entry_id) = entry_id
+                    && players.iter().any(|&p| recent.was_shown(p, entry_id))
+                {
+                    skipped += 1;
+                    continue;
+                }
                 let menu_id = format!("{session_id},{}", Uuid::new_v4());
-                let mut menu = Menu::new(&http, menu_id, question, entry);
+                let mut menu = Menu::new(
+                    &http,
+                    &db,
+                    &feedback,
+                    tts.as_deref(),
+                    options.teams.then_some(&mut teams),
+                    taking_turns.then_some(&mut turns),
+                    &handicaps,
+                    menu_id,
+                    question,
+                    options,
+                    saved.starter,
+                );
 
-                if channel_id
-                    .send_files(
-                        &http,
-                        menu.create_files(),
-                        menu.create_message(round + 1, mode),
+                if menu
+                    .send(
+                        channel_id,
+                        GuildId::new(session_id.guild),
+                        round - skipped + 1,
+                        source.name(),
                     )
                     .await
                     .is_err()
@@ -266,21 +867,97 @@ impl Manager {
                     exit_reason = InteractionExitReason::NetworkError;
                     break;
                 }
+                #[cfg(feature = "voice")]
+                if let Some(voice) = &voice
+                    && let Some(tts) = tts.as_deref()
+                    && let Some(prompt) = menu.voice_prompt()
+                    && let Err(e) = voice.say(tts, prompt).await
+                {
+                    eprintln!("Failed to read {prompt} aloud in session {session_id}: {e}");
+                }
+                let record = menu.record(source.name());
+                if source.shuffles_options() {
+                    perf::record_answer_position(record.answer);
+                }
+                rounds.entry(session_id).or_default().push(record.clone());
+                records.push(record);
 
-                if let Err(reason) = menu.handle_interactions(&mut rx).await {
+                let result = menu.handle_interactions(&mut rx, &mut rng).await;
+                if let Some(record) = records.last_mut() {
+                    record.answers = menu.first_answers().to_vec();
+                }
+                players.extend(menu.first_answers().iter().map(|&(user_id, _)| user_id));
+                for &(user_id, points) in menu.round_points() {
+                    *scores.entry(user_id).or_default() += points;
+                }
+                // The players who saw the question aren't asked about its entry
+                // again for a while.
+                if let Some(entry_id) = entry_id {
+                    let answered = menu.first_answers().iter().map(|&(user_id, _)| user_id);
+                    for user_id in saved.starter.into_iter().chain(answered) {
+                        recent.mark(user_id, entry_id);
+                    }
+                }
+                // Practice answers still move the ability of adaptive games,
+                // which only lasts for the game.
+                if let Some(adaptive) = &adaptive
+                    && !menu.rerolled()
+                {
+                    let question = adaptive.question_rating(entry_id, menu.difficulty());
+                    for &(_, correct) in menu.first_answers() {
+                        adaptive.answer(question, correct);
+                    }
+                }
+                // A re-rolled question is skipped rather than missed, so it
+                // isn't rated or replayed. It can still be reported.
+                if menu.rerolled() {
+                    skipped += 1;
+                    records.pop();
+                } else if !options.practice {
+                    let question =
+                        rating::question_rating(menu.difficulty(), saved.filters.distractors);
+                    for &(user_id, correct) in menu.first_answers() {
+                        // Games in DMs are keyed by the player, and aren't rated.
+                        if session_id.guild == user_id.get() {
+                            continue;
+                        }
+                        if let Err(e) = db.rate_answer(
+                            GuildId::new(session_id.guild),
+                            user_id,
+                            question,
+                            correct,
+                        ) {
+                            eprintln!("Failed to rate answer of {user_id}: {e}");
+                        }
+                    }
+                }
+                if let Err(reason) = result {
+                    if matches!(reason, InteractionExitReason::Shutdown) {
+                        menu.close().await.ok();
+                    }
                     exit_reason = reason;
                     break;
                 }
+
+                if let Err(e) = db.save_session_progress(session_id, round + 1, skipped, &scores) {
+                    eprintln!("Failed to save progress of session {session_id}: {e}");
+                }
             }
 
             let message = match exit_reason {
                 InteractionExitReason::PoolExhausted => {
-                    Some("There are no more words left in the pool")
+                    Some("There are no more words left in the pool".to_string())
+                }
+                InteractionExitReason::RoundLimitReached => {
+                    Some("That was the last round. Thanks for playing!".to_string())
+                }
+                InteractionExitReason::Timeout => {
+                    Some("Stopping game due to inactivity...".to_string())
                 }
-                InteractionExitReason::Timeout => Some("Stopping game due to inactivity..."),
                 InteractionExitReason::NetworkError => {
-                    Some("Stopping game due to network error...")
+                    Some("Stopping game due to network error...".to_string())
                 }
+                InteractionExitReason::Shutdown => Some(shutdown_message(&scores)),
                 InteractionExitReason::CloseRequest => None,
             };
 
@@ -290,19 +967,116 @@ impl Manager {
                     .await
                     .ok();
             }
+            if options.teams && !records.is_empty() {
+                channel_id
+                    .send_message(
+                        &http,
+                        CreateMessage::new().embed(teams.results_embed(&scores)),
+                    )
+                    .await
+                    .ok();
+            }
+
+            // Games stopped by a shutdown stay saved to be resumed after it.
+            if !matches!(exit_reason, InteractionExitReason::Shutdown)
+                && let Err(e) = db.remove_saved_session(session_id)
+            {
+                eprintln!("Failed to forget session {session_id}: {e}");
+            }
+
+            #[cfg(feature = "voice")]
+            if let Some(voice) = voice {
+                voice.leave().await;
+            }
 
+            // Games stopped by a shutdown are sent one once resumed and over,
+            // of the rounds played since.
+            if options.transcript
+                && !records.is_empty()
+                && !matches!(exit_reason, InteractionExitReason::Shutdown)
+                && let Some(starter) = saved.starter
+                && let Err(e) =
+                    transcript::send_transcript(&http, starter, channel_id, &records, &scores).await
+            {
+                eprintln!("Failed to send transcript of session {session_id}: {e}");
+            }
+
+            if source.shuffles_options() {
+                let mut positions = [0; OPTIONS];
+                for record in &records {
+                    positions[record.answer] += 1;
+                }
+                if is_position_biased(&positions) {
+                    eprintln!(
+                        "Answers of session {session_id} in {} were placed unevenly: {positions:?}",
+                        source.name()
+                    );
+                }
+            }
+
+            if !records.is_empty() {
+                replays.insert(session_id, records.into());
+            }
             sessions.remove(&session_id);
+            paused.remove(&session_id);
         });
 
         Ok(())
     }
 
+    /// Determines whether `session_id` has an active game.
+    pub fn is_active(&self, session_id: SessionId) -> bool {
+        self.sessions.contains_key(&session_id)
+    }
+
+    /// Gets the rounds of `session_id`'s last finished game, if any.
+    pub fn replay(&self, session_id: SessionId) -> Option<Arc<Vec<RoundRecord>>> {
+        self.replays.get(&session_id).map(|r| Arc::clone(&r))
+    }
+
+    /// Gets the round whose menu is identified by `id`, if it was asked in the
+    /// current or last game of its channel.
+    pub fn round(&self, id: &str) -> Option<RoundRecord> {
+        let session_id = parse_session_id(id)?;
+
+        self.rounds
+            .get(&session_id)?
+            .iter()
+            .find(|round| round.id == id)
+            .cloned()
+    }
+
+    /// Stops every active game, telling their players the bot is restarting along
+    /// with their final scores, and waits up to `timeout` for the games to finish.
+    pub async fn shutdown(&self, timeout: Duration) {
+        // Cloned out of the map so it isn't locked while the games finish.
+        let senders: Vec<_> = self.sessions.iter().map(|tx| tx.value().clone()).collect();
+        if senders.is_empty() {
+            return;
+        }
+        println!("Stopping {} active game(s)", senders.len());
+
+        for tx in &senders {
+            tx.send(GameMessage::Shutdown).await.ok();
+        }
+
+        // A game's receiver is dropped once it has finished.
+        let finished = async {
+            for tx in &senders {
+                tx.closed().await;
+            }
+        };
+        if tokio::time::timeout(timeout, finished).await.is_err() {
+            eprintln!("Timed out waiting for games to finish");
+        }
+    }
+
     /// Stops `session_id`'s game if it exists.
     ///
     /// Returns true if there was an active game stopped.
     ///
     /// Returns false if there was no game associated with the `session_id`.
-    pub async fn stop(&self, session_id: u64) -> bool {
+    pub async fn stop(&self, session_id: SessionId) -> bool {
         if let Some(tx) = self.sessions.get(&session_id) {
             tx.send(GameMessage::Close).await.ok();
             return true;
@@ -311,19 +1085,67 @@ impl Manager {
         false
     }
 
+    /// Pauses `session_id`'s game on behalf of `user_id` until it is
+    /// [resumed](Self::unpause).
+    ///
+    /// Returns false if there is no active game or it is already paused.
+    pub async fn pause(&self, session_id: SessionId, user_id: UserId) -> bool {
+        let Some(tx) = self.sessions.get(&session_id).map(|tx| tx.clone()) else {
+            return false;
+        };
+        if !self.paused.insert(session_id) {
+            return false;
+        }
+
+        tx.send(GameMessage::Pause(user_id)).await.ok();
+        true
+    }
+
+    /// Resumes `session_id`'s paused game on behalf of `user_id`.
+    ///
+    /// Returns false if there is no active game or it isn't paused.
+    pub async fn unpause(&self, session_id: SessionId, user_id: UserId) -> bool {
+        let Some(tx) = self.sessions.get(&session_id).map(|tx| tx.clone()) else {
+            return false;
+        };
+        if self.paused.remove(&session_id).is_none() {
+            return false;
+        }
+
+        tx.send(GameMessage::Resume(user_id)).await.ok();
+        true
+    }
+
+    /// Determines whether `session_id`'s game is paused.
+    pub fn is_paused(&self, session_id: SessionId) -> bool {
+        self.paused.contains(&session_id)
+    }
+
     /// Sends `interaction` to the game session compatible with the interaction's custom_id.
     /// Does nothing if no matching game sesssion.
     pub async fn send(&self, interaction: ComponentInteraction) {
         if let Some(tx) =
             parse_session_id(&interaction.data.custom_id).and_then(|id| self.sessions.get(&id))
         {
-            tx.send(GameMessage::Interaction(interaction)).await.ok();
+            tx.send(GameMessage::Interaction(interaction.into()))
+                .await
+                .ok();
+        }
+    }
+
+    /// Sends `message` to the game session of the channel it was sent in.
+    /// Does nothing if no matching game session.
+    pub async fn send_message(&self, message: Message) {
+        let session_id = SessionId::new(message.guild_id, message.author.id, message.channel_id);
+
+        if let Some(tx) = self.sessions.get(&session_id) {
+            tx.send(GameMessage::Message(message.into())).await.ok();
         }
     }
 }
 
 /// Converts [`PosFilter`]'s to [`Pos`] using [`PosFilter::as_pos`].
-fn pos_filters_to_pos(filters: Vec<PosFilter>) -> Vec<Pos> {
+fn pos_filters_to_pos(filters: &[PosFilter]) -> Vec<Pos> {
     let mut res = Vec::new();
 
     for filter in filters {
@@ -337,507 +1159,93 @@ fn pos_filters_to_pos(filters: Vec<PosFilter>) -> Vec<Pos> {
 enum InteractionExitReason {
     /// There are no more words left in the pool.
     PoolExhausted,
+    /// All of the rounds set by [`GameOptions::max_rounds`] were played.
+    RoundLimitReached,
     /// Sender took too long to send a message.
     Timeout,
     /// Error sending data to Discord.
     NetworkError,
     /// Game should close.
     CloseRequest,
+    /// The bot is shutting down.
+    Shutdown,
 }
 
-/// Extracts game session_id from interaction's custom_id.
-fn parse_session_id(interaction_id: &str) -> Option<u64> {
-    static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d+").unwrap());
-
-    let session_id = RE.find(interaction_id)?.as_str().parse().ok()?;
-
-    Some(session_id)
-}
-
-#[derive(Debug)]
-pub struct SessionAlreadyCreated;
-
-impl Display for SessionAlreadyCreated {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "User has already created a session")
-    }
-}
-
-impl std::error::Error for SessionAlreadyCreated {}
-
-/// Game question
-struct Question {
-    /// The word to translate.
-    prompt: String,
-    /// Possible translations of [`Self::prompt`].
-    options: [String; 5],
-    /// The index of the correct translation of [`Self::prompt`].
-    answer: usize,
-}
-
-impl Question {
-    fn new(entry: &DictEntry, mode: ModeChoice, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        match mode {
-            ModeChoice::EngToHir => Self::new_eng_to_hir(entry, pos, dictionary),
-            ModeChoice::HirToEng => Self::new_hir_to_eng(entry, pos, dictionary),
-            ModeChoice::HirToKan => Self::new_hir_to_kan(entry, pos, dictionary),
-            ModeChoice::KanToHir => Self::new_kan_to_hir(entry, pos, dictionary),
-            ModeChoice::KanToEng => Self::new_kan_to_eng(entry, pos, dictionary),
-            ModeChoice::EngToKan => Self::new_eng_to_kan(entry, pos, dictionary),
+/// Tells players their game was stopped because the bot is restarting, listing
+/// how many questions each of them answered correctly, the most first.
+fn shutdown_message(scores: &HashMap<UserId, usize>) -> String {
+    let mut message =
+        "The bot is restarting, so the game was stopped. It can be resumed once the bot is back."
+            .to_string();
+
+    let mut scores: Vec<_> = scores.iter().collect();
+    scores.sort_by(|(_, a), (_, b)| b.cmp(a));
+    if !scores.is_empty() {
+        message.push_str("\nFinal scores ・ 最終スコア:");
+        for (rank, (user_id, score)) in scores.into_iter().enumerate() {
+            message.push_str(&format!("\n{}. <@{user_id}> {score}", rank + 1));
         }
     }
 
-    fn new_eng_to_hir(entry: &DictEntry, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        let (reading, sense) = reading_sense_pair(entry, pos)?;
-
-        let mut options = std::array::from_fn(|_| "".to_string());
-        options[0] = reading.text.clone();
-
-        dictionary
-            .entries
-            .iter()
-            .filter_map(|e| {
-                if e.id == entry.id {
-                    return None;
-                }
-                let (reading, _) = reading_sense_pair(e, pos)?;
-                Some(reading.text.clone())
-            })
-            .choose_multiple_fill(&mut rng(), &mut options[1..]);
-
-        options.shuffle(&mut rng());
-
-        let answer = options.iter().position(|o| reading.text == *o).unwrap();
-
-        Some(Question {
-            prompt: sense.gloss[0].content.clone(),
-            options,
-            answer,
-        })
-    }
-
-    fn new_hir_to_eng(entry: &DictEntry, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        let (reading, sense) = reading_sense_pair(entry, pos)?;
-
-        let mut options = std::array::from_fn(|_| "".to_string());
-        options[0] = sense.gloss[0].content.clone();
-
-        dictionary
-            .entries
-            .iter()
-            .filter_map(|e| {
-                if e.id == entry.id {
-                    return None;
-                }
-                let (_, sense) = reading_sense_pair(e, pos)?;
-                Some(sense.gloss[0].content.clone())
-            })
-            .choose_multiple_fill(&mut rng(), &mut options[1..]);
-
-        options.shuffle(&mut rng());
-
-        let answer = options
-            .iter()
-            .position(|o| sense.gloss[0].content == *o)
-            .unwrap();
-
-        Some(Question {
-            prompt: reading.text.clone(),
-            options,
-            answer,
-        })
-    }
-
-    fn new_hir_to_kan(entry: &DictEntry, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        let (kanji, reading) = kanji_reading_pair(entry, pos)?;
-
-        let mut options = std::array::from_fn(|_| "".to_string());
-        options[0] = kanji.text.clone();
-        dictionary
-            .entries
-            .iter()
-            .filter_map(|e| {
-                if e.id == entry.id {
-                    return None;
-                }
-
-                let (kanji, _) = kanji_reading_pair(e, pos)?;
-                Some(kanji.text.clone())
-            })
-            .choose_multiple_fill(&mut rng(), &mut options[1..]);
-
-        options.shuffle(&mut rng());
-
-        let answer = options.iter().position(|o| kanji.text == *o).unwrap();
-
-        Some(Question {
-            prompt: reading.text.clone(),
-            options,
-            answer,
-        })
-    }
-
-    fn new_kan_to_hir(entry: &DictEntry, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        let (kanji, reading) = kanji_reading_pair(entry, pos)?;
-
-        let mut options = std::array::from_fn(|_| "".to_string());
-        options[0] = reading.text.clone();
-
-        dictionary
-            .entries
-            .iter()
-            .filter_map(|e| {
-                if e.id == entry.id {
-                    return None;
-                }
-                let (_, reading) = kanji_reading_pair(e, pos)?;
-                Some(reading.text.clone())
-            })
-            .choose_multiple_fill(&mut rng(), &mut options[1..]);
-
-        options.shuffle(&mut rng());
-
-        let answer = options.iter().position(|o| reading.text == *o).unwrap();
-
-        Some(Question {
-            prompt: kanji.text.clone(),
-            options,
-            answer,
-        })
-    }
-
-    fn new_kan_to_eng(entry: &DictEntry, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        let (kanji, sense) = kanji_sense_pair(entry, pos)?;
-
-        let mut options = std::array::from_fn(|_| "".to_string());
-        options[0] = sense.gloss[0].content.clone();
-
-        dictionary
-            .entries
-            .iter()
-            .filter_map(|e| {
-                if e.id == entry.id {
-                    return None;
-                }
-                let (_, sense) = kanji_sense_pair(e, pos)?;
-                Some(sense.gloss[0].content.clone())
-            })
-            .choose_multiple_fill(&mut rng(), &mut options[1..]);
-
-        options.shuffle(&mut rng());
-
-        let answer = options
-            .iter()
-            .position(|o| sense.gloss[0].content == *o)
-            .unwrap();
-
-        Some(Question {
-            prompt: kanji.text.clone(),
-            options,
-            answer,
-        })
-    }
-
-    fn new_eng_to_kan(entry: &DictEntry, pos: Pos, dictionary: &Dictionary) -> Option<Self> {
-        let (kanji, sense) = kanji_sense_pair(entry, pos)?;
-
-        let mut options = std::array::from_fn(|_| "".to_string());
-        options[0] = kanji.text.clone();
-
-        dictionary
-            .entries
-            .iter()
-            .filter_map(|e| {
-                if e.id == entry.id {
-                    return None;
-                }
-                let (kanji, _) = kanji_sense_pair(e, pos)?;
-                Some(kanji.text.clone())
-            })
-            .choose_multiple_fill(&mut rng(), &mut options[1..]);
-
-        options.shuffle(&mut rng());
-
-        let answer = options.iter().position(|o| kanji.text == *o).unwrap();
-
-        Some(Question {
-            prompt: sense.gloss[0].content.clone(),
-            options,
-            answer,
-        })
-    }
+    message
 }
 
-/// Conventiently extracts a [`Reading`] and correlated [`Sense`] from a [`DictEntry`] where
-/// the sense has the `pos` tag and is guaranteed to have at least one gloss.
-///
-/// Returns [`None`] if no possible extraction.
-fn reading_sense_pair(entry: &DictEntry, pos: Pos) -> Option<(&Reading, &Sense)> {
-    let sense = entry
-        .senses
-        .iter()
-        .find(|s| s.pos.contains(&pos) && !s.gloss.is_empty())?;
-
-    let reading = entry
-        .readings
-        .iter()
-        .find(|r| sense.relevant_reading.is_empty() || sense.relevant_reading.contains(&r.text))?;
-
-    Some((reading, sense))
+/// Identifies a game session by the Server (or the user, in DMs) and channel
+/// it's played in, so each channel can host its own game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionId {
+    pub guild: u64,
+    pub channel: u64,
 }
 
-/// Conveniently extracts a [`Kanji`] and correlated [`Reading`] from a [`DictEntry`] where
-/// the reading has the `pos` tag.
-///
-/// Returns [`None`] if no possible extraction.
-fn kanji_reading_pair(entry: &DictEntry, pos: Pos) -> Option<(&Kanji, &Reading)> {
-    let sense = entry.senses.iter().find(|s| s.pos.contains(&pos))?;
-
-    let kanji = entry.kanjis.first()?;
-
-    let reading = entry.readings.iter().find(|r| {
-        (r.relevant_to.is_empty() || r.relevant_to.contains(&kanji.text))
-            && (sense.relevant_reading.is_empty() || sense.relevant_reading.contains(&r.text))
-    })?;
-
-    Some((kanji, reading))
-}
-
-/// Conventiently extracts a [`Kanji`] and correlated [`Sense`] from a [`DictEntry`] where
-/// the sense has the `pos` tag and is guaranteed to have at least one gloss.
-///
-/// Returns [`None`] if no possible extraction.
-fn kanji_sense_pair(entry: &DictEntry, pos: Pos) -> Option<(&Kanji, &Sense)> {
-    let sense = entry
-        .senses
-        .iter()
-        .find(|s| s.pos.contains(&pos) && !s.gloss.is_empty())?;
-
-    let kanji = entry.kanjis.first()?;
-
-    Some((kanji, sense))
-}
-
-/// Manages the components of a game question.
-struct Menu<'a> {
-    id: String,
-    prompt: String,
-    questions: Vec<QuestionComponent>,
-    answer: usize,
-    entry: &'a DictEntry,
-    http: &'a Http,
-}
-
-/// Contains data on a game button.
-struct QuestionComponent {
-    /// The component's unique identifier.
-    id: String,
-    /// Possible translation text.
-    text: String,
-    /// Whether this button should be disabled.
-    disabled: bool,
-}
-
-impl<'a> Menu<'a> {
-    fn new(http: &'a Http, id: String, question: Question, entry: &'a DictEntry) -> Self {
-        let questions = question
-            .options
-            .into_iter()
-            .enumerate()
-            .map(|(i, text)| QuestionComponent {
-                id: format!("{id},{i}"),
-                text,
-                disabled: false,
-            })
-            .collect();
-
-        Menu {
-            id,
-            prompt: question.prompt,
-            questions,
-            answer: question.answer,
-            entry,
-            http,
+impl SessionId {
+    /// Gets the id of the session in `channel_id` of `guild_id`, or of the DM
+    /// with `user_id` if it isn't in a Server.
+    pub fn new(guild_id: Option<GuildId>, user_id: UserId, channel_id: ChannelId) -> Self {
+        SessionId {
+            guild: guild_id.map(|g| g.get()).unwrap_or(user_id.get()),
+            channel: channel_id.get(),
         }
     }
 
-    /// Collects all the levels of [`Self::entry`].
-    fn levels(&self) -> Vec<NLevel> {
-        let mut levels: Vec<_> = self
-            .entry
-            .readings
-            .iter()
-            .flat_map(|r| r.levels.clone())
-            .collect();
-
-        levels.sort_unstable();
-        levels.dedup();
-
-        levels
-    }
-
-    /// Equivalent to [`Self::questions`]\[\].[`id`]
-    fn answer_id(&self) -> &str {
-        &self.questions[self.answer].id
-    }
-
-    fn create_files(&self) -> Vec<CreateAttachment> {
-        vec![CreateAttachment::bytes(
-            image::text_to_image(&self.prompt),
-            "prompt.png",
-        )]
-    }
-
-    fn create_message(&self, round: usize, mode: ModeChoice) -> CreateMessage {
-        CreateMessage::new()
-            .embed(
-                CreateEmbed::new()
-                    .title(format!("Question {round}"))
-                    .field(mode.name(), "", false)
-                    .attachment("prompt.png"),
-            )
-            .components(self.create_components())
+    /// Gets the id of the session in the Server/DM and channel `ctx` was invoked in.
+    pub fn of(ctx: &Context<'_>) -> Self {
+        SessionId::new(ctx.guild_id(), ctx.author().id, ctx.channel_id())
     }
+}
 
-    /// Create all of the components of this menu.
-    fn create_components(&self) -> Vec<CreateActionRow> {
-        let buttons = self
-            .questions
-            .iter()
-            .map(|q| CreateButton::new(&q.id).label(&q.text).disabled(q.disabled))
-            .collect();
-
-        vec![CreateActionRow::Buttons(buttons)]
+impl Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.guild, self.channel)
     }
+}
 
-    /// Listens for button interactions until the answer is chosen.
-    async fn handle_interactions(
-        &mut self,
-        rx: &mut Receiver<GameMessage>,
-    ) -> Result<(), InteractionExitReason> {
-        loop {
-            let mut ci = component_interaction(rx).await?;
-
-            let Some((menu_id, choice)) = parse_custom_id(&ci.data.custom_id) else {
-                continue;
-            };
-            // Skip if menu_id of previous round.
-            if menu_id != self.id {
-                continue;
-            }
-
-            let correct = self.questions[choice].id == self.answer_id();
+/// Extracts game session_id from interaction's custom_id, which starts with
+/// the session_id formatted as `guild:channel`.
+fn parse_session_id(interaction_id: &str) -> Option<SessionId> {
+    static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+):(\d+)").unwrap());
 
-            // If correct, disable all buttons since this round is finished.
-            // Otherwise, just the wrongly selected button.
-            if correct {
-                self.questions.iter_mut().for_each(|q| q.disabled = true);
-            } else {
-                self.questions[choice].disabled = true;
-            }
+    let captures = RE.captures(interaction_id)?;
 
-            ci.message
-                .edit(
-                    self.http,
-                    EditMessage::new().components(self.create_components()),
-                )
-                .await
-                .map_err(|_| InteractionExitReason::NetworkError)?;
-
-            let message = if correct {
-                const THUMBNAIL: &str = r"https://raw.githubusercontent.com/jasonly027/jplearnbot/dedaa826e9bbc942cf035ba8eeac15479e8d9416/assets/correct.png";
-                let header = format!("{} {:?}", &self.questions[self.answer].text, self.levels());
-                let body = format!(
-                    "[**Definition ・ 意味**](https://jisho.org/search/{})\n{} {}",
-                    urlencoding::encode(&self.questions[self.answer].text),
-                    ci.user.name,
-                    emote::WOW.as_str()
-                );
-
-                CreateInteractionResponseMessage::new().embed(
-                    CreateEmbed::new()
-                        .title("Answer · 正解")
-                        .thumbnail(THUMBNAIL)
-                        .field(header, body, false),
-                )
-            } else {
-                CreateInteractionResponseMessage::new()
-                    .content(insult_message(ci.user.id, &self.questions[choice].text))
-            };
-
-            ci.create_response(self.http, CreateInteractionResponse::Message(message))
-                .await
-                .map_err(|_| InteractionExitReason::NetworkError)?;
+    Some(SessionId {
+        guild: captures[1].parse().ok()?,
+        channel: captures[2].parse().ok()?,
+    })
+}
 
-            if correct {
-                break;
-            }
-        }
+#[derive(Debug)]
+pub struct SessionAlreadyCreated;
 
-        Ok(())
+impl Display for SessionAlreadyCreated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Channel already has an active session")
     }
 }
 
-/// Unwraps component interactions from `rx`.
-///
-/// Returns [`InteractionExitReason::Timeout`] if sender takes
-/// too long.
-///
-/// Returns [`InteractionExitReason::CloseRequest`] if sender sends
-/// [`GameMessage::Close`].
-async fn component_interaction(
-    rx: &mut Receiver<GameMessage>,
-) -> Result<ComponentInteraction, InteractionExitReason> {
-    let Ok(Some(msg)) = timeout(Duration::from_secs(120), rx.recv()).await else {
-        return Err(InteractionExitReason::Timeout);
-    };
-
-    let GameMessage::Interaction(ci) = msg else {
-        return Err(InteractionExitReason::CloseRequest);
-    };
-
-    Ok(ci)
-}
-
-/// Parses a component's custom_id for its menu_id and the user's button choice.
-fn parse_custom_id(custom_id: &str) -> Option<(&str, usize)> {
-    static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*),([0-4])$").unwrap());
-
-    RE.captures(custom_id)
-        .and_then(|m| Some((m.get(1)?, m.get(2)?))) // Get capture groups
-        .and_then(|(menu_id, choice)| {
-            Some((menu_id.as_str(), choice.as_str().parse().ok()?)) // Convert format
-        })
-}
+impl std::error::Error for SessionAlreadyCreated {}
 
-/// Creates a randomized insult message that mentions `user_id`.
-fn insult_message(user_id: UserId, choice: &str) -> String {
-    lazy_static! {
-        static ref insults: [String; 20] = [
-            format!("{} noob", emote::WAT.as_str()),
-            format!("{} nuh-uh", emote::WAT.as_str()),
-            format!("{} what is he cooking", emote::WAT.as_str()),
-            format!("{} refund nitro", emote::WAT.as_str()),
-            format!("{} trolling are we?", emote::WAT.as_str()),
-            format!("{} nt bro", emote::WAT.as_str()),
-            format!("{} smooth brain", emote::WAT.as_str()),
-            format!("{} stop", emote::WAT.as_str()),
-            format!("{} ?", emote::WAT.as_str()),
-            format!("{} so bad", emote::WAT.as_str()),
-            format!("{} meow", emote::WAT.as_str()),
-            format!("{} imagine", emote::WAT.as_str()),
-            format!("{} no", emote::WAT.as_str()),
-            format!("{} wrong", emote::WAT.as_str()),
-            format!("{} ぴえん", emote::WAT.as_str()),
-            format!("{} あほ", emote::WAT.as_str()),
-            emote::WAT.to_string(),
-            emote::FUBU_LAUGH.to_string(),
-            emote::SCRAJJ.to_string(),
-            emote::ANW.to_string(),
-        ];
+impl From<SessionAlreadyCreated> for Error {
+    fn from(error: SessionAlreadyCreated) -> Self {
+        Error::Other(Box::new(error))
     }
-
-    let insult = insults.choose(&mut rng()).unwrap();
-
-    format!("{insult} <@{user_id}> ({choice})")
 }
@@ -0,0 +1,72 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use jplearnbot::{
+    dictionary::EntryRef,
+    distractor::Difficulty,
+    rating::{self, Rating},
+};
+
+/// Ability of the players of an [adaptive](super::GameOptions::adaptive) game,
+/// which the words asked about are picked near. It rises as they answer correctly
+/// and falls as they miss, so questions get harder or easier along with them.
+#[derive(Debug, Clone)]
+pub struct Adaptive {
+    /// Rating of the players, adjusted after every first answer of the game.
+    ability: Arc<Mutex<Rating>>,
+    /// How many times each entry was answered correctly and how many times it
+    /// was answered, by entry id.
+    accuracy: Arc<HashMap<u32, (u32, u32)>>,
+    /// How closely the wrong options of the game resemble the answer.
+    distractors: Difficulty,
+}
+
+impl Adaptive {
+    /// Starts at the rating `ability`, estimating the rating of words from their
+    /// `accuracy` in past answers and the `distractors` of the game. The ability
+    /// moves as fast as a [provisional](Rating::is_provisional) rating at first,
+    /// since it is only a guess until the players answer.
+    pub fn new(ability: f64, accuracy: HashMap<u32, (u32, u32)>, distractors: Difficulty) -> Self {
+        Adaptive {
+            ability: Arc::new(Mutex::new(Rating {
+                value: ability,
+                answers: 0,
+            })),
+            accuracy: Arc::new(accuracy),
+            distractors,
+        }
+    }
+
+    /// Gets the current ability of the players.
+    pub fn ability(&self) -> f64 {
+        self.ability.lock().unwrap_or_else(|e| e.into_inner()).value
+    }
+
+    /// Estimates the rating of a question about the entry with the id `entry_id`,
+    /// whose difficulty is `stars`, if it is about one.
+    pub fn question_rating(&self, entry_id: Option<u32>, stars: Option<u8>) -> f64 {
+        let prior = rating::question_rating(stars, self.distractors);
+        let (correct, answers) = entry_id
+            .and_then(|id| self.accuracy.get(&id))
+            .copied()
+            .unwrap_or_default();
+
+        rating::estimate_rating(prior, correct, answers)
+    }
+
+    /// Gets how likely `entry` is to be asked about next, the more the closer
+    /// its rating is to the players' ability.
+    pub fn weight(&self, ability: f64, entry: EntryRef<'_>) -> f64 {
+        let question = self.question_rating(Some(entry.id()), entry.difficulty());
+        rating::fit(ability, question)
+    }
+
+    /// Adjusts the ability of the players for a first answer to a question
+    /// rated `question`.
+    pub fn answer(&self, question: f64, correct: bool) {
+        let mut ability = self.ability.lock().unwrap_or_else(|e| e.into_inner());
+        *ability = ability.answer(question, correct);
+    }
+}
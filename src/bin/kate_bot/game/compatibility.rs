@@ -0,0 +1,98 @@
+use std::collections::{HashMap, HashSet};
+
+use jplearnbot::{dictionary::NLevel, distractor::Difficulty};
+use rand::{SeedableRng, rngs::StdRng};
+use strum::IntoEnumIterator;
+
+use super::{Filters, PosFilter, QuestionSource};
+
+/// How many questions a game mode has at each level, with every part of speech
+/// and with each on its own. Counted once when the mode is registered, so
+/// `/start` only offers the combinations that can be played.
+#[derive(Debug, Default)]
+pub struct Compatibility {
+    /// Levels the mode has at least [`MIN_POOL_SIZE`](super::MIN_POOL_SIZE)
+    /// questions at, in the order of the dictionary.
+    playable: Vec<NLevel>,
+    /// Questions at each level with every part of speech.
+    totals: HashMap<NLevel, usize>,
+    /// Questions at each level with only one part of speech.
+    counts: HashMap<(NLevel, PosFilter), usize>,
+}
+
+impl Compatibility {
+    /// Counts the questions `source` has at each of `levels`, warning about
+    /// the levels it has too few at, which won't be playable.
+    pub fn count(source: &dyn QuestionSource, levels: &[NLevel]) -> Self {
+        let size = |level: NLevel, pos: Vec<PosFilter>| {
+            let filters = Filters {
+                levels: vec![level],
+                pos,
+                entries: None,
+                distractors: Difficulty::default(),
+                grades: Vec::new(),
+                excluded: HashSet::new(),
+                adaptive: None,
+            };
+            source.questions(&filters, StdRng::seed_from_u64(0)).len()
+        };
+
+        let mut compatibility = Compatibility::default();
+        for &level in levels {
+            let total = size(level, PosFilter::iter().collect());
+            if total < super::MIN_POOL_SIZE {
+                eprintln!(
+                    "Warning: {} has only {total} question(s) at {level}, hiding it",
+                    source.name()
+                );
+            } else {
+                compatibility.playable.push(level);
+            }
+            compatibility.totals.insert(level, total);
+
+            for pos in PosFilter::iter() {
+                compatibility
+                    .counts
+                    .insert((level, pos), size(level, vec![pos]));
+            }
+        }
+
+        compatibility
+    }
+
+    /// Gets the levels the mode can be played at.
+    pub fn playable_levels(&self) -> &[NLevel] {
+        &self.playable
+    }
+
+    /// Gets how many questions the mode has at `level`.
+    pub fn total(&self, level: NLevel) -> usize {
+        self.totals.get(&level).copied().unwrap_or_default()
+    }
+
+    /// Gets how many questions the mode has at `levels` with only `pos`.
+    fn count_of(&self, levels: &[NLevel], pos: PosFilter) -> usize {
+        levels
+            .iter()
+            .filter_map(|&level| self.counts.get(&(level, pos)))
+            .sum()
+    }
+
+    /// Gets the parts of speech the mode has questions of at `levels`, each
+    /// with how many it has.
+    pub fn pos_at(&self, levels: &[NLevel]) -> Vec<(PosFilter, usize)> {
+        PosFilter::iter()
+            .map(|pos| (pos, self.count_of(levels, pos)))
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    /// Whether picking parts of speech changes the questions of the mode.
+    /// Modes that aren't based on the words of the dictionary, e.g. kana
+    /// charts, have as many questions of each as of all of them.
+    pub fn uses_pos(&self) -> bool {
+        self.counts
+            .iter()
+            .any(|((level, _), &count)| count != self.total(*level))
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use jplearnbot::{
+    conjugation::{self, CONJUGABLE, Form},
+    dictionary::{Dictionary, EntryRef, Pos},
+    question::{OPTIONS, shuffle_options},
+};
+use rand::{
+    rngs::StdRng,
+    seq::{IndexedRandom, IteratorRandom, SliceRandom},
+};
+use strum::IntoEnumIterator;
+
+use super::{Filters, Question, QuestionSource, pos_filters_to_pos};
+
+/// Asks for the form of a verb or i-adjective of the dictionary conjugated
+/// into a random [`Form`], e.g. the te-form of 書く.
+pub struct ConjugationSource {
+    dictionary: Arc<Dictionary>,
+}
+
+impl ConjugationSource {
+    pub fn new(dictionary: Arc<Dictionary>) -> Self {
+        ConjugationSource { dictionary }
+    }
+}
+
+impl QuestionSource for ConjugationSource {
+    fn name(&self) -> &str {
+        "活用 ▶ Conjugation"
+    }
+
+    fn questions(
+        &self,
+        filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        let dictionary = Arc::clone(&self.dictionary);
+
+        let pos: Vec<_> = pos_filters_to_pos(&filters.pos)
+            .into_iter()
+            .filter(|p| CONJUGABLE.contains(p))
+            .collect();
+        let mut pool: Vec<_> = dictionary
+            .filter(&filters.levels, &pos)
+            .filter(|e| filters.allows(e.id()))
+            .filter(|&e| conjugable(e, &pos).is_some())
+            .map(|e| e.id())
+            .collect();
+        pool.shuffle(&mut rng);
+
+        Box::new(pool.into_iter().map(move |id| {
+            let entry = dictionary.entry(id).expect("Pool should only have entries");
+            let (word, reading, pos) = conjugable(entry, &pos).expect("Pool should be conjugable");
+
+            let forms: Vec<_> = Form::iter()
+                .filter(|&f| conjugation::conjugate(&word, pos, f).is_some())
+                .collect();
+            let form = *forms.choose(&mut rng).unwrap();
+            let answer = conjugation::conjugate(&word, pos, form).unwrap_or_default();
+
+            // Conjugating as the other kind of る verb is the likeliest
+            // mistake, so it is preferred over the word's other forms.
+            let mistaken = match pos {
+                Pos::V1 => conjugation::conjugate(&word, Pos::V5r, form),
+                Pos::V5r => conjugation::conjugate(&word, Pos::V1, form),
+                _ => None,
+            };
+            let mut distractors: Vec<_> = mistaken.into_iter().filter(|m| *m != answer).collect();
+            // Forms can be spelled alike, e.g. the potential and passive of
+            // ichidan verbs.
+            let mut others: Vec<_> = forms
+                .iter()
+                .filter_map(|&f| conjugation::conjugate(&word, pos, f))
+                .filter(|o| *o != answer && !distractors.contains(o))
+                .collect();
+            others.sort_unstable();
+            others.dedup();
+            let others = others
+                .into_iter()
+                .choose_multiple(&mut rng, OPTIONS - 1 - distractors.len());
+            distractors.extend(others);
+
+            let mut options = std::array::from_fn(|_| answer.clone());
+            for (option, distractor) in options[1..].iter_mut().zip(distractors) {
+                *option = distractor;
+            }
+            let answer = shuffle_options(&mut options, &mut rng);
+
+            let note = (reading != word)
+                .then(|| conjugation::conjugate(&reading, pos, form))
+                .flatten()
+                .map(|r| format!("Reading ・ 読み: {r}"));
+
+            Question {
+                prompt: format!("{word} ▶ {form}"),
+                answer,
+                options,
+                entry: Some(Arc::new(entry.to_entry())),
+                sense: None,
+                mode: None,
+                spoken: false,
+                note,
+                single_kana: false,
+            }
+        }))
+    }
+}
+
+/// Gets how `entry` is written, read, and conjugated, as the first of its
+/// parts of speech among `pos` that it can be conjugated as. It is written
+/// with its first kanji spelling, if any.
+///
+/// Returns [`None`] if it can't be conjugated as any of them.
+fn conjugable(entry: EntryRef<'_>, pos: &[Pos]) -> Option<(String, String, Pos)> {
+    let kanji = entry.kanjis().next().map(|k| k.text());
+    let reading = entry
+        .readings()
+        .find(|r| kanji.is_none_or(|k| r.applies_to(k)))?
+        .text();
+    let word = kanji.unwrap_or(reading);
+
+    let pos = entry
+        .senses()
+        .flat_map(|s| s.pos().iter().copied())
+        .filter(|p| pos.contains(p))
+        .find(|&p| conjugation::conjugate(word, p, Form::Past).is_some())?;
+
+    Some((word.to_string(), reading.to_string(), pos))
+}
@@ -0,0 +1,66 @@
+use jplearnbot::{counter::COUNTERS, question::shuffle_options};
+use rand::{
+    Rng,
+    rngs::StdRng,
+    seq::{IndexedRandom, IteratorRandom},
+};
+
+use super::{Filters, Question, QuestionSource};
+
+/// Asks for the hiragana reading of counting something with a counter word.
+pub struct CountersSource;
+
+impl CountersSource {
+    /// How many questions are asked per session.
+    const ROUNDS: usize = 20;
+}
+
+impl QuestionSource for CountersSource {
+    fn name(&self) -> &str {
+        "Counter ▶ ひらがな"
+    }
+
+    fn questions(
+        &self,
+        _filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        Box::new((0..Self::ROUNDS).map(move |_| {
+            let counter = COUNTERS.choose(&mut rng).unwrap();
+            let n = rng.random_range(1..=10);
+            let reading = counter.reading(n).unwrap();
+
+            // Distractors are the same count with other counters, or
+            // other counts with the same counter.
+            let mut candidates: Vec<_> = COUNTERS
+                .iter()
+                .filter_map(|c| c.reading(n))
+                .chain(counter.readings)
+                .filter(|&r| r != reading)
+                .collect();
+            candidates.sort_unstable();
+            candidates.dedup();
+
+            let mut options = std::array::from_fn(|_| reading.to_string());
+            for (option, candidate) in options[1..]
+                .iter_mut()
+                .zip(candidates.into_iter().choose_multiple(&mut rng, 4))
+            {
+                *option = candidate.to_string();
+            }
+            let answer = shuffle_options(&mut options, &mut rng);
+
+            Question {
+                prompt: format!("{n}{}", counter.kanji),
+                answer,
+                options,
+                entry: None,
+                sense: None,
+                mode: None,
+                spoken: false,
+                note: None,
+                single_kana: false,
+            }
+        }))
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::{Arc, LazyLock};
+
+use jplearnbot::{
+    grammar::{self, GrammarPoint},
+    question::{OPTIONS, shuffle_options},
+};
+use rand::{
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+};
+
+use super::{Filters, Question, QuestionSource};
+
+/// Loads the grammar points generated by dict_combine that are bundled with the
+/// bot, once for every bot the process runs.
+pub fn bundled_grammar() -> Arc<Vec<GrammarPoint>> {
+    static GRAMMAR: LazyLock<Arc<Vec<GrammarPoint>>> = LazyLock::new(|| {
+        static GRAMMAR_FILE: &[u8] = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/content/grammar.jsonl"
+        ));
+
+        grammar::read_grammar(GRAMMAR_FILE)
+            .expect("failed to deserialize grammar points")
+            .into()
+    });
+
+    Arc::clone(&GRAMMAR)
+}
+
+/// Asks for the meaning of JLPT grammar points, separately from the words of
+/// the dictionary.
+pub struct GrammarSource {
+    points: Arc<Vec<GrammarPoint>>,
+}
+
+impl GrammarSource {
+    /// Name of the game mode, which `/grammar` starts.
+    pub const NAME: &str = "文法 ▶ English";
+
+    pub fn new(points: Arc<Vec<GrammarPoint>>) -> Self {
+        GrammarSource { points }
+    }
+}
+
+impl QuestionSource for GrammarSource {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn questions(
+        &self,
+        filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        let points = Arc::clone(&self.points);
+
+        let mut pool: Vec<_> = (0..points.len())
+            .filter(|&i| filters.levels.contains(&points[i].level))
+            .collect();
+        pool.shuffle(&mut rng);
+
+        Box::new(pool.into_iter().map(move |i| {
+            let point = &points[i];
+
+            // Distractors are the meanings of other points of the same level,
+            // which are the likeliest to be confused, or any level if too few.
+            let others = || points.iter().filter(|p| p.meaning != point.meaning);
+            let mut distractors: Vec<_> = others()
+                .filter(|p| p.level == point.level)
+                .choose_multiple(&mut rng, OPTIONS - 1);
+            if distractors.len() < OPTIONS - 1 {
+                let more = others()
+                    .filter(|p| p.level != point.level)
+                    .choose_multiple(&mut rng, OPTIONS - 1 - distractors.len());
+                distractors.extend(more);
+            }
+
+            let mut options = std::array::from_fn(|_| point.meaning.clone());
+            for (option, distractor) in options[1..].iter_mut().zip(distractors) {
+                *option = distractor.meaning.clone();
+            }
+            let answer = shuffle_options(&mut options, &mut rng);
+
+            Question {
+                prompt: point.pattern.clone(),
+                answer,
+                options,
+                entry: None,
+                sense: None,
+                mode: None,
+                spoken: false,
+                note: Some(format!("Example ・ 例文: {}", point.example)),
+                single_kana: false,
+            }
+        }))
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use jplearnbot::{
+    dictionary::Dictionary,
+    quiz::{self, Mode, QuestionBuilder},
+};
+use rand::rngs::StdRng;
+
+use crate::perf::{self, Stage};
+
+use super::{Filters, Question, QuestionSource, pos_filters_to_pos};
+
+/// Asks questions about the words of the dictionary in one of the [`Mode`]'s.
+pub struct JmdictSource {
+    mode: Mode,
+    name: String,
+    dictionary: Arc<Dictionary>,
+}
+
+impl JmdictSource {
+    pub fn new(mode: Mode, dictionary: Arc<Dictionary>) -> Self {
+        JmdictSource {
+            mode,
+            name: mode.to_string(),
+            dictionary,
+        }
+    }
+}
+
+impl QuestionSource for JmdictSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn questions(
+        &self,
+        filters: &Filters,
+        rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        let mode = self.mode;
+        let dictionary = Arc::clone(&self.dictionary);
+
+        let mut builder = QuestionBuilder::new(Arc::clone(&dictionary))
+            .mode(mode)
+            .levels(filters.levels.iter().copied())
+            .pos(pos_filters_to_pos(&filters.pos))
+            .distractors(filters.distractors)
+            .exclude(filters.excluded.iter().copied())
+            .rng(rng);
+        if let Some(ids) = &filters.entries {
+            builder = builder.entries(ids.iter().copied());
+        }
+
+        let mut questions = perf::time(Stage::Sampling, || builder.build());
+        let adaptive = filters.adaptive.clone();
+
+        Box::new((0..questions.len()).map(move |_| {
+            perf::time(Stage::Generation, || {
+                // Adaptive games draw each word near the players' ability as
+                // it is once the previous question was answered.
+                let question = match &adaptive {
+                    Some(adaptive) => {
+                        let ability = adaptive.ability();
+                        questions.next_weighted(|entry| adaptive.weight(ability, entry))
+                    }
+                    None => questions.next(),
+                }
+                .expect("Pool should have a question left");
+                Question::from_quiz(question, mode, &dictionary)
+            })
+        }))
+    }
+}
+
+impl Question {
+    /// Converts `question`, generated by a source in `mode`, to a game question.
+    fn from_quiz(question: quiz::Question, mode: Mode, dictionary: &Dictionary) -> Self {
+        Question {
+            prompt: question.prompt,
+            options: question.options,
+            answer: question.answer,
+            entry: dictionary
+                .entry(question.entry)
+                .map(|entry| Arc::new(entry.to_entry())),
+            sense: question.sense,
+            mode: (question.mode != mode).then(|| question.mode.to_string()),
+            spoken: question.mode.is_spoken(),
+            note: None,
+            single_kana: false,
+        }
+    }
+}
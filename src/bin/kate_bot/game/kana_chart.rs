@@ -0,0 +1,89 @@
+use jplearnbot::{
+    kana,
+    question::{OPTIONS, shuffle_options},
+};
+use rand::{
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+};
+
+use super::{Filters, Question, QuestionSource};
+
+/// Asks for the romaji of the kana of the chart, for beginners who can't read
+/// the words of the dictionary yet.
+pub struct KanaSource {
+    /// Whether the kana are asked in katakana instead of hiragana.
+    katakana: bool,
+}
+
+impl KanaSource {
+    pub fn hiragana() -> Self {
+        KanaSource { katakana: false }
+    }
+
+    pub fn katakana() -> Self {
+        KanaSource { katakana: true }
+    }
+}
+
+impl QuestionSource for KanaSource {
+    fn name(&self) -> &str {
+        if self.katakana {
+            "カタカナ ▶ Romaji"
+        } else {
+            "ひらがな ▶ Romaji"
+        }
+    }
+
+    fn questions(
+        &self,
+        _filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        let katakana = self.katakana;
+
+        let mut pool: Vec<_> = kana::CHART.iter().collect();
+        pool.shuffle(&mut rng);
+
+        Box::new(pool.into_iter().map(move |&(hiragana, romaji)| {
+            // Distractors are digraphs if the kana is one, so they can't be
+            // told apart by length. Kana read the same, e.g. じ and ぢ, are
+            // left out, as are repeated romaji.
+            let digraph = hiragana.chars().count() > 1;
+            let mut distractors: Vec<&str> = Vec::new();
+            for &(other, other_romaji) in kana::CHART {
+                if (other.chars().count() > 1) == digraph
+                    && other_romaji != romaji
+                    && !distractors.contains(&other_romaji)
+                {
+                    distractors.push(other_romaji);
+                }
+            }
+
+            let mut options: [String; OPTIONS] = std::array::from_fn(|_| romaji.to_string());
+            let picked = distractors
+                .into_iter()
+                .choose_multiple(&mut rng, OPTIONS - 1);
+            for (option, distractor) in options[1..].iter_mut().zip(picked) {
+                *option = distractor.to_string();
+            }
+            let answer = shuffle_options(&mut options, &mut rng);
+
+            Question {
+                prompt: if katakana {
+                    kana::hiragana_to_katakana(hiragana)
+                } else {
+                    hiragana.to_string()
+                },
+                answer,
+                options,
+                entry: None,
+                sense: None,
+                mode: None,
+                spoken: false,
+                note: None,
+                single_kana: true,
+            }
+        }))
+    }
+}
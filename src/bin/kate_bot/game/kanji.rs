@@ -0,0 +1,216 @@
+use std::{
+    collections::HashSet,
+    env,
+    path::PathBuf,
+    sync::{Arc, LazyLock},
+};
+
+use jplearnbot::{
+    kanjidic::{self, KanjiCharacter},
+    open_reader,
+    question::{OPTIONS, shuffle_options},
+};
+use rand::{
+    rngs::StdRng,
+    seq::{IteratorRandom, SliceRandom},
+};
+use strum_macros::EnumIter;
+
+use super::{Filters, Question, QuestionSource};
+
+/// Most readings an option lists.
+const MAX_READINGS: usize = 2;
+
+/// Most meanings an option lists.
+const MAX_MEANINGS: usize = 3;
+
+/// Loads the kanji generated by `dict_combine kanji` from `KANJI_PATH`, by
+/// default content/kanji.jsonl, once for every bot the process runs. Returns
+/// [`None`] if they haven't been generated, since KANJIDIC2 isn't bundled with
+/// the bot.
+pub fn load_kanji() -> Option<Arc<Vec<KanjiCharacter>>> {
+    static KANJI: LazyLock<Option<Arc<Vec<KanjiCharacter>>>> = LazyLock::new(read_kanji);
+
+    KANJI.clone()
+}
+
+/// Reads the kanji [`load_kanji`] loads.
+fn read_kanji() -> Option<Arc<Vec<KanjiCharacter>>> {
+    let path = env::var("KANJI_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("content/kanji.jsonl"));
+    if !path.exists() {
+        eprintln!(
+            "No kanji at {}, kanji modes won't be playable. Generate them with dict_combine kanji",
+            path.display()
+        );
+        return None;
+    }
+
+    match open_reader(&path).and_then(kanjidic::read_kanji) {
+        Ok(kanji) => Some(kanji.into()),
+        Err(e) => {
+            eprintln!("Failed to read the kanji at {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Ways of asking about a single kanji, named as what is shown ▶ what is picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, strum_macros::Display)]
+pub enum KanjiMode {
+    #[strum(to_string = "字 ▶ 音読み")]
+    On,
+    #[strum(to_string = "字 ▶ 訓読み")]
+    Kun,
+    #[strum(to_string = "字 ▶ English")]
+    Meaning,
+}
+
+impl KanjiMode {
+    /// Gets every reading or meaning of `kanji` that answers this mode.
+    fn parts(self, kanji: &KanjiCharacter) -> &[String] {
+        match self {
+            KanjiMode::On => &kanji.on,
+            KanjiMode::Kun => &kanji.kun,
+            KanjiMode::Meaning => &kanji.meanings,
+        }
+    }
+
+    /// Writes the option answering this mode for `kanji`, or [`None`] if it
+    /// has no reading or meaning of this mode.
+    fn option(self, kanji: &KanjiCharacter) -> Option<String> {
+        let parts = self.parts(kanji);
+        let option = match self {
+            KanjiMode::On => parts[..parts.len().min(MAX_READINGS)].join("、"),
+            KanjiMode::Kun => parts
+                .iter()
+                .take(MAX_READINGS)
+                .map(|r| kanjidic::kun_label(r))
+                .collect::<Vec<_>>()
+                .join("、"),
+            KanjiMode::Meaning => parts[..parts.len().min(MAX_MEANINGS)].join(", "),
+        };
+
+        (!option.is_empty()).then_some(option)
+    }
+}
+
+/// Asks about the readings or meanings of single kanji from KANJIDIC2,
+/// separately from the words of the dictionary.
+///
+/// Kanji are picked by their level, or by the school grade they are taught in
+/// if [`Filters::grades`] is set.
+pub struct KanjiSource {
+    mode: KanjiMode,
+    name: String,
+    kanji: Arc<Vec<KanjiCharacter>>,
+}
+
+impl KanjiSource {
+    pub fn new(mode: KanjiMode, kanji: Arc<Vec<KanjiCharacter>>) -> Self {
+        KanjiSource {
+            mode,
+            name: mode.to_string(),
+            kanji,
+        }
+    }
+}
+
+impl QuestionSource for KanjiSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn questions(
+        &self,
+        filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        let mode = self.mode;
+        let kanji = Arc::clone(&self.kanji);
+
+        let is_selected = |k: &KanjiCharacter| {
+            if filters.grades.is_empty() {
+                k.level.is_some_and(|l| filters.levels.contains(&l))
+            } else {
+                k.grade.is_some_and(|g| filters.grades.contains(&g))
+            }
+        };
+        let mut pool: Vec<_> = (0..kanji.len())
+            .filter(|&i| {
+                let k = &kanji[i];
+                k.literal.chars().count() == 1 && is_selected(k) && mode.option(k).is_some()
+            })
+            .collect();
+        pool.shuffle(&mut rng);
+
+        Box::new(pool.into_iter().map(move |i| {
+            let prompt = &kanji[i];
+            let answer = mode.option(prompt).unwrap_or_default();
+
+            // Distractors share no reading or meaning with the kanji, so only
+            // one option is right, and are told apart from each other. Kanji of
+            // the same level and grade are the likeliest to be confused, so
+            // they are preferred.
+            let candidates = |similar: bool| {
+                let mut seen = HashSet::new();
+                kanji
+                    .iter()
+                    .filter(move |k| {
+                        (k.level == prompt.level && k.grade == prompt.grade) == similar
+                            && !mode.parts(k).iter().any(|p| mode.parts(prompt).contains(p))
+                    })
+                    .filter_map(move |k| mode.option(k))
+                    .filter(move |o| seen.insert(o.clone()))
+            };
+            let mut distractors = candidates(true).choose_multiple(&mut rng, OPTIONS - 1);
+            if distractors.len() < OPTIONS - 1 {
+                let more = candidates(false)
+                    .filter(|o| !distractors.contains(o))
+                    .choose_multiple(&mut rng, OPTIONS - 1 - distractors.len());
+                distractors.extend(more);
+            }
+
+            let mut options = std::array::from_fn(|_| answer.clone());
+            for (option, distractor) in options[1..].iter_mut().zip(distractors) {
+                *option = distractor;
+            }
+            let answer = shuffle_options(&mut options, &mut rng);
+
+            Question {
+                prompt: prompt.literal.clone(),
+                answer,
+                options,
+                entry: None,
+                sense: None,
+                mode: None,
+                spoken: false,
+                note: Some(kanji_note(prompt)),
+                single_kana: false,
+            }
+        }))
+    }
+}
+
+/// Lists the readings, meanings, and stroke count of `kanji`, shown along with
+/// the answer.
+fn kanji_note(kanji: &KanjiCharacter) -> String {
+    let kun: Vec<_> = kanji.kun.iter().map(|r| kanjidic::kun_label(r)).collect();
+    let mut lines = Vec::new();
+
+    if !kanji.on.is_empty() {
+        lines.push(format!("On ・ 音読み: {}", kanji.on.join("、")));
+    }
+    if !kun.is_empty() {
+        lines.push(format!("Kun ・ 訓読み: {}", kun.join("、")));
+    }
+    if !kanji.meanings.is_empty() {
+        lines.push(format!("Meaning ・ 意味: {}", kanji.meanings.join(", ")));
+    }
+    if let Some(strokes) = kanji.strokes {
+        lines.push(format!("Strokes ・ 画数: {strokes}"));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,248 @@
+use std::{collections::HashMap, time::Duration};
+
+use jplearnbot::scoring::Handicap;
+use poise::serenity_prelude::{
+    ButtonStyle, ChannelId, ComponentInteraction, ComponentInteractionDataKind, CreateActionRow,
+    CreateButton, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage, CreateSelectMenu, CreateSelectMenuKind, EditMessage, UserId, http::Http,
+};
+use tokio::{sync::mpsc::Receiver, time::Instant};
+
+use super::{
+    GameMessage, InteractionExitReason, SessionId,
+    menu::{game_message, wait_while_paused},
+};
+
+/// How long players can join a game before its first question.
+pub const JOIN_PHASE: Duration = Duration::from_secs(30);
+
+/// Handicaps the starter can give players during the join phase, along with
+/// the suffix of the custom id of the menu picking who gets them and their label.
+const HANDICAPS: [(&str, &str, Handicap); 2] = [
+    (
+        "deadline",
+        "⏱ Must answer within 5s ・ 5秒以内に回答",
+        Handicap::Deadline(Duration::from_secs(5)),
+    ),
+    (
+        "half",
+        "½ Half points in races ・ 早押しで得点半分",
+        Handicap::Weight(0.5),
+    ),
+];
+
+/// Most players a menu can give a handicap to at once.
+const MAX_HANDICAPPED: u8 = 25;
+
+/// Creates the button that joins `session_id`'s game.
+pub fn join_button(session_id: SessionId) -> CreateButton {
+    CreateButton::new(format!("{session_id},join"))
+        .label("✋ Join ・ 参加")
+        .style(ButtonStyle::Primary)
+}
+
+/// Determines whether `custom_id` is the custom id of a join button.
+pub fn is_join_button(custom_id: &str) -> bool {
+    custom_id.ends_with(",join")
+}
+
+/// Players who joined a game during its join phase.
+#[derive(Debug, Default)]
+pub struct Joined {
+    /// The players in the order they joined.
+    pub players: Vec<UserId>,
+    /// The handicaps the starter gave players.
+    pub handicaps: HashMap<UserId, Handicap>,
+}
+
+/// Runs the join phase of `session_id`'s game: posts a join button explaining
+/// `rules`, and registers the players pressing it until [`JOIN_PHASE`] passes.
+/// The `starter` is registered first, if known, and can give players one of
+/// the [`HANDICAPS`], which also registers them. Other interactions are
+/// ignored, since no question was asked yet. The join phase is extended by how
+/// long the game is paused, if it is. The button is disabled afterwards unless
+/// players can `join_later`.
+///
+/// Returns [`InteractionExitReason::CloseRequest`] or
+/// [`InteractionExitReason::Shutdown`] if the game stops before then.
+pub(super) async fn join_phase(
+    http: &Http,
+    session_id: SessionId,
+    rx: &mut Receiver<GameMessage>,
+    starter: Option<UserId>,
+    rules: &str,
+    join_later: bool,
+) -> Result<Joined, InteractionExitReason> {
+    let mut joined = Joined {
+        players: starter.into_iter().collect(),
+        handicaps: HashMap::new(),
+    };
+    let mut deadline = Instant::now() + JOIN_PHASE;
+
+    let mut description = format!(
+        "{rules}\nThe first question is asked in {} seconds",
+        JOIN_PHASE.as_secs()
+    );
+    let mut components = vec![CreateActionRow::Buttons(vec![join_button(session_id)])];
+    // Handicaps are given by the starter, who isn't known for games resumed
+    // from before starters were saved.
+    if starter.is_some() {
+        description.push_str("\nThe starter can give players a handicap below");
+        components.extend(HANDICAPS.iter().map(|&(suffix, label, _)| {
+            CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    format!("{session_id},handicap,{suffix}"),
+                    CreateSelectMenuKind::User {
+                        default_users: None,
+                    },
+                )
+                .placeholder(label)
+                .min_values(0)
+                .max_values(MAX_HANDICAPPED),
+            )
+        }));
+    }
+    let mut message = ChannelId::new(session_id.channel)
+        .send_message(
+            http,
+            CreateMessage::new()
+                .embed(
+                    CreateEmbed::new()
+                        .title("Join the game ・ ゲームに参加")
+                        .description(&description),
+                )
+                .components(components),
+        )
+        .await
+        .map_err(|_| InteractionExitReason::NetworkError)?;
+
+    loop {
+        let ci = match game_message(rx, deadline).await {
+            Ok(GameMessage::Interaction(ci)) => ci,
+            Ok(GameMessage::Pause(user_id)) => {
+                let channel_id = ChannelId::new(session_id.channel);
+                deadline += wait_while_paused(http, rx, channel_id, user_id).await?;
+                continue;
+            }
+            Ok(_) => continue,
+            Err(InteractionExitReason::Timeout) => break,
+            Err(reason) => return Err(reason),
+        };
+        if is_join_button(&ci.data.custom_id) {
+            respond_to_join(http, &ci, &mut joined.players).await?;
+        } else {
+            give_handicaps(http, &ci, starter, &mut joined).await?;
+        }
+    }
+
+    // The handicaps are listed for everyone once they can't change anymore.
+    let mut embed = CreateEmbed::new()
+        .title("Join the game ・ ゲームに参加")
+        .description(description);
+    let mut handicapped: Vec<_> = joined.handicaps.iter().collect();
+    handicapped.sort_by_key(|&(&user_id, _)| user_id);
+    if !handicapped.is_empty() {
+        let list = handicapped
+            .into_iter()
+            .map(|(user_id, &handicap)| format!("<@{user_id}>: {}", handicap_label(handicap)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field("Handicaps ・ ハンデ", list, false);
+    }
+    message
+        .edit(
+            http,
+            EditMessage::new()
+                .embed(embed)
+                .components(vec![CreateActionRow::Buttons(vec![
+                    join_button(session_id).disabled(!join_later),
+                ])]),
+        )
+        .await
+        .ok();
+
+    Ok(joined)
+}
+
+/// Gives the players picked in `ci`, a choice of the starter in one of the
+/// handicap menus, its handicap, and registers them. Players who had it but
+/// weren't picked lose it. Other players can't give handicaps.
+async fn give_handicaps(
+    http: &Http,
+    ci: &ComponentInteraction,
+    starter: Option<UserId>,
+    joined: &mut Joined,
+) -> Result<(), InteractionExitReason> {
+    let Some((_, suffix)) = ci.data.custom_id.rsplit_once(",handicap,") else {
+        return Ok(());
+    };
+    let Some(&(_, label, handicap)) = HANDICAPS.iter().find(|(s, ..)| *s == suffix) else {
+        return Ok(());
+    };
+    let ComponentInteractionDataKind::UserSelect { values } = &ci.data.kind else {
+        return Ok(());
+    };
+
+    let content = if starter != Some(ci.user.id) {
+        "Only the player who started the game can give handicaps".to_string()
+    } else {
+        joined.handicaps.retain(|_, &mut h| h != handicap);
+        for &user_id in values {
+            joined.handicaps.insert(user_id, handicap);
+            if !joined.players.contains(&user_id) {
+                joined.players.push(user_id);
+            }
+        }
+        if values.is_empty() {
+            format!("Nobody has the handicap {label}")
+        } else {
+            let players: Vec<_> = values.iter().map(|u| format!("<@{u}>")).collect();
+            format!("{} have the handicap {label}", players.join(", "))
+        }
+    };
+
+    ci.create_response(
+        http,
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(true),
+        ),
+    )
+    .await
+    .map_err(|_| InteractionExitReason::NetworkError)
+}
+
+/// Names `handicap` as it is shown to players.
+fn handicap_label(handicap: Handicap) -> &'static str {
+    HANDICAPS
+        .iter()
+        .find(|&&(.., h)| h == handicap)
+        .map_or("None", |&(_, label, _)| label)
+}
+
+/// Adds the player of `ci`, a press of the join button, to the end of `players`
+/// unless they already joined, and privately tells them where they are in it.
+pub(super) async fn respond_to_join(
+    http: &Http,
+    ci: &ComponentInteraction,
+    players: &mut Vec<UserId>,
+) -> Result<(), InteractionExitReason> {
+    let content = if players.contains(&ci.user.id) {
+        "You've already joined".to_string()
+    } else {
+        players.push(ci.user.id);
+        format!("You joined as player {}", players.len())
+    };
+
+    ci.create_response(
+        http,
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(true),
+        ),
+    )
+    .await
+    .map_err(|_| InteractionExitReason::NetworkError)
+}
@@ -0,0 +1,1847 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use jplearnbot::{
+    dictionary::{DictEntry, NLevel},
+    kana,
+    scoring::{self, Handicap},
+};
+use poise::serenity_prelude::{
+    self as serenity, ButtonStyle, ChannelId, ComponentInteraction, CreateActionRow,
+    CreateAttachment, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateMessage, EditMessage, GuildId, Message, UserId,
+    http::Http,
+};
+use rand::{
+    Rng,
+    seq::{IndexedRandom, SliceRandom},
+};
+use regex::Regex;
+use tokio::{
+    sync::mpsc::Receiver,
+    time::{Instant, timeout_at},
+};
+
+use crate::{
+    audio::{self, Speech, TtsBackend},
+    command,
+    db::{Database, Mnemonic},
+    dictionary,
+    emote::{self, Emote, EmoteSet},
+    feedback::{self, MessagePacks},
+    format::{self, TimestampStyle},
+    image,
+    perf::{self, Stage},
+};
+
+use super::{
+    GameMessage, GameOptions, InteractionExitReason, KanjiTyping, Question, Resolution,
+    RoundRecord, Team, Teams, Turns, describe_prompt, lobby,
+};
+
+/// How long players can still answer after the last new answer in a
+/// [`Resolution::EveryoneOnce`] round.
+const ANSWER_WINDOW: Duration = Duration::from_secs(20);
+
+/// How long players can answer a [`Resolution::FixedTimer`] round.
+const ROUND_TIMER: Duration = Duration::from_secs(30);
+
+/// How long the player whose turn it is can answer a [`Resolution::Turns`]
+/// round, unless [`GameOptions::time_limit`] is set.
+const TURN_TIMER: Duration = Duration::from_secs(20);
+
+/// How long a team can't answer after a player of the other team answers wrong
+/// in a [team game](GameOptions::teams).
+const TEAM_LOCKOUT: Duration = Duration::from_secs(5);
+
+/// Points of answers made after a hint was used are multiplied by this.
+const HINT_WEIGHT: f64 = 0.5;
+
+/// How many wrong options a hint rules out.
+const HINT_ELIMINATIONS: usize = 2;
+
+/// How many players have to vote to skip a question, unless
+/// [`GameOptions::skip_votes`] is set.
+const SKIP_VOTES: usize = 3;
+
+/// Longest description of the word of a question shown once it is answered,
+/// leaving room within Discord's limit on embed descriptions for the rest.
+const MAX_DETAILS_LEN: usize = 2500;
+
+/// How long a question waits for any interaction before the game is stopped.
+const INACTIVITY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long a game can stay paused before it is stopped.
+const PAUSE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Manages the components of a game question.
+pub struct Menu<'a> {
+    id: String,
+    prompt: String,
+    /// Whether [`Self::prompt`] is read aloud instead of shown.
+    spoken: bool,
+    /// Shown along with the answer once it is revealed, if any.
+    note: Option<String>,
+    /// Whether [`Self::prompt`] is a single kana, drawn large.
+    single_kana: bool,
+    questions: Vec<QuestionComponent>,
+    answer: usize,
+    entry: Option<Arc<DictEntry>>,
+    /// All the levels of [`Self::entry`]. Empty if the question wasn't made
+    /// from a dictionary entry.
+    levels: Vec<NLevel>,
+    /// Estimated difficulty of [`Self::entry`] in stars, if known.
+    difficulty: Option<u8>,
+    /// Number of the sense of [`Self::entry`] the question asks about, if any.
+    sense: Option<u32>,
+    /// Name of the mode the question is asked in. Set to the name of the game's
+    /// mode when sent if the question doesn't have its own.
+    mode: Option<String>,
+    options: GameOptions,
+    /// The Server the question was sent in, or [`None`] if it wasn't sent yet.
+    guild_id: Option<GuildId>,
+    /// The sent question message.
+    message: Option<Message>,
+    /// When the question message was sent.
+    sent_at: Option<Instant>,
+    /// Players who answered correctly with the points they scored for the game,
+    /// in the order they answered.
+    correct: Vec<(UserId, usize)>,
+    /// Whether each player's first answer was correct, in the order they answered.
+    answers: Vec<(UserId, bool)>,
+    http: &'a Http,
+    db: &'a Database,
+    /// Messages telling players their answers are wrong, of the bot asking.
+    feedback: &'a MessagePacks,
+    /// Service reading spoken prompts aloud. Without one, they are shown instead.
+    tts: Option<&'a dyn TtsBackend>,
+    /// The teams of the game, if it is a [team game](GameOptions::teams).
+    teams: Option<&'a mut Teams>,
+    /// The players taking turns, if it is a [turn-based game](Resolution::Turns).
+    turns: Option<&'a mut Turns>,
+    /// The handicaps the starter gave players during the join phase.
+    handicaps: &'a HashMap<UserId, Handicap>,
+    /// The team that can't answer, and until when, after the other team answered wrong.
+    locked: Option<(Team, Instant)>,
+    /// Whether a hint was used this round, which lowers the points of answers.
+    hinted: bool,
+    /// The player who started the game, who can re-roll the question. Games
+    /// resumed from before starters were saved can't be re-rolled.
+    starter: Option<UserId>,
+    /// Whether the starter re-rolled the question, skipping this round.
+    rerolled: bool,
+    /// Players who voted to skip the question.
+    skip_votes: HashSet<UserId>,
+    /// Whether the question was skipped by vote or by the starter, which
+    /// reveals the answer and ends the round.
+    skipped: bool,
+}
+
+/// Contains data on a game button.
+struct QuestionComponent {
+    /// The component's unique identifier.
+    id: String,
+    /// Possible translation text.
+    text: String,
+    /// Whether this button should be disabled.
+    disabled: bool,
+}
+
+impl<'a> Menu<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        http: &'a Http,
+        db: &'a Database,
+        feedback: &'a MessagePacks,
+        tts: Option<&'a dyn TtsBackend>,
+        teams: Option<&'a mut Teams>,
+        turns: Option<&'a mut Turns>,
+        handicaps: &'a HashMap<UserId, Handicap>,
+        id: String,
+        question: Question,
+        options: GameOptions,
+        starter: Option<UserId>,
+    ) -> Self {
+        let questions = question
+            .options
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| QuestionComponent {
+                id: format!("{id},{i}"),
+                text,
+                disabled: false,
+            })
+            .collect();
+        let levels = question
+            .entry
+            .as_ref()
+            .map(|entry| entry.levels())
+            .unwrap_or_default();
+        let difficulty = question.entry.as_ref().and_then(|entry| entry.difficulty());
+
+        Menu {
+            id,
+            prompt: question.prompt,
+            spoken: question.spoken,
+            note: question.note,
+            single_kana: question.single_kana,
+            questions,
+            answer: question.answer,
+            entry: question.entry,
+            levels,
+            difficulty,
+            sense: question.sense,
+            mode: question.mode,
+            options,
+            guild_id: None,
+            message: None,
+            sent_at: None,
+            correct: Vec::new(),
+            answers: Vec::new(),
+            http,
+            db,
+            feedback,
+            tts,
+            teams,
+            turns,
+            handicaps,
+            locked: None,
+            hinted: false,
+            starter,
+            rerolled: false,
+            skip_votes: HashSet::new(),
+            skipped: false,
+        }
+    }
+
+    /// Identifier of the button that shows a player their own answer layout
+    /// when [`GameOptions::shuffle_per_player`] is set.
+    fn open_id(&self) -> String {
+        format!("{},open", self.id)
+    }
+
+    /// Identifier of the button that gives a hint.
+    fn hint_id(&self) -> String {
+        format!("{},hint", self.id)
+    }
+
+    /// Identifier of the button that re-rolls the question.
+    fn reroll_id(&self) -> String {
+        format!("{},reroll", self.id)
+    }
+
+    /// Identifier of the button that votes to skip the question.
+    fn skip_id(&self) -> String {
+        format!("{},skip", self.id)
+    }
+
+    /// Sends the question to `channel_id` of `guild_id`.
+    pub async fn send(
+        &mut self,
+        channel_id: ChannelId,
+        guild_id: GuildId,
+        round: usize,
+        mode: &str,
+    ) -> serenity::Result<()> {
+        self.mode.get_or_insert_with(|| mode.to_string());
+        self.guild_id = Some(guild_id);
+
+        let speech = self.speech().await;
+        let message = self.create_message(round, mode, speech.is_some());
+        let files = match speech {
+            Some(speech) => vec![CreateAttachment::bytes(
+                speech.bytes,
+                format!("prompt.{}", speech.extension),
+            )],
+            None => self.create_files(guild_id),
+        };
+
+        let message = perf::time_async(
+            Stage::Send,
+            channel_id.send_files(self.http, files, message),
+        )
+        .await?;
+        self.message = Some(message);
+        self.sent_at = Some(Instant::now());
+
+        Ok(())
+    }
+
+    /// Gets the players who answered correctly with the points they scored for
+    /// the game, in the order they answered. Correct answers score a point, or
+    /// more the faster they were in [`Resolution::Race`] rounds.
+    pub fn round_points(&self) -> &[(UserId, usize)] {
+        &self.correct
+    }
+
+    /// Gets whether each player's first answer was correct, in the order they answered.
+    pub fn first_answers(&self) -> &[(UserId, bool)] {
+        &self.answers
+    }
+
+    /// Estimated difficulty in stars of the word the question is about, if known.
+    pub fn difficulty(&self) -> Option<u8> {
+        self.difficulty
+    }
+
+    /// Whether the starter re-rolled the question, so the round is skipped
+    /// rather than missed.
+    pub fn rerolled(&self) -> bool {
+        self.rerolled
+    }
+
+    /// Disables the answer buttons of the sent question message, e.g. when the
+    /// game stops before the round ends.
+    pub async fn close(&mut self) -> serenity::Result<()> {
+        self.questions.iter_mut().for_each(|q| q.disabled = true);
+        self.update_message().await
+    }
+
+    /// Updates the components of the sent question message.
+    async fn update_message(&mut self) -> serenity::Result<()> {
+        let components = self.create_components();
+
+        if let Some(message) = &mut self.message {
+            message
+                .edit(self.http, EditMessage::new().components(components))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Labels the levels and difficulty of the question, e.g.
+    /// `[N3/N4] ★★☆☆☆`. [`None`] if they are unknown.
+    fn difficulty_label(&self) -> Option<String> {
+        const MAX_STARS: usize = 5;
+
+        let stars = usize::from(self.difficulty?).min(MAX_STARS);
+        let levels: Vec<_> = self.levels.iter().map(NLevel::to_string).collect();
+
+        Some(format!(
+            "[{}] {}{}",
+            levels.join("/"),
+            "★".repeat(stars),
+            "☆".repeat(MAX_STARS - stars)
+        ))
+    }
+
+    /// Creates a record of the question asked by this menu. The question's own mode
+    /// is recorded in place of `mode` if it has one.
+    pub fn record(&self, mode: &str) -> RoundRecord {
+        RoundRecord {
+            id: self.id.clone(),
+            entry_id: self.entry.as_ref().map(|entry| entry.id),
+            prompt: self.prompt.clone(),
+            spoken: self.spoken,
+            options: self.questions.iter().map(|q| q.text.clone()).collect(),
+            answer: self.answer,
+            mode: self.mode.as_deref().unwrap_or(mode).to_string(),
+            answers: self.answers.clone(),
+        }
+    }
+
+    /// Equivalent to [`Self::questions`]\[\].[`id`]
+    fn answer_id(&self) -> &str {
+        &self.questions[self.answer].id
+    }
+
+    /// Synthesizes the prompt into speech if it is spoken. [`None`] if it isn't
+    /// or can't be synthesized, in which case it is shown instead.
+    async fn speech(&self) -> Option<Speech> {
+        if !self.spoken {
+            return None;
+        }
+
+        audio::speak(self.tts?, &self.prompt)
+            .await
+            .inspect_err(|e| eprintln!("Failed to synthesize {}: {e}", self.prompt))
+            .ok()
+    }
+
+    /// Gets the prompt to read aloud in the voice channel of the game, if it can
+    /// be read without giving away the answer, e.g. the reading of a kanji
+    /// prompt. English prompts aren't read, since the voice speaks Japanese.
+    #[cfg(feature = "voice")]
+    pub fn voice_prompt(&self) -> Option<&str> {
+        let japanese = !self.prompt.is_ascii();
+        // Reading kana aloud gives away their romaji too, as does reading a
+        // kanji aloud its readings, however they are listed.
+        let answer: String = self.questions[self.answer]
+            .text
+            .chars()
+            .filter(|c| !"、()〜".contains(*c))
+            .collect();
+        let gives_away =
+            kana::is_hiragana(&kana::katakana_to_hiragana(&answer)) || self.single_kana;
+
+        (self.spoken || (japanese && !gives_away)).then_some(&self.prompt)
+    }
+
+    /// Draws the prompt in the font of `guild_id`.
+    fn create_files(&self, guild_id: GuildId) -> Vec<CreateAttachment> {
+        let image = if self.single_kana {
+            image::kana_to_image(&self.prompt, Some(guild_id))
+        } else {
+            image::text_to_image(&self.prompt, Some(guild_id))
+        };
+
+        let description = describe_prompt(
+            &self.prompt,
+            self.entry.as_deref(),
+            self.sense,
+            !self.options.hard_mode,
+        );
+
+        vec![CreateAttachment::bytes(image, "prompt.png").description(description)]
+    }
+
+    /// Creates the question message. The question's own mode is shown
+    /// in place of `mode` if it has one. The question's difficulty is shown
+    /// unless [`GameOptions::hard_mode`] is set, and practice questions are labeled.
+    /// If `heard`, the prompt is attached as speech rather than an image, and
+    /// the voice is credited.
+    fn create_message(&self, round: usize, mode: &str, heard: bool) -> CreateMessage {
+        let title = if self.options.practice {
+            format!("Question {round} ・ Practice 練習")
+        } else {
+            format!("Question {round}")
+        };
+        let mode = self.mode.as_deref().unwrap_or(mode);
+        let mut embed = CreateEmbed::new().title(title).field(mode, "", false);
+        if !heard {
+            embed = embed.attachment("prompt.png");
+        }
+        if !self.options.hard_mode
+            && let Some(label) = self.difficulty_label()
+        {
+            embed = embed.footer(CreateEmbedFooter::new(label));
+        }
+
+        let mut description = Vec::new();
+        if heard {
+            description.push("🔊 Play the attached audio ・ 音声を再生してください");
+            if let Some(attribution) = self.tts.and_then(|tts| tts.attribution()) {
+                description.push(attribution);
+            }
+        }
+        if self.is_typed() {
+            description.push(
+                "Type the answer in kana or romaji ・ かなかローマ字で答えを入力してください",
+            );
+        }
+        if !description.is_empty() {
+            embed = embed.description(description.join("\n"));
+        }
+
+        let mut message = CreateMessage::new()
+            .embed(embed)
+            .components(self.create_components());
+        if let Some(player) = self.turns.as_deref().and_then(Turns::current) {
+            message = message.content(format!("<@{player}>, it's your turn ・ あなたの番です"));
+        }
+
+        message
+    }
+
+    /// Determines whether players only get one try at the question, i.e. it is a
+    /// [race](Resolution::Race) round or a [team game](GameOptions::teams).
+    fn is_one_try(&self) -> bool {
+        self.options.resolution == Resolution::Race || self.options.teams
+    }
+
+    /// Determines whether the question is answered by typing, i.e. [`GameOptions::typed`]
+    /// is set, it isn't a team or turn-based game, and the answer is hiragana or
+    /// katakana, or is a word spelled with kanji and [`GameOptions::kanji_typing`]
+    /// has it typed.
+    fn is_typed(&self) -> bool {
+        let answer = &self.questions[self.answer].text;
+
+        self.options.typed
+            && !self.options.teams
+            && self.turns.is_none()
+            && (kana::is_kana(answer)
+                || (self.options.kanji_typing != KanjiTyping::Buttons
+                    && self.entry.is_some()
+                    && kana::has_kanji(answer)))
+    }
+
+    /// Gets the emotes of the Server the question was sent in.
+    fn emotes(&self) -> Arc<EmoteSet> {
+        emote::emotes_of(self.guild_id)
+    }
+
+    /// Creates a message telling `user_id` their answer `choice` is wrong, in the
+    /// tone and language the Server picked with `/settings feedback`, from the
+    /// messages of the bot asking.
+    fn wrong_answer_message<R: Rng + ?Sized>(
+        &self,
+        user_id: UserId,
+        choice: &str,
+        rng: &mut R,
+    ) -> String {
+        let settings = self
+            .guild_id
+            .and_then(|guild_id| self.db.guild_feedback(guild_id).ok().flatten())
+            .unwrap_or_default();
+
+        feedback::wrong_answer(
+            self.feedback,
+            settings,
+            &self.emotes(),
+            user_id,
+            choice,
+            rng,
+        )
+    }
+
+    /// Checks `text`, a message typed in the channel, as an answer to the question.
+    /// Kana answers accept hiragana, katakana, or romaji within the game's
+    /// [`Tolerance`](jplearnbot::kana::Tolerance). Kanji answers accept the answer
+    /// as spelled, or with [`KanjiTyping::AnyOkurigana`], any spelling of its word
+    /// with the same kanji.
+    ///
+    /// Wrong romaji only counts as a guess if it is as many morae long as the
+    /// answer, so chatter such as "no" isn't taken for a guess of の.
+    ///
+    /// Returns the guess and whether it is correct, or [`None`] if `text` isn't a guess.
+    fn check_typed(&self, text: &str) -> Option<(String, bool)> {
+        let answer = &self.questions[self.answer].text;
+
+        if kana::is_kana(answer) {
+            let guess = kana::normalize(text);
+            if !kana::is_hiragana(&guess) {
+                return None;
+            }
+            let answer = kana::normalize(answer);
+            let correct = self.options.tolerance.accepts(&guess, &answer);
+            let romaji = text.chars().any(|c| c.is_ascii_alphabetic());
+            if romaji && !correct && kana::morae(&guess).len() != kana::morae(&answer).len() {
+                return None;
+            }
+            return Some((guess, correct));
+        }
+
+        let guess: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        if !kana::has_kanji(&guess) {
+            return None;
+        }
+        let correct = guess == *answer
+            || (self.options.kanji_typing == KanjiTyping::AnyOkurigana
+                && kana::same_kanji(&guess, answer)
+                && self
+                    .entry
+                    .as_ref()
+                    .is_some_and(|entry| entry.kanjis.iter().any(|k| k.text == guess)));
+
+        Some((guess, correct))
+    }
+
+    /// Create all of the components of this menu. When [`GameOptions::shuffle_per_player`]
+    /// is set, the answer buttons are replaced with a button that privately shows them
+    /// to the player instead. Typed questions have no answer buttons. Every question
+    /// can be reported, and gives a hint until one is used or the round ends. The
+    /// starter can re-roll it, and players can vote to skip it, until the round ends.
+    fn create_components(&self) -> Vec<CreateActionRow> {
+        let finished = self.questions.iter().all(|q| q.disabled);
+        let hint = CreateButton::new(self.hint_id())
+            .label("💡 Hint ・ ヒント")
+            .style(ButtonStyle::Secondary)
+            .disabled(self.hinted || finished);
+        let mut buttons = vec![hint];
+        if self.starter.is_some() {
+            buttons.push(
+                CreateButton::new(self.reroll_id())
+                    .label("↻ New question ・ 別の問題")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(self.rerolled || finished),
+            );
+        }
+        buttons.push(
+            CreateButton::new(self.skip_id())
+                .label("⏭ Skip ・ スキップ")
+                .style(ButtonStyle::Secondary)
+                .disabled(finished),
+        );
+        buttons.push(command::report_button(&self.id));
+        let report = CreateActionRow::Buttons(buttons);
+
+        if self.is_typed() {
+            return vec![report];
+        }
+
+        if self.options.shuffle_per_player {
+            let button = CreateButton::new(self.open_id())
+                .label("Answer ・ 回答")
+                .disabled(finished);
+
+            return vec![CreateActionRow::Buttons(vec![button]), report];
+        }
+
+        let mut components =
+            self.create_answer_components(&(0..self.questions.len()).collect::<Vec<_>>());
+        components.push(report);
+
+        components
+    }
+
+    /// Creates the answer buttons, ordered by the indices of `order`.
+    fn create_answer_components(&self, order: &[usize]) -> Vec<CreateActionRow> {
+        let buttons = order
+            .iter()
+            .map(|&i| &self.questions[i])
+            .map(|q| CreateButton::new(&q.id).label(&q.text).disabled(q.disabled))
+            .collect();
+
+        vec![CreateActionRow::Buttons(buttons)]
+    }
+
+    /// Gets how long an answer Discord says was made at `answered_at` took.
+    /// See [`scoring::compensated_response_time`].
+    fn response_time(&self, answered_at: SystemTime) -> Duration {
+        let received = self.sent_at.map(|t| t.elapsed()).unwrap_or_default();
+        match &self.message {
+            Some(message) => scoring::compensated_response_time(
+                created_at(message.id.get()),
+                answered_at,
+                received,
+            ),
+            None => received,
+        }
+    }
+
+    /// Scores an answer Discord says was made at `answered_at`, weighted by
+    /// how fast it was and lowered if a hint was used.
+    fn points(&self, correct: bool, answered_at: SystemTime) -> u32 {
+        let response_time = self.response_time(answered_at);
+        let points = scoring::points(self.entry.as_deref(), response_time, correct);
+
+        if self.hinted {
+            (f64::from(points) * HINT_WEIGHT).round() as u32
+        } else {
+            points
+        }
+    }
+
+    /// Scores a correct answer of `user_id` Discord says was made at `answered_at`
+    /// for the game after their handicap, if any: a point, or its [points](Self::points)
+    /// in [race](Resolution::Race) rounds.
+    fn game_points(&self, user_id: UserId, answered_at: SystemTime) -> usize {
+        let handicap = self.handicaps.get(&user_id).copied().unwrap_or_default();
+        let response_time = self.response_time(answered_at);
+
+        match self.options.resolution {
+            Resolution::Race => {
+                handicap.apply(self.points(true, answered_at), response_time) as usize
+            }
+            _ => usize::from(handicap.counts(response_time)),
+        }
+    }
+
+    /// Stores the answer of `user_id` in their stats unless [`GameOptions::practice`]
+    /// is set, and counts it towards their study goal, congratulating them in
+    /// `channel_id` if it was reached. Correct answers are also tallied for the game.
+    /// Discord says the answer was made at `answered_at`.
+    async fn record_answer(
+        &mut self,
+        user_id: UserId,
+        channel_id: ChannelId,
+        correct: bool,
+        answered_at: SystemTime,
+    ) -> Result<(), InteractionExitReason> {
+        if correct {
+            self.correct
+                .push((user_id, self.game_points(user_id, answered_at)));
+        }
+        if !self.answers.iter().any(|&(u, _)| u == user_id) {
+            self.answers.push((user_id, correct));
+        }
+
+        if !self.options.practice
+            && let Err(e) = self.db.add_answer(
+                user_id,
+                // Games in DMs are keyed by the player, and aren't in a Server.
+                self.guild_id
+                    .filter(|guild_id| guild_id.get() != user_id.get()),
+                self.entry.as_ref().map(|entry| entry.id),
+                self.mode.as_deref().unwrap_or_default(),
+                correct,
+                self.points(correct, answered_at),
+            )
+        {
+            eprintln!("Failed to store answer of {}: {e}", user_id);
+        }
+
+        let day = match self.db.record_answer(user_id) {
+            Ok(day) => day,
+            Err(e) => {
+                eprintln!("Failed to record answer of {}: {e}", user_id);
+                return Ok(());
+            }
+        };
+
+        if day.just_reached() {
+            channel_id
+                .send_message(
+                    self.http,
+                    CreateMessage::new().content(command::goal_reached_message(user_id, &day)),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+        }
+
+        Ok(())
+    }
+
+    /// Listens for button interactions until the round ends as decided by
+    /// [`GameOptions::resolution`], or at the first correct answer in team games.
+    /// Listens for typed answers instead if the question [is typed](Self::is_typed).
+    pub async fn handle_interactions<R: Rng + ?Sized>(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        rng: &mut R,
+    ) -> Result<(), InteractionExitReason> {
+        if self.turns.is_some() {
+            return self.handle_turn_interactions(rx, rng).await;
+        }
+        if self.is_typed() {
+            return self.handle_typed_answers(rx, rng).await;
+        }
+        if self.options.teams {
+            return self.handle_first_correct_interactions(rx, rng).await;
+        }
+
+        match self.options.resolution {
+            Resolution::FirstCorrect | Resolution::Race => {
+                self.handle_first_correct_interactions(rx, rng).await
+            }
+            Resolution::EveryoneOnce | Resolution::FixedTimer => {
+                self.handle_tallied_interactions(rx, rng).await
+            }
+            // Turn-based rounds are handled above once the players joined.
+            Resolution::Turns => self.handle_first_correct_interactions(rx, rng).await,
+        }
+    }
+
+    /// Listens for messages in the question's channel until the answer is typed.
+    /// Messages that aren't hiragana, even after converting katakana and romaji,
+    /// are ignored as chatter, as are the guesses of players who already guessed
+    /// a [race](Resolution::Race) round. The hint button is still listened to.
+    async fn handle_typed_answers<R: Rng + ?Sized>(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        rng: &mut R,
+    ) -> Result<(), InteractionExitReason> {
+        let channel_id = self.message.as_ref().map(|m| m.channel_id);
+        let mut deadline = self.time_limit_deadline();
+        let mut answered = false;
+        // Players who guessed, who can't guess again if they only get one try.
+        let mut players = Vec::new();
+
+        loop {
+            if self.rerolled || self.skipped {
+                return Ok(());
+            }
+
+            let message = match self.next_message(rx, &mut deadline).await {
+                Ok(GameMessage::Message(message)) => *message,
+                Ok(GameMessage::Interaction(ci)) => {
+                    if !self.give_hint(&ci, rng).await? && !self.reroll(&ci).await? {
+                        self.vote_skip(&ci).await?;
+                    }
+                    continue;
+                }
+                Ok(_) => continue,
+                Err(InteractionExitReason::Timeout) if deadline.is_some() => {
+                    return self.reveal_after_time_limit(answered).await;
+                }
+                Err(reason) => return Err(reason),
+            };
+            if Some(message.channel_id) != channel_id {
+                continue;
+            }
+
+            let Some((guess, correct)) = self.check_typed(&message.content) else {
+                continue;
+            };
+            if self.is_one_try() && players.contains(&message.author.id) {
+                continue;
+            }
+            players.push(message.author.id);
+
+            let reply = if correct {
+                CreateMessage::new()
+                    .embed(self.answer_embed(&format!(
+                        "{} {}",
+                        message.author.name,
+                        self.emotes().get(Emote::Wow)
+                    )))
+                    .components(self.save_components())
+            } else {
+                CreateMessage::new().content(self.wrong_answer_message(
+                    message.author.id,
+                    &guess,
+                    rng,
+                ))
+            };
+            message
+                .channel_id
+                .send_message(self.http, reply.reference_message(&message))
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            self.record_answer(
+                message.author.id,
+                message.channel_id,
+                correct,
+                created_at(message.id.get()),
+            )
+            .await?;
+            answered = true;
+
+            if correct {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Listens for button interactions until the answer is chosen. Players only
+    /// answer once in [race](Resolution::Race) rounds and team games, where a
+    /// wrong answer also locks the other team out.
+    async fn handle_first_correct_interactions<R: Rng + ?Sized>(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        rng: &mut R,
+    ) -> Result<(), InteractionExitReason> {
+        let mut deadline = self.time_limit_deadline();
+        let mut answered = false;
+        // Players who answered, who can't answer again if they only get one try.
+        let mut players = Vec::new();
+
+        loop {
+            if self.rerolled || self.skipped {
+                return Ok(());
+            }
+
+            let ci = match self.next_interaction(rx, &mut deadline).await {
+                Ok(ci) => ci,
+                Err(InteractionExitReason::Timeout) if deadline.is_some() => {
+                    return self.reveal_after_time_limit(answered).await;
+                }
+                Err(reason) => return Err(reason),
+            };
+
+            if self.open_private_layout(&ci, rng).await?
+                || self.join_team(&ci).await?
+                || self.give_hint(&ci, rng).await?
+                || self.reroll(&ci).await?
+                || self.vote_skip(&ci).await?
+            {
+                continue;
+            }
+
+            let Some((menu_id, choice)) = parse_custom_id(&ci.data.custom_id) else {
+                continue;
+            };
+            // Skip if menu_id of previous round.
+            if menu_id != self.id {
+                continue;
+            }
+            if self.ignore_early_answer(&ci).await? {
+                continue;
+            }
+
+            let team = self.teams.as_deref_mut().map(|t| t.assign(ci.user.id));
+            let refusal = if self.is_one_try() && players.contains(&ci.user.id) {
+                Some("You've already answered this question".to_string())
+            } else {
+                team.and_then(|team| {
+                    self.locked
+                        .filter(|&(locked, until)| locked == team && Instant::now() < until)
+                        .map(|(_, until)| {
+                            format!(
+                                "{} is locked out for {} more second(s)",
+                                team.label(),
+                                until.saturating_duration_since(Instant::now()).as_secs() + 1
+                            )
+                        })
+                })
+            };
+            if let Some(refusal) = refusal {
+                ci.create_response(
+                    self.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(refusal)
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+                continue;
+            }
+
+            // Private answer layouts aren't updated when another player rules out an option.
+            if self.questions[choice].disabled {
+                ci.create_response(
+                    self.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("That answer was already ruled out")
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+                continue;
+            }
+
+            let correct = self.questions[choice].id == self.answer_id();
+
+            // If correct, disable all buttons since this round is finished.
+            // Otherwise, just the wrongly selected button.
+            if correct {
+                self.questions.iter_mut().for_each(|q| q.disabled = true);
+            } else {
+                self.questions[choice].disabled = true;
+            }
+
+            // A wrong answer isn't revealed on the shared message when players
+            // have their own answer layouts.
+            if correct || !self.options.shuffle_per_player {
+                self.update_message()
+                    .await
+                    .map_err(|_| InteractionExitReason::NetworkError)?;
+            }
+
+            let message = if correct {
+                let mut winner = match team {
+                    Some(team) => format!(
+                        "{} ({}) {}",
+                        ci.user.name,
+                        team.label(),
+                        self.emotes().get(Emote::Wow)
+                    ),
+                    None => format!("{} {}", ci.user.name, self.emotes().get(Emote::Wow)),
+                };
+                if self.options.resolution == Resolution::Race {
+                    winner.push_str(&format!(
+                        " +{} pts",
+                        self.game_points(ci.user.id, created_at(ci.id.get()))
+                    ));
+                }
+                CreateInteractionResponseMessage::new()
+                    .embed(self.answer_embed(&winner))
+                    .components(self.save_components())
+            } else {
+                let mut content =
+                    self.wrong_answer_message(ci.user.id, &self.questions[choice].text, rng);
+                if let Some(team) = team {
+                    self.locked = Some((team.other(), Instant::now() + TEAM_LOCKOUT));
+                    content.push_str(&format!(
+                        "\n{} is locked out for {} seconds",
+                        team.other().label(),
+                        TEAM_LOCKOUT.as_secs()
+                    ));
+                }
+                CreateInteractionResponseMessage::new().content(content)
+            };
+
+            ci.create_response(self.http, CreateInteractionResponse::Message(message))
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            self.record_answer(ci.user.id, ci.channel_id, correct, created_at(ci.id.get()))
+                .await?;
+            answered = true;
+            players.push(ci.user.id);
+
+            if correct {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Listens for button interactions until the player whose turn it is answers,
+    /// ignoring the answers of other players, or until their turn runs out after
+    /// [`TURN_TIMER`] or [`GameOptions::time_limit`]. The round ends either way,
+    /// and the turn passes to the next player. Players can still join.
+    ///
+    /// Returns [`InteractionExitReason::Timeout`] once every player's turn was
+    /// skipped in a row, since nobody is playing.
+    async fn handle_turn_interactions<R: Rng + ?Sized>(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        rng: &mut R,
+    ) -> Result<(), InteractionExitReason> {
+        let Some(player) = self.turns.as_deref().and_then(Turns::current) else {
+            return Err(InteractionExitReason::Timeout);
+        };
+        let mut deadline = Some(
+            self.sent_at.unwrap_or_else(Instant::now)
+                + self.options.time_limit.unwrap_or(TURN_TIMER),
+        );
+
+        loop {
+            // The player keeps their turn for the next question.
+            if self.rerolled {
+                return Ok(());
+            }
+            // The turn passes to the next player, as if it was played.
+            if self.skipped {
+                if let Some(turns) = self.turns.as_deref_mut() {
+                    turns.advance(true);
+                }
+                return Ok(());
+            }
+
+            let ci = match self.next_interaction(rx, &mut deadline).await {
+                Ok(ci) => ci,
+                Err(InteractionExitReason::Timeout) => break,
+                Err(reason) => return Err(reason),
+            };
+
+            if lobby::is_join_button(&ci.data.custom_id) {
+                if let Some(turns) = self.turns.as_deref_mut() {
+                    turns.respond_to_join(self.http, &ci).await?;
+                }
+                continue;
+            }
+            if self.open_private_layout(&ci, rng).await?
+                || self.give_hint(&ci, rng).await?
+                || self.reroll(&ci).await?
+                || self.vote_skip(&ci).await?
+            {
+                continue;
+            }
+
+            let Some((menu_id, choice)) = parse_custom_id(&ci.data.custom_id) else {
+                continue;
+            };
+            // Skip if menu_id of previous round.
+            if menu_id != self.id {
+                continue;
+            }
+            if ci.user.id != player {
+                ci.create_response(
+                    self.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(format!("It's <@{player}>'s turn"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+                continue;
+            }
+            if self.ignore_early_answer(&ci).await? {
+                continue;
+            }
+
+            let correct = self.questions[choice].id == self.answer_id();
+            self.questions.iter_mut().for_each(|q| q.disabled = true);
+            self.update_message()
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            let footer = if correct {
+                format!("{} {}", ci.user.name, self.emotes().get(Emote::Wow))
+            } else {
+                self.wrong_answer_message(ci.user.id, &self.questions[choice].text, rng)
+            };
+            ci.create_response(
+                self.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .embed(self.answer_embed(&footer))
+                        .components(self.save_components()),
+                ),
+            )
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            self.record_answer(ci.user.id, ci.channel_id, correct, created_at(ci.id.get()))
+                .await?;
+            if let Some(turns) = self.turns.as_deref_mut() {
+                turns.advance(true);
+            }
+
+            return Ok(());
+        }
+
+        // The player's turn ran out, so it is skipped.
+        self.questions.iter_mut().for_each(|q| q.disabled = true);
+        self.update_message()
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+        if let Some(message) = &self.message {
+            message
+                .channel_id
+                .send_message(
+                    self.http,
+                    CreateMessage::new()
+                        .embed(self.answer_embed(&format!(
+                            "Time's up! ・ 時間切れ！ <@{player}>'s turn was skipped"
+                        )))
+                        .components(self.save_components()),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+        }
+
+        let Some(turns) = self.turns.as_deref_mut() else {
+            return Ok(());
+        };
+        turns.advance(false);
+        if turns.all_missed() {
+            return Err(InteractionExitReason::Timeout);
+        }
+
+        Ok(())
+    }
+
+    /// Listens for button interactions, collecting the first answer of every player
+    /// until [`Self::reveal_deadline`]. Then reveals the answer along with how many
+    /// players picked each option.
+    ///
+    /// Returns [`InteractionExitReason::Timeout`] if a timed round ends without answers.
+    async fn handle_tallied_interactions<R: Rng + ?Sized>(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        rng: &mut R,
+    ) -> Result<(), InteractionExitReason> {
+        // The players who answered and their choices, in the order they answered.
+        let mut picks: Vec<(UserId, String, usize)> = Vec::new();
+        let mut deadline = self.reveal_deadline(None);
+
+        loop {
+            if self.rerolled || self.skipped {
+                return Ok(());
+            }
+
+            let ci = match self.next_interaction(rx, &mut deadline).await {
+                Ok(ci) => ci,
+                // The window for answering is over.
+                Err(InteractionExitReason::Timeout) if deadline.is_some() => break,
+                Err(reason) => return Err(reason),
+            };
+
+            if self.open_private_layout(&ci, rng).await?
+                || self.give_hint(&ci, rng).await?
+                || self.reroll(&ci).await?
+                || self.vote_skip(&ci).await?
+            {
+                continue;
+            }
+
+            let Some((menu_id, choice)) = parse_custom_id(&ci.data.custom_id) else {
+                continue;
+            };
+            // Skip if menu_id of previous round.
+            if menu_id != self.id {
+                continue;
+            }
+            if self.ignore_early_answer(&ci).await? {
+                continue;
+            }
+
+            let answered = picks.iter().any(|(user_id, ..)| *user_id == ci.user.id);
+            let content = if answered {
+                "You've already answered this question".to_string()
+            } else {
+                picks.push((ci.user.id, ci.user.name.clone(), choice));
+                deadline = self.reveal_deadline(Some(Instant::now()));
+
+                // Discord renders the timestamp as a countdown.
+                let reveal_at = SystemTime::now()
+                    + deadline.map_or(Duration::ZERO, |d| {
+                        d.saturating_duration_since(Instant::now())
+                    });
+                format!(
+                    "Locked in {}. The answer is revealed {}",
+                    self.questions[choice].text,
+                    format::timestamp(
+                        reveal_at
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                        TimestampStyle::Relative
+                    )
+                )
+            };
+
+            ci.create_response(
+                self.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            if !answered {
+                self.record_answer(
+                    ci.user.id,
+                    ci.channel_id,
+                    choice == self.answer,
+                    created_at(ci.id.get()),
+                )
+                .await?;
+            }
+        }
+
+        self.questions.iter_mut().for_each(|q| q.disabled = true);
+        self.update_message()
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        let winners: Vec<_> = picks
+            .iter()
+            .filter(|(.., choice)| *choice == self.answer)
+            .map(|(_, name, _)| name.as_str())
+            .collect();
+        let winners = if winners.is_empty() {
+            format!("Nobody got it right {}", self.emotes().get(Emote::Wat))
+        } else {
+            format!("{} {}", winners.join(", "), self.emotes().get(Emote::Wow))
+        };
+        let choices: Vec<_> = picks.iter().map(|(.., choice)| *choice).collect();
+
+        if let Some(message) = &self.message {
+            message
+                .channel_id
+                .send_message(
+                    self.http,
+                    CreateMessage::new()
+                        .embed(
+                            self.answer_embed(&format!(
+                                "{winners}\n\n{}",
+                                self.histogram(&choices)
+                            )),
+                        )
+                        .components(self.save_components()),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+        }
+
+        // Nobody is playing if a timed round goes unanswered.
+        if picks.is_empty() {
+            return Err(InteractionExitReason::Timeout);
+        }
+
+        Ok(())
+    }
+
+    /// Receives the next message from `rx` until `deadline`, or for up to
+    /// [`INACTIVITY_TIMEOUT`] if there is none. The round is [paused](Self::pause)
+    /// when the game is.
+    async fn next_message(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        deadline: &mut Option<Instant>,
+    ) -> Result<GameMessage, InteractionExitReason> {
+        loop {
+            match game_message(rx, Instant::now() + time_left(*deadline)).await? {
+                GameMessage::Pause(user_id) => self.pause(rx, user_id, deadline).await?,
+                // The game isn't paused.
+                GameMessage::Resume(_) => {}
+                message => return Ok(message),
+            }
+        }
+    }
+
+    /// Receives the next component interaction from `rx` as in
+    /// [`Self::next_message`], skipping channel messages.
+    async fn next_interaction(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        deadline: &mut Option<Instant>,
+    ) -> Result<ComponentInteraction, InteractionExitReason> {
+        let mut wait_until = Instant::now() + time_left(*deadline);
+
+        loop {
+            match game_message(rx, wait_until).await? {
+                GameMessage::Interaction(ci) => return Ok(*ci),
+                GameMessage::Pause(user_id) => {
+                    self.pause(rx, user_id, deadline).await?;
+                    wait_until = Instant::now() + time_left(*deadline);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Pauses the round because `user_id` paused the game: disables its buttons
+    /// and [waits](wait_while_paused) for the game to be resumed. Then restores
+    /// the buttons, and pushes `deadline` and the round's other timers back by
+    /// how long the game was paused.
+    async fn pause(
+        &mut self,
+        rx: &mut Receiver<GameMessage>,
+        user_id: UserId,
+        deadline: &mut Option<Instant>,
+    ) -> Result<(), InteractionExitReason> {
+        let Some(channel_id) = self.message.as_ref().map(|m| m.channel_id) else {
+            return Ok(());
+        };
+
+        let disabled: Vec<_> = self.questions.iter().map(|q| q.disabled).collect();
+        self.questions.iter_mut().for_each(|q| q.disabled = true);
+        self.update_message()
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        let paused = wait_while_paused(self.http, rx, channel_id, user_id).await?;
+
+        for (question, disabled) in self.questions.iter_mut().zip(disabled) {
+            question.disabled = disabled;
+        }
+        self.update_message()
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        *deadline = deadline.map(|d| d + paused);
+        self.sent_at = self.sent_at.map(|t| t + paused);
+        if let Some((_, until)) = &mut self.locked {
+            *until += paused;
+        }
+
+        Ok(())
+    }
+
+    /// Gently tells the player of `ci` to read the question first if they answered
+    /// sooner than [`GameOptions::min_read_time`] after it was sent.
+    ///
+    /// Returns whether the answer of `ci` should be ignored.
+    async fn ignore_early_answer(
+        &self,
+        ci: &ComponentInteraction,
+    ) -> Result<bool, InteractionExitReason> {
+        if self
+            .sent_at
+            .is_none_or(|sent_at| sent_at.elapsed() >= self.options.min_read_time)
+        {
+            return Ok(false);
+        }
+
+        ci.create_response(
+            self.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content("Slow down! Take a moment to read the question first")
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        Ok(true)
+    }
+
+    /// Gets when answers stop being collected in a tallied round, given when the last
+    /// new answer was made. [`None`] until the first answer if the round isn't timed.
+    fn reveal_deadline(&self, last_answer: Option<Instant>) -> Option<Instant> {
+        let sent_at = self.sent_at.unwrap_or_else(Instant::now);
+
+        match self.options.resolution {
+            Resolution::FirstCorrect | Resolution::Race | Resolution::Turns => None,
+            Resolution::EveryoneOnce => {
+                let window = last_answer.map(|t| t + ANSWER_WINDOW);
+                match (window, self.time_limit_deadline()) {
+                    (Some(window), Some(limit)) => Some(window.min(limit)),
+                    (window, limit) => window.or(limit),
+                }
+            }
+            Resolution::FixedTimer => {
+                Some(sent_at + self.options.time_limit.unwrap_or(ROUND_TIMER))
+            }
+        }
+    }
+
+    /// Gets when the round ends due to [`GameOptions::time_limit`], if set.
+    fn time_limit_deadline(&self) -> Option<Instant> {
+        self.options
+            .time_limit
+            .map(|limit| self.sent_at.unwrap_or_else(Instant::now) + limit)
+    }
+
+    /// Ends a round whose [`GameOptions::time_limit`] ran out by revealing the answer.
+    ///
+    /// Returns [`InteractionExitReason::Timeout`] if nobody `answered`, since
+    /// nobody is playing.
+    async fn reveal_after_time_limit(
+        &mut self,
+        answered: bool,
+    ) -> Result<(), InteractionExitReason> {
+        self.questions.iter_mut().for_each(|q| q.disabled = true);
+        self.update_message()
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        if let Some(message) = &self.message {
+            message
+                .channel_id
+                .send_message(
+                    self.http,
+                    CreateMessage::new()
+                        .embed(self.answer_embed(&format!(
+                            "Time's up! ・ 時間切れ！ {}",
+                            self.emotes().get(Emote::Wat)
+                        )))
+                        .components(self.save_components()),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+        }
+
+        if !answered {
+            return Err(InteractionExitReason::Timeout);
+        }
+
+        Ok(())
+    }
+
+    /// Privately shows the player of `ci` the answers in their own order if `ci`
+    /// is a press of the button for it.
+    ///
+    /// Returns whether `ci` was handled.
+    async fn open_private_layout<R: Rng + ?Sized>(
+        &self,
+        ci: &ComponentInteraction,
+        rng: &mut R,
+    ) -> Result<bool, InteractionExitReason> {
+        if !self.options.shuffle_per_player || ci.data.custom_id != self.open_id() {
+            return Ok(false);
+        }
+
+        let mut order: Vec<_> = (0..self.questions.len()).collect();
+        order.shuffle(rng);
+
+        ci.create_response(
+            self.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .components(self.create_answer_components(&order))
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        Ok(true)
+    }
+
+    /// Puts the player of `ci` on the team they picked if `ci` is a press of a
+    /// team button, unless they are already on a team.
+    ///
+    /// Returns whether `ci` was handled.
+    async fn join_team(
+        &mut self,
+        ci: &ComponentInteraction,
+    ) -> Result<bool, InteractionExitReason> {
+        let (Some(teams), Some(team)) = (
+            self.teams.as_deref_mut(),
+            Team::from_button(&ci.data.custom_id),
+        ) else {
+            return Ok(false);
+        };
+
+        let joined = teams.join(ci.user.id, team);
+        let content = if joined == team {
+            format!("You're on {}", team.label())
+        } else {
+            format!("You're already on {}", joined.label())
+        };
+
+        ci.create_response(
+            self.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await
+        .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        Ok(true)
+    }
+
+    /// Gives a hint to every player if `ci` is a press of the hint button: rules
+    /// out [`HINT_ELIMINATIONS`] wrong options, always leaving one, or reveals the
+    /// first kana of typed answers. Only one hint is given a round.
+    ///
+    /// Returns whether `ci` was handled.
+    async fn give_hint<R: Rng + ?Sized>(
+        &mut self,
+        ci: &ComponentInteraction,
+        rng: &mut R,
+    ) -> Result<bool, InteractionExitReason> {
+        if ci.data.custom_id != self.hint_id() {
+            return Ok(false);
+        }
+
+        let response = if self.hinted {
+            CreateInteractionResponseMessage::new()
+                .content("A hint was already given this round")
+                .ephemeral(true)
+        } else {
+            self.hinted = true;
+
+            let hint = if self.is_typed() {
+                let first = self.questions[self.answer].text.chars().next();
+                format!("The answer starts with {}", first.unwrap_or_default())
+            } else {
+                let wrong: Vec<_> = (0..self.questions.len())
+                    .filter(|&i| i != self.answer && !self.questions[i].disabled)
+                    .collect();
+                let eliminations = HINT_ELIMINATIONS.min(wrong.len().saturating_sub(1));
+                for &i in wrong.choose_multiple(rng, eliminations) {
+                    self.questions[i].disabled = true;
+                }
+                format!("{eliminations} wrong answer(s) were ruled out")
+            };
+            self.update_message()
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            CreateInteractionResponseMessage::new().content(format!(
+                "💡 {} used a hint: {hint}. Answers are worth {}% of the points this round",
+                ci.user.name,
+                HINT_WEIGHT * 100.0
+            ))
+        };
+
+        ci.create_response(self.http, CreateInteractionResponse::Message(response))
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        Ok(true)
+    }
+
+    /// Skips the question if `ci` is the starter pressing the re-roll button, so
+    /// the next one of the pool is asked instead. The skipped question isn't
+    /// counted as missed. Other players are told only the starter can re-roll.
+    ///
+    /// Returns whether `ci` was handled.
+    async fn reroll(&mut self, ci: &ComponentInteraction) -> Result<bool, InteractionExitReason> {
+        if ci.data.custom_id != self.reroll_id() {
+            return Ok(false);
+        }
+
+        let response = if self.starter != Some(ci.user.id) {
+            CreateInteractionResponseMessage::new()
+                .content("Only the player who started the game can re-roll the question")
+                .ephemeral(true)
+        } else {
+            self.rerolled = true;
+            self.questions.iter_mut().for_each(|q| q.disabled = true);
+            self.update_message()
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            CreateInteractionResponseMessage::new().content(format!(
+                "↻ {} drew a new question. This one is skipped",
+                ci.user.name
+            ))
+        };
+
+        ci.create_response(self.http, CreateInteractionResponse::Message(response))
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        Ok(true)
+    }
+
+    /// Counts the vote of the player of `ci` to skip the question if `ci` is a
+    /// press of the skip button. Once enough players voted, or the starter did,
+    /// the answer is revealed without anyone scoring and the round ends.
+    ///
+    /// Returns whether `ci` was handled.
+    async fn vote_skip(
+        &mut self,
+        ci: &ComponentInteraction,
+    ) -> Result<bool, InteractionExitReason> {
+        if ci.data.custom_id != self.skip_id() {
+            return Ok(false);
+        }
+
+        let needed = self.options.skip_votes.unwrap_or(SKIP_VOTES);
+        let response = if !self.skip_votes.insert(ci.user.id) {
+            CreateInteractionResponseMessage::new()
+                .content("You've already voted to skip this question")
+                .ephemeral(true)
+        } else if self.starter == Some(ci.user.id) || self.skip_votes.len() >= needed {
+            self.skipped = true;
+            self.questions.iter_mut().for_each(|q| q.disabled = true);
+            self.update_message()
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+
+            CreateInteractionResponseMessage::new()
+                .embed(self.answer_embed("⏭ Skipped ・ スキップ"))
+                .components(self.save_components())
+        } else {
+            CreateInteractionResponseMessage::new().content(format!(
+                "⏭ {} voted to skip this question ({}/{needed})",
+                ci.user.name,
+                self.skip_votes.len()
+            ))
+        };
+
+        ci.create_response(self.http, CreateInteractionResponse::Message(response))
+            .await
+            .map_err(|_| InteractionExitReason::NetworkError)?;
+
+        Ok(true)
+    }
+
+    /// Creates the embed revealing the answer, followed by `footer`. The word
+    /// the question is about, if any, is [described](dictionary::describe_entry)
+    /// in full along with its [links](dictionary::entry_links), and the title
+    /// links to it on jisho.org.
+    fn answer_embed(&self, footer: &str) -> CreateEmbed {
+        const THUMBNAIL: &str = r"https://raw.githubusercontent.com/jasonly027/jplearnbot/dedaa826e9bbc942cf035ba8eeac15479e8d9416/assets/correct.png";
+
+        let answer = &self.questions[self.answer].text;
+        let marked = match &self.entry {
+            Some(entry) => dictionary::mark_spelling(entry, answer),
+            None => answer.clone(),
+        };
+        let header = match self.difficulty_label() {
+            Some(label) => format!("{marked} {label}"),
+            None => marked,
+        };
+        let mut body = format!("### {header}\n");
+        if self.spoken && self.prompt != *answer {
+            body.push_str(&format!("Heard ・ 聞いた言葉: {}\n", self.prompt));
+        }
+        if let (Some(entry), Some(sense)) = (&self.entry, self.sense) {
+            body.push_str(&format!("{}\n", dictionary::sense_label(entry, sense)));
+        }
+        if let Some(entry) = &self.entry {
+            let details = dictionary::describe_entry(entry);
+            body.push_str(&format!(
+                "{}\n\n",
+                dictionary::truncate(&details, MAX_DETAILS_LEN)
+            ));
+        }
+        if let Some(pitch) = self.entry.as_deref().and_then(dictionary::pitch_label) {
+            body.push_str(&format!("{pitch}\n"));
+        }
+        if let Some(example) = self.entry.as_deref().and_then(dictionary::example_label) {
+            body.push_str(&format!("{example}\n"));
+        }
+        if let Some(note) = &self.note {
+            body.push_str(&format!("{note}\n"));
+        }
+        if let Some(mnemonic) = self.mnemonic() {
+            body.push_str(&format!(
+                "💡 {} ・ shared by <@{}>\n",
+                mnemonic.text, mnemonic.user_id
+            ));
+        }
+        if let Some(entry) = &self.entry {
+            body.push_str(&format!("{}\n", dictionary::entry_links(entry)));
+        }
+        body.push_str(footer);
+
+        CreateEmbed::new()
+            .title("Answer · 正解")
+            .url(dictionary::jisho_url(answer))
+            .thumbnail(THUMBNAIL)
+            .description(body)
+    }
+
+    /// Gets the mnemonic for the question's word a member of the Server shared,
+    /// if its pool is open and one was approved.
+    fn mnemonic(&self) -> Option<Mnemonic> {
+        let (guild_id, entry) = (self.guild_id?, self.entry.as_ref()?);
+
+        self.db
+            .approved_mnemonic(guild_id, entry.id)
+            .inspect_err(|e| eprintln!("Failed to get mnemonic of {}: {e}", entry.id))
+            .ok()
+            .flatten()
+    }
+
+    /// Creates the buttons that bookmark the question's word and show how its
+    /// kanji are written, if it has them.
+    fn save_components(&self) -> Vec<CreateActionRow> {
+        let mut buttons = Vec::new();
+        if let Some(entry) = &self.entry {
+            buttons.push(command::save_button(entry.id));
+        }
+
+        // The answer itself for kanji modes, otherwise the word's spelling.
+        let answer = self.questions[self.answer].text.as_str();
+        let spelling = self
+            .entry
+            .as_ref()
+            .and_then(|e| e.kanjis.first())
+            .map(|k| k.text.as_str());
+        if let Some(button) = [Some(answer), spelling]
+            .into_iter()
+            .flatten()
+            .find_map(command::stroke_order_button)
+        {
+            buttons.push(button);
+        }
+
+        if buttons.is_empty() {
+            Vec::new()
+        } else {
+            vec![CreateActionRow::Buttons(buttons)]
+        }
+    }
+
+    /// Renders how many of `choices` picked each option as bars. The answer is marked.
+    fn histogram(&self, choices: &[usize]) -> String {
+        const WIDTH: usize = 10;
+
+        let counts: Vec<_> = (0..self.questions.len())
+            .map(|i| choices.iter().filter(|&&c| c == i).count())
+            .collect();
+        let max = counts.iter().copied().max().unwrap_or(0).max(1);
+
+        self.questions
+            .iter()
+            .zip(counts)
+            .enumerate()
+            .map(|(i, (q, count))| {
+                let filled = count * WIDTH / max;
+                format!(
+                    "{} {}\n`{}{}` {count}",
+                    if i == self.answer { "✅" } else { "▫️" },
+                    q.text,
+                    "█".repeat(filled),
+                    "░".repeat(WIDTH - filled),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Gets how long until `deadline`, or [`INACTIVITY_TIMEOUT`] if there is none.
+fn time_left(deadline: Option<Instant>) -> Duration {
+    deadline.map_or(INACTIVITY_TIMEOUT, |d| {
+        d.saturating_duration_since(Instant::now())
+    })
+}
+
+/// Posts a banner in `channel_id` saying `user_id` paused the game, and waits
+/// for [`GameMessage::Resume`] for up to [`PAUSE_TIMEOUT`]. Players pressing
+/// buttons meanwhile are told the game is paused, and channel messages are
+/// ignored. The banner says who resumed the game once it is.
+///
+/// Returns how long the game was paused.
+///
+/// Returns [`InteractionExitReason::Timeout`] if the game isn't resumed in time,
+/// or the reasons of [`game_message`] if it stops.
+pub(super) async fn wait_while_paused(
+    http: &Http,
+    rx: &mut Receiver<GameMessage>,
+    channel_id: ChannelId,
+    user_id: UserId,
+) -> Result<Duration, InteractionExitReason> {
+    let paused_at = Instant::now();
+    let mut banner = channel_id
+        .send_message(
+            http,
+            CreateMessage::new().embed(
+                CreateEmbed::new()
+                    .title("⏸️ Paused ・ 一時停止")
+                    .description(format!(
+                        "<@{user_id}> paused the game. Continue it with `/resume`"
+                    )),
+            ),
+        )
+        .await
+        .map_err(|_| InteractionExitReason::NetworkError)?;
+
+    let resumed_by = loop {
+        match game_message(rx, paused_at + PAUSE_TIMEOUT).await? {
+            GameMessage::Resume(user_id) => break user_id,
+            GameMessage::Interaction(ci) => {
+                ci.create_response(
+                    http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("The game is paused. Continue it with `/resume`")
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .map_err(|_| InteractionExitReason::NetworkError)?;
+            }
+            _ => {}
+        }
+    };
+
+    banner
+        .edit(
+            http,
+            EditMessage::new().embed(
+                CreateEmbed::new()
+                    .title("▶️ Resumed ・ 再開")
+                    .description(format!("<@{resumed_by}> resumed the game")),
+            ),
+        )
+        .await
+        .ok();
+
+    Ok(paused_at.elapsed())
+}
+
+/// Receives the next message from `rx`.
+///
+/// Returns [`InteractionExitReason::Timeout`] if sender doesn't send
+/// anything before `deadline`.
+///
+/// Returns [`InteractionExitReason::CloseRequest`] if sender sends
+/// [`GameMessage::Close`], or [`InteractionExitReason::Shutdown`] if it sends
+/// [`GameMessage::Shutdown`].
+pub(super) async fn game_message(
+    rx: &mut Receiver<GameMessage>,
+    deadline: Instant,
+) -> Result<GameMessage, InteractionExitReason> {
+    match timeout_at(deadline, rx.recv()).await {
+        Ok(Some(GameMessage::Close)) => Err(InteractionExitReason::CloseRequest),
+        Ok(Some(GameMessage::Shutdown)) => Err(InteractionExitReason::Shutdown),
+        Ok(Some(msg)) => Ok(msg),
+        _ => Err(InteractionExitReason::Timeout),
+    }
+}
+
+/// Gets when Discord created whatever has the id `snowflake`, e.g. a message
+/// or an interaction, which is encoded in the id itself.
+fn created_at(snowflake: u64) -> SystemTime {
+    /// Milliseconds from the Unix epoch to the first second of 2015, which
+    /// the times of Discord's ids count from.
+    const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+    UNIX_EPOCH + Duration::from_millis((snowflake >> 22) + DISCORD_EPOCH)
+}
+
+/// Parses a component's custom_id for its menu_id and the user's button choice.
+fn parse_custom_id(custom_id: &str) -> Option<(&str, usize)> {
+    static RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(.*),([0-4])$").unwrap());
+
+    RE.captures(custom_id)
+        .and_then(|m| Some((m.get(1)?, m.get(2)?))) // Get capture groups
+        .and_then(|(menu_id, choice)| {
+            Some((menu_id.as_str(), choice.as_str().parse().ok()?)) // Convert format
+        })
+}
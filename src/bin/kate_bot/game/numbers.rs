@@ -0,0 +1,86 @@
+use jplearnbot::{number, question::shuffle_options};
+use rand::{Rng, rngs::StdRng};
+
+use super::{Filters, Question, QuestionSource};
+
+/// Asks for the hiragana reading of numbers written in digits.
+pub struct NumbersSource;
+
+impl NumbersSource {
+    /// How many questions are asked per session.
+    const ROUNDS: usize = 20;
+}
+
+impl QuestionSource for NumbersSource {
+    fn name(&self) -> &str {
+        "Number ▶ ひらがな"
+    }
+
+    fn questions(
+        &self,
+        _filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        Box::new((0..Self::ROUNDS).map(move |_| {
+            let digits = rng.random_range(1..=6);
+            let n = rng.random_range(10u32.pow(digits - 1)..10u32.pow(digits));
+
+            // Distractors differ from the answer by a single digit, so they can't
+            // be told apart by length alone.
+            let mut numbers = vec![n];
+            while numbers.len() < 5 {
+                let candidate = change_digit(n, &mut rng);
+                if !numbers.contains(&candidate) {
+                    numbers.push(candidate);
+                }
+            }
+            let answer = shuffle_options(&mut numbers, &mut rng);
+
+            Question {
+                prompt: group_digits(n),
+                options: std::array::from_fn(|i| number::reading(numbers[i])),
+                answer,
+                entry: None,
+                sense: None,
+                mode: None,
+                spoken: false,
+                note: None,
+                single_kana: false,
+            }
+        }))
+    }
+}
+
+/// Replaces a random digit of `n` with a different one, keeping the number of digits.
+fn change_digit<R: Rng + ?Sized>(n: u32, rng: &mut R) -> u32 {
+    let digits = n.to_string().len() as u32;
+    let place = rng.random_range(0..digits);
+    let scale = 10u32.pow(place);
+
+    let old = n / scale % 10;
+    // The leading digit can't become zero.
+    let lowest = if place == digits - 1 { 1 } else { 0 };
+    let new = loop {
+        let d = rng.random_range(lowest..10);
+        if d != old {
+            break d;
+        }
+    };
+
+    n - old * scale + new * scale
+}
+
+/// Formats `n` with thousands separators, e.g. 12,345.
+fn group_digits(n: u32) -> String {
+    let digits = n.to_string();
+
+    let mut res = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            res.push(',');
+        }
+        res.push(c);
+    }
+
+    res
+}
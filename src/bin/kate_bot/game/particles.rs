@@ -0,0 +1,87 @@
+use jplearnbot::{
+    dictionary::NLevel,
+    grammar::GrammarPoint,
+    particle::{self, Blank, PARTICLES},
+};
+use rand::{
+    rngs::StdRng,
+    seq::{IndexedRandom, SliceRandom},
+};
+
+use super::{Filters, Question, QuestionSource};
+
+/// Asks which particle fills the blank of an example sentence of the grammar
+/// points, e.g. 雨（　）降っている.
+pub struct ParticlesSource {
+    sentences: Vec<Sentence>,
+}
+
+/// An example sentence with particles that can be blanked out.
+struct Sentence {
+    text: String,
+    /// The level of the grammar point the sentence is an example of.
+    level: NLevel,
+    blanks: Vec<Blank>,
+}
+
+impl ParticlesSource {
+    /// Creates a source asking about the example sentences of `points` that
+    /// have particles that can be blanked out.
+    pub fn new(points: &[GrammarPoint]) -> Self {
+        let sentences = points
+            .iter()
+            .map(|point| Sentence {
+                text: point.example.clone(),
+                level: point.level,
+                blanks: particle::blanks(&point.example),
+            })
+            .filter(|sentence| !sentence.blanks.is_empty())
+            .collect();
+
+        ParticlesSource { sentences }
+    }
+}
+
+impl QuestionSource for ParticlesSource {
+    fn name(&self) -> &str {
+        "文 ▶ 助詞"
+    }
+
+    fn questions(
+        &self,
+        filters: &Filters,
+        mut rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send> {
+        // A single particle of each sentence is asked about, since the rest
+        // are given away when the sentence is revealed.
+        let mut questions: Vec<_> = self
+            .sentences
+            .iter()
+            .filter(|sentence| filters.levels.contains(&sentence.level))
+            .map(|sentence| {
+                let blank = sentence.blanks.choose(&mut rng).unwrap();
+
+                Question {
+                    prompt: blank.prompt.clone(),
+                    // Particles are listed in the same order every time, so
+                    // they're quick to find.
+                    options: PARTICLES.map(String::from),
+                    answer: PARTICLES.iter().position(|&p| p == blank.particle).unwrap(),
+                    entry: None,
+                    sense: None,
+                    mode: None,
+                    spoken: false,
+                    note: Some(format!("Sentence ・ 文: {}", sentence.text)),
+                    single_kana: false,
+                }
+            })
+            .collect();
+        questions.shuffle(&mut rng);
+
+        Box::new(questions.into_iter())
+    }
+
+    fn shuffles_options(&self) -> bool {
+        false
+    }
+}
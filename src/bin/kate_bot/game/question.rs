@@ -0,0 +1,180 @@
+use std::{collections::HashSet, sync::Arc};
+
+use jplearnbot::{
+    dictionary::{DictEntry, NLevel, Pos},
+    distractor::Difficulty,
+    question::OPTIONS,
+};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use super::{Adaptive, PosFilter};
+
+/// Game question
+pub struct Question {
+    /// The word to translate.
+    pub prompt: String,
+    /// Possible translations of [`Self::prompt`].
+    pub options: [String; OPTIONS],
+    /// The index of the correct translation of [`Self::prompt`].
+    pub answer: usize,
+    /// The dictionary entry the question was made from, if any.
+    pub entry: Option<Arc<DictEntry>>,
+    /// The [number](`jplearnbot::dictionary::Sense::number`) of the sense of
+    /// [`Self::entry`] the question asks about, if it asks about a meaning.
+    pub sense: Option<u32>,
+    /// Name of the mode the question is asked in, if it differs from the
+    /// name of its [`QuestionSource`].
+    pub mode: Option<String>,
+    /// Whether [`Self::prompt`] is read aloud to players instead of shown.
+    pub spoken: bool,
+    /// Shown along with the answer once it is revealed, e.g. an example sentence.
+    pub note: Option<String>,
+    /// Whether [`Self::prompt`] is a single kana or digraph of the chart, which
+    /// is drawn large so its strokes can be made out.
+    pub single_kana: bool,
+}
+
+impl Question {
+    /// Describes the image of [`Self::prompt`] to screen readers without giving
+    /// away the answer, e.g. `Japanese word, 3 characters, N3 noun`.
+    pub fn prompt_description(&self) -> String {
+        describe_prompt(&self.prompt, self.entry.as_deref(), self.sense, true)
+    }
+}
+
+/// Describes the image of `prompt` to screen readers without giving away the
+/// answer, by its language and length and, if it was made from `entry`, the
+/// part of speech of the sense numbered `sense` or of its first sense. The
+/// easiest level of `entry` is included if `show_level`.
+pub fn describe_prompt(
+    prompt: &str,
+    entry: Option<&DictEntry>,
+    sense: Option<u32>,
+    show_level: bool,
+) -> String {
+    let (language, length) = if prompt.is_ascii() {
+        let words = prompt.split_whitespace().count();
+        ("English", plural(words, "word"))
+    } else {
+        let characters = prompt.chars().filter(|c| !c.is_whitespace()).count();
+        ("Japanese", plural(characters, "character"))
+    };
+    let kind = if entry.is_some() { "word" } else { "text" };
+    let mut description = format!("{language} {kind}, {length}");
+
+    let Some(entry) = entry else {
+        return description;
+    };
+    let level = show_level
+        .then(|| entry.levels().last().map(ToString::to_string))
+        .flatten();
+    let class = entry
+        .senses
+        .iter()
+        .find(|s| sense.is_none_or(|number| s.number == number))
+        .and_then(|s| s.pos.iter().find_map(|&pos| word_class(pos)));
+    let details: Vec<_> = level.as_deref().into_iter().chain(class).collect();
+    if !details.is_empty() {
+        description.push_str(", ");
+        description.push_str(&details.join(" "));
+    }
+
+    description
+}
+
+/// Writes `count` of `noun`, e.g. `3 characters`.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Names the broad class of words `pos` belongs to, e.g. `noun`, if it is one
+/// players would know.
+fn word_class(pos: Pos) -> Option<&'static str> {
+    let class = match pos {
+        Pos::N | Pos::NPr | Pos::NAdv | Pos::NPref | Pos::NSuf | Pos::NT | Pos::Pn => "noun",
+        Pos::AdjI
+        | Pos::AdjIx
+        | Pos::AdjKari
+        | Pos::AdjKu
+        | Pos::AdjNa
+        | Pos::AdjNari
+        | Pos::AdjShiku
+        | Pos::AdjT
+        | Pos::AdjF
+        | Pos::AdjNo
+        | Pos::AdjPn => "adjective",
+        Pos::Adv | Pos::AdvTo => "adverb",
+        Pos::Exp => "expression",
+        Pos::Int => "interjection",
+        Pos::Conj => "conjunction",
+        Pos::Ctr => "counter",
+        Pos::Num => "numeral",
+        Pos::Prt => "particle",
+        pos if PosFilter::Verbs.as_pos().contains(&pos) => "verb",
+        _ => return None,
+    };
+
+    Some(class)
+}
+
+/// Filters selected when creating a game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Filters {
+    /// Selected NLevels.
+    pub levels: Vec<NLevel>,
+    /// Selected parts of speech.
+    pub pos: Vec<PosFilter>,
+    /// If set, only the entries with these ids are asked about.
+    pub entries: Option<HashSet<u32>>,
+    /// How closely the wrong options resemble the answer.
+    pub distractors: Difficulty,
+    /// If not empty, kanji modes ask about the kanji taught in these school
+    /// grades instead of the ones of [`Self::levels`].
+    #[serde(default)]
+    pub grades: Vec<u8>,
+    /// Entries never asked about, e.g. the ones the player was shown within the
+    /// [`REPEAT_COOLDOWN`](super::REPEAT_COOLDOWN).
+    #[serde(default)]
+    pub excluded: HashSet<u32>,
+    /// If set, the words asked about are picked near the ability of the
+    /// players of the session, as it is when each question is asked.
+    #[serde(skip)]
+    pub adaptive: Option<Adaptive>,
+}
+
+impl Filters {
+    /// Determines whether the entry with the id `id` can be asked about.
+    pub fn allows(&self, id: u32) -> bool {
+        self.entries.as_ref().is_none_or(|ids| ids.contains(&id)) && !self.excluded.contains(&id)
+    }
+}
+
+/// A generator of [`Question`]s that can be played as a game mode.
+///
+/// Sources are registered with [`Manager::register`](super::Manager::register)
+/// and listed by `/start`.
+pub trait QuestionSource: Send + Sync {
+    /// Name shown when picking a game mode. Must be unique among the registered sources.
+    fn name(&self) -> &str;
+
+    /// Creates the questions of a session in the order they are asked, drawing any
+    /// randomness from `rng`. The length of the iterator is the size of the session's pool.
+    /// Sources that aren't based on the dictionary may ignore `filters`.
+    fn questions(
+        &self,
+        filters: &Filters,
+        rng: StdRng,
+    ) -> Box<dyn ExactSizeIterator<Item = Question> + Send>;
+
+    /// Whether the answer of each question is as likely to be any of its
+    /// options, as audited by [`is_position_biased`](jplearnbot::question::is_position_biased).
+    /// Sources listing their options in a fixed order aren't audited.
+    fn shuffles_options(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use poise::serenity_prelude::UserId;
+
+/// How long a player isn't asked about an entry again after seeing it in a
+/// game, flashcard review, mock test or worksheet.
+pub const REPEAT_COOLDOWN: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The entries each player was shown within the last [`REPEAT_COOLDOWN`], which
+/// are left out of the pools made for them. It is kept in memory, so players
+/// may see entries again after the bot restarts.
+///
+/// Daily quizzes ask everyone in a server the same questions, so they only
+/// note the entries of the questions a player answers. Kanji and grammar games
+/// aren't about dictionary entries, and `/preview` asks about the one entry it
+/// is given, so they neither leave entries out nor note them.
+#[derive(Debug, Default)]
+pub struct RecentEntries {
+    /// When each player was last shown each entry, by entry id.
+    shown: DashMap<UserId, HashMap<u32, Instant>>,
+}
+
+impl RecentEntries {
+    /// Notes that `user_id` was shown the entry with the id `entry_id` just now.
+    pub fn mark(&self, user_id: UserId, entry_id: u32) {
+        self.shown
+            .entry(user_id)
+            .or_default()
+            .insert(entry_id, Instant::now());
+    }
+
+    /// Determines whether `user_id` was shown the entry with the id `entry_id`
+    /// within the last [`REPEAT_COOLDOWN`].
+    pub fn was_shown(&self, user_id: UserId, entry_id: u32) -> bool {
+        self.shown
+            .get(&user_id)
+            .and_then(|shown| shown.get(&entry_id).copied())
+            .is_some_and(|at| at.elapsed() < REPEAT_COOLDOWN)
+    }
+
+    /// Gets the ids of the entries `user_id` was shown within the last
+    /// [`REPEAT_COOLDOWN`], forgetting the ones shown before.
+    pub fn recent(&self, user_id: UserId) -> HashSet<u32> {
+        let Some(mut shown) = self.shown.get_mut(&user_id) else {
+            return HashSet::new();
+        };
+        shown.retain(|_, at| at.elapsed() < REPEAT_COOLDOWN);
+
+        shown.keys().copied().collect()
+    }
+}
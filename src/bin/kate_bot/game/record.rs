@@ -0,0 +1,24 @@
+use poise::serenity_prelude::UserId;
+
+/// A record of a question asked during a game.
+#[derive(Debug, Clone)]
+pub struct RoundRecord {
+    /// Identifier of the menu the question was asked with.
+    pub id: String,
+    /// Id of the dictionary entry the question was made from, if any.
+    pub entry_id: Option<u32>,
+    /// The word that was translated.
+    pub prompt: String,
+    /// Whether the prompt was heard rather than shown. It is then the reading
+    /// the answer is written with, so it can't be shown without giving it away.
+    pub spoken: bool,
+    /// The presented options.
+    pub options: Vec<String>,
+    /// The index of the correct option.
+    pub answer: usize,
+    /// Name of the mode the question was asked in.
+    pub mode: String,
+    /// Whether each player's first answer was correct, in the order they
+    /// answered. Empty until the round ends.
+    pub answers: Vec<(UserId, bool)>,
+}
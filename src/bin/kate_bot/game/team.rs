@@ -0,0 +1,136 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use poise::serenity_prelude::{ButtonStyle, CreateButton, CreateEmbed, UserId};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use super::SessionId;
+
+/// The two teams of a team game, named after the teams of a Japanese sports day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Team {
+    Red,
+    White,
+}
+
+impl Team {
+    /// Name of the team shown to players, along with its color.
+    pub fn label(self) -> &'static str {
+        match self {
+            Team::Red => "🟥 Red ・ 赤組",
+            Team::White => "⬜ White ・ 白組",
+        }
+    }
+
+    /// Gets the team playing against this one.
+    pub fn other(self) -> Team {
+        match self {
+            Team::Red => Team::White,
+            Team::White => Team::Red,
+        }
+    }
+
+    /// Suffix of the custom id of the button that joins this team.
+    fn id_suffix(self) -> &'static str {
+        match self {
+            Team::Red => "red",
+            Team::White => "white",
+        }
+    }
+
+    /// Creates the button that joins this team in `session_id`'s game.
+    pub fn button(self, session_id: SessionId) -> CreateButton {
+        let style = match self {
+            Team::Red => ButtonStyle::Danger,
+            Team::White => ButtonStyle::Secondary,
+        };
+
+        CreateButton::new(format!("{session_id},team,{}", self.id_suffix()))
+            .label(self.label())
+            .style(style)
+    }
+
+    /// Gets the team whose button has the custom id `custom_id`, if it is a team button.
+    pub fn from_button(custom_id: &str) -> Option<Team> {
+        let (_, suffix) = custom_id.rsplit_once(",team,")?;
+
+        Team::iter().find(|team| team.id_suffix() == suffix)
+    }
+}
+
+/// The team each player of a team game is on. Players stay on their team
+/// until the game ends.
+#[derive(Debug, Default)]
+pub struct Teams {
+    members: HashMap<UserId, Team>,
+}
+
+impl Teams {
+    /// Puts `user_id` on `team` unless they are already on a team.
+    ///
+    /// Returns the team they are on.
+    pub fn join(&mut self, user_id: UserId, team: Team) -> Team {
+        *self.members.entry(user_id).or_insert(team)
+    }
+
+    /// Gets the team of `user_id`, putting them on the team with fewer players
+    /// if they aren't on one yet, e.g. when they answer without picking one.
+    pub fn assign(&mut self, user_id: UserId) -> Team {
+        let size = |team| self.members.values().filter(|&&t| t == team).count();
+        let smaller = if size(Team::White) < size(Team::Red) {
+            Team::White
+        } else {
+            Team::Red
+        };
+
+        self.join(user_id, smaller)
+    }
+
+    /// Gets the players of `team` with their `scores`, the best first.
+    fn roster(&self, team: Team, scores: &HashMap<UserId, usize>) -> Vec<(UserId, usize)> {
+        let mut players: Vec<_> = self
+            .members
+            .iter()
+            .filter(|&(_, &t)| t == team)
+            .map(|(&user_id, _)| (user_id, scores.get(&user_id).copied().unwrap_or_default()))
+            .collect();
+        players.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        players
+    }
+
+    /// Creates the embed of the final results, where each team scores the sum
+    /// of its players' `scores`.
+    pub fn results_embed(&self, scores: &HashMap<UserId, usize>) -> CreateEmbed {
+        let red = self.roster(Team::Red, scores);
+        let white = self.roster(Team::White, scores);
+        let total = |roster: &[(UserId, usize)]| roster.iter().map(|(_, s)| s).sum::<usize>();
+
+        let title = match total(&red).cmp(&total(&white)) {
+            Ordering::Greater => "🟥 Red wins! ・ 赤組の勝ち！",
+            Ordering::Less => "⬜ White wins! ・ 白組の勝ち！",
+            Ordering::Equal => "It's a tie! ・ 引き分け！",
+        };
+
+        let mut embed = CreateEmbed::new().title(title);
+        for (team, roster) in [(Team::Red, red), (Team::White, white)] {
+            let players = if roster.is_empty() {
+                "No players".to_string()
+            } else {
+                roster
+                    .iter()
+                    .map(|(user_id, score)| format!("<@{user_id}>: {score}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+
+            embed = embed.field(
+                format!("{} ({})", team.label(), total(&roster)),
+                players,
+                true,
+            );
+        }
+
+        embed
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet};
+
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, CreateAttachment, CreateMessage, UserId, http::Http,
+};
+
+use super::RoundRecord;
+
+/// Sends `starter` a transcript of the game played in `channel_id` with the
+/// `records` of its rounds and the final `scores`, as a text file in DMs.
+pub(super) async fn send_transcript(
+    http: &Http,
+    starter: UserId,
+    channel_id: ChannelId,
+    records: &[RoundRecord],
+    scores: &HashMap<UserId, usize>,
+) -> Result<(), serenity::Error> {
+    // Players are named in the transcript, since mentions don't work in files.
+    let players: HashSet<UserId> = scores
+        .keys()
+        .copied()
+        .chain(
+            records
+                .iter()
+                .flat_map(|r| r.answers.iter().map(|&(user_id, _)| user_id)),
+        )
+        .collect();
+    let mut names = HashMap::new();
+    for user_id in players {
+        let name = match http.get_user(user_id).await {
+            Ok(user) => user.name,
+            Err(_) => user_id.to_string(),
+        };
+        names.insert(user_id, name);
+    }
+
+    let channel_name = match http.get_channel(channel_id).await {
+        Ok(serenity::Channel::Guild(channel)) => format!("#{}", channel.name),
+        _ => "DMs".to_string(),
+    };
+    let text = transcript(&channel_name, records, scores, &names);
+    let dm = starter.create_dm_channel(http).await?;
+    dm.send_message(
+        http,
+        CreateMessage::new()
+            .content(format!(
+                "Here is the transcript of your game in <#{channel_id}> ・ ゲームの記録です"
+            ))
+            .add_file(CreateAttachment::bytes(text, "transcript.txt")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Writes out each of the `records` of a game with its options, its answer along
+/// with the id of the entry it was made from, and who answered right and wrong,
+/// then the final `scores`. Players are written as their `names`.
+fn transcript(
+    channel_name: &str,
+    records: &[RoundRecord],
+    scores: &HashMap<UserId, usize>,
+    names: &HashMap<UserId, String>,
+) -> String {
+    let name = |user_id: &UserId| {
+        names
+            .get(user_id)
+            .cloned()
+            .unwrap_or_else(|| user_id.to_string())
+    };
+
+    let mut text = format!("Game in {channel_name}\n");
+    for (round, record) in records.iter().enumerate() {
+        text.push_str(&format!(
+            "\nQuestion {} · {}\n{}\n",
+            round + 1,
+            record.mode,
+            record.prompt
+        ));
+        for (i, option) in record.options.iter().enumerate() {
+            let mark = match record.entry_id {
+                _ if i != record.answer => String::new(),
+                Some(id) => format!("  ← answer, entry {id}"),
+                None => "  ← answer".to_string(),
+            };
+            text.push_str(&format!("  {}. {option}{mark}\n", i + 1));
+        }
+
+        let answered = |correct: bool| {
+            record
+                .answers
+                .iter()
+                .filter(|&&(_, c)| c == correct)
+                .map(|(user_id, _)| name(user_id))
+                .collect::<Vec<_>>()
+        };
+        let (right, wrong) = (answered(true), answered(false));
+        if right.is_empty() && wrong.is_empty() {
+            text.push_str("  Nobody answered\n");
+        }
+        if !right.is_empty() {
+            text.push_str(&format!("  Right: {}\n", right.join(", ")));
+        }
+        if !wrong.is_empty() {
+            text.push_str(&format!("  Wrong: {}\n", wrong.join(", ")));
+        }
+    }
+
+    let mut scores: Vec<_> = scores.iter().collect();
+    scores.sort_by(|(_, a), (_, b)| b.cmp(a));
+    if !scores.is_empty() {
+        text.push_str("\nFinal scores\n");
+        for (rank, (user_id, score)) in scores.into_iter().enumerate() {
+            text.push_str(&format!("  {}. {} {score}\n", rank + 1, name(user_id)));
+        }
+    }
+
+    text
+}
@@ -0,0 +1,52 @@
+use poise::serenity_prelude::{ComponentInteraction, UserId, http::Http};
+
+use super::{InteractionExitReason, lobby};
+
+/// The players of a [turn-based](super::Resolution::Turns) game, who are called
+/// on in the order they joined.
+#[derive(Debug, Default)]
+pub struct Turns {
+    players: Vec<UserId>,
+    /// Index of the player whose turn it is in [`Self::players`].
+    current: usize,
+    /// How many turns in a row were skipped because the player didn't answer.
+    missed: usize,
+}
+
+impl Turns {
+    /// Creates the rotation of `players`, who joined in that order.
+    pub fn new(players: Vec<UserId>) -> Self {
+        Turns {
+            players,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the player whose turn it is, or [`None`] if nobody joined.
+    pub fn current(&self) -> Option<UserId> {
+        self.players.get(self.current).copied()
+    }
+
+    /// Passes the turn to the next player, noting whether the current one `answered`.
+    pub fn advance(&mut self, answered: bool) {
+        self.missed = if answered { 0 } else { self.missed + 1 };
+        if !self.players.is_empty() {
+            self.current = (self.current + 1) % self.players.len();
+        }
+    }
+
+    /// Determines whether every player's last turn was skipped, so nobody is playing.
+    pub fn all_missed(&self) -> bool {
+        !self.players.is_empty() && self.missed >= self.players.len()
+    }
+
+    /// Adds the player of `ci`, a press of the join button after the join
+    /// phase, to the end of the rotation.
+    pub(super) async fn respond_to_join(
+        &mut self,
+        http: &Http,
+        ci: &ComponentInteraction,
+    ) -> Result<(), InteractionExitReason> {
+        lobby::respond_to_join(http, ci, &mut self.players).await
+    }
+}
@@ -1,56 +1,206 @@
-use std::{io::Cursor, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Cursor,
+    sync::{Arc, LazyLock, RwLock},
+};
 
-use image::{ImageBuffer, Luma};
+use image::{ImageBuffer, Luma, Rgb, RgbImage};
+use poise::serenity_prelude::GuildId;
+use regex::Regex;
 use rusttype::{Font, Scale, point};
 
-/// Converts `text` into a rasterized PNG image in bytes.
-pub fn text_to_image(text: &str) -> Vec<u8> {
-    static FONT: LazyLock<Font<'static>> = LazyLock::new(|| {
-        static FONT_DATA: &[u8; 5728064] = include_bytes!(concat!(
-            env!("CARGO_MANIFEST_DIR"),
-            "/fonts/NotoSansJPBold.ttf"
-        ));
+use crate::{
+    db::Database,
+    perf::{self, Stage},
+};
 
-        Font::try_from_bytes(FONT_DATA).unwrap()
-    });
+/// Font text is rendered in, unless the Server has its own.
+static FONT: LazyLock<Arc<Font<'static>>> = LazyLock::new(|| {
+    static FONT_DATA: &[u8; 5728064] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/fonts/NotoSansJPBold.ttf"
+    ));
 
-    let scale = Scale::uniform(72.0);
-    let v_metrics = FONT.v_metrics(scale);
+    Font::try_from_bytes(FONT_DATA).unwrap().into()
+});
 
-    const PADDING: f32 = 60.0;
+/// Fonts Servers uploaded with `/settings font`, which replace [`FONT`] in their images.
+static GUILD_FONTS: LazyLock<RwLock<HashMap<GuildId, Arc<Font<'static>>>>> =
+    LazyLock::new(Default::default);
+
+/// Largest font file in bytes a Server can upload.
+pub const MAX_FONT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Text a font must be able to render to be used, covering the scripts of prompts.
+const FONT_SAMPLE: &str = "あア漢字Aa1";
+
+/// Parses an uploaded font file, checking that it can render [`FONT_SAMPLE`].
+///
+/// # Errors
+/// Fails with the reason to show the uploader if `bytes` aren't a TrueType or
+/// OpenType font, or the font is missing glyphs of the sample.
+pub fn parse_font(bytes: Vec<u8>) -> Result<Font<'static>, &'static str> {
+    let font = Font::try_from_vec(bytes).ok_or("That file isn't a TrueType or OpenType font")?;
+
+    // Characters without a glyph are mapped to the missing glyph, whose id is 0.
+    if FONT_SAMPLE.chars().any(|c| font.glyph(c).id().0 == 0) {
+        return Err("That font is missing hiragana, katakana, kanji, or Latin characters");
+    }
+    render_text(FONT_SAMPLE, &font);
+
+    Ok(font)
+}
 
-    let glyphs: Vec<_> = FONT
-        .layout(text, scale, point(PADDING, PADDING + v_metrics.ascent))
-        .collect();
-
-    let glyphs_height = (v_metrics.ascent - v_metrics.descent).ceil() as u32;
-    let glyphs_width = {
-        let min_x = glyphs
-            .first()
-            .map(|g| g.pixel_bounding_box().unwrap().min.x)
-            .unwrap();
-        let max_x = glyphs
-            .last()
-            .map(|g| g.pixel_bounding_box().unwrap().max.x)
-            .unwrap();
-        (max_x - min_x) as u32
+/// Loads the fonts Servers uploaded from `db`.
+///
+/// Returns how many were loaded. Fonts that can no longer be parsed are skipped.
+pub fn load_guild_fonts(db: &Database) -> usize {
+    let fonts = match db.guild_fonts() {
+        Ok(fonts) => fonts,
+        Err(e) => {
+            eprintln!("Failed to get fonts of Servers: {e}");
+            return 0;
+        }
     };
 
+    let mut guild_fonts = GUILD_FONTS.write().unwrap_or_else(|e| e.into_inner());
+    for (guild_id, bytes) in fonts {
+        match parse_font(bytes) {
+            Ok(font) => {
+                guild_fonts.insert(guild_id, font.into());
+            }
+            Err(e) => eprintln!("Failed to load font of {guild_id}: {e}"),
+        }
+    }
+
+    guild_fonts.len()
+}
+
+/// Renders the images of `guild_id` in `font`, or in the default font if [`None`].
+pub fn set_guild_font(guild_id: GuildId, font: Option<Font<'static>>) {
+    let mut guild_fonts = GUILD_FONTS.write().unwrap_or_else(|e| e.into_inner());
+    match font {
+        Some(font) => guild_fonts.insert(guild_id, font.into()),
+        None => guild_fonts.remove(&guild_id),
+    };
+}
+
+/// Gets the font the images of `guild_id` are rendered in.
+fn font_of(guild_id: Option<GuildId>) -> Arc<Font<'static>> {
+    guild_id
+        .and_then(|guild_id| {
+            GUILD_FONTS
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&guild_id)
+                .cloned()
+        })
+        .unwrap_or_else(|| Arc::clone(&FONT))
+}
+
+/// Converts `text` into a rasterized PNG image in bytes, in the font of
+/// `guild_id` if it has one.
+///
+/// Long lines are wrapped, and text that would take more than a few lines is
+/// scaled down, e.g. for long glosses.
+pub fn text_to_image(text: &str, guild_id: Option<GuildId>) -> Vec<u8> {
+    let font = font_of(guild_id);
+
+    perf::time(Stage::Rendering, || render_text(text, &font))
+}
+
+/// Renders `text` in `font` for [`text_to_image`].
+fn render_text(text: &str, font: &Font) -> Vec<u8> {
+    const PADDING: f32 = 60.0;
+    /// Widest a line can be in pixels before it's wrapped.
+    const MAX_WIDTH: f32 = 1200.0;
+    /// Lines the text is scaled down to fit in, if it can be.
+    const MAX_LINES: usize = 3;
+    const MAX_SCALE: f32 = 72.0;
+    const MIN_SCALE: f32 = 36.0;
+    const SCALE_STEP: f32 = 12.0;
+
+    let mut scale = Scale::uniform(MAX_SCALE);
+    let mut lines = wrap(text, font, scale, MAX_WIDTH);
+    while lines.len() > MAX_LINES && scale.y > MIN_SCALE {
+        scale = Scale::uniform(scale.y - SCALE_STEP);
+        lines = wrap(text, font, scale, MAX_WIDTH);
+    }
+
+    let v_metrics = font.v_metrics(scale);
+    let line_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
+
+    let glyphs_width = lines
+        .iter()
+        .map(|line| line_width(line, font, scale))
+        .fold(0.0, f32::max)
+        .ceil() as u32;
+    let glyphs_height = (line_height * lines.len() as f32 - v_metrics.line_gap).ceil() as u32;
+
     let mut image = ImageBuffer::<Luma<u8>, Vec<u8>>::from_pixel(
         glyphs_width + (PADDING * 2.0) as u32,
         glyphs_height + (PADDING * 2.0) as u32,
         Luma([255]),
     );
 
-    for glyph in glyphs {
+    for (i, line) in lines.iter().enumerate() {
+        let baseline = PADDING + v_metrics.ascent + line_height * i as f32;
+
+        for glyph in font.layout(line, scale, point(PADDING, baseline)) {
+            if let Some(bounding_box) = glyph.pixel_bounding_box() {
+                glyph.draw(|x, y, v| {
+                    // Offset the position by the glyph bounding box
+                    let x = x as i32 + bounding_box.min.x;
+                    let y = y as i32 + bounding_box.min.y;
+                    if let Some(pixel) = image.get_pixel_mut_checked(x as u32, y as u32) {
+                        *pixel = Luma([255 - (v * 255.0) as u8]);
+                    }
+                });
+            }
+        }
+    }
+
+    let mut buf = Cursor::new(Vec::new());
+    image.write_to(&mut buf, image::ImageFormat::Png).unwrap();
+
+    buf.into_inner()
+}
+
+/// Converts a single kana, or a digraph like きゃ, into a rasterized PNG image
+/// in bytes, drawn large and centered so beginners can make out its strokes.
+/// It is drawn in the font of `guild_id` if it has one.
+pub fn kana_to_image(kana: &str, guild_id: Option<GuildId>) -> Vec<u8> {
+    let font = font_of(guild_id);
+
+    perf::time(Stage::Rendering, || render_kana(kana, &font))
+}
+
+/// Renders `kana` in `font` for [`kana_to_image`].
+fn render_kana(kana: &str, font: &Font) -> Vec<u8> {
+    /// Height of the image, and its least width.
+    const SIZE: u32 = 400;
+    /// Least space left on either side of the kana, e.g. of wide digraphs.
+    const PADDING: f32 = 60.0;
+    const SCALE: f32 = 240.0;
+
+    let scale = Scale::uniform(SCALE);
+    let v_metrics = font.v_metrics(scale);
+
+    let glyphs_width = line_width(kana, font, scale);
+    let width = SIZE.max((glyphs_width + PADDING * 2.0).ceil() as u32);
+    let x = (width as f32 - glyphs_width) / 2.0;
+    let baseline = (SIZE as f32 + v_metrics.ascent + v_metrics.descent) / 2.0;
+
+    let mut image = ImageBuffer::<Luma<u8>, Vec<u8>>::from_pixel(width, SIZE, Luma([255]));
+    for glyph in font.layout(kana, scale, point(x, baseline)) {
         if let Some(bounding_box) = glyph.pixel_bounding_box() {
             glyph.draw(|x, y, v| {
-                image.put_pixel(
-                    // Offset the position by the glyph bounding box
-                    x + bounding_box.min.x as u32,
-                    y + bounding_box.min.y as u32,
-                    Luma([255 - (v * 255.0) as u8]),
-                )
+                let x = x as i32 + bounding_box.min.x;
+                let y = y as i32 + bounding_box.min.y;
+                if let Some(pixel) = image.get_pixel_mut_checked(x as u32, y as u32) {
+                    *pixel = Luma([255 - (v * 255.0) as u8]);
+                }
             });
         }
     }
@@ -60,3 +210,407 @@ pub fn text_to_image(text: &str) -> Vec<u8> {
 
     buf.into_inner()
 }
+
+/// Converts a printable page into a rasterized PNG image in bytes, in the font
+/// of `guild_id` if it has one. The page has a `title`, followed by `paragraphs`
+/// with some space between each, and grows as long as they need.
+pub fn page_to_image(title: &str, paragraphs: &[String], guild_id: Option<GuildId>) -> Vec<u8> {
+    let font = font_of(guild_id);
+
+    perf::time(Stage::Rendering, || render_page(title, paragraphs, &font))
+}
+
+/// Renders a page in `font` for [`page_to_image`].
+fn render_page(title: &str, paragraphs: &[String], font: &Font) -> Vec<u8> {
+    /// Width of the page, that of A4 paper printed at 150 DPI.
+    const WIDTH: u32 = 1240;
+    const PADDING: f32 = 90.0;
+    const TITLE_SCALE: f32 = 52.0;
+    const BODY_SCALE: f32 = 34.0;
+    /// Space between paragraphs, in lines of the body.
+    const PARAGRAPH_GAP: f32 = 0.6;
+
+    let max_width = WIDTH as f32 - PADDING * 2.0;
+    let line_height = |scale: Scale| {
+        let v_metrics = font.v_metrics(scale);
+        v_metrics.ascent - v_metrics.descent + v_metrics.line_gap
+    };
+
+    // Every line with its scale and where its baseline is.
+    let mut lines = Vec::new();
+    let mut y = PADDING;
+    let title_scale = Scale::uniform(TITLE_SCALE);
+    for line in wrap(title, font, title_scale, max_width) {
+        lines.push((line, title_scale, y + font.v_metrics(title_scale).ascent));
+        y += line_height(title_scale);
+    }
+    let body_scale = Scale::uniform(BODY_SCALE);
+    for paragraph in paragraphs {
+        y += line_height(body_scale) * PARAGRAPH_GAP;
+        for line in wrap(paragraph, font, body_scale, max_width) {
+            lines.push((line, body_scale, y + font.v_metrics(body_scale).ascent));
+            y += line_height(body_scale);
+        }
+    }
+
+    let height = (y + PADDING).ceil() as u32;
+    let mut image = ImageBuffer::<Luma<u8>, Vec<u8>>::from_pixel(WIDTH, height, Luma([255]));
+    for (line, scale, baseline) in lines {
+        for glyph in font.layout(&line, scale, point(PADDING, baseline)) {
+            if let Some(bounding_box) = glyph.pixel_bounding_box() {
+                glyph.draw(|x, y, v| {
+                    let x = x as i32 + bounding_box.min.x;
+                    let y = y as i32 + bounding_box.min.y;
+                    if let Some(pixel) = image.get_pixel_mut_checked(x as u32, y as u32) {
+                        *pixel = Luma([255 - (v * 255.0) as u8]);
+                    }
+                });
+            }
+        }
+    }
+
+    let mut buf = Cursor::new(Vec::new());
+    image.write_to(&mut buf, image::ImageFormat::Png).unwrap();
+
+    buf.into_inner()
+}
+
+/// Breaks `text` into lines no wider than `max_width` in `font` at `scale`, keeping
+/// its own line breaks. A word wider than `max_width` gets a line of its own.
+fn wrap(text: &str, font: &Font, scale: Scale, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+
+        for unit in break_units(paragraph) {
+            let candidate = format!("{line}{unit}");
+            if line.is_empty() || line_width(candidate.trim_end(), font, scale) <= max_width {
+                line = candidate;
+            } else {
+                lines.push(line.trim_end().to_string());
+                line = unit.trim_start().to_string();
+            }
+        }
+
+        lines.push(line.trim_end().to_string());
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    lines
+}
+
+/// Splits `text` into the pieces a line can be broken between: words along with
+/// the whitespace after them, and single characters of scripts written without
+/// spaces, like Japanese.
+fn break_units(text: &str) -> Vec<&str> {
+    /// Start of the CJK blocks of Unicode, whose characters can be broken between.
+    const CJK_START: char = '\u{3000}';
+
+    let mut units = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        let end = i + c.len_utf8();
+
+        if c >= CJK_START {
+            if start < i {
+                units.push(&text[start..i]);
+            }
+            units.push(&text[i..end]);
+            start = end;
+        } else if c.is_whitespace() {
+            units.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        units.push(&text[start..]);
+    }
+
+    units
+}
+
+/// Measures how wide `line` is in pixels in `font` at `scale`.
+fn line_width(line: &str, font: &Font, scale: Scale) -> f32 {
+    font.layout(line, scale, point(0.0, 0.0))
+        .last()
+        .map_or(0.0, |g| {
+            g.position().x + g.unpositioned().h_metrics().advance_width
+        })
+}
+
+/// A stroke of a kanji as a polyline in KanjiVG's 109×109 coordinate space.
+type Stroke = Vec<(f32, f32)>;
+
+/// Strokes of every kanji with KanjiVG data, in the order they are written.
+///
+/// Loaded from the directory at the `KANJIVG_PATH` env var, `content/kanjivg`
+/// by default, which should hold KanjiVG's `kanji/` files, e.g. `04e00.svg`.
+static STROKE_ORDERS: LazyLock<HashMap<char, Vec<Stroke>>> = LazyLock::new(|| {
+    let dir = std::env::var("KANJIVG_PATH").unwrap_or("content/kanjivg".to_string());
+
+    let Ok(files) = fs::read_dir(&dir) else {
+        eprintln!("No KanjiVG data at {dir}, stroke orders are unavailable");
+        return HashMap::new();
+    };
+
+    files
+        .filter_map(Result::ok)
+        .filter_map(|file| {
+            let path = file.path();
+            // Variants like 04e00-Kaisho.svg aren't parsed as a code point.
+            let code = u32::from_str_radix(path.file_stem()?.to_str()?, 16).ok()?;
+            let kanji = char::from_u32(code)?;
+            let strokes = parse_kanjivg(&fs::read_to_string(&path).ok()?);
+
+            (!strokes.is_empty()).then_some((kanji, strokes))
+        })
+        .collect()
+});
+
+/// Loads the KanjiVG data and returns how many kanji have a stroke order.
+pub fn load_stroke_orders() -> usize {
+    STROKE_ORDERS.len()
+}
+
+/// Determines whether `kanji` has a stroke order to render.
+pub fn has_stroke_order(kanji: char) -> bool {
+    STROKE_ORDERS.contains_key(&kanji)
+}
+
+/// Renders how each character of `text` with a stroke order is written, one stroke
+/// at a time, as a PNG image in bytes. Characters without one are skipped.
+///
+/// Returns None if no character of `text` has a stroke order.
+pub fn stroke_order_to_image(text: &str) -> Option<Vec<u8>> {
+    perf::time(Stage::Rendering, || render_stroke_order(text))
+}
+
+/// Renders the stroke order of `text` for [`stroke_order_to_image`].
+fn render_stroke_order(text: &str) -> Option<Vec<u8>> {
+    /// Size of a panel in pixels.
+    const PANEL: u32 = 160;
+    /// Panels per row before wrapping.
+    const PER_ROW: usize = 8;
+    /// Radius of strokes in pixels.
+    const PEN: f32 = 4.0;
+
+    const BACKGROUND: Rgb<u8> = Rgb([255, 255, 255]);
+    const GUIDE: Rgb<u8> = Rgb([220, 220, 220]);
+    const WRITTEN: Rgb<u8> = Rgb([170, 170, 170]);
+    const CURRENT: Rgb<u8> = Rgb([0, 0, 0]);
+    const START: Rgb<u8> = Rgb([220, 40, 40]);
+
+    let kanjis: Vec<_> = text.chars().filter_map(|c| STROKE_ORDERS.get(&c)).collect();
+    if kanjis.is_empty() {
+        return None;
+    }
+
+    let rows = |strokes: &[Stroke]| strokes.len().div_ceil(PER_ROW) as u32;
+    let columns = kanjis.iter().map(|s| s.len()).max()?.min(PER_ROW) as u32;
+    let height = kanjis.iter().map(|s| rows(s)).sum::<u32>();
+
+    let mut image = RgbImage::from_pixel(columns * PANEL, height * PANEL, BACKGROUND);
+    let scale = PANEL as f32 / 109.0;
+
+    let mut top = 0;
+    for strokes in kanjis {
+        for i in 0..strokes.len() {
+            let left = (i % PER_ROW) as u32 * PANEL;
+            let top = top + (i / PER_ROW) as u32 * PANEL;
+            let at = |(x, y): (f32, f32)| (left as f32 + x * scale, top as f32 + y * scale);
+
+            // Border and center guides of the panel.
+            let (right, bottom) = ((left + PANEL - 1) as f32, (top + PANEL - 1) as f32);
+            let (mid_x, mid_y) = at((54.5, 54.5));
+            for (from, to) in [
+                ((left as f32, top as f32), (right, top as f32)),
+                ((left as f32, top as f32), (left as f32, bottom)),
+                ((mid_x, top as f32), (mid_x, bottom)),
+                ((left as f32, mid_y), (right, mid_y)),
+            ] {
+                draw_line(&mut image, from, to, 0.5, GUIDE);
+            }
+
+            for (j, stroke) in strokes[..=i].iter().enumerate() {
+                let color = if j == i { CURRENT } else { WRITTEN };
+                for segment in stroke.windows(2) {
+                    draw_line(&mut image, at(segment[0]), at(segment[1]), PEN, color);
+                }
+            }
+
+            if let Some(&start) = strokes[i].first() {
+                draw_line(&mut image, at(start), at(start), PEN * 1.5, START);
+            }
+        }
+
+        top += rows(strokes) * PANEL;
+    }
+
+    let mut buf = Cursor::new(Vec::new());
+    image.write_to(&mut buf, image::ImageFormat::Png).unwrap();
+
+    Some(buf.into_inner())
+}
+
+/// Draws a line from `from` to `to` with round ends of `radius` in `color`.
+fn draw_line(
+    image: &mut RgbImage,
+    (x0, y0): (f32, f32),
+    (x1, y1): (f32, f32),
+    radius: f32,
+    color: Rgb<u8>,
+) {
+    let steps = ((x1 - x0).hypot(y1 - y0) * 2.0).ceil().max(1.0) as u32;
+    let reach = radius.ceil() as i32;
+
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        let (cx, cy) = (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+                if x < 0 || y < 0 || x as u32 >= image.width() || y as u32 >= image.height() {
+                    continue;
+                }
+                if (x as f32 - cx).hypot(y as f32 - cy) <= radius {
+                    image.put_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+}
+
+/// Parses the strokes of a KanjiVG SVG file, in the order they are written.
+fn parse_kanjivg(svg: &str) -> Vec<Stroke> {
+    static PATH: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"<path\b[^>]*?\bd="([^"]*)""#).unwrap());
+
+    PATH.captures_iter(svg)
+        .map(|caps| parse_path(&caps[1]))
+        .filter(|stroke| stroke.len() > 1)
+        .collect()
+}
+
+/// Flattens the path data `d` of an SVG path into a polyline. Supports the
+/// move, line, and cubic Bézier commands, which are all KanjiVG uses.
+fn parse_path(d: &str) -> Stroke {
+    static TOKEN: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"[MmLlHhVvCcSsZz]|[-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?").unwrap()
+    });
+    /// Segments each curve is split into.
+    const CURVE_STEPS: u32 = 12;
+
+    let mut points = Vec::new();
+    let mut numbers = Vec::new();
+    let mut command = 'M';
+    let mut current = (0.0, 0.0);
+    // Second control point of the last curve, reflected by smooth curves.
+    let mut last_control: Option<(f32, f32)> = None;
+
+    // Runs `command` on the collected `numbers` if there are enough of them.
+    let mut apply = |command: char, numbers: &mut Vec<f32>| {
+        let relative = command.is_ascii_lowercase();
+        let offset = |(x, y): (f32, f32), (cx, cy): (f32, f32)| {
+            if relative { (cx + x, cy + y) } else { (x, y) }
+        };
+
+        let needed = match command.to_ascii_uppercase() {
+            'M' | 'L' => 2,
+            'H' | 'V' => 1,
+            'C' => 6,
+            'S' => 4,
+            _ => {
+                numbers.clear();
+                return;
+            }
+        };
+        if numbers.len() < needed {
+            return;
+        }
+
+        match command.to_ascii_uppercase() {
+            'M' | 'L' => {
+                current = offset((numbers[0], numbers[1]), current);
+                points.push(current);
+                last_control = None;
+            }
+            'H' => {
+                current.0 = if relative {
+                    current.0 + numbers[0]
+                } else {
+                    numbers[0]
+                };
+                points.push(current);
+                last_control = None;
+            }
+            'V' => {
+                current.1 = if relative {
+                    current.1 + numbers[0]
+                } else {
+                    numbers[0]
+                };
+                points.push(current);
+                last_control = None;
+            }
+            // Cubic Bézier curves. Smooth ones reflect the last control point.
+            _ => {
+                let c1 = if needed == 4 {
+                    last_control
+                        .map_or(current, |(x, y)| (2.0 * current.0 - x, 2.0 * current.1 - y))
+                } else {
+                    offset((numbers[0], numbers[1]), current)
+                };
+                let rest = &numbers[needed - 4..needed];
+                let c2 = offset((rest[0], rest[1]), current);
+                let end = offset((rest[2], rest[3]), current);
+
+                for step in 1..=CURVE_STEPS {
+                    let t = step as f32 / CURVE_STEPS as f32;
+                    let u = 1.0 - t;
+                    let bezier = |p0: f32, p1: f32, p2: f32, p3: f32| {
+                        u * u * u * p0
+                            + 3.0 * u * u * t * p1
+                            + 3.0 * u * t * t * p2
+                            + t * t * t * p3
+                    };
+                    points.push((
+                        bezier(current.0, c1.0, c2.0, end.0),
+                        bezier(current.1, c1.1, c2.1, end.1),
+                    ));
+                }
+
+                current = end;
+                last_control = Some(c2);
+            }
+        }
+
+        numbers.drain(..needed);
+    };
+
+    for token in TOKEN.find_iter(d).map(|m| m.as_str()) {
+        match token.parse::<f32>() {
+            Ok(number) => {
+                numbers.push(number);
+                apply(command, &mut numbers);
+                // Extra coordinates after a move are implicit lines.
+                if command == 'M' && numbers.is_empty() {
+                    command = 'L';
+                } else if command == 'm' && numbers.is_empty() {
+                    command = 'l';
+                }
+            }
+            Err(_) => {
+                command = token.chars().next().unwrap_or('M');
+                numbers.clear();
+            }
+        }
+    }
+
+    points
+}
@@ -1,44 +1,180 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use dotenvy::dotenv;
+use jplearnbot::dictionary::Dictionary;
 use poise::{
     Framework,
-    serenity_prelude::{self as serenity, GuildId},
+    serenity_prelude::{self as serenity, ChannelId, GuildId},
 };
+use tokio::task::JoinSet;
 
+use crate::{audio::TtsBackend, personality::Personality};
+
+mod audio;
 mod command;
+mod daily;
+mod db;
 mod dictionary;
 mod game;
 mod image;
 mod emote;
+mod error;
+mod feedback;
+mod format;
+mod pagination;
+mod perf;
+mod personality;
+#[cfg(feature = "voice")]
+mod voice;
 
 pub struct Data {
     pub manager: Arc<game::Manager>,
+    pub db: Arc<db::Database>,
+    /// Channel where reported questions are posted for maintainers, if configured.
+    pub report_channel: Option<ChannelId>,
 }
 pub type Context<'a> = poise::Context<'a, Data, Error>;
-pub type Error = Box<dyn std::error::Error + Send + Sync>;
+pub type Error = jplearnbot::Error;
+
+/// Longest active games are waited for to finish when the bot shuts down,
+/// within the grace period `docker stop` gives before killing the bot.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(8);
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
 
-    let token = std::env::var("DISCORD_TOKEN").expect("Missing `DISCORD_TOKEN` env var.");
-    let intents = serenity::GatewayIntents::non_privileged();
+    let personalities = personality::from_env().expect("Failed to configure bots");
+
+    let db_path: PathBuf = std::env::var("DATABASE_PATH")
+        .unwrap_or("kate_bot.db".to_string())
+        .into();
+    let db: Arc<_> = db::Database::open(&db_path)
+        .expect("Failed to open database")
+        .into();
+
+    println!(
+        "Loaded stroke orders of {} kanji",
+        image::load_stroke_orders()
+    );
+    println!("Loaded fonts of {} Server(s)", image::load_guild_fonts(&db));
+    println!(
+        "Loaded emotes of {} Server(s)",
+        emote::load_guild_emotes(&db)
+    );
+
+    let dictionary: Arc<_> = dictionary::load(&db).into();
+    println!(
+        "Dictionary of {} entries uses about {}",
+        dictionary.len(),
+        dictionary.footprint()
+    );
+
+    let report_channel = std::env::var("REPORT_CHANNEL_ID")
+        .ok()
+        .map(|v| ChannelId::new(v.parse().unwrap()));
+
+    let tts = audio::backend_from_env();
+    if tts.is_none() {
+        println!("No text-to-speech service configured, hiding listening modes");
+    }
+
+    let mut bots = JoinSet::new();
+    for personality in personalities {
+        let shared = Shared {
+            db: Arc::clone(&db),
+            dictionary: Arc::clone(&dictionary),
+            tts: tts.clone(),
+            report_channel,
+        };
+        bots.spawn(run(personality, shared));
+    }
+    while let Some(result) = bots.join_next().await {
+        result.unwrap();
+    }
+}
+
+/// State shared by every bot the process runs.
+struct Shared {
+    db: Arc<db::Database>,
+    dictionary: Arc<Dictionary>,
+    tts: Option<Arc<dyn TtsBackend>>,
+    /// Channel where reported questions are posted for maintainers, if configured.
+    report_channel: Option<ChannelId>,
+}
+
+/// Runs the bot of `personality` with the state it `shared` with the others,
+/// until it shuts down.
+async fn run(personality: Personality, shared: Shared) {
+    let primary = personality.is_primary();
+    let Personality {
+        name,
+        token,
+        feedback,
+    } = personality;
+
+    // Message content is needed to read typed answers.
+    let intents =
+        serenity::GatewayIntents::non_privileged() | serenity::GatewayIntents::MESSAGE_CONTENT;
+    // Voice states are needed to find the voice channel of players and to join it.
+    #[cfg(feature = "voice")]
+    let intents = intents | serenity::GatewayIntents::GUILD_VOICE_STATES;
 
     let framework: Framework<Data, Error> = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![command::start(), command::stop(), command::info()],
-            event_handler: |_ctx, event, framework, _data| {
-                Box::pin(event_handler(event.clone(), framework))
+            commands: vec![
+                command::start(),
+                command::stop(),
+                command::pause(),
+                command::resume(),
+                command::info(),
+                command::replay(),
+                command::bookmarks(),
+                command::flashcards(),
+                command::wordlist(),
+                command::study(),
+                command::mocktest(),
+                command::worksheet(),
+                command::stats(),
+                command::leaderboard(),
+                command::rank(),
+                command::help(),
+                command::daily(),
+                command::settings(),
+                command::lookup(),
+                command::entry(),
+                command::preview(),
+                command::grammar(),
+                command::kanji(),
+                command::mnemonic(),
+                command::mnemonics(),
+                command::reports(),
+                command::admin(),
+            ],
+            event_handler: |ctx, event, framework, _data| {
+                Box::pin(event_handler(ctx.clone(), event.clone(), framework))
             },
+            pre_command: |ctx| {
+                Box::pin(async move {
+                    let command = &ctx.command().qualified_name;
+                    if let Err(e) =
+                        ctx.data()
+                            .db
+                            .record_command(command, ctx.author().id, ctx.guild_id())
+                    {
+                        eprintln!("Failed to record usage of /{command}: {e}");
+                    }
+                })
+            },
+            on_error: |error| Box::pin(error::on_error(error)),
             ..Default::default()
         })
-        .setup(|ctx, _ready, framework| {
+        .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 if let Ok(guild_id) =
                     std::env::var("DISCORD_DEV_GUILD_ID").map(|v| v.parse().unwrap())
                 {
-                    println!("Registering commands to DEV Guild");
+                    println!("Registering commands of {name} to DEV Guild");
                     poise::builtins::register_in_guild(
                         ctx,
                         &framework.options().commands,
@@ -46,33 +182,112 @@ async fn main() {
                     )
                     .await?;
                 } else {
-                    println!("Registering commands globally");
+                    println!("Registering commands of {name} globally");
                     poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 }
 
+                let Shared {
+                    db,
+                    dictionary,
+                    tts,
+                    report_channel,
+                } = shared;
+                let manager =
+                    game::Manager::new(ctx.http.clone(), Arc::clone(&db), dictionary, tts)
+                        .with_feedback(feedback);
+                #[cfg(feature = "voice")]
+                let manager = manager.with_songbird(songbird::get(ctx).await);
+                let manager: Arc<_> = manager.into();
+
+                if primary {
+                    tokio::spawn(daily::schedule(
+                        ctx.http.clone(),
+                        Arc::clone(&db),
+                        Arc::clone(&manager),
+                    ));
+
+                    let resume_manager = Arc::clone(&manager);
+                    tokio::spawn(async move { resume_manager.offer_resumes().await });
+                }
+
+                let shard_manager = Arc::clone(framework.shard_manager());
+                let shutdown_manager = Arc::clone(&manager);
+                tokio::spawn(async move {
+                    shutdown_signal().await;
+                    println!("Shutting down {name}");
+                    shutdown_manager.shutdown(SHUTDOWN_TIMEOUT).await;
+                    shard_manager.shutdown_all().await;
+                });
+
                 Ok(Data {
-                    manager: game::Manager::new(ctx.http.clone()).into(),
+                    manager,
+                    db,
+                    report_channel,
                 })
             })
         })
         .build();
 
-    let client = serenity::ClientBuilder::new(token, intents)
-        .framework(framework)
-        .await;
+    let client = serenity::ClientBuilder::new(token, intents).framework(framework);
+    #[cfg(feature = "voice")]
+    let client = songbird::SerenityInit::register_songbird(client);
+    let client = client.await;
 
     client.unwrap().start().await.unwrap();
 }
 
+/// Waits for ctrl-c, or for SIGTERM on Unix, e.g. from `docker stop`.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut terminate = signal(SignalKind::terminate()).expect("failed to listen for SIGTERM");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await.ok();
+}
+
 async fn event_handler(
+    ctx: serenity::Context,
     event: serenity::FullEvent,
     framework: poise::FrameworkContext<'_, Data, Error>,
 ) -> Result<(), Error> {
-    if let serenity::FullEvent::InteractionCreate { interaction } = event {
-        if let Some(interaction) = interaction.into_message_component() {
-            framework.user_data.manager.send(interaction).await;
-        };
-    };
+    match event {
+        serenity::FullEvent::InteractionCreate { interaction } => {
+            if let Some(interaction) = interaction.into_message_component() {
+                let result = if command::is_save_button(&interaction) {
+                    command::handle_save_button(&ctx, &interaction, framework.user_data).await
+                } else if command::is_report_button(&interaction) {
+                    command::handle_report_button(&ctx, &interaction, framework.user_data).await
+                } else if command::is_stroke_order_button(&interaction) {
+                    command::handle_stroke_order_button(&ctx, &interaction).await
+                } else if command::is_resume_button(&interaction) {
+                    command::handle_resume_button(&ctx, &interaction, framework.user_data).await
+                } else if command::is_daily_answer_button(&interaction) {
+                    command::handle_daily_answer_button(&ctx, &interaction, framework.user_data)
+                        .await
+                } else {
+                    framework.user_data.manager.send(interaction).await;
+                    return Ok(());
+                };
+                // Failed presses are shown to the player rather than left to
+                // time out in Discord.
+                if let Err(e) = result {
+                    error::respond_to_component(&ctx, &interaction, &e).await;
+                }
+            }
+        }
+        serenity::FullEvent::Message { new_message } if !new_message.author.bot => {
+            framework.user_data.manager.send_message(new_message).await;
+        }
+        _ => {}
+    }
 
     Ok(())
 }
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use poise::{
+    CreateReply,
+    serenity_prelude::{
+        ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+        CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+    },
+};
+
+use crate::{Context, Error};
+
+/// Replies with the first of `pages` in an embed titled `title`, with buttons
+/// to flip through the rest. Listens for the buttons until they go unused for
+/// five minutes.
+pub async fn paginate(
+    ctx: Context<'_>,
+    title: &str,
+    pages: &[String],
+    ephemeral: bool,
+) -> Result<(), Error> {
+    paginate_with(ctx, title, pages, ephemeral, |_| Vec::new()).await
+}
+
+/// Like [`paginate`], with the `buttons` of each page, e.g. to save the entry
+/// it describes, next to the buttons flipping through them.
+pub async fn paginate_with(
+    ctx: Context<'_>,
+    title: &str,
+    pages: &[String],
+    ephemeral: bool,
+    buttons: impl Fn(usize) -> Vec<CreateButton>,
+) -> Result<(), Error> {
+    let prev_id = format!("{}-prev", ctx.id());
+    let next_id = format!("{}-next", ctx.id());
+
+    let embed = |page: usize| {
+        CreateEmbed::new()
+            .title(title)
+            .description(&pages[page])
+            .footer(CreateEmbedFooter::new(format!(
+                "Page {}/{}",
+                page + 1,
+                pages.len()
+            )))
+    };
+
+    let components = |page: usize| {
+        let mut row = Vec::new();
+        // No need to flip through a single page.
+        if pages.len() > 1 {
+            row.push(CreateButton::new(&prev_id).emoji('◀'));
+            row.push(CreateButton::new(&next_id).emoji('▶'));
+        }
+        row.extend(buttons(page));
+
+        if row.is_empty() {
+            Vec::new()
+        } else {
+            vec![CreateActionRow::Buttons(row)]
+        }
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed(0))
+            .components(components(0))
+            .ephemeral(ephemeral),
+    )
+    .await?;
+
+    if pages.len() <= 1 {
+        return Ok(());
+    }
+
+    let mut page = 0;
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .filter({
+            let ids = [prev_id.clone(), next_id.clone()];
+            move |press| ids.contains(&press.data.custom_id)
+        })
+        .timeout(Duration::from_secs(300))
+        .await
+    {
+        page = if press.data.custom_id == next_id {
+            (page + 1) % pages.len()
+        } else {
+            page.checked_sub(1).unwrap_or(pages.len() - 1)
+        };
+
+        press
+            .create_response(
+                ctx,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed(page))
+                        .components(components(page)),
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+#[cfg(feature = "perf")]
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{LazyLock, Mutex},
+    time::Instant,
+};
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use jplearnbot::question::OPTIONS;
+
+#[cfg(feature = "perf")]
+use tracing::Instrument;
+
+/// Hot paths of the bot whose latencies are profiled when the `perf` feature
+/// is enabled. Each is timed in a `tracing` span named `perf`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum_macros::EnumIter, strum_macros::Display,
+)]
+pub enum Stage {
+    #[strum(to_string = "Sampling")]
+    Sampling,
+    #[strum(to_string = "Question generation")]
+    Generation,
+    #[strum(to_string = "Image rendering")]
+    Rendering,
+    #[strum(to_string = "Speech synthesis")]
+    Speech,
+    #[strum(to_string = "Discord sends")]
+    Send,
+}
+
+/// Latencies of the recent runs of a [`Stage`].
+pub struct Latency {
+    pub stage: Stage,
+    /// How many runs the percentiles are of.
+    pub samples: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+}
+
+/// Most recent timings kept per [`Stage`].
+#[cfg(feature = "perf")]
+const MAX_SAMPLES: usize = 1000;
+
+/// How many questions were asked with their answer at each option since the
+/// bot started, of the sources that [shuffle their options](crate::game::QuestionSource::shuffles_options).
+static ANSWER_POSITIONS: [AtomicU64; OPTIONS] = [const { AtomicU64::new(0) }; OPTIONS];
+
+#[cfg(feature = "perf")]
+static TIMINGS: LazyLock<Mutex<HashMap<Stage, VecDeque<Duration>>>> =
+    LazyLock::new(Default::default);
+
+/// Runs `f` as part of `stage`.
+pub fn time<T>(stage: Stage, f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "perf")]
+    {
+        let _span = tracing::info_span!("perf", %stage).entered();
+        let start = Instant::now();
+        let res = f();
+        record(stage, start.elapsed());
+        res
+    }
+
+    #[cfg(not(feature = "perf"))]
+    {
+        let _ = stage;
+        f()
+    }
+}
+
+/// Awaits `future` as part of `stage`.
+pub async fn time_async<T>(stage: Stage, future: impl Future<Output = T>) -> T {
+    #[cfg(feature = "perf")]
+    {
+        let start = Instant::now();
+        let res = future.instrument(tracing::info_span!("perf", %stage)).await;
+        record(stage, start.elapsed());
+        res
+    }
+
+    #[cfg(not(feature = "perf"))]
+    {
+        let _ = stage;
+        future.await
+    }
+}
+
+/// Stores that a run of `stage` took `elapsed`, forgetting the oldest run past
+/// [`MAX_SAMPLES`].
+#[cfg(feature = "perf")]
+fn record(stage: Stage, elapsed: Duration) {
+    let mut timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    let samples = timings.entry(stage).or_default();
+
+    if samples.len() == MAX_SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(elapsed);
+}
+
+/// Gets the latencies of every [`Stage`] that has run.
+///
+/// Returns [`None`] if the `perf` feature is disabled.
+pub fn latencies() -> Option<Vec<Latency>> {
+    #[cfg(feature = "perf")]
+    {
+        use strum::IntoEnumIterator;
+
+        let timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+
+        Some(
+            Stage::iter()
+                .filter_map(|stage| {
+                    let mut samples: Vec<_> = timings.get(&stage)?.iter().copied().collect();
+                    samples.sort();
+
+                    Some(Latency {
+                        stage,
+                        samples: samples.len(),
+                        p50: percentile(&samples, 0.5),
+                        p95: percentile(&samples, 0.95),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    #[cfg(not(feature = "perf"))]
+    None
+}
+
+/// Gets the `p`th percentile of the non-empty `sorted` durations.
+#[cfg(feature = "perf")]
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index]
+}
+
+/// Counts a question asked with its answer at the `position`th option.
+pub fn record_answer_position(position: usize) {
+    if let Some(count) = ANSWER_POSITIONS.get(position) {
+        count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Gets how many questions were asked with their answer at each option.
+pub fn answer_positions() -> [u64; OPTIONS] {
+    std::array::from_fn(|i| ANSWER_POSITIONS[i].load(Ordering::Relaxed))
+}
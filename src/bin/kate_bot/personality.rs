@@ -0,0 +1,79 @@
+use std::{env, path::PathBuf, sync::Arc};
+
+use crate::{Error, feedback::MessagePacks};
+
+/// Name of the bot of `DISCORD_TOKEN`, which is always run.
+const PRIMARY: &str = "kate";
+
+/// A bot the process runs with its own Discord token and messages. Every bot
+/// shares the dictionary and the database, so progress carries over between them.
+pub struct Personality {
+    /// Name of the bot, e.g. `sensei`, which its environment variables end with.
+    pub name: String,
+    pub token: String,
+    /// Messages telling players their answers are wrong.
+    pub feedback: Arc<MessagePacks>,
+}
+
+impl Personality {
+    /// Whether this is the bot of `DISCORD_TOKEN`, which posts the daily quizzes
+    /// and offers to resume interrupted games for every bot, so they aren't
+    /// posted once per bot.
+    pub fn is_primary(&self) -> bool {
+        self.name == PRIMARY
+    }
+}
+
+/// Reads the bots to run: the one of `DISCORD_TOKEN`, then one for each name
+/// listed in `PERSONALITIES`, separated by commas, e.g. `sensei` with the token
+/// `DISCORD_TOKEN_SENSEI`. A bot's messages are read from `FEEDBACK_PATH`, e.g.
+/// `FEEDBACK_PATH_SENSEI`, or are the bundled ones.
+///
+/// # Errors
+/// Fails if a bot's token is missing, a name is listed twice, or a bot's
+/// messages can't be read.
+pub fn from_env() -> Result<Vec<Personality>, Error> {
+    let others = env::var("PERSONALITIES").unwrap_or_default();
+    let names = std::iter::once(PRIMARY).chain(
+        others
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty()),
+    );
+
+    let mut personalities: Vec<Personality> = Vec::new();
+    for name in names {
+        let name = name.to_lowercase();
+        if personalities.iter().any(|p| p.name == name) {
+            return Err(Error::Other(
+                format!("Bot {name} is listed twice in `PERSONALITIES`").into(),
+            ));
+        }
+
+        let token_var = var_of(&name, "DISCORD_TOKEN");
+        let token = env::var(&token_var)
+            .map_err(|_| Error::Other(format!("Missing `{token_var}` env var.").into()))?;
+        let feedback = match env::var(var_of(&name, "FEEDBACK_PATH")) {
+            Ok(path) => MessagePacks::load(&PathBuf::from(path))?.into(),
+            Err(_) => MessagePacks::bundled(),
+        };
+
+        personalities.push(Personality {
+            name,
+            token,
+            feedback,
+        });
+    }
+
+    Ok(personalities)
+}
+
+/// Gets the environment variable `var` of the bot named `name`, e.g.
+/// `DISCORD_TOKEN_SENSEI`, or `var` itself for the bot of `DISCORD_TOKEN`.
+fn var_of(name: &str, var: &str) -> String {
+    if name == PRIMARY {
+        var.to_string()
+    } else {
+        format!("{var}_{}", name.to_uppercase())
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use poise::serenity_prelude::{ChannelId, GuildId};
+use songbird::{Call, Songbird};
+use tokio::sync::Mutex;
+
+use crate::{
+    Context, Error,
+    audio::{self, TtsBackend},
+};
+
+/// Gets the voice channel of the Server `ctx` was invoked in that its author is in, if any.
+pub fn author_channel(ctx: &Context<'_>) -> Option<ChannelId> {
+    ctx.guild()?.voice_states.get(&ctx.author().id)?.channel_id
+}
+
+/// The bot's connection to the voice channel a game's prompts are read aloud in.
+pub struct VoiceHost {
+    songbird: Arc<Songbird>,
+    guild_id: GuildId,
+    call: Arc<Mutex<Call>>,
+}
+
+impl VoiceHost {
+    /// Joins `channel_id` of `guild_id`.
+    ///
+    /// # Errors
+    /// Fails if the bot is already in a voice channel of the Server, e.g. for
+    /// another game, or can't connect to `channel_id`.
+    pub async fn join(
+        songbird: Arc<Songbird>,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<Self, Error> {
+        if songbird.get(guild_id).is_some() {
+            return Err(Error::Other(
+                "already in a voice channel of the Server".into(),
+            ));
+        }
+
+        match songbird.join(guild_id, channel_id).await {
+            Ok(call) => Ok(VoiceHost {
+                songbird,
+                guild_id,
+                call,
+            }),
+            Err(e) => {
+                // Failed joins still leave a call behind, which would block later ones.
+                songbird.remove(guild_id).await.ok();
+                Err(Error::Other(e.into()))
+            }
+        }
+    }
+
+    /// Reads `text` aloud with `tts`, cutting off whatever was still being read.
+    pub async fn say(&self, tts: &dyn TtsBackend, text: &str) -> Result<(), Error> {
+        let speech = audio::speak(tts, text).await?;
+        self.call.lock().await.play_only_input(speech.bytes.into());
+
+        Ok(())
+    }
+
+    /// Leaves the voice channel.
+    pub async fn leave(self) {
+        if let Err(e) = self.songbird.remove(self.guild_id).await {
+            eprintln!("Failed to leave voice channel in {}: {e}", self.guild_id);
+        }
+    }
+}
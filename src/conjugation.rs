@@ -0,0 +1,567 @@
+use strum_macros::EnumIter;
+
+use crate::dictionary::Pos;
+
+/// Parts of speech whose words can be conjugated with [`conjugate`].
+pub const CONJUGABLE: [Pos; 20] = [
+    Pos::V1,
+    Pos::V1S,
+    Pos::V5aru,
+    Pos::V5b,
+    Pos::V5g,
+    Pos::V5k,
+    Pos::V5kS,
+    Pos::V5m,
+    Pos::V5n,
+    Pos::V5r,
+    Pos::V5rI,
+    Pos::V5s,
+    Pos::V5t,
+    Pos::V5u,
+    Pos::V5uS,
+    Pos::Vk,
+    Pos::VsI,
+    Pos::Vz,
+    Pos::AdjI,
+    Pos::AdjIx,
+];
+
+/// A form verbs and i-adjectives are conjugated into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, strum_macros::Display)]
+pub enum Form {
+    /// e.g. 食べない.
+    #[strum(to_string = "Negative ない形")]
+    Negative,
+    /// e.g. 食べた.
+    #[strum(to_string = "Past た形")]
+    Past,
+    /// e.g. 食べなかった.
+    #[strum(to_string = "Past negative なかった形")]
+    PastNegative,
+    /// e.g. 食べて.
+    #[strum(to_string = "Te-form て形")]
+    Te,
+    /// e.g. 食べます.
+    #[strum(to_string = "Polite ます形")]
+    Polite,
+    /// e.g. 食べられる.
+    #[strum(to_string = "Potential 可能形")]
+    Potential,
+    /// e.g. 食べられる.
+    #[strum(to_string = "Passive 受身形")]
+    Passive,
+    /// e.g. 食べさせる.
+    #[strum(to_string = "Causative 使役形")]
+    Causative,
+    /// e.g. 食べよう.
+    #[strum(to_string = "Volitional 意向形")]
+    Volitional,
+    /// e.g. 食べろ.
+    #[strum(to_string = "Imperative 命令形")]
+    Imperative,
+    /// e.g. 食べれば.
+    #[strum(to_string = "Conditional ば形")]
+    Conditional,
+}
+
+/// Conjugates `word`, a verb or i-adjective in its dictionary form, into
+/// `form` as a word of the part of speech `pos`. Words can be spelled in kanji
+/// or kana, since only their kana ending changes.
+///
+/// Returns [`None`] if words of `pos` aren't conjugated, `word` doesn't end as
+/// words of `pos` do, or `pos` has no such form, e.g. i-adjectives have no
+/// [`Form::Potential`].
+pub fn conjugate(word: &str, pos: Pos, form: Form) -> Option<String> {
+    match pos {
+        Pos::V1 | Pos::V1S => ichidan(word, pos, form),
+        Pos::V5aru
+        | Pos::V5b
+        | Pos::V5g
+        | Pos::V5k
+        | Pos::V5kS
+        | Pos::V5m
+        | Pos::V5n
+        | Pos::V5r
+        | Pos::V5rI
+        | Pos::V5s
+        | Pos::V5t
+        | Pos::V5u
+        | Pos::V5uS => godan(word, pos, form),
+        Pos::Vk => kuru(word, form),
+        Pos::VsI => suru(word, form),
+        Pos::Vz => zuru(word, form),
+        Pos::AdjI | Pos::AdjIx => adjective(word, pos, form),
+        _ => None,
+    }
+}
+
+/// Conjugates an ichidan verb, e.g. 食べる. くれる has no ろ in its imperative.
+fn ichidan(word: &str, pos: Pos, form: Form) -> Option<String> {
+    let stem = word.strip_suffix('る')?;
+    let ending = match form {
+        Form::Negative => "ない",
+        Form::Past => "た",
+        Form::PastNegative => "なかった",
+        Form::Te => "て",
+        Form::Polite => "ます",
+        Form::Potential | Form::Passive => "られる",
+        Form::Causative => "させる",
+        Form::Volitional => "よう",
+        Form::Imperative if pos == Pos::V1S => "",
+        Form::Imperative => "ろ",
+        Form::Conditional => "れば",
+    };
+
+    Some(format!("{stem}{ending}"))
+}
+
+/// Conjugates a godan verb, e.g. 書く, by changing the vowel of its last kana.
+fn godan(word: &str, pos: Pos, form: Form) -> Option<String> {
+    let last = word.chars().last()?;
+    let stem = &word[..word.len() - last.len_utf8()];
+    let expected = match pos {
+        Pos::V5b => 'ぶ',
+        Pos::V5g => 'ぐ',
+        Pos::V5k | Pos::V5kS => 'く',
+        Pos::V5m => 'む',
+        Pos::V5n => 'ぬ',
+        Pos::V5aru | Pos::V5r | Pos::V5rI => 'る',
+        Pos::V5s => 'す',
+        Pos::V5t => 'つ',
+        _ => 'う',
+    };
+    if last != expected {
+        return None;
+    }
+
+    let [a, i, e, o] = godan_row(last)?;
+    let (te, ta) = match pos {
+        // 行く is the only く verb whose te-form is 行って.
+        Pos::V5kS => ("って", "った"),
+        // e.g. 問うて rather than 問って.
+        Pos::V5uS => ("うて", "うた"),
+        _ => godan_te(last)?,
+    };
+
+    let conjugated = match form {
+        // ある has no あ stem, so its negatives replace it altogether.
+        Form::Negative if pos == Pos::V5rI => format!("{}ない", drop_last(stem)),
+        Form::PastNegative if pos == Pos::V5rI => format!("{}なかった", drop_last(stem)),
+        Form::Negative => format!("{stem}{a}ない"),
+        Form::Past => format!("{stem}{ta}"),
+        Form::PastNegative => format!("{stem}{a}なかった"),
+        Form::Te => format!("{stem}{te}"),
+        // e.g. なさいます and なさい rather than なさります and なされ.
+        Form::Polite if pos == Pos::V5aru => format!("{stem}います"),
+        Form::Imperative if pos == Pos::V5aru => format!("{stem}い"),
+        Form::Polite => format!("{stem}{i}ます"),
+        Form::Potential => format!("{stem}{e}る"),
+        Form::Passive => format!("{stem}{a}れる"),
+        Form::Causative => format!("{stem}{a}せる"),
+        Form::Volitional => format!("{stem}{o}う"),
+        Form::Imperative => format!("{stem}{e}"),
+        Form::Conditional => format!("{stem}{e}ば"),
+    };
+
+    Some(conjugated)
+}
+
+/// Gets the kana of the a, i, e, and o rows that godan verbs ending in `last`
+/// conjugate with, e.g. か, き, け, and こ for く. う verbs take わ in place of あ.
+fn godan_row(last: char) -> Option<[char; 4]> {
+    let row = match last {
+        'う' => ['わ', 'い', 'え', 'お'],
+        'く' => ['か', 'き', 'け', 'こ'],
+        'ぐ' => ['が', 'ぎ', 'げ', 'ご'],
+        'す' => ['さ', 'し', 'せ', 'そ'],
+        'つ' => ['た', 'ち', 'て', 'と'],
+        'ぬ' => ['な', 'に', 'ね', 'の'],
+        'ぶ' => ['ば', 'び', 'べ', 'ぼ'],
+        'む' => ['ま', 'み', 'め', 'も'],
+        'る' => ['ら', 'り', 'れ', 'ろ'],
+        _ => return None,
+    };
+
+    Some(row)
+}
+
+/// Gets the endings of the te-form and past of godan verbs ending in `last`,
+/// e.g. いて and いた for く.
+fn godan_te(last: char) -> Option<(&'static str, &'static str)> {
+    let endings = match last {
+        'う' | 'つ' | 'る' => ("って", "った"),
+        'く' => ("いて", "いた"),
+        'ぐ' => ("いで", "いだ"),
+        'す' => ("して", "した"),
+        'ぬ' | 'ぶ' | 'む' => ("んで", "んだ"),
+        _ => return None,
+    };
+
+    Some(endings)
+}
+
+/// Conjugates 来る, whose stem changes with its form, or a word ending in it,
+/// e.g. 持って来る. Written in kanji, only the ending after 来 changes.
+fn kuru(word: &str, form: Form) -> Option<String> {
+    if let Some(prefix) = word.strip_suffix("来る") {
+        let ending = match form {
+            Form::Negative => "ない",
+            Form::Past => "た",
+            Form::PastNegative => "なかった",
+            Form::Te => "て",
+            Form::Polite => "ます",
+            Form::Potential | Form::Passive => "られる",
+            Form::Causative => "させる",
+            Form::Volitional => "よう",
+            Form::Imperative => "い",
+            Form::Conditional => "れば",
+        };
+        return Some(format!("{prefix}来{ending}"));
+    }
+
+    let prefix = word.strip_suffix("くる")?;
+    let conjugated = match form {
+        Form::Negative => "こない",
+        Form::Past => "きた",
+        Form::PastNegative => "こなかった",
+        Form::Te => "きて",
+        Form::Polite => "きます",
+        Form::Potential | Form::Passive => "こられる",
+        Form::Causative => "こさせる",
+        Form::Volitional => "こよう",
+        Form::Imperative => "こい",
+        Form::Conditional => "くれば",
+    };
+
+    Some(format!("{prefix}{conjugated}"))
+}
+
+/// Conjugates する or a word ending in it, e.g. 勉強する.
+fn suru(word: &str, form: Form) -> Option<String> {
+    let prefix = word.strip_suffix("する")?;
+    let conjugated = match form {
+        Form::Negative => "しない",
+        Form::Past => "した",
+        Form::PastNegative => "しなかった",
+        Form::Te => "して",
+        Form::Polite => "します",
+        Form::Potential => "できる",
+        Form::Passive => "される",
+        Form::Causative => "させる",
+        Form::Volitional => "しよう",
+        Form::Imperative => "しろ",
+        Form::Conditional => "すれば",
+    };
+
+    Some(format!("{prefix}{conjugated}"))
+}
+
+/// Conjugates a verb ending in ずる, e.g. 信ずる, in its modern forms, which
+/// are those of the ichidan verb ending in じる but for the conditional.
+fn zuru(word: &str, form: Form) -> Option<String> {
+    let prefix = word.strip_suffix("ずる")?;
+    if form == Form::Conditional {
+        return Some(format!("{prefix}ずれば"));
+    }
+
+    ichidan(&format!("{prefix}じる"), Pos::V1, form)
+}
+
+/// Conjugates an i-adjective, e.g. 高い, or one of the いい class, e.g.
+/// かっこいい, which conjugates as よい. I-adjectives only have negatives,
+/// pasts, te-forms, and conditionals.
+fn adjective(word: &str, pos: Pos, form: Form) -> Option<String> {
+    let stem = if pos == Pos::AdjIx {
+        match word.strip_suffix("いい") {
+            Some(prefix) => format!("{prefix}よ"),
+            None => word.strip_suffix('い')?.to_string(),
+        }
+    } else {
+        word.strip_suffix('い')?.to_string()
+    };
+    let ending = match form {
+        Form::Negative => "くない",
+        Form::Past => "かった",
+        Form::PastNegative => "くなかった",
+        Form::Te => "くて",
+        Form::Conditional => "ければ",
+        _ => return None,
+    };
+
+    Some(format!("{stem}{ending}"))
+}
+
+/// Drops the last character of `text`.
+fn drop_last(text: &str) -> &str {
+    let mut chars = text.chars();
+    chars.next_back();
+    chars.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    /// Conjugates `word` into every form, in the order of [`Form`].
+    fn all_forms(word: &str, pos: Pos) -> Vec<Option<String>> {
+        Form::iter()
+            .map(|form| conjugate(word, pos, form))
+            .collect()
+    }
+
+    /// Wraps `forms` as conjugations, in the order of [`Form`].
+    fn expected(forms: [&str; 11]) -> Vec<Option<String>> {
+        forms.iter().map(|f| Some(f.to_string())).collect()
+    }
+
+    #[test]
+    fn conjugates_ichidan_verbs() {
+        assert_eq!(
+            all_forms("食べる", Pos::V1),
+            expected([
+                "食べない",
+                "食べた",
+                "食べなかった",
+                "食べて",
+                "食べます",
+                "食べられる",
+                "食べられる",
+                "食べさせる",
+                "食べよう",
+                "食べろ",
+                "食べれば",
+            ])
+        );
+        assert_eq!(
+            conjugate("みる", Pos::V1, Form::Volitional).as_deref(),
+            Some("みよう")
+        );
+        assert_eq!(
+            conjugate("くれる", Pos::V1S, Form::Imperative).as_deref(),
+            Some("くれ")
+        );
+        assert_eq!(
+            conjugate("くれる", Pos::V1S, Form::Past).as_deref(),
+            Some("くれた")
+        );
+    }
+
+    #[test]
+    fn conjugates_godan_verbs() {
+        assert_eq!(
+            all_forms("書く", Pos::V5k),
+            expected([
+                "書かない",
+                "書いた",
+                "書かなかった",
+                "書いて",
+                "書きます",
+                "書ける",
+                "書かれる",
+                "書かせる",
+                "書こう",
+                "書け",
+                "書けば",
+            ])
+        );
+        assert_eq!(
+            all_forms("買う", Pos::V5u),
+            expected([
+                "買わない",
+                "買った",
+                "買わなかった",
+                "買って",
+                "買います",
+                "買える",
+                "買われる",
+                "買わせる",
+                "買おう",
+                "買え",
+                "買えば",
+            ])
+        );
+    }
+
+    #[test]
+    fn conjugates_godan_te_forms_by_ending() {
+        let cases = [
+            ("泳ぐ", Pos::V5g, "泳いで", "泳いだ"),
+            ("話す", Pos::V5s, "話して", "話した"),
+            ("待つ", Pos::V5t, "待って", "待った"),
+            ("死ぬ", Pos::V5n, "死んで", "死んだ"),
+            ("遊ぶ", Pos::V5b, "遊んで", "遊んだ"),
+            ("読む", Pos::V5m, "読んで", "読んだ"),
+            ("帰る", Pos::V5r, "帰って", "帰った"),
+            ("かく", Pos::V5k, "かいて", "かいた"),
+        ];
+
+        for (word, pos, te, past) in cases {
+            assert_eq!(
+                conjugate(word, pos, Form::Te).as_deref(),
+                Some(te),
+                "{word}"
+            );
+            assert_eq!(
+                conjugate(word, pos, Form::Past).as_deref(),
+                Some(past),
+                "{word}"
+            );
+        }
+    }
+
+    #[test]
+    fn conjugates_special_godan_verbs() {
+        assert_eq!(
+            conjugate("行く", Pos::V5kS, Form::Te).as_deref(),
+            Some("行って")
+        );
+        assert_eq!(
+            conjugate("いく", Pos::V5kS, Form::Past).as_deref(),
+            Some("いった")
+        );
+        assert_eq!(
+            conjugate("行く", Pos::V5kS, Form::Negative).as_deref(),
+            Some("行かない")
+        );
+
+        assert_eq!(
+            conjugate("問う", Pos::V5uS, Form::Te).as_deref(),
+            Some("問うて")
+        );
+        assert_eq!(
+            conjugate("問う", Pos::V5uS, Form::Past).as_deref(),
+            Some("問うた")
+        );
+        assert_eq!(
+            conjugate("問う", Pos::V5uS, Form::Negative).as_deref(),
+            Some("問わない")
+        );
+
+        assert_eq!(
+            conjugate("ある", Pos::V5rI, Form::Negative).as_deref(),
+            Some("ない")
+        );
+        assert_eq!(
+            conjugate("有る", Pos::V5rI, Form::PastNegative).as_deref(),
+            Some("なかった")
+        );
+        assert_eq!(
+            conjugate("ある", Pos::V5rI, Form::Past).as_deref(),
+            Some("あった")
+        );
+
+        assert_eq!(
+            conjugate("なさる", Pos::V5aru, Form::Polite).as_deref(),
+            Some("なさいます")
+        );
+        assert_eq!(
+            conjugate("いらっしゃる", Pos::V5aru, Form::Imperative).as_deref(),
+            Some("いらっしゃい")
+        );
+        assert_eq!(
+            conjugate("くださる", Pos::V5aru, Form::Te).as_deref(),
+            Some("くださって")
+        );
+    }
+
+    #[test]
+    fn conjugates_irregular_verbs() {
+        assert_eq!(
+            all_forms("くる", Pos::Vk),
+            expected([
+                "こない",
+                "きた",
+                "こなかった",
+                "きて",
+                "きます",
+                "こられる",
+                "こられる",
+                "こさせる",
+                "こよう",
+                "こい",
+                "くれば",
+            ])
+        );
+        assert_eq!(
+            conjugate("持って来る", Pos::Vk, Form::Imperative).as_deref(),
+            Some("持って来い")
+        );
+
+        assert_eq!(
+            all_forms("勉強する", Pos::VsI),
+            expected([
+                "勉強しない",
+                "勉強した",
+                "勉強しなかった",
+                "勉強して",
+                "勉強します",
+                "勉強できる",
+                "勉強される",
+                "勉強させる",
+                "勉強しよう",
+                "勉強しろ",
+                "勉強すれば",
+            ])
+        );
+        assert_eq!(
+            conjugate("する", Pos::VsI, Form::Te).as_deref(),
+            Some("して")
+        );
+
+        assert_eq!(
+            conjugate("信ずる", Pos::Vz, Form::Negative).as_deref(),
+            Some("信じない")
+        );
+        assert_eq!(
+            conjugate("信ずる", Pos::Vz, Form::Conditional).as_deref(),
+            Some("信ずれば")
+        );
+    }
+
+    #[test]
+    fn conjugates_adjectives() {
+        let forms = [
+            Form::Negative,
+            Form::Past,
+            Form::PastNegative,
+            Form::Te,
+            Form::Conditional,
+        ];
+        let conjugated = |word, pos| {
+            forms
+                .iter()
+                .map(|&form| conjugate(word, pos, form).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            conjugated("高い", Pos::AdjI),
+            ["高くない", "高かった", "高くなかった", "高くて", "高ければ"]
+        );
+        assert_eq!(
+            conjugated("いい", Pos::AdjIx),
+            ["よくない", "よかった", "よくなかった", "よくて", "よければ"]
+        );
+        assert_eq!(
+            conjugate("かっこいい", Pos::AdjIx, Form::Past).as_deref(),
+            Some("かっこよかった")
+        );
+        assert_eq!(
+            conjugate("良い", Pos::AdjIx, Form::Past).as_deref(),
+            Some("良かった")
+        );
+
+        assert_eq!(conjugate("高い", Pos::AdjI, Form::Potential), None);
+        assert_eq!(conjugate("高い", Pos::AdjI, Form::Polite), None);
+    }
+
+    #[test]
+    fn rejects_mismatched_words() {
+        assert_eq!(conjugate("書く", Pos::V5g, Form::Te), None);
+        assert_eq!(conjugate("食べた", Pos::V1, Form::Te), None);
+        assert_eq!(conjugate("静か", Pos::AdjI, Form::Past), None);
+        assert_eq!(conjugate("本", Pos::N, Form::Past), None);
+        assert_eq!(conjugate("する", Pos::Vk, Form::Past), None);
+    }
+}
@@ -0,0 +1,83 @@
+/// A counter word (助数詞) attached to numbers when counting things.
+pub struct Counter {
+    /// How the counter is written, e.g. 本.
+    pub kanji: &'static str,
+    /// What the counter is used to count.
+    pub usage: &'static str,
+    /// Readings of counting one through ten with this counter.
+    pub readings: [&'static str; 10],
+}
+
+impl Counter {
+    /// Gets the reading of counting `n` with this counter.
+    ///
+    /// Returns [`None`] if `n` is outside of one through ten.
+    pub fn reading(&self, n: usize) -> Option<&'static str> {
+        self.readings.get(n.checked_sub(1)?).copied()
+    }
+}
+
+/// Commonly used counters.
+#[rustfmt::skip]
+pub const COUNTERS: [Counter; 12] = [
+    Counter {
+        kanji: "つ",
+        usage: "general things",
+        readings: ["ひとつ", "ふたつ", "みっつ", "よっつ", "いつつ", "むっつ", "ななつ", "やっつ", "ここのつ", "とお"],
+    },
+    Counter {
+        kanji: "人",
+        usage: "people",
+        readings: ["ひとり", "ふたり", "さんにん", "よにん", "ごにん", "ろくにん", "しちにん", "はちにん", "きゅうにん", "じゅうにん"],
+    },
+    Counter {
+        kanji: "本",
+        usage: "long, thin objects",
+        readings: ["いっぽん", "にほん", "さんぼん", "よんほん", "ごほん", "ろっぽん", "ななほん", "はっぽん", "きゅうほん", "じゅっぽん"],
+    },
+    Counter {
+        kanji: "枚",
+        usage: "flat objects",
+        readings: ["いちまい", "にまい", "さんまい", "よんまい", "ごまい", "ろくまい", "ななまい", "はちまい", "きゅうまい", "じゅうまい"],
+    },
+    Counter {
+        kanji: "匹",
+        usage: "small animals",
+        readings: ["いっぴき", "にひき", "さんびき", "よんひき", "ごひき", "ろっぴき", "ななひき", "はっぴき", "きゅうひき", "じゅっぴき"],
+    },
+    Counter {
+        kanji: "個",
+        usage: "small objects",
+        readings: ["いっこ", "にこ", "さんこ", "よんこ", "ごこ", "ろっこ", "ななこ", "はっこ", "きゅうこ", "じゅっこ"],
+    },
+    Counter {
+        kanji: "冊",
+        usage: "books",
+        readings: ["いっさつ", "にさつ", "さんさつ", "よんさつ", "ごさつ", "ろくさつ", "ななさつ", "はっさつ", "きゅうさつ", "じゅっさつ"],
+    },
+    Counter {
+        kanji: "杯",
+        usage: "cups and glasses of drink",
+        readings: ["いっぱい", "にはい", "さんばい", "よんはい", "ごはい", "ろっぱい", "ななはい", "はっぱい", "きゅうはい", "じゅっぱい"],
+    },
+    Counter {
+        kanji: "回",
+        usage: "occurrences",
+        readings: ["いっかい", "にかい", "さんかい", "よんかい", "ごかい", "ろっかい", "ななかい", "はっかい", "きゅうかい", "じゅっかい"],
+    },
+    Counter {
+        kanji: "階",
+        usage: "floors of a building",
+        readings: ["いっかい", "にかい", "さんがい", "よんかい", "ごかい", "ろっかい", "ななかい", "はっかい", "きゅうかい", "じゅっかい"],
+    },
+    Counter {
+        kanji: "歳",
+        usage: "years of age",
+        readings: ["いっさい", "にさい", "さんさい", "よんさい", "ごさい", "ろくさい", "ななさい", "はっさい", "きゅうさい", "じゅっさい"],
+    },
+    Counter {
+        kanji: "日",
+        usage: "days of the month",
+        readings: ["ついたち", "ふつか", "みっか", "よっか", "いつか", "むいか", "なのか", "ようか", "ここのか", "とおか"],
+    },
+];
@@ -1,11 +1,417 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+    io::BufRead,
+    sync::RwLock,
+};
+
+use rand::{
+    Rng,
+    seq::{IndexedRandom, SliceRandom},
+};
 use serde::{self, Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumString};
 
+use crate::{
+    heap_size::{HeapSize, format_bytes, no_heap},
+    kana,
+    question::Pairing,
+};
+
+mod packed;
+mod provider;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use packed::{EntryRef, ExampleRef, KanjiRef, ReadingRef, SenseRef};
+pub use provider::DictionaryProvider;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDictionary;
+
+use packed::{Span, Tables, TextIndex};
+
+/// A collection of [`DictEntry`]'s, e.g. the one generated by dict_combine,
+/// that can be queried by id, spelling, meaning, [`NLevel`], and [`Pos`].
+///
+/// Entries are packed into tables of kanjis, readings, senses, and glosses when
+/// the dictionary is created, and are handed out as [`EntryRef`] handles into
+/// them. Entries are indexed at the same time, so lookups don't scan every entry.
+/// Indexes hold rows of the entry table, in ascending order.
+pub struct Dictionary {
+    /// Contains all of the entries, in the order they were read.
+    tables: Tables,
+    /// Maps an entry's id to its row.
+    ids: HashMap<u32, u32>,
+    /// Maps a reading to the entries with it.
+    readings: TextIndex,
+    /// Maps a kanji spelling to the entries with it.
+    kanjis: TextIndex,
+    /// Every gloss in lowercase along with its entry.
+    glosses: Vec<(Span, u32)>,
+    /// All the levels at least one entry is tagged with, in order.
+    levels: Vec<NLevel>,
+    /// Maps a level to the entries tagged with it.
+    by_level: BTreeMap<NLevel, Vec<u32>>,
+    /// Maps a part of speech to the entries with a sense tagged with it.
+    by_pos: BTreeMap<Pos, Vec<u32>>,
+    /// Maps a pairing and part of speech to the entries the pair can be extracted
+    /// from with it.
+    pairs: BTreeMap<(Pairing, Pos), Vec<u32>>,
+    /// Ids of the entries left out of samples, e.g. for having bad data.
+    blocked: RwLock<HashSet<u32>>,
+}
+
+impl Dictionary {
+    /// Creates a dictionary of `entries`.
+    pub fn new(entries: impl IntoIterator<Item = DictEntry>) -> Self {
+        let entries: Vec<_> = entries.into_iter().collect();
+        let mut tables = Tables::new(&entries);
+        drop(entries);
+
+        let glosses = tables.lowercase_glosses();
+
+        let mut ids = HashMap::new();
+        let mut by_level = BTreeMap::new();
+        let mut by_pos = BTreeMap::new();
+        for row in 0..tables.len() as u32 {
+            let entry = tables.entry(row);
+            ids.insert(entry.id(), row);
+            for level in entry.levels() {
+                push_index(by_level.entry(level).or_default(), row);
+            }
+            for &pos in entry.senses().flat_map(|s| s.pos()) {
+                push_index(by_pos.entry(pos).or_default(), row);
+            }
+        }
+
+        let mut pairs = BTreeMap::new();
+        for (&pos, rows) in &by_pos {
+            for pairing in Pairing::iter() {
+                let paired: Vec<_> = rows
+                    .iter()
+                    .copied()
+                    .filter(|&row| pairing.has_pair(tables.entry(row), pos))
+                    .collect();
+                if !paired.is_empty() {
+                    pairs.insert((pairing, pos), paired);
+                }
+            }
+        }
+
+        Dictionary {
+            ids,
+            readings: TextIndex::new(&tables, tables.reading_texts()),
+            kanjis: TextIndex::new(&tables, tables.kanji_texts()),
+            glosses,
+            levels: by_level.keys().copied().collect(),
+            by_level,
+            by_pos,
+            pairs,
+            blocked: RwLock::new(HashSet::new()),
+            tables,
+        }
+    }
+
+    /// Creates a dictionary of the entries read from `reader`, a JSON object per line.
+    ///
+    /// # Errors
+    /// Fails if `reader` can't be read or an entry can't be parsed.
+    pub fn from_reader(reader: impl BufRead) -> crate::Result<Self> {
+        read_entries(reader).map(Dictionary::new)
+    }
+
+    /// Creates a dictionary of the entries in `bytes`, a JSON object per line,
+    /// e.g. a file embedded with `include_bytes!` or fetched by a browser.
+    ///
+    /// # Errors
+    /// Fails if an entry can't be parsed.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        Dictionary::from_reader(bytes)
+    }
+
+    /// Gets how many entries there are.
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Determines whether there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over all of the entries, in the order they were read.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = EntryRef<'_>> {
+        (0..self.tables.len() as u32).map(|row| self.tables.entry(row))
+    }
+
+    /// Gets the entry with the id `id`, if it exists.
+    pub fn entry(&self, id: u32) -> Option<EntryRef<'_>> {
+        self.ids.get(&id).map(|&row| self.tables.entry(row))
+    }
+
+    /// Gets all the levels at least one entry is tagged with, in order.
+    pub fn levels(&self) -> &[NLevel] {
+        &self.levels
+    }
+
+    /// Finds the entries with a [reading](`DictEntry::readings`) of exactly `reading`.
+    pub fn by_reading(&self, reading: &str) -> impl Iterator<Item = EntryRef<'_>> + use<'_> {
+        self.readings
+            .get(&self.tables, reading)
+            .map(|row| self.tables.entry(row))
+    }
+
+    /// Finds the entries with a [kanji](`DictEntry::kanjis`) spelling of exactly `kanji`.
+    pub fn by_kanji(&self, kanji: &str) -> impl Iterator<Item = EntryRef<'_>> + use<'_> {
+        self.kanjis
+            .get(&self.tables, kanji)
+            .map(|row| self.tables.entry(row))
+    }
+
+    /// Finds the entries with a gloss containing `text`, ignoring case.
+    pub fn by_gloss(&self, text: &str) -> impl Iterator<Item = EntryRef<'_>> + use<'_> {
+        let text = text.to_lowercase();
+
+        let mut rows: Vec<_> = self
+            .glosses
+            .iter()
+            .filter(|&&(gloss, _)| self.tables.text(gloss).contains(&text))
+            .map(|&(_, row)| row)
+            .collect();
+        rows.dedup();
+
+        rows.into_iter().map(|row| self.tables.entry(row))
+    }
+
+    /// Finds the entries spelled or read exactly as `word`, followed by the ones
+    /// with a gloss containing it, the closest matches first. Romaji and katakana
+    /// are also looked up as hiragana.
+    pub fn lookup(&self, word: &str) -> impl Iterator<Item = EntryRef<'_>> + use<'_> {
+        let hiragana = kana::to_hiragana(word);
+        let lowercase = word.to_lowercase();
+
+        let mut by_gloss: Vec<_> = self.by_gloss(word).collect();
+        by_gloss.sort_by_key(|&entry| gloss_rank(entry, &lowercase));
+
+        let mut seen = HashSet::new();
+        self.by_kanji(word)
+            .chain(self.by_reading(word))
+            .chain(self.by_reading(&hiragana))
+            .chain(by_gloss)
+            .filter(move |entry| seen.insert(entry.id()))
+    }
+
+    /// Finds the entries tagged with at least one of `levels` and with a sense
+    /// tagged with at least one of `pos`, in the order they were read.
+    pub fn filter(&self, levels: &[NLevel], pos: &[Pos]) -> impl Iterator<Item = EntryRef<'_>> {
+        let by_level = rows_of(&self.by_level, levels);
+        let by_pos = rows_of(&self.by_pos, pos);
+
+        let mut rows: Vec<_> = by_level.intersection(&by_pos).copied().collect();
+        rows.sort_unstable();
+
+        rows.into_iter().map(|row| self.tables.entry(row))
+    }
+
+    /// Picks up to `amount` random entries other than the one with the id `id`
+    /// that `pairing` can be extracted from with `pos`, e.g. for distractors.
+    pub fn random_paired<R: Rng + ?Sized>(
+        &self,
+        pairing: Pairing,
+        pos: Pos,
+        id: u32,
+        amount: usize,
+        rng: &mut R,
+    ) -> Vec<EntryRef<'_>> {
+        let Some(rows) = self.pairs.get(&(pairing, pos)) else {
+            return Vec::new();
+        };
+
+        // One extra in case the entry itself is picked.
+        rows.choose_multiple(rng, amount + 1)
+            .map(|&row| self.tables.entry(row))
+            .filter(|e| e.id() != id)
+            .take(amount)
+            .collect()
+    }
+
+    /// Counts the entries that `pairing` can be extracted from with `pos`.
+    pub fn count_paired(&self, pairing: Pairing, pos: Pos) -> usize {
+        self.pairs.get(&(pairing, pos)).map_or(0, Vec::len)
+    }
+
+    /// Estimates how much memory the dictionary uses on the heap.
+    pub fn footprint(&self) -> Footprint {
+        let blocked = self.blocked.read().unwrap_or_else(|e| e.into_inner());
+
+        Footprint {
+            tables: self.tables.heap_size(),
+            strings: self.tables.text_size(),
+            indexes: self.ids.heap_size()
+                + self.readings.heap_size()
+                + self.kanjis.heap_size()
+                + self.glosses.heap_size()
+                + self.levels.heap_size()
+                + self.by_level.heap_size()
+                + self.by_pos.heap_size()
+                + self.pairs.heap_size()
+                + blocked.heap_size(),
+        }
+    }
+
+    /// Leaves the entry with the id `id` out of samples.
+    ///
+    /// Returns false if it already was.
+    pub fn block(&self, id: u32) -> bool {
+        self.blocked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id)
+    }
+
+    /// Includes the entry with the id `id` in samples again.
+    ///
+    /// Returns false if it wasn't blocked.
+    pub fn unblock(&self, id: u32) -> bool {
+        self.blocked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id)
+    }
+
+    /// Creates a randomized subset of the entries that aren't blocked based on the
+    /// parameter filters, as in [`Self::filter`]. The order of the subset is drawn
+    /// from `rng`.
+    pub fn sample<R: Rng + ?Sized>(
+        &self,
+        levels: &[NLevel],
+        pos: &[Pos],
+        rng: &mut R,
+    ) -> Vec<EntryRef<'_>> {
+        let blocked = self.blocked.read().unwrap_or_else(|e| e.into_inner());
+
+        let mut sample: Vec<_> = self
+            .filter(levels, pos)
+            .filter(|entry| !blocked.contains(&entry.id()))
+            .collect();
+        sample.shuffle(rng);
+
+        sample
+    }
+}
+
+/// Estimated heap usage of a [`Dictionary`] in bytes, from [`Dictionary::footprint`].
+#[derive(Debug, Clone, Copy)]
+pub struct Footprint {
+    /// Used by the tables of the entries, excluding their text.
+    pub tables: usize,
+    /// Used by the text of the entries.
+    pub strings: usize,
+    /// Used by the lookup indexes.
+    pub indexes: usize,
+}
+
+impl Footprint {
+    /// Gets the bytes used altogether.
+    pub fn total(&self) -> usize {
+        self.tables + self.strings + self.indexes
+    }
+}
+
+impl Display for Footprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} of tables, {} of strings, {} of indexes)",
+            format_bytes(self.total()),
+            format_bytes(self.tables),
+            format_bytes(self.strings),
+            format_bytes(self.indexes)
+        )
+    }
+}
+
+/// Ranks how closely a gloss of `entry` matches `word`, in lowercase, as in
+/// [`rank_gloss`].
+fn gloss_rank(entry: EntryRef<'_>, word: &str) -> u8 {
+    entry
+        .senses()
+        .flat_map(|s| s.glosses())
+        .map(|gloss| rank_gloss(&gloss.to_lowercase(), word))
+        .min()
+        .unwrap_or(2)
+}
+
+/// Ranks how closely `gloss` matches `word`, both in lowercase: zero if it is
+/// exactly `word`, one if it has `word` as a whole word, two otherwise.
+fn rank_gloss(gloss: &str, word: &str) -> u8 {
+    if gloss == word || gloss.strip_prefix("to ") == Some(word) {
+        0
+    } else if gloss
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|w| w == word)
+    {
+        1
+    } else {
+        2
+    }
+}
+
+/// Gets the rows mapped to any of `keys` by `index`.
+fn rows_of<K: Ord>(index: &BTreeMap<K, Vec<u32>>, keys: &[K]) -> HashSet<u32> {
+    keys.iter()
+        .filter_map(|key| index.get(key))
+        .flatten()
+        .copied()
+        .collect()
+}
+
+/// Adds `row` to `rows` unless it was just added, e.g. for an entry
+/// with two senses of the same part of speech.
+fn push_index(rows: &mut Vec<u32>, row: u32) {
+    if rows.last() != Some(&row) {
+        rows.push(row);
+    }
+}
+
+/// Gets the [difficulty](DictEntry::difficulty) of an entry whose easiest level
+/// is `easiest`, and which may be written `irregular`ly.
+fn stars(easiest: NLevel, irregular: bool) -> u8 {
+    let base = match easiest {
+        NLevel::N5 => 1,
+        NLevel::N4 => 2,
+        NLevel::N3 => 3,
+        NLevel::N2 => 4,
+        NLevel::N1 => 5,
+    };
+
+    (base + u8::from(irregular)).min(5)
+}
+
+/// Parses the entries read from `reader`, a JSON object per line.
+///
+/// # Errors
+/// Fails if `reader` can't be read or an entry can't be parsed.
+pub fn read_entries(reader: impl BufRead) -> crate::Result<Vec<DictEntry>> {
+    stream_entries(reader).collect()
+}
+
+/// Parses the entries read from `reader`, a JSON object per line, one at a
+/// time as they are iterated, so that only one is held in memory at once.
+///
+/// Each item fails if `reader` can't be read or the entry can't be parsed.
+pub fn stream_entries(reader: impl BufRead) -> impl Iterator<Item = crate::Result<DictEntry>> {
+    serde_json::Deserializer::from_reader(reader)
+        .into_iter()
+        .map(|entry| entry.map_err(crate::Error::from))
+}
+
 /// An entry in the JMDict dictionary
 ///
 /// # See also
 /// <https://en.wikipedia.org/wiki/JMdict>
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DictEntry {
     #[serde(alias = "ent_seq")]
     pub id: u32,
@@ -18,6 +424,10 @@ pub struct DictEntry {
 
     #[serde(rename = "sense")]
     pub senses: Vec<Sense>,
+
+    /// Sentences using this entry, e.g. from the Tatoeba corpus, the best first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Example>,
 }
 
 impl DictEntry {
@@ -44,6 +454,57 @@ impl DictEntry {
         levels
     }
 
+    /// Estimates how difficult this entry is from one to five stars. Based on the
+    /// easiest [`NLevel`] the entry is tagged with, plus a star if it is written
+    /// irregularly, e.g. with ateji or rare kanji, up to five.
+    ///
+    /// Returns [`None`] if the entry isn't tagged with any [`NLevel`].
+    pub fn difficulty(&self) -> Option<u8> {
+        let easiest = *self.levels().last()?;
+
+        let irregular = self.kanjis.iter().flat_map(|k| &k.tags).any(|tag| {
+            matches!(
+                tag,
+                KTag::Ateji | KTag::IrKanji | KTag::Outdated | KTag::Rare
+            )
+        }) || self
+            .readings
+            .iter()
+            .flat_map(|r| &r.tags)
+            .any(|tag| matches!(tag, RTag::Gikun));
+
+        Some(stars(easiest, irregular))
+    }
+
+    /// Lists the [kanjis](`DictEntry::kanjis`) and [readings](`DictEntry::readings`)
+    /// that are spelled unusually, e.g. with ateji, along with the descriptions
+    /// of their [`KTag`]s and [`RTag`]s.
+    pub fn unusual_spellings(&self) -> Vec<(&str, Vec<&'static str>)> {
+        use strum::EnumMessage;
+
+        let kanjis = self.kanjis.iter().map(|k| {
+            let descs = k.tags.iter().filter_map(|t| t.get_message()).collect();
+            (k.text.as_str(), descs)
+        });
+        let readings = self.readings.iter().map(|r| {
+            let descs = r.tags.iter().filter_map(|t| t.get_message()).collect();
+            (r.text.as_str(), descs)
+        });
+
+        kanjis
+            .chain(readings)
+            .filter(|(_, descs): &(_, Vec<_>)| !descs.is_empty())
+            .collect()
+    }
+
+    /// Numbers the [senses](`DictEntry::senses`) in their current order, which
+    /// should be the order of JMDict so they can be cross-referenced.
+    pub fn number_senses(&mut self) {
+        for (i, sense) in self.senses.iter_mut().enumerate() {
+            sense.number = i as u32 + 1;
+        }
+    }
+
     /// Annotates a [reading](`DictEntry::readings`) that matches `hiragana`
     /// with `level`. Annotates all [kanjis](`DictEntry::kanjis`) with the
     /// same `level` or only the ones in [relevant_to](`Reading::relevant_to`) if that
@@ -81,7 +542,7 @@ impl DictEntry {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Kanji {
     #[serde(rename = "keb")]
     pub text: String,
@@ -93,7 +554,7 @@ pub struct Kanji {
     pub tags: Vec<KTag>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Reading {
     #[serde(rename = "reb")]
     pub text: String,
@@ -106,6 +567,11 @@ pub struct Reading {
 
     #[serde(rename = "re_inf", default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<RTag>,
+
+    /// The mora after which the pitch of this reading drops, or 0 if it never
+    /// does, if known. See [`kana::mark_downstep`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<u8>,
 }
 
 #[derive(
@@ -128,6 +594,7 @@ pub enum NLevel {
     N2,
     N3,
     N4,
+    N5,
 }
 
 impl From<NLevel> for i32 {
@@ -137,44 +604,61 @@ impl From<NLevel> for i32 {
             NLevel::N2 => 2,
             NLevel::N3 => 3,
             NLevel::N4 => 4,
+            NLevel::N5 => 5,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, strum_macros::EnumMessage)]
 pub enum KTag {
     #[serde(rename = "&ateji;")]
+    #[strum(message = "ateji (phonetic) reading")]
     Ateji,
     #[serde(rename = "&ik;")]
+    #[strum(message = "word containing irregular kana usage")]
     IrKana,
     #[serde(rename = "&iK;")]
+    #[strum(message = "word containing irregular kanji usage")]
     IrKanji,
     #[serde(rename = "&io;")]
+    #[strum(message = "irregular okurigana usage")]
     IrOkurigana,
     #[serde(rename = "&oK;")]
+    #[strum(message = "word containing out-dated kanji or kanji usage")]
     Outdated,
     #[serde(rename = "&rK;")]
+    #[strum(message = "rarely used kanji form")]
     Rare,
     #[serde(rename = "&sK;")]
+    #[strum(message = "search-only kanji form")]
     SearchOnly,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, strum_macros::EnumMessage)]
 pub enum RTag {
     #[serde(rename = "&gikun;")]
+    #[strum(message = "gikun (meaning as reading) or jukujikun (special kanji reading)")]
     Gikun,
     #[serde(rename = "&ik;")]
+    #[strum(message = "word containing irregular kana usage")]
     IrKana,
     #[serde(rename = "&ok;")]
+    #[strum(message = "out-dated or obsolete kana usage")]
     Outdated,
     #[serde(rename = "&sk;")]
+    #[strum(message = "search-only kana form")]
     SearchOnly,
     #[serde(rename = "&rk;")]
+    #[strum(message = "rarely used kana form")]
     Archaic,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Sense {
+    /// Position of this sense in its JMDict entry, starting at one.
+    #[serde(default)]
+    pub number: u32,
+
     #[serde(rename = "stagk", default, skip_serializing_if = "Vec::is_empty")]
     pub relevant_kanji: Vec<String>,
 
@@ -191,7 +675,7 @@ pub struct Sense {
 macro_rules! pos_enum {
     (
         $(
-            ($name:ident, $tag:literal, $desc:literal)
+            ($name:ident, $tag:literal, $desc:literal, $ja:literal)
         ),* $(,)?
     ) => {
         #[derive(
@@ -213,106 +697,145 @@ macro_rules! pos_enum {
                 $name,
             )*
         }
+
+        impl Pos {
+            /// Every part of speech, in order.
+            pub const ALL: &'static [Pos] = &[$(Pos::$name),*];
+
+            /// Gets the Japanese description of this part of speech.
+            pub const fn ja_message(&self) -> &'static str {
+                match self {
+                    $(
+                        Pos::$name => $ja,
+                    )*
+                }
+            }
+        }
     };
 }
 
+impl Pos {
+    /// Formats the Japanese and English descriptions of this part of speech,
+    /// e.g. `名詞 / noun (common) (futsuumeishi)`.
+    pub fn bilingual(&self) -> String {
+        format!(
+            "{} / {}",
+            self.ja_message(),
+            strum::EnumMessage::get_message(self).unwrap_or_default()
+        )
+    }
+}
+
 #[rustfmt::skip]
 pos_enum!(
-    (AdjF, "&adj-f;", "noun or verb acting prenominally"),
-    (AdjI, "&adj-i;", "adjective (keiyoushi)"),
-    (AdjIx, "&adj-ix;", "adjective (keiyoushi) - yoi/ii class"),
-    (AdjKari, "&adj-kari;", "'kari' adjective (archaic)"),
-    (AdjKu, "&adj-ku;", "'ku' adjective (archaic)"),
-    (AdjNa, "&adj-na;", "adjectival nouns or quasi-adjectives (keiyodoshi)"),
-    (AdjNari, "&adj-nari;", "archaic/formal form of na-adjective"),
-    (AdjNo, "&adj-no;", "nouns which may take the genitive case particle 'no'"),
-    (AdjPn, "&adj-pn;", "pre-noun adjectival (rentaishi)"),
-    (AdjShiku, "&adj-shiku;", "'shiku' adjective (archaic)"),
-    (AdjT, "&adj-t;", "'taru' adjective"),
-    (Adv, "&adv;", "adverb (fukushi)"),
-    (AdvTo, "&adv-to;", "adverb taking the 'to' particle"),
-    (Aux, "&aux;", "auxiliary"),
-    (AuxAdj, "&aux-adj;", "auxiliary adjective"),
-    (AuxV, "&aux-v;", "auxiliary verb"),
-    (Conj, "&conj;", "conjunction"),
-    (Cop, "&cop;", "copula"),
-    (Ctr, "&ctr;", "counter"),
-    (Exp, "&exp;", "expressions (phrases, clauses, etc.)"),
-    (Int, "&int;", "interjection (kandoushi)"),
-    (N, "&n;", "noun (common) (futsuumeishi)"),
-    (NAdv, "&n-adv;", "adverbial noun (fukushitekimeishi)"),
-    (NPr, "&n-pr;", "proper noun"),
-    (NPref, "&n-pref;", "noun, used as a prefix"),
-    (NSuf, "&n-suf;", "noun, used as a suffix"),
-    (NT, "&n-t;", "noun (temporal) (jisoumeishi)"),
-    (Num, "&num;", "numeric"),
-    (Pn, "&pn;", "pronoun"),
-    (Pref, "&pref;", "prefix"),
-    (Prt, "&prt;", "particle"),
-    (Suf, "&suf;", "suffix"),
-    (Unc, "&unc;", "unclassified"),
-    (VUnspec, "&v-unspec;", "verb unspecified"),
-    (V1, "&v1;", "Ichidan verb"),
-    (V1S, "&v1-s;", "Ichidan verb - kureru special class"),
-    (V2aS, "&v2a-s;", "Nidan verb with 'u' ending (archaic)"),
-    (V2bK, "&v2b-k;", "Nidan verb (upper class) with 'bu' ending (archaic)"),
-    (V2bS, "&v2b-s;", "Nidan verb (lower class) with 'bu' ending (archaic)"),
-    (V2dK, "&v2d-k;", "Nidan verb (upper class) with 'dzu' ending (archaic)"),
-    (V2dS, "&v2d-s;", "Nidan verb (lower class) with 'dzu' ending (archaic)"),
-    (V2gk, "&v2g-k;", "Nidan verb (upper class) with 'gu' ending (archaic)"),
-    (V2gS, "&v2g-s;", "Nidan verb (lower class) with 'gu' ending (archaic)"),
-    (V2hK, "&v2h-k;", "Nidan verb (upper class) with 'hu/fu' ending (archaic)"),
-    (V2hS, "&v2h-s;", "Nidan verb (lower class) with 'hu/fu' ending (archaic)"),
-    (V2kK, "&v2k-k;", "Nidan verb (upper class) with 'ku' ending (archaic)"),
-    (V2kS, "&v2k-s;", "Nidan verb (lower class) with 'ku' ending (archaic)"),
-    (V2mK, "&v2m-k;", "Nidan verb (upper class) with 'mu' ending (archaic)"),
-    (V2mS, "&v2m-s;", "Nidan verb (lower class) with 'mu' ending (archaic)"),
-    (V2nS, "&v2n-s;", "Nidan verb (lower class) with 'nu' ending (archaic)"),
-    (V2rK, "&v2r-k;", "Nidan verb (upper class) with 'ru' ending (archaic)"),
-    (V2rS, "&v2r-s;", "Nidan verb (lower class) with 'ru' ending (archaic)"),
-    (V2sS, "&v2s-s;", "Nidan verb (lower class) with 'su' ending (archaic)"),
-    (V2tK, "&v2t-k;", "Nidan verb (upper class) with 'tsu' ending (archaic)"),
-    (V2tS, "&v2t-s;", "Nidan verb (lower class) with 'tsu' ending (archaic)"),
-    (V2wS, "&v2w-s;", "Nidan verb (lower class) with 'u' ending and 'we' conjugation (archaic)"),
-    (V2yK, "&v2y-k;", "Nidan verb (upper class) with 'yu' ending (archaic)"),
-    (V2yS, "&v2y-s;", "Nidan verb (lower class) with 'yu' ending (archaic)"),
-    (V2zS, "&v2z-s;", "Nidan verb (lower class) with 'zu' ending (archaic)"),
-    (V4b, "&v4b;", "Yodan verb with 'bu' ending (archaic)"),
-    (V4g, "&v4g;", "Yodan verb with 'gu' ending (archaic)"),
-    (V4h, "&v4h;", "Yodan verb with 'hu/fu' ending (archaic)"),
-    (V4k, "&v4k;", "Yodan verb with 'ku' ending (archaic)"),
-    (V4m, "&v4m;", "Yodan verb with 'mu' ending (archaic)"),
-    (V4n, "&v4n;", "Yodan verb with 'nu' ending (archaic)"),
-    (V4r, "&v4r;", "Yodan verb with 'ru' ending (archaic)"),
-    (V4s, "&v4s;", "Yodan verb with 'su' ending (archaic)"),
-    (V4t, "&v4t;", "Yodan verb with 'tsu' ending (archaic)"),
-    (V5aru, "&v5aru;", "Godan verb - -aru special class"),
-    (V5b, "&v5b;", "Godan verb with 'bu' ending"),
-    (V5g, "&v5g;", "Godan verb with 'gu' ending"),
-    (V5k, "&v5k;", "Godan verb with 'ku' ending"),
-    (V5kS, "&v5k-s;", "Godan verb - Iku/Yuku special class"),
-    (V5m, "&v5m;", "Godan verb with 'mu' ending"),
-    (V5n, "&v5n;", "Godan verb with 'nu' ending"),
-    (V5r, "&v5r;", "Godan verb with 'ru' ending"),
-    (V5rI, "&v5r-i;", "Godan verb with 'ru' ending (irregular verb)"),
-    (V5s, "&v5s;", "Godan verb with 'su' ending"),
-    (V5t, "&v5t;", "Godan verb with 'tsu' ending"),
-    (V5u, "&v5u;", "Godan verb with 'u' ending"),
-    (V5uS, "&v5u-s;", "Godan verb with 'u' ending (special class)"),
-    (V5uru, "&v5uru;", "Godan verb - Uru old class verb (old form of Eru)"),
-    (Vi, "&vi;", "intransitive verb"),
-    (Vk, "&vk;", "Kuru verb - special class"),
-    (Vn, "&vn;", "irregular nu verb"),
-    (Vr, "&vr;", "irregular ru verb, plain form ends with -ri"),
-    (Vs, "&vs;", "noun or participle which takes the aux. verb suru"),
-    (VsC, "&vs-c;", "su verb - precursor to the modern suru"),
-    (VsI, "&vs-i;", "suru verb - included"),
-    (VsS, "&vs-s;", "suru verb - special class"),
-    (Vt, "&vt;", "transitive verb"),
-    (Vz, "&vz;", "Ichidan verb - zuru verb (alternative form of -jiru verbs)"),
+    (AdjF, "&adj-f;", "noun or verb acting prenominally", "連体詞的な名詞・動詞"),
+    (AdjI, "&adj-i;", "adjective (keiyoushi)", "形容詞"),
+    (AdjIx, "&adj-ix;", "adjective (keiyoushi) - yoi/ii class", "形容詞（いい・よい）"),
+    (AdjKari, "&adj-kari;", "'kari' adjective (archaic)", "カリ活用形容詞（古語）"),
+    (AdjKu, "&adj-ku;", "'ku' adjective (archaic)", "ク活用形容詞（古語）"),
+    (AdjNa, "&adj-na;", "adjectival nouns or quasi-adjectives (keiyodoshi)", "形容動詞"),
+    (AdjNari, "&adj-nari;", "archaic/formal form of na-adjective", "ナリ活用形容動詞（古語）"),
+    (AdjNo, "&adj-no;", "nouns which may take the genitive case particle 'no'", "「の」を伴う名詞"),
+    (AdjPn, "&adj-pn;", "pre-noun adjectival (rentaishi)", "連体詞"),
+    (AdjShiku, "&adj-shiku;", "'shiku' adjective (archaic)", "シク活用形容詞（古語）"),
+    (AdjT, "&adj-t;", "'taru' adjective", "タルト活用形容動詞"),
+    (Adv, "&adv;", "adverb (fukushi)", "副詞"),
+    (AdvTo, "&adv-to;", "adverb taking the 'to' particle", "「と」を伴う副詞"),
+    (Aux, "&aux;", "auxiliary", "助動詞等"),
+    (AuxAdj, "&aux-adj;", "auxiliary adjective", "補助形容詞"),
+    (AuxV, "&aux-v;", "auxiliary verb", "助動詞"),
+    (Conj, "&conj;", "conjunction", "接続詞"),
+    (Cop, "&cop;", "copula", "繋辞"),
+    (Ctr, "&ctr;", "counter", "助数詞"),
+    (Exp, "&exp;", "expressions (phrases, clauses, etc.)", "表現"),
+    (Int, "&int;", "interjection (kandoushi)", "感動詞"),
+    (N, "&n;", "noun (common) (futsuumeishi)", "名詞"),
+    (NAdv, "&n-adv;", "adverbial noun (fukushitekimeishi)", "副詞的名詞"),
+    (NPr, "&n-pr;", "proper noun", "固有名詞"),
+    (NPref, "&n-pref;", "noun, used as a prefix", "接頭名詞"),
+    (NSuf, "&n-suf;", "noun, used as a suffix", "接尾名詞"),
+    (NT, "&n-t;", "noun (temporal) (jisoumeishi)", "時詞"),
+    (Num, "&num;", "numeric", "数詞"),
+    (Pn, "&pn;", "pronoun", "代名詞"),
+    (Pref, "&pref;", "prefix", "接頭辞"),
+    (Prt, "&prt;", "particle", "助詞"),
+    (Suf, "&suf;", "suffix", "接尾辞"),
+    (Unc, "&unc;", "unclassified", "未分類"),
+    (VUnspec, "&v-unspec;", "verb unspecified", "動詞（未指定）"),
+    (V1, "&v1;", "Ichidan verb", "一段動詞"),
+    (V1S, "&v1-s;", "Ichidan verb - kureru special class", "一段動詞（くれる）"),
+    (V2aS, "&v2a-s;", "Nidan verb with 'u' ending (archaic)", "下二段動詞（う・古語）"),
+    (V2bK, "&v2b-k;", "Nidan verb (upper class) with 'bu' ending (archaic)", "上二段動詞（ぶ・古語）"),
+    (V2bS, "&v2b-s;", "Nidan verb (lower class) with 'bu' ending (archaic)", "下二段動詞（ぶ・古語）"),
+    (V2dK, "&v2d-k;", "Nidan verb (upper class) with 'dzu' ending (archaic)", "上二段動詞（づ・古語）"),
+    (V2dS, "&v2d-s;", "Nidan verb (lower class) with 'dzu' ending (archaic)", "下二段動詞（づ・古語）"),
+    (V2gk, "&v2g-k;", "Nidan verb (upper class) with 'gu' ending (archaic)", "上二段動詞（ぐ・古語）"),
+    (V2gS, "&v2g-s;", "Nidan verb (lower class) with 'gu' ending (archaic)", "下二段動詞（ぐ・古語）"),
+    (V2hK, "&v2h-k;", "Nidan verb (upper class) with 'hu/fu' ending (archaic)", "上二段動詞（ふ・古語）"),
+    (V2hS, "&v2h-s;", "Nidan verb (lower class) with 'hu/fu' ending (archaic)", "下二段動詞（ふ・古語）"),
+    (V2kK, "&v2k-k;", "Nidan verb (upper class) with 'ku' ending (archaic)", "上二段動詞（く・古語）"),
+    (V2kS, "&v2k-s;", "Nidan verb (lower class) with 'ku' ending (archaic)", "下二段動詞（く・古語）"),
+    (V2mK, "&v2m-k;", "Nidan verb (upper class) with 'mu' ending (archaic)", "上二段動詞（む・古語）"),
+    (V2mS, "&v2m-s;", "Nidan verb (lower class) with 'mu' ending (archaic)", "下二段動詞（む・古語）"),
+    (V2nS, "&v2n-s;", "Nidan verb (lower class) with 'nu' ending (archaic)", "下二段動詞（ぬ・古語）"),
+    (V2rK, "&v2r-k;", "Nidan verb (upper class) with 'ru' ending (archaic)", "上二段動詞（る・古語）"),
+    (V2rS, "&v2r-s;", "Nidan verb (lower class) with 'ru' ending (archaic)", "下二段動詞（る・古語）"),
+    (V2sS, "&v2s-s;", "Nidan verb (lower class) with 'su' ending (archaic)", "下二段動詞（す・古語）"),
+    (V2tK, "&v2t-k;", "Nidan verb (upper class) with 'tsu' ending (archaic)", "上二段動詞（つ・古語）"),
+    (V2tS, "&v2t-s;", "Nidan verb (lower class) with 'tsu' ending (archaic)", "下二段動詞（つ・古語）"),
+    (V2wS, "&v2w-s;", "Nidan verb (lower class) with 'u' ending and 'we' conjugation (archaic)", "下二段動詞（う・ゑ・古語）"),
+    (V2yK, "&v2y-k;", "Nidan verb (upper class) with 'yu' ending (archaic)", "上二段動詞（ゆ・古語）"),
+    (V2yS, "&v2y-s;", "Nidan verb (lower class) with 'yu' ending (archaic)", "下二段動詞（ゆ・古語）"),
+    (V2zS, "&v2z-s;", "Nidan verb (lower class) with 'zu' ending (archaic)", "下二段動詞（ず・古語）"),
+    (V4b, "&v4b;", "Yodan verb with 'bu' ending (archaic)", "四段動詞（ぶ・古語）"),
+    (V4g, "&v4g;", "Yodan verb with 'gu' ending (archaic)", "四段動詞（ぐ・古語）"),
+    (V4h, "&v4h;", "Yodan verb with 'hu/fu' ending (archaic)", "四段動詞（ふ・古語）"),
+    (V4k, "&v4k;", "Yodan verb with 'ku' ending (archaic)", "四段動詞（く・古語）"),
+    (V4m, "&v4m;", "Yodan verb with 'mu' ending (archaic)", "四段動詞（む・古語）"),
+    (V4n, "&v4n;", "Yodan verb with 'nu' ending (archaic)", "四段動詞（ぬ・古語）"),
+    (V4r, "&v4r;", "Yodan verb with 'ru' ending (archaic)", "四段動詞（る・古語）"),
+    (V4s, "&v4s;", "Yodan verb with 'su' ending (archaic)", "四段動詞（す・古語）"),
+    (V4t, "&v4t;", "Yodan verb with 'tsu' ending (archaic)", "四段動詞（つ・古語）"),
+    (V5aru, "&v5aru;", "Godan verb - -aru special class", "五段動詞（〜ある）"),
+    (V5b, "&v5b;", "Godan verb with 'bu' ending", "五段動詞（ぶ）"),
+    (V5g, "&v5g;", "Godan verb with 'gu' ending", "五段動詞（ぐ）"),
+    (V5k, "&v5k;", "Godan verb with 'ku' ending", "五段動詞（く）"),
+    (V5kS, "&v5k-s;", "Godan verb - Iku/Yuku special class", "五段動詞（行く）"),
+    (V5m, "&v5m;", "Godan verb with 'mu' ending", "五段動詞（む）"),
+    (V5n, "&v5n;", "Godan verb with 'nu' ending", "五段動詞（ぬ）"),
+    (V5r, "&v5r;", "Godan verb with 'ru' ending", "五段動詞（る）"),
+    (V5rI, "&v5r-i;", "Godan verb with 'ru' ending (irregular verb)", "五段動詞（る・不規則）"),
+    (V5s, "&v5s;", "Godan verb with 'su' ending", "五段動詞（す）"),
+    (V5t, "&v5t;", "Godan verb with 'tsu' ending", "五段動詞（つ）"),
+    (V5u, "&v5u;", "Godan verb with 'u' ending", "五段動詞（う）"),
+    (V5uS, "&v5u-s;", "Godan verb with 'u' ending (special class)", "五段動詞（う・特殊）"),
+    (V5uru, "&v5uru;", "Godan verb - Uru old class verb (old form of Eru)", "五段動詞（うる）"),
+    (Vi, "&vi;", "intransitive verb", "自動詞"),
+    (Vk, "&vk;", "Kuru verb - special class", "カ変動詞"),
+    (Vn, "&vn;", "irregular nu verb", "ナ変動詞"),
+    (Vr, "&vr;", "irregular ru verb, plain form ends with -ri", "ラ変動詞"),
+    (Vs, "&vs;", "noun or participle which takes the aux. verb suru", "サ変名詞"),
+    (VsC, "&vs-c;", "su verb - precursor to the modern suru", "サ変動詞（す）"),
+    (VsI, "&vs-i;", "suru verb - included", "サ変動詞"),
+    (VsS, "&vs-s;", "suru verb - special class", "サ変動詞（特殊）"),
+    (Vt, "&vt;", "transitive verb", "他動詞"),
+    (Vz, "&vz;", "Ichidan verb - zuru verb (alternative form of -jiru verbs)", "ザ変動詞"),
 );
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Gloss {
     pub content: String,
 }
+
+/// A sentence using a [`DictEntry`], along with its translation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Example {
+    /// The sentence in Japanese.
+    pub sentence: String,
+    /// The sentence in English.
+    pub translation: String,
+    /// The entry as it is written in [`Self::sentence`], e.g. conjugated.
+    pub word: String,
+}
+
+no_heap!(NLevel, KTag, RTag, Pos);
@@ -0,0 +1,654 @@
+use std::{collections::HashMap, ops::Range};
+
+use crate::heap_size::{HeapSize, no_heap};
+
+use super::{DictEntry, Example, Gloss, KTag, Kanji, NLevel, Pos, RTag, Reading, Sense};
+
+/// A range of rows of a table or of bytes of [`Tables::text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Span {
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    fn range(self) -> Range<usize> {
+        self.start as usize..self.end as usize
+    }
+
+    fn rows(self) -> Range<u32> {
+        self.start..self.end
+    }
+}
+
+/// Converts a length of a table to an index, which is kept at 32 bits to halve
+/// the size of the tables.
+fn index(len: usize) -> u32 {
+    u32::try_from(len).expect("Dictionary should fit in 32-bit indices")
+}
+
+struct EntryRow {
+    id: u32,
+    kanjis: Span,
+    readings: Span,
+    senses: Span,
+    examples: Span,
+}
+
+struct KanjiRow {
+    text: Span,
+    levels: Span,
+    tags: Span,
+}
+
+struct ReadingRow {
+    text: Span,
+    relevant_to: Span,
+    levels: Span,
+    tags: Span,
+    pitch: Option<u8>,
+}
+
+struct SenseRow {
+    number: u32,
+    relevant_kanji: Span,
+    relevant_reading: Span,
+    pos: Span,
+    glosses: Span,
+}
+
+struct ExampleRow {
+    sentence: Span,
+    translation: Span,
+    word: Span,
+}
+
+/// The entries of a [`Dictionary`](super::Dictionary) flattened into a table per
+/// element, where rows refer to each other and to their text by 32-bit [`Span`]s
+/// instead of owning nested vectors and strings.
+#[derive(Default)]
+pub(super) struct Tables {
+    /// Text of every distinct string, back to back.
+    text: String,
+    entries: Vec<EntryRow>,
+    kanjis: Vec<KanjiRow>,
+    readings: Vec<ReadingRow>,
+    senses: Vec<SenseRow>,
+    examples: Vec<ExampleRow>,
+    /// Text of every gloss.
+    glosses: Vec<Span>,
+    /// Text of the spellings readings and senses are restricted to.
+    restrictions: Vec<Span>,
+    levels: Vec<NLevel>,
+    ktags: Vec<KTag>,
+    rtags: Vec<RTag>,
+    pos: Vec<Pos>,
+}
+
+impl Tables {
+    /// Flattens `entries`, storing each distinct string once.
+    pub(super) fn new(entries: &[DictEntry]) -> Self {
+        let mut builder = Builder::default();
+
+        for entry in entries {
+            let kanjis = builder.rows(&entry.kanjis, Builder::kanji, |t| t.kanjis.len());
+            let readings = builder.rows(&entry.readings, Builder::reading, |t| t.readings.len());
+            let senses = builder.rows(&entry.senses, Builder::sense, |t| t.senses.len());
+            let examples = builder.rows(&entry.examples, Builder::example, |t| t.examples.len());
+
+            builder.tables.entries.push(EntryRow {
+                id: entry.id,
+                kanjis,
+                readings,
+                senses,
+                examples,
+            });
+        }
+
+        let mut tables = builder.tables;
+        tables.text.shrink_to_fit();
+        tables
+    }
+
+    /// Gets the number of entries.
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Gets the handle of the entry in row `row`.
+    pub(super) fn entry(&self, row: u32) -> EntryRef<'_> {
+        EntryRef { tables: self, row }
+    }
+
+    /// Gets the text at `span`.
+    pub(super) fn text(&self, span: Span) -> &str {
+        &self.text[span.range()]
+    }
+
+    /// Gets the number of bytes of text.
+    pub(super) fn text_size(&self) -> usize {
+        self.text.heap_size()
+    }
+
+    /// Appends `text` to the text, returning its span. Unlike the strings of the
+    /// entries, appended text isn't shared.
+    fn push_text(&mut self, text: &str) -> Span {
+        let start = index(self.text.len());
+        self.text.push_str(text);
+
+        Span {
+            start,
+            end: index(self.text.len()),
+        }
+    }
+
+    /// Gets the span of the text of each kanji spelling along with the row of its entry.
+    pub(super) fn kanji_texts(&self) -> impl Iterator<Item = (Span, u32)> + '_ {
+        self.entry_rows().flat_map(move |(entry, row)| {
+            self.kanjis[entry.kanjis.range()]
+                .iter()
+                .map(move |k| (k.text, row))
+        })
+    }
+
+    /// Gets the span of the text of each reading along with the row of its entry.
+    pub(super) fn reading_texts(&self) -> impl Iterator<Item = (Span, u32)> + '_ {
+        self.entry_rows().flat_map(move |(entry, row)| {
+            self.readings[entry.readings.range()]
+                .iter()
+                .map(move |r| (r.text, row))
+        })
+    }
+
+    /// Gets the span of each gloss in lowercase along with the row of its entry,
+    /// appending the lowercase text of glosses that aren't already.
+    pub(super) fn lowercase_glosses(&mut self) -> Vec<(Span, u32)> {
+        let mut glosses = Vec::new();
+
+        for row in 0..index(self.entries.len()) {
+            let senses = self.entries[row as usize].senses;
+            for sense in senses.rows() {
+                for gloss in self.senses[sense as usize].glosses.rows() {
+                    let span = self.glosses[gloss as usize];
+                    let text = self.text(span);
+
+                    let span = if text.chars().any(char::is_uppercase) {
+                        let lowercase = text.to_lowercase();
+                        self.push_text(&lowercase)
+                    } else {
+                        span
+                    };
+                    glosses.push((span, row));
+                }
+            }
+        }
+
+        self.text.shrink_to_fit();
+        glosses
+    }
+
+    fn entry_rows(&self) -> impl Iterator<Item = (&EntryRow, u32)> {
+        self.entries.iter().zip(0..)
+    }
+
+    fn restrictions(&self, span: Span) -> impl ExactSizeIterator<Item = &str> + Clone {
+        self.restrictions[span.range()]
+            .iter()
+            .map(|&s| self.text(s))
+    }
+}
+
+impl HeapSize for Tables {
+    /// Excludes the text, see [`Tables::text_size`].
+    fn heap_size(&self) -> usize {
+        self.entries.heap_size()
+            + self.kanjis.heap_size()
+            + self.readings.heap_size()
+            + self.senses.heap_size()
+            + self.examples.heap_size()
+            + self.glosses.heap_size()
+            + self.restrictions.heap_size()
+            + self.levels.heap_size()
+            + self.ktags.heap_size()
+            + self.rtags.heap_size()
+            + self.pos.heap_size()
+    }
+}
+
+no_heap!(Span, EntryRow, KanjiRow, ReadingRow, SenseRow, ExampleRow);
+
+/// Maps text, e.g. readings, to the rows of the entries with it. Kept sorted by
+/// text so lookups are binary searches instead of hashes of owned strings.
+pub(super) struct TextIndex {
+    keys: Vec<(Span, u32)>,
+}
+
+impl TextIndex {
+    /// Indexes the rows of `keys` by the text of their spans in `tables`.
+    pub(super) fn new(tables: &Tables, keys: impl Iterator<Item = (Span, u32)>) -> Self {
+        let mut keys: Vec<_> = keys.collect();
+        keys.sort_unstable_by(|&(a, i), &(b, j)| {
+            tables.text(a).cmp(tables.text(b)).then(i.cmp(&j))
+        });
+        keys.dedup_by(|(a, i), (b, j)| i == j && tables.text(*a) == tables.text(*b));
+        keys.shrink_to_fit();
+
+        TextIndex { keys }
+    }
+
+    /// Finds the rows of the entries with the text `key`, in ascending order.
+    pub(super) fn get<'a>(
+        &'a self,
+        tables: &Tables,
+        key: &str,
+    ) -> impl Iterator<Item = u32> + use<'a> {
+        let start = self
+            .keys
+            .partition_point(|&(span, _)| tables.text(span) < key);
+        let end = self
+            .keys
+            .partition_point(|&(span, _)| tables.text(span) <= key);
+
+        self.keys[start..end].iter().map(|&(_, row)| row)
+    }
+}
+
+impl HeapSize for TextIndex {
+    fn heap_size(&self) -> usize {
+        self.keys.heap_size()
+    }
+}
+
+/// Fills [`Tables`], remembering the spans of the strings already stored.
+#[derive(Default)]
+struct Builder<'e> {
+    tables: Tables,
+    interned: HashMap<&'e str, Span>,
+}
+
+impl<'e> Builder<'e> {
+    /// Adds a row for each of `items` with `add`, returning the span of rows of
+    /// the table whose length is measured by `len`.
+    fn rows<T>(
+        &mut self,
+        items: &'e [T],
+        add: impl Fn(&mut Self, &'e T),
+        len: impl Fn(&Tables) -> usize,
+    ) -> Span {
+        let start = index(len(&self.tables));
+        for item in items {
+            add(self, item);
+        }
+
+        Span {
+            start,
+            end: index(len(&self.tables)),
+        }
+    }
+
+    fn kanji(&mut self, kanji: &'e Kanji) {
+        let row = KanjiRow {
+            text: self.string(&kanji.text),
+            levels: copy(&mut self.tables.levels, &kanji.levels),
+            tags: copy(&mut self.tables.ktags, &kanji.tags),
+        };
+        self.tables.kanjis.push(row);
+    }
+
+    fn reading(&mut self, reading: &'e Reading) {
+        let row = ReadingRow {
+            text: self.string(&reading.text),
+            relevant_to: self.restrictions(&reading.relevant_to),
+            levels: copy(&mut self.tables.levels, &reading.levels),
+            tags: copy(&mut self.tables.rtags, &reading.tags),
+            pitch: reading.pitch,
+        };
+        self.tables.readings.push(row);
+    }
+
+    fn sense(&mut self, sense: &'e Sense) {
+        let start = index(self.tables.glosses.len());
+        for gloss in &sense.gloss {
+            let span = self.string(&gloss.content);
+            self.tables.glosses.push(span);
+        }
+        let glosses = Span {
+            start,
+            end: index(self.tables.glosses.len()),
+        };
+
+        let row = SenseRow {
+            number: sense.number,
+            relevant_kanji: self.restrictions(&sense.relevant_kanji),
+            relevant_reading: self.restrictions(&sense.relevant_reading),
+            pos: copy(&mut self.tables.pos, &sense.pos),
+            glosses,
+        };
+        self.tables.senses.push(row);
+    }
+
+    fn example(&mut self, example: &'e Example) {
+        let row = ExampleRow {
+            sentence: self.string(&example.sentence),
+            translation: self.string(&example.translation),
+            word: self.string(&example.word),
+        };
+        self.tables.examples.push(row);
+    }
+
+    fn restrictions(&mut self, spellings: &'e [String]) -> Span {
+        let start = index(self.tables.restrictions.len());
+        for spelling in spellings {
+            let span = self.string(spelling);
+            self.tables.restrictions.push(span);
+        }
+
+        Span {
+            start,
+            end: index(self.tables.restrictions.len()),
+        }
+    }
+
+    /// Gets the span of `text`, storing it if it wasn't already.
+    fn string(&mut self, text: &'e str) -> Span {
+        if let Some(&span) = self.interned.get(text) {
+            return span;
+        }
+
+        let span = self.tables.push_text(text);
+        self.interned.insert(text, span);
+        span
+    }
+}
+
+/// Appends `items` to `table`, returning the span of rows they were put in.
+fn copy<T: Copy>(table: &mut Vec<T>, items: &[T]) -> Span {
+    let start = index(table.len());
+    table.extend_from_slice(items);
+
+    Span {
+        start,
+        end: index(table.len()),
+    }
+}
+
+/// A handle to an entry of a [`Dictionary`](super::Dictionary), the packed
+/// counterpart of a [`DictEntry`]. Handles are cheap to copy.
+#[derive(Clone, Copy)]
+pub struct EntryRef<'a> {
+    tables: &'a Tables,
+    row: u32,
+}
+
+impl<'a> EntryRef<'a> {
+    fn row(self) -> &'a EntryRow {
+        &self.tables.entries[self.row as usize]
+    }
+
+    /// See [`DictEntry::id`].
+    pub fn id(self) -> u32 {
+        self.row().id
+    }
+
+    /// See [`DictEntry::kanjis`].
+    pub fn kanjis(self) -> impl ExactSizeIterator<Item = KanjiRef<'a>> + Clone {
+        let tables = self.tables;
+        self.row()
+            .kanjis
+            .rows()
+            .map(move |row| KanjiRef { tables, row })
+    }
+
+    /// See [`DictEntry::readings`].
+    pub fn readings(self) -> impl ExactSizeIterator<Item = ReadingRef<'a>> + Clone {
+        let tables = self.tables;
+        self.row()
+            .readings
+            .rows()
+            .map(move |row| ReadingRef { tables, row })
+    }
+
+    /// See [`DictEntry::senses`].
+    pub fn senses(self) -> impl ExactSizeIterator<Item = SenseRef<'a>> + Clone {
+        let tables = self.tables;
+        self.row()
+            .senses
+            .rows()
+            .map(move |row| SenseRef { tables, row })
+    }
+
+    /// See [`DictEntry::examples`].
+    pub fn examples(self) -> impl ExactSizeIterator<Item = ExampleRef<'a>> + Clone {
+        let tables = self.tables;
+        self.row()
+            .examples
+            .rows()
+            .map(move |row| ExampleRef { tables, row })
+    }
+
+    /// See [`DictEntry::levels`].
+    pub fn levels(self) -> Vec<NLevel> {
+        let mut levels: Vec<_> = self
+            .readings()
+            .flat_map(|r| r.levels().iter().copied())
+            .collect();
+        levels.sort_unstable();
+        levels.dedup();
+
+        levels
+    }
+
+    /// See [`DictEntry::difficulty`].
+    pub fn difficulty(self) -> Option<u8> {
+        let easiest = *self.levels().last()?;
+        let irregular = self.kanjis().flat_map(|k| k.tags()).any(|tag| {
+            matches!(
+                tag,
+                KTag::Ateji | KTag::IrKanji | KTag::Outdated | KTag::Rare
+            )
+        }) || self
+            .readings()
+            .flat_map(|r| r.tags())
+            .any(|tag| matches!(tag, RTag::Gikun));
+
+        Some(super::stars(easiest, irregular))
+    }
+
+    /// Copies this entry out of the dictionary, e.g. to keep it
+    /// around longer than the dictionary is borrowed.
+    pub fn to_entry(self) -> DictEntry {
+        DictEntry {
+            id: self.id(),
+            kanjis: self
+                .kanjis()
+                .map(|k| Kanji {
+                    text: k.text().to_string(),
+                    levels: k.levels().to_vec(),
+                    tags: k.tags().to_vec(),
+                })
+                .collect(),
+            readings: self
+                .readings()
+                .map(|r| Reading {
+                    text: r.text().to_string(),
+                    relevant_to: r.relevant_to().map(str::to_string).collect(),
+                    levels: r.levels().to_vec(),
+                    tags: r.tags().to_vec(),
+                    pitch: r.pitch(),
+                })
+                .collect(),
+            senses: self
+                .senses()
+                .map(|s| Sense {
+                    number: s.number(),
+                    relevant_kanji: s.relevant_kanji().map(str::to_string).collect(),
+                    relevant_reading: s.relevant_reading().map(str::to_string).collect(),
+                    pos: s.pos().to_vec(),
+                    gloss: s
+                        .glosses()
+                        .map(|g| Gloss {
+                            content: g.to_string(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            examples: self
+                .examples()
+                .map(|e| Example {
+                    sentence: e.sentence().to_string(),
+                    translation: e.translation().to_string(),
+                    word: e.word().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A handle to a kanji spelling of an [`EntryRef`], the packed counterpart of a [`Kanji`].
+#[derive(Clone, Copy)]
+pub struct KanjiRef<'a> {
+    tables: &'a Tables,
+    row: u32,
+}
+
+impl<'a> KanjiRef<'a> {
+    fn row(self) -> &'a KanjiRow {
+        &self.tables.kanjis[self.row as usize]
+    }
+
+    /// See [`Kanji::text`].
+    pub fn text(self) -> &'a str {
+        self.tables.text(self.row().text)
+    }
+
+    /// See [`Kanji::levels`].
+    pub fn levels(self) -> &'a [NLevel] {
+        &self.tables.levels[self.row().levels.range()]
+    }
+
+    /// See [`Kanji::tags`].
+    pub fn tags(self) -> &'a [KTag] {
+        &self.tables.ktags[self.row().tags.range()]
+    }
+}
+
+/// A handle to a reading of an [`EntryRef`], the packed counterpart of a [`Reading`].
+#[derive(Clone, Copy)]
+pub struct ReadingRef<'a> {
+    tables: &'a Tables,
+    row: u32,
+}
+
+impl<'a> ReadingRef<'a> {
+    fn row(self) -> &'a ReadingRow {
+        &self.tables.readings[self.row as usize]
+    }
+
+    /// See [`Reading::text`].
+    pub fn text(self) -> &'a str {
+        self.tables.text(self.row().text)
+    }
+
+    /// See [`Reading::relevant_to`].
+    pub fn relevant_to(self) -> impl ExactSizeIterator<Item = &'a str> + Clone {
+        self.tables.restrictions(self.row().relevant_to)
+    }
+
+    /// Determines whether this reading applies to the kanji spelling `kanji`,
+    /// i.e. it isn't restricted to other spellings.
+    pub fn applies_to(self, kanji: &str) -> bool {
+        let mut relevant = self.relevant_to();
+        relevant.len() == 0 || relevant.any(|k| k == kanji)
+    }
+
+    /// See [`Reading::levels`].
+    pub fn levels(self) -> &'a [NLevel] {
+        &self.tables.levels[self.row().levels.range()]
+    }
+
+    /// See [`Reading::tags`].
+    pub fn tags(self) -> &'a [RTag] {
+        &self.tables.rtags[self.row().tags.range()]
+    }
+
+    /// See [`Reading::pitch`].
+    pub fn pitch(self) -> Option<u8> {
+        self.row().pitch
+    }
+}
+
+/// A handle to a sense of an [`EntryRef`], the packed counterpart of a [`Sense`].
+#[derive(Clone, Copy)]
+pub struct SenseRef<'a> {
+    tables: &'a Tables,
+    row: u32,
+}
+
+impl<'a> SenseRef<'a> {
+    fn row(self) -> &'a SenseRow {
+        &self.tables.senses[self.row as usize]
+    }
+
+    /// See [`Sense::number`].
+    pub fn number(self) -> u32 {
+        self.row().number
+    }
+
+    /// See [`Sense::relevant_kanji`].
+    pub fn relevant_kanji(self) -> impl ExactSizeIterator<Item = &'a str> + Clone {
+        self.tables.restrictions(self.row().relevant_kanji)
+    }
+
+    /// See [`Sense::relevant_reading`].
+    pub fn relevant_reading(self) -> impl ExactSizeIterator<Item = &'a str> + Clone {
+        self.tables.restrictions(self.row().relevant_reading)
+    }
+
+    /// Determines whether this sense applies to the reading `reading`,
+    /// i.e. it isn't restricted to other readings.
+    pub fn applies_to_reading(self, reading: &str) -> bool {
+        let mut relevant = self.relevant_reading();
+        relevant.len() == 0 || relevant.any(|r| r == reading)
+    }
+
+    /// See [`Sense::pos`].
+    pub fn pos(self) -> &'a [Pos] {
+        &self.tables.pos[self.row().pos.range()]
+    }
+
+    /// Gets the [content](`Gloss::content`) of each of the [glosses](`Sense::gloss`).
+    pub fn glosses(self) -> impl ExactSizeIterator<Item = &'a str> + Clone {
+        let tables = self.tables;
+        tables.glosses[self.row().glosses.range()]
+            .iter()
+            .map(move |&s| tables.text(s))
+    }
+}
+
+/// A handle to an example sentence of an [`EntryRef`], the packed counterpart of an [`Example`].
+#[derive(Clone, Copy)]
+pub struct ExampleRef<'a> {
+    tables: &'a Tables,
+    row: u32,
+}
+
+impl<'a> ExampleRef<'a> {
+    fn row(self) -> &'a ExampleRow {
+        &self.tables.examples[self.row as usize]
+    }
+
+    /// See [`Example::sentence`].
+    pub fn sentence(self) -> &'a str {
+        self.tables.text(self.row().sentence)
+    }
+
+    /// See [`Example::translation`].
+    pub fn translation(self) -> &'a str {
+        self.tables.text(self.row().translation)
+    }
+
+    /// See [`Example::word`].
+    pub fn word(self) -> &'a str {
+        self.tables.text(self.row().word)
+    }
+}
@@ -0,0 +1,43 @@
+use rand::RngCore;
+
+use super::{DictEntry, Dictionary, NLevel, Pos};
+
+/// A source of [`DictEntry`]'s that hands out owned entries, so it can be backed
+/// by something other than memory.
+///
+/// [`Dictionary`] is the default implementation, holding every entry in memory.
+/// Hosts short on RAM can implement it over e.g. an SQLite database or a remote
+/// API instead, trading lookup speed for memory.
+pub trait DictionaryProvider: Send + Sync {
+    /// Gets the entry with the id `id`, if it exists.
+    fn by_id(&self, id: u32) -> Option<DictEntry>;
+
+    /// Finds at most `limit` entries spelled or read as `word` or with a gloss
+    /// containing it, the closest matches first, as in [`Dictionary::lookup`].
+    fn lookup(&self, word: &str, limit: usize) -> Vec<DictEntry>;
+
+    /// Gets the ids of the entries that aren't blocked, tagged with at least one
+    /// of `levels` and with a sense tagged with at least one of `pos`, in an
+    /// order drawn from `rng`.
+    fn sample(&self, levels: &[NLevel], pos: &[Pos], rng: &mut dyn RngCore) -> Vec<u32>;
+}
+
+impl DictionaryProvider for Dictionary {
+    fn by_id(&self, id: u32) -> Option<DictEntry> {
+        self.entry(id).map(|entry| entry.to_entry())
+    }
+
+    fn lookup(&self, word: &str, limit: usize) -> Vec<DictEntry> {
+        Dictionary::lookup(self, word)
+            .take(limit)
+            .map(|entry| entry.to_entry())
+            .collect()
+    }
+
+    fn sample(&self, levels: &[NLevel], pos: &[Pos], rng: &mut dyn RngCore) -> Vec<u32> {
+        Dictionary::sample(self, levels, pos, rng)
+            .into_iter()
+            .map(|entry| entry.id())
+            .collect()
+    }
+}
@@ -0,0 +1,329 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    num::NonZeroUsize,
+    path::Path,
+    sync::{Mutex, MutexGuard, RwLock},
+};
+
+use lru::LruCache;
+use rand::{RngCore, seq::SliceRandom};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::kana;
+
+use super::{DictEntry, DictionaryProvider, NLevel, Pos, rank_gloss, rows_of};
+
+const SCHEMA: &str = "
+DROP TABLE IF EXISTS entries;
+DROP TABLE IF EXISTS spellings;
+DROP TABLE IF EXISTS glosses;
+DROP TABLE IF EXISTS entry_levels;
+DROP TABLE IF EXISTS entry_pos;
+CREATE TABLE entries (
+    id INTEGER PRIMARY KEY,
+    entry TEXT NOT NULL
+);
+CREATE TABLE spellings (
+    text TEXT NOT NULL,
+    is_kanji INTEGER NOT NULL,
+    entry_id INTEGER NOT NULL
+);
+CREATE INDEX spellings_by_text ON spellings (text, is_kanji);
+CREATE TABLE glosses (
+    gloss TEXT NOT NULL,
+    entry_id INTEGER NOT NULL
+);
+CREATE TABLE entry_levels (
+    entry_id INTEGER NOT NULL,
+    level TEXT NOT NULL
+);
+CREATE TABLE entry_pos (
+    entry_id INTEGER NOT NULL,
+    pos TEXT NOT NULL
+);
+";
+
+/// A [`DictionaryProvider`] that pages entries in from an SQLite database on
+/// demand, for hosts short on RAM.
+///
+/// Only the indexes of levels and parts of speech used for sampling are kept
+/// in memory, along with the most recently used entries. Spellings and glosses
+/// are looked up in the database, in the order the entries were written.
+/// The database is written by [`SqliteDictionary::create`], e.g. with
+/// `dict_combine sqlite`.
+pub struct SqliteDictionary {
+    conn: Mutex<Connection>,
+    /// How many entries there are.
+    len: usize,
+    /// Maps a level to the ids of the entries tagged with it.
+    by_level: BTreeMap<NLevel, Vec<u32>>,
+    /// Maps a part of speech to the ids of the entries with a sense tagged with it.
+    by_pos: BTreeMap<Pos, Vec<u32>>,
+    /// The most recently used entries.
+    cache: Mutex<LruCache<u32, DictEntry>>,
+    /// Ids of the entries left out of samples, e.g. for having bad data.
+    blocked: RwLock<HashSet<u32>>,
+}
+
+impl SqliteDictionary {
+    /// Writes `entries` to the database at `path`, creating it if needed and
+    /// replacing any dictionary it had.
+    ///
+    /// # Errors
+    /// Fails if the database can't be opened or written.
+    pub fn create(path: &Path, entries: &[DictEntry]) -> rusqlite::Result<()> {
+        let mut conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        let tx = conn.transaction()?;
+        {
+            let mut entry_stmt = tx.prepare("INSERT INTO entries (id, entry) VALUES (?1, ?2)")?;
+            let mut spelling_stmt =
+                tx.prepare("INSERT INTO spellings (text, is_kanji, entry_id) VALUES (?1, ?2, ?3)")?;
+            let mut gloss_stmt =
+                tx.prepare("INSERT INTO glosses (gloss, entry_id) VALUES (?1, ?2)")?;
+            let mut level_stmt =
+                tx.prepare("INSERT INTO entry_levels (entry_id, level) VALUES (?1, ?2)")?;
+            let mut pos_stmt =
+                tx.prepare("INSERT INTO entry_pos (entry_id, pos) VALUES (?1, ?2)")?;
+
+            for entry in entries {
+                entry_stmt.execute(params![entry.id, to_json(entry)?])?;
+
+                for kanji in &entry.kanjis {
+                    spelling_stmt.execute(params![kanji.text, true, entry.id])?;
+                }
+                for reading in &entry.readings {
+                    spelling_stmt.execute(params![reading.text, false, entry.id])?;
+                }
+                for gloss in entry.senses.iter().flat_map(|s| &s.gloss) {
+                    gloss_stmt.execute(params![gloss.content.to_lowercase(), entry.id])?;
+                }
+                for level in entry.levels() {
+                    level_stmt.execute(params![entry.id, to_json(&level)?])?;
+                }
+
+                let pos: BTreeSet<_> = entry.senses.iter().flat_map(|s| &s.pos).collect();
+                for pos in pos {
+                    pos_stmt.execute(params![entry.id, to_json(pos)?])?;
+                }
+            }
+        }
+        tx.commit()
+    }
+
+    /// Opens the dictionary written to the database at `path`, keeping up to
+    /// `cache_size` entries in memory.
+    ///
+    /// # Errors
+    /// Fails if the database can't be opened or has no dictionary.
+    pub fn open(path: &Path, cache_size: NonZeroUsize) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+
+        let len = conn.query_row("SELECT COUNT(*) FROM entries", [], |row| {
+            row.get::<_, i64>(0)
+        })? as usize;
+        let by_level = index(
+            &conn,
+            "SELECT level, entry_id FROM entry_levels ORDER BY rowid",
+        )?;
+        let by_pos = index(&conn, "SELECT pos, entry_id FROM entry_pos ORDER BY rowid")?;
+
+        Ok(SqliteDictionary {
+            conn: Mutex::new(conn),
+            len,
+            by_level,
+            by_pos,
+            cache: Mutex::new(LruCache::new(cache_size)),
+            blocked: RwLock::new(HashSet::new()),
+        })
+    }
+
+    /// Gets how many entries there are.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determines whether there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Leaves the entry with the id `id` out of samples.
+    ///
+    /// Returns false if it was already blocked.
+    pub fn block(&self, id: u32) -> bool {
+        self.blocked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id)
+    }
+
+    /// Puts the entry with the id `id` back into samples.
+    ///
+    /// Returns false if it wasn't blocked.
+    pub fn unblock(&self, id: u32) -> bool {
+        self.blocked
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id)
+    }
+
+    /// Locks the connection for the duration of a query.
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Gets the ids of the entries spelled exactly `text` in kanji if `is_kanji`
+    /// is set, or in kana otherwise.
+    fn by_spelling(&self, text: &str, is_kanji: bool) -> rusqlite::Result<Vec<u32>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached(
+            "SELECT entry_id FROM spellings WHERE text = ?1 AND is_kanji = ?2 ORDER BY rowid",
+        )?;
+
+        stmt.query_map(params![text, is_kanji], |row| row.get(0))?
+            .collect()
+    }
+
+    /// Gets the ids of the entries with a gloss containing `word`, in lowercase,
+    /// along with how closely the gloss matches as ranked by [`rank_gloss`].
+    fn by_gloss(&self, word: &str) -> rusqlite::Result<Vec<(u32, u8)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare_cached(
+            "SELECT entry_id, gloss FROM glosses WHERE instr(gloss, ?1) > 0 ORDER BY rowid",
+        )?;
+
+        stmt.query_map(params![word], |row| {
+            let gloss: String = row.get(1)?;
+            Ok((row.get(0)?, rank_gloss(&gloss, word)))
+        })?
+        .collect()
+    }
+
+    /// Loads the entry with the id `id` from the cache, or from the database
+    /// into the cache.
+    fn load(&self, id: u32) -> rusqlite::Result<Option<DictEntry>> {
+        if let Some(entry) = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+        {
+            return Ok(Some(entry.clone()));
+        }
+
+        let json: Option<String> = self
+            .conn()
+            .prepare_cached("SELECT entry FROM entries WHERE id = ?1")?
+            .query_row(params![id], |row| row.get(0))
+            .optional()?;
+        let Some(json) = json else {
+            return Ok(None);
+        };
+
+        let entry: DictEntry = from_json(&json)?;
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(id, entry.clone());
+
+        Ok(Some(entry))
+    }
+
+    /// Finds the ids of the entries matching `word` in the order of
+    /// [`Dictionary::lookup`](super::Dictionary::lookup).
+    fn lookup_ids(&self, word: &str) -> rusqlite::Result<Vec<u32>> {
+        let hiragana = kana::to_hiragana(word);
+        let lowercase = word.to_lowercase();
+
+        // Keeps the best rank of each entry, in the order they were first found.
+        let mut by_gloss: Vec<(u32, u8)> = Vec::new();
+        for (id, rank) in self.by_gloss(&lowercase)? {
+            match by_gloss.iter_mut().find(|(found, _)| *found == id) {
+                Some((_, best)) => *best = (*best).min(rank),
+                None => by_gloss.push((id, rank)),
+            }
+        }
+        by_gloss.sort_by_key(|&(_, rank)| rank);
+
+        let mut seen = HashSet::new();
+        Ok(self
+            .by_spelling(word, true)?
+            .into_iter()
+            .chain(self.by_spelling(word, false)?)
+            .chain(self.by_spelling(&hiragana, false)?)
+            .chain(by_gloss.into_iter().map(|(id, _)| id))
+            .filter(|&id| seen.insert(id))
+            .collect())
+    }
+}
+
+impl DictionaryProvider for SqliteDictionary {
+    fn by_id(&self, id: u32) -> Option<DictEntry> {
+        self.load(id).unwrap_or_else(|e| {
+            eprintln!("Failed to load entry {id}: {e}");
+            None
+        })
+    }
+
+    fn lookup(&self, word: &str, limit: usize) -> Vec<DictEntry> {
+        match self.lookup_ids(word) {
+            Ok(ids) => ids
+                .into_iter()
+                .filter_map(|id| self.by_id(id))
+                .take(limit)
+                .collect(),
+            Err(e) => {
+                eprintln!("Failed to look up {word}: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    fn sample(&self, levels: &[NLevel], pos: &[Pos], rng: &mut dyn RngCore) -> Vec<u32> {
+        let by_level = rows_of(&self.by_level, levels);
+        let by_pos = rows_of(&self.by_pos, pos);
+        let blocked = self.blocked.read().unwrap_or_else(|e| e.into_inner());
+
+        let mut sample: Vec<_> = by_level
+            .intersection(&by_pos)
+            .copied()
+            .filter(|id| !blocked.contains(id))
+            .collect();
+        // Sorted first so the order only depends on `rng`.
+        sample.sort_unstable();
+        sample.shuffle(rng);
+
+        sample
+    }
+}
+
+/// Reads an index of entry ids by key from the rows of `sql`, each a key
+/// stored as JSON followed by an entry id.
+fn index<K: Ord + DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+) -> rusqlite::Result<BTreeMap<K, Vec<u32>>> {
+    let mut index: BTreeMap<K, Vec<u32>> = BTreeMap::new();
+
+    let mut stmt = conn.prepare(sql)?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let key: String = row.get(0)?;
+        index.entry(from_json(&key)?).or_default().push(row.get(1)?);
+    }
+
+    Ok(index)
+}
+
+fn to_json(value: &impl Serialize) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))
+}
+
+fn from_json<T: DeserializeOwned>(json: &str) -> rusqlite::Result<T> {
+    serde_json::from_str(json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+    })
+}
@@ -0,0 +1,151 @@
+use std::{cmp::Ordering, collections::HashSet};
+
+use rand::{Rng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::kana::katakana_to_hiragana;
+
+/// Most candidates a distractor is picked from when options should resemble the answer.
+pub const CANDIDATES: usize = 200;
+
+/// How closely the wrong options of a question resemble the answer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// Options are random words that share the part of speech of the answer.
+    Easy,
+    /// Options are picked among the words most like the answer.
+    #[default]
+    Normal,
+    /// Options are the words most like the answer.
+    Hard,
+}
+
+impl Difficulty {
+    /// Gets how many random candidates to draw to pick `amount` distractors from,
+    /// leaving room for candidates that duplicate another option.
+    pub fn candidates(self, amount: usize) -> usize {
+        match self {
+            Difficulty::Easy => amount * 2,
+            Difficulty::Normal | Difficulty::Hard => CANDIDATES,
+        }
+    }
+}
+
+/// What makes an option resemble the answer, depending on what the options are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resemblance {
+    /// Kanji spellings look alike when they share kanji, e.g. 会社 and 社会.
+    Look,
+    /// Readings sound alike when few kana need changing to turn one into the other,
+    /// e.g. きょう and きゅう.
+    Sound,
+    /// Glosses are in the same semantic field when they share words,
+    /// e.g. "younger sister" and "older sister".
+    Meaning,
+}
+
+impl Resemblance {
+    /// Scores how much `option` resembles `answer` from zero (not at all) to one (identical).
+    pub fn score(self, answer: &str, option: &str) -> f32 {
+        match self {
+            Resemblance::Look => {
+                let answer = kanji_of(answer);
+                let option = kanji_of(option);
+                jaccard(&answer, &option)
+            }
+            Resemblance::Sound => {
+                let answer: Vec<_> = katakana_to_hiragana(answer).chars().collect();
+                let option: Vec<_> = katakana_to_hiragana(option).chars().collect();
+                let longest = answer.len().max(option.len()).max(1);
+                1.0 - edit_distance(&answer, &option) as f32 / longest as f32
+            }
+            Resemblance::Meaning => jaccard(&words_of(answer), &words_of(option)),
+        }
+    }
+}
+
+/// Picks up to `amount` distractors for a question whose answer is `answer`
+/// from `candidates`, which should be in random order. Candidates that
+/// duplicate the answer or another distractor are skipped, so every option is
+/// unique. The harder `difficulty` is, the more the distractors resemble the answer.
+pub fn pick<'a, R: Rng + ?Sized>(
+    answer: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+    resemblance: Resemblance,
+    difficulty: Difficulty,
+    amount: usize,
+    rng: &mut R,
+) -> Vec<&'a str> {
+    let mut seen = HashSet::from([answer]);
+    let candidates = candidates.into_iter().filter(|&c| seen.insert(c));
+
+    if difficulty == Difficulty::Easy {
+        return candidates.take(amount).collect();
+    }
+
+    let mut scored: Vec<_> = candidates
+        .map(|c| (resemblance.score(answer, c), c))
+        .collect();
+    // Stable, so ties keep their random order.
+    scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    let closest: Vec<_> = scored.into_iter().map(|(_, c)| c).collect();
+    match difficulty {
+        Difficulty::Hard => closest.into_iter().take(amount).collect(),
+        _ => closest[..closest.len().min(amount * 2)]
+            .choose_multiple(rng, amount)
+            .copied()
+            .collect(),
+    }
+}
+
+/// Determines whether `c` is a kanji, as opposed to kana or punctuation.
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}')
+}
+
+/// Gets the distinct kanji of `text`.
+fn kanji_of(text: &str) -> HashSet<char> {
+    text.chars().filter(|&c| is_kanji(c)).collect()
+}
+
+/// Words too common in glosses to relate their meanings.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "to", "of", "in", "on", "at", "for", "or", "and", "be", "one", "'s", "esp",
+    "etc",
+];
+
+/// Gets the distinct meaningful words of the gloss `text`, in lowercase.
+fn words_of(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(str::to_lowercase)
+        .filter(|w| !w.is_empty() && !STOP_WORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Measures how much two sets overlap from zero (disjoint or empty) to one (equal).
+fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// Counts the insertions, deletions, and substitutions needed to turn `a` into `b`.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<_> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
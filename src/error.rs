@@ -0,0 +1,49 @@
+//! Failures of the library's loaders, and of the tools and bot built on them.
+
+use std::{io, path::PathBuf};
+
+/// A failure of the library.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A file couldn't be opened.
+    #[error("failed to open {}: {source}", path.display())]
+    File {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    /// Data couldn't be read or written.
+    #[error("failed to read or write: {0}")]
+    Io(#[from] io::Error),
+    /// Data couldn't be parsed, e.g. a line of a JSON Lines file.
+    #[error("failed to parse: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// Data was parsed but isn't a usable dictionary, e.g. it was cut off or
+    /// has entries that can never be asked.
+    #[error("malformed dictionary: {0}")]
+    DictionaryFormat(String),
+    /// An SQLite database couldn't be read or written.
+    #[cfg(feature = "sqlite")]
+    #[error("database failed: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    /// Discord couldn't be reached or refused a request.
+    #[cfg(feature = "bin")]
+    #[error("Discord request failed: {0}")]
+    Discord(Box<poise::serenity_prelude::Error>),
+    /// Anything else, e.g. a failure of a [`TtsBackend`](crate::tts::TtsBackend)
+    /// or one particular to a caller.
+    #[error(transparent)]
+    Other(#[from] Box<dyn std::error::Error + Send + Sync>),
+}
+
+// Boxed, since Discord's errors are many times larger than the others.
+#[cfg(feature = "bin")]
+impl From<poise::serenity_prelude::Error> for Error {
+    fn from(error: poise::serenity_prelude::Error) -> Self {
+        Error::Discord(Box::new(error))
+    }
+}
+
+/// Result of the library's fallible functions.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
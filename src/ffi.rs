@@ -0,0 +1,326 @@
+//! C bindings for looking up words and generating questions, so apps written in
+//! other languages can reuse the same dictionary and question engine. The
+//! declarations are in `include/jplearnbot.h`.
+//!
+//! Dictionaries and question streams are opaque handles, freed with their
+//! `_free` function. Every string returned is JSON owned by the caller and
+//! freed with [`jp_string_free`]. Functions return null instead of crashing on
+//! null or invalid arguments.
+//!
+//! Build a C library with
+//! `cargo rustc --lib --crate-type cdylib --no-default-features --features ffi`.
+
+use std::{
+    ffi::{CStr, CString, c_char},
+    ptr, slice,
+    sync::Arc,
+};
+
+use rand::{SeedableRng, rngs::StdRng};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use crate::{
+    dictionary::{Dictionary, NLevel},
+    distractor::Difficulty,
+    quiz::{Mode, QuestionBuilder, Questions},
+};
+
+/// A dictionary shared by the question streams created from it, so it can be
+/// freed before them.
+pub struct JpDictionary(Arc<Dictionary>);
+
+/// A stream of questions about the entries of a dictionary.
+pub struct JpQuestions(Questions<Arc<Dictionary>, StdRng>);
+
+/// Creates a dictionary of the entries in the `len` bytes at `bytes`, a JSON
+/// object per line. Returns null if `bytes` is null or an entry can't be parsed.
+///
+/// # Safety
+/// `bytes` must be null or point to `len` readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_dictionary_from_bytes(
+    bytes: *const u8,
+    len: usize,
+) -> *mut JpDictionary {
+    if bytes.is_null() {
+        return ptr::null_mut();
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(bytes, len) };
+    match Dictionary::from_bytes(bytes) {
+        Ok(dictionary) => Box::into_raw(Box::new(JpDictionary(Arc::new(dictionary)))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees `dictionary`. Question streams created from it stay valid.
+///
+/// # Safety
+/// `dictionary` must be null or a handle from [`jp_dictionary_from_bytes`]
+/// that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_dictionary_free(dictionary: *mut JpDictionary) {
+    if !dictionary.is_null() {
+        drop(unsafe { Box::from_raw(dictionary) });
+    }
+}
+
+/// Gets how many entries `dictionary` has, or zero if it is null.
+///
+/// # Safety
+/// `dictionary` must be null or a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_dictionary_len(dictionary: *const JpDictionary) -> usize {
+    unsafe { dictionary.as_ref() }.map_or(0, |d| d.0.len())
+}
+
+/// Looks up `word` like the bot's /lookup, by kanji, kana, romaji, or English.
+/// Returns a JSON array of at most `limit` entries, the closest matches first,
+/// or null if an argument is null or `word` isn't UTF-8.
+///
+/// # Safety
+/// `dictionary` must be null or a valid handle, and `word` null or a
+/// nul-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_lookup(
+    dictionary: *const JpDictionary,
+    word: *const c_char,
+    limit: usize,
+) -> *mut c_char {
+    let Some(dictionary) = (unsafe { dictionary.as_ref() }) else {
+        return ptr::null_mut();
+    };
+    let Some(word) = (unsafe { str_from(word) }) else {
+        return ptr::null_mut();
+    };
+
+    let entries: Vec<_> = dictionary
+        .0
+        .lookup(word.trim())
+        .take(limit)
+        .map(|entry| entry.to_entry())
+        .collect();
+
+    to_json(&entries)
+}
+
+/// Creates a stream of questions about the entries of `dictionary`.
+///
+/// `mode` is the index of the [`Mode`] in declaration order, `difficulty` 0, 1,
+/// or 2 for easy, normal, or hard distractors, and `levels` a bit mask of the
+/// levels to ask about from N1 (bit 0) to N5 (bit 4), or 0 for every level.
+/// Streams created with the same arguments and `seed` ask the same questions.
+///
+/// Returns null if `dictionary` is null or `mode` or `difficulty` is out of range.
+///
+/// # Safety
+/// `dictionary` must be null or a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_questions_new(
+    dictionary: *const JpDictionary,
+    mode: u32,
+    difficulty: u32,
+    levels: u32,
+    seed: u64,
+) -> *mut JpQuestions {
+    let Some(dictionary) = (unsafe { dictionary.as_ref() }) else {
+        return ptr::null_mut();
+    };
+    let Some(mode) = Mode::iter().nth(mode as usize) else {
+        return ptr::null_mut();
+    };
+    let difficulty = match difficulty {
+        0 => Difficulty::Easy,
+        1 => Difficulty::Normal,
+        2 => Difficulty::Hard,
+        _ => return ptr::null_mut(),
+    };
+
+    let mut builder =
+        QuestionBuilder::with_rng(Arc::clone(&dictionary.0), StdRng::seed_from_u64(seed))
+            .mode(mode)
+            .distractors(difficulty);
+    if levels != 0 {
+        builder = builder.levels(
+            NLevel::iter()
+                .enumerate()
+                .filter(|(i, _)| levels & (1 << i) != 0)
+                .map(|(_, level)| level),
+        );
+    }
+
+    Box::into_raw(Box::new(JpQuestions(builder.build())))
+}
+
+/// Gets how many questions `questions` has left, or zero if it is null.
+///
+/// # Safety
+/// `questions` must be null or a valid handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_questions_len(questions: *const JpQuestions) -> usize {
+    unsafe { questions.as_ref() }.map_or(0, |q| q.0.len())
+}
+
+/// Gets the next question of `questions` as a JSON object. Returns null once
+/// there are none left or if `questions` is null.
+///
+/// # Safety
+/// `questions` must be null or a valid handle, not used by another thread
+/// at the same time.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_questions_next(questions: *mut JpQuestions) -> *mut c_char {
+    unsafe { questions.as_mut() }
+        .and_then(|q| q.0.next())
+        .map_or(ptr::null_mut(), |question| to_json(&question))
+}
+
+/// Frees `questions`.
+///
+/// # Safety
+/// `questions` must be null or a handle from [`jp_questions_new`] that hasn't
+/// been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_questions_free(questions: *mut JpQuestions) {
+    if !questions.is_null() {
+        drop(unsafe { Box::from_raw(questions) });
+    }
+}
+
+/// Frees a string returned by one of the other functions.
+///
+/// # Safety
+/// `string` must be null or a string returned by this library that hasn't
+/// been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn jp_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Borrows the nul-terminated string at `string`, if it isn't null and is UTF-8.
+///
+/// # Safety
+/// `string` must be null or a nul-terminated string that outlives `'a`.
+unsafe fn str_from<'a>(string: *const c_char) -> Option<&'a str> {
+    if string.is_null() {
+        return None;
+    }
+
+    unsafe { CStr::from_ptr(string) }.to_str().ok()
+}
+
+/// Serializes `value` into a string for the caller to free with [`jp_string_free`].
+fn to_json(value: &impl Serialize) -> *mut c_char {
+    serde_json::to_string(value)
+        .ok()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    fn dictionary() -> *mut JpDictionary {
+        let bytes = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/content/dictionary.jsonl"
+        ))
+        .unwrap();
+        let dictionary = unsafe { jp_dictionary_from_bytes(bytes.as_ptr(), bytes.len()) };
+        assert!(!dictionary.is_null());
+        dictionary
+    }
+
+    /// Takes ownership of a returned string and parses it.
+    fn take_json(string: *mut c_char) -> Value {
+        assert!(!string.is_null());
+        let value = serde_json::from_str(unsafe { CStr::from_ptr(string) }.to_str().unwrap());
+        unsafe { jp_string_free(string) };
+        value.unwrap()
+    }
+
+    #[test]
+    fn invalid_arguments_return_null() {
+        let bytes = b"not json";
+        unsafe {
+            assert!(jp_dictionary_from_bytes(ptr::null(), 0).is_null());
+            assert!(jp_dictionary_from_bytes(bytes.as_ptr(), bytes.len()).is_null());
+            assert_eq!(jp_dictionary_len(ptr::null()), 0);
+            assert!(jp_lookup(ptr::null(), c"食べる".as_ptr(), 1).is_null());
+            assert!(jp_questions_new(ptr::null(), 0, 0, 0, 0).is_null());
+            assert!(jp_questions_next(ptr::null_mut()).is_null());
+            assert_eq!(jp_questions_len(ptr::null()), 0);
+
+            jp_dictionary_free(ptr::null_mut());
+            jp_questions_free(ptr::null_mut());
+            jp_string_free(ptr::null_mut());
+        }
+
+        // One past the last mode, whichever it is.
+        let modes = Mode::iter().count() as u32;
+        let dictionary = dictionary();
+        unsafe {
+            assert!(jp_lookup(dictionary, ptr::null(), 1).is_null());
+            assert!(jp_lookup(dictionary, c"\xff".as_ptr(), 1).is_null());
+            assert!(jp_questions_new(dictionary, modes, 0, 0, 0).is_null());
+            assert!(jp_questions_new(dictionary, 0, 3, 0, 0).is_null());
+            jp_dictionary_free(dictionary);
+        }
+    }
+
+    #[test]
+    fn lookup_finds_entries() {
+        let dictionary = dictionary();
+
+        let entries = take_json(unsafe { jp_lookup(dictionary, c"taberu".as_ptr(), 5) });
+        let entries = entries.as_array().unwrap();
+        assert!(!entries.is_empty() && entries.len() <= 5);
+        assert_eq!(entries[0]["k_ele"][0]["keb"], "食べる");
+
+        let entries = take_json(unsafe { jp_lookup(dictionary, c"食べる".as_ptr(), 0) });
+        assert_eq!(entries, Value::Array(Vec::new()));
+
+        unsafe { jp_dictionary_free(dictionary) };
+    }
+
+    #[test]
+    fn questions_outlive_dictionary() {
+        let dictionary = dictionary();
+        let questions = unsafe { jp_questions_new(dictionary, 3, 1, 1 << 3, 1) };
+        assert!(!questions.is_null());
+        unsafe { jp_dictionary_free(dictionary) };
+
+        let len = unsafe { jp_questions_len(questions) };
+        assert!(len > 0);
+        for _ in 0..len {
+            let question = take_json(unsafe { jp_questions_next(questions) });
+            assert_eq!(question["mode"], "KanToHir");
+        }
+        assert!(unsafe { jp_questions_next(questions) }.is_null());
+
+        unsafe { jp_questions_free(questions) };
+    }
+
+    #[test]
+    fn same_seed_asks_same_questions() {
+        let dictionary = dictionary();
+        let ask = |seed| {
+            let questions = unsafe { jp_questions_new(dictionary, 6, 2, 0, seed) };
+            let asked: Vec<_> = (0..5)
+                .map(|_| take_json(unsafe { jp_questions_next(questions) }))
+                .collect();
+            unsafe { jp_questions_free(questions) };
+            asked
+        };
+
+        assert_eq!(ask(42), ask(42));
+        assert_ne!(ask(42), ask(43));
+
+        unsafe { jp_dictionary_free(dictionary) };
+    }
+}
@@ -0,0 +1,30 @@
+use std::io::BufRead;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dictionary::NLevel;
+
+/// A grammar point of the JLPT, e.g. 〜ばかりか.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrammarPoint {
+    /// How the point is used, with 〜 where the word it attaches to goes.
+    pub pattern: String,
+    /// What the point means, in English.
+    pub meaning: String,
+    /// A sentence using the point.
+    pub example: String,
+    /// The level the point is tested at.
+    pub level: NLevel,
+}
+
+/// Reads grammar points from `reader`, a JSON object per line, e.g. the
+/// `grammar.jsonl` generated by dict_combine.
+///
+/// # Errors
+/// Fails if `reader` can't be read or a point can't be parsed.
+pub fn read_grammar(reader: impl BufRead) -> crate::Result<Vec<GrammarPoint>> {
+    serde_json::Deserializer::from_reader(reader)
+        .into_iter()
+        .collect::<serde_json::Result<_>>()
+        .map_err(crate::Error::from)
+}
@@ -0,0 +1,103 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    mem::size_of,
+    sync::Arc,
+};
+
+/// Estimates how many bytes a value owns on the heap, not counting the value itself.
+///
+/// Estimates ignore the allocator's own overhead, and the layout of maps and sets
+/// is approximated.
+pub trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+/// Implements [`HeapSize`] for types that own nothing on the heap.
+macro_rules! no_heap {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $crate::heap_size::HeapSize for $ty {
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+pub(crate) use no_heap;
+
+no_heap!(u8, u16, u32, u64, usize, bool, char);
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+/// Counts the shared value in full, so a value shared by several [`Arc`]'s is
+/// counted once for each of them.
+impl<T: HeapSize> HeapSize for Arc<T> {
+    fn heap_size(&self) -> usize {
+        // The reference counts are allocated along with the value.
+        2 * size_of::<usize>() + size_of::<T>() + T::heap_size(self)
+    }
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize, S> HeapSize for HashMap<K, V, S> {
+    fn heap_size(&self) -> usize {
+        // A control byte per bucket.
+        self.capacity() * (size_of::<(K, V)>() + 1)
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_size() + v.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl<T: HeapSize, S> HeapSize for HashSet<T, S> {
+    fn heap_size(&self) -> usize {
+        // A control byte per bucket.
+        self.capacity() * (size_of::<T>() + 1) + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<K: HeapSize, V: HeapSize> HeapSize for BTreeMap<K, V> {
+    fn heap_size(&self) -> usize {
+        // Nodes aren't always full, so this is a lower bound.
+        self.len() * size_of::<(K, V)>()
+            + self
+                .iter()
+                .map(|(k, v)| k.heap_size() + v.heap_size())
+                .sum::<usize>()
+    }
+}
+
+/// Formats `bytes` in the largest binary unit it is at least one of, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,712 @@
+use serde::{Deserialize, Serialize};
+
+/// Offset between a katakana and its hiragana in Unicode.
+const KATAKANA_OFFSET: u32 = 0x60;
+
+/// The longest romaji spelling of a single kana, e.g. `xtsu`.
+const MAX_ROMAJI_LEN: usize = 4;
+
+/// Romaji spellings of kana, covering Hepburn, Kunrei-shiki, and common
+/// IME (wāpuro) spellings.
+#[rustfmt::skip]
+const ROMAJI: &[(&str, &str)] = &[
+    ("a", "あ"), ("i", "い"), ("u", "う"), ("e", "え"), ("o", "お"),
+    ("ka", "か"), ("ki", "き"), ("ku", "く"), ("ke", "け"), ("ko", "こ"),
+    ("kya", "きゃ"), ("kyu", "きゅ"), ("kyo", "きょ"),
+    ("ga", "が"), ("gi", "ぎ"), ("gu", "ぐ"), ("ge", "げ"), ("go", "ご"),
+    ("gya", "ぎゃ"), ("gyu", "ぎゅ"), ("gyo", "ぎょ"),
+    ("sa", "さ"), ("si", "し"), ("shi", "し"), ("su", "す"), ("se", "せ"), ("so", "そ"),
+    ("sha", "しゃ"), ("shu", "しゅ"), ("sho", "しょ"), ("she", "しぇ"),
+    ("sya", "しゃ"), ("syu", "しゅ"), ("syo", "しょ"),
+    ("za", "ざ"), ("zi", "じ"), ("ji", "じ"), ("zu", "ず"), ("ze", "ぜ"), ("zo", "ぞ"),
+    ("ja", "じゃ"), ("ju", "じゅ"), ("jo", "じょ"), ("je", "じぇ"),
+    ("jya", "じゃ"), ("jyu", "じゅ"), ("jyo", "じょ"),
+    ("zya", "じゃ"), ("zyu", "じゅ"), ("zyo", "じょ"),
+    ("ta", "た"), ("ti", "ち"), ("chi", "ち"), ("tu", "つ"), ("tsu", "つ"), ("te", "て"), ("to", "と"),
+    ("cha", "ちゃ"), ("chu", "ちゅ"), ("cho", "ちょ"), ("che", "ちぇ"),
+    ("tya", "ちゃ"), ("tyu", "ちゅ"), ("tyo", "ちょ"),
+    ("cya", "ちゃ"), ("cyu", "ちゅ"), ("cyo", "ちょ"),
+    ("thi", "てぃ"), ("dhi", "でぃ"),
+    ("da", "だ"), ("di", "ぢ"), ("du", "づ"), ("dzu", "づ"), ("de", "で"), ("do", "ど"),
+    ("dya", "ぢゃ"), ("dyu", "ぢゅ"), ("dyo", "ぢょ"),
+    ("na", "な"), ("ni", "に"), ("nu", "ぬ"), ("ne", "ね"), ("no", "の"),
+    ("nya", "にゃ"), ("nyu", "にゅ"), ("nyo", "にょ"),
+    ("ha", "は"), ("hi", "ひ"), ("hu", "ふ"), ("fu", "ふ"), ("he", "へ"), ("ho", "ほ"),
+    ("hya", "ひゃ"), ("hyu", "ひゅ"), ("hyo", "ひょ"),
+    ("fa", "ふぁ"), ("fi", "ふぃ"), ("fe", "ふぇ"), ("fo", "ふぉ"),
+    ("ba", "ば"), ("bi", "び"), ("bu", "ぶ"), ("be", "べ"), ("bo", "ぼ"),
+    ("bya", "びゃ"), ("byu", "びゅ"), ("byo", "びょ"),
+    ("pa", "ぱ"), ("pi", "ぴ"), ("pu", "ぷ"), ("pe", "ぺ"), ("po", "ぽ"),
+    ("pya", "ぴゃ"), ("pyu", "ぴゅ"), ("pyo", "ぴょ"),
+    ("ma", "ま"), ("mi", "み"), ("mu", "む"), ("me", "め"), ("mo", "も"),
+    ("mya", "みゃ"), ("myu", "みゅ"), ("myo", "みょ"),
+    ("ya", "や"), ("yu", "ゆ"), ("yo", "よ"),
+    ("ra", "ら"), ("ri", "り"), ("ru", "る"), ("re", "れ"), ("ro", "ろ"),
+    ("rya", "りゃ"), ("ryu", "りゅ"), ("ryo", "りょ"),
+    ("wa", "わ"), ("wi", "ゐ"), ("we", "ゑ"), ("wo", "を"),
+    ("va", "ゔぁ"), ("vi", "ゔぃ"), ("vu", "ゔ"), ("ve", "ゔぇ"), ("vo", "ゔぉ"),
+    ("xa", "ぁ"), ("xi", "ぃ"), ("xu", "ぅ"), ("xe", "ぇ"), ("xo", "ぉ"),
+    ("la", "ぁ"), ("li", "ぃ"), ("lu", "ぅ"), ("le", "ぇ"), ("lo", "ぉ"),
+    ("xya", "ゃ"), ("xyu", "ゅ"), ("xyo", "ょ"),
+    ("lya", "ゃ"), ("lyu", "ゅ"), ("lyo", "ょ"),
+    ("xtu", "っ"), ("xtsu", "っ"), ("ltu", "っ"), ("ltsu", "っ"),
+    ("xwa", "ゎ"), ("lwa", "ゎ"),
+];
+
+/// The kana of the chart beginners learn, in hiragana with their Hepburn
+/// romaji: the basic kana, those with dakuten and handakuten, and the
+/// digraphs (yōon) made with small ゃ, ゅ, and ょ.
+#[rustfmt::skip]
+pub const CHART: &[(&str, &str)] = &[
+    ("あ", "a"), ("い", "i"), ("う", "u"), ("え", "e"), ("お", "o"),
+    ("か", "ka"), ("き", "ki"), ("く", "ku"), ("け", "ke"), ("こ", "ko"),
+    ("さ", "sa"), ("し", "shi"), ("す", "su"), ("せ", "se"), ("そ", "so"),
+    ("た", "ta"), ("ち", "chi"), ("つ", "tsu"), ("て", "te"), ("と", "to"),
+    ("な", "na"), ("に", "ni"), ("ぬ", "nu"), ("ね", "ne"), ("の", "no"),
+    ("は", "ha"), ("ひ", "hi"), ("ふ", "fu"), ("へ", "he"), ("ほ", "ho"),
+    ("ま", "ma"), ("み", "mi"), ("む", "mu"), ("め", "me"), ("も", "mo"),
+    ("や", "ya"), ("ゆ", "yu"), ("よ", "yo"),
+    ("ら", "ra"), ("り", "ri"), ("る", "ru"), ("れ", "re"), ("ろ", "ro"),
+    ("わ", "wa"), ("を", "wo"), ("ん", "n"),
+    ("が", "ga"), ("ぎ", "gi"), ("ぐ", "gu"), ("げ", "ge"), ("ご", "go"),
+    ("ざ", "za"), ("じ", "ji"), ("ず", "zu"), ("ぜ", "ze"), ("ぞ", "zo"),
+    ("だ", "da"), ("ぢ", "ji"), ("づ", "zu"), ("で", "de"), ("ど", "do"),
+    ("ば", "ba"), ("び", "bi"), ("ぶ", "bu"), ("べ", "be"), ("ぼ", "bo"),
+    ("ぱ", "pa"), ("ぴ", "pi"), ("ぷ", "pu"), ("ぺ", "pe"), ("ぽ", "po"),
+    ("きゃ", "kya"), ("きゅ", "kyu"), ("きょ", "kyo"),
+    ("しゃ", "sha"), ("しゅ", "shu"), ("しょ", "sho"),
+    ("ちゃ", "cha"), ("ちゅ", "chu"), ("ちょ", "cho"),
+    ("にゃ", "nya"), ("にゅ", "nyu"), ("にょ", "nyo"),
+    ("ひゃ", "hya"), ("ひゅ", "hyu"), ("ひょ", "hyo"),
+    ("みゃ", "mya"), ("みゅ", "myu"), ("みょ", "myo"),
+    ("りゃ", "rya"), ("りゅ", "ryu"), ("りょ", "ryo"),
+    ("ぎゃ", "gya"), ("ぎゅ", "gyu"), ("ぎょ", "gyo"),
+    ("じゃ", "ja"), ("じゅ", "ju"), ("じょ", "jo"),
+    ("びゃ", "bya"), ("びゅ", "byu"), ("びょ", "byo"),
+    ("ぴゃ", "pya"), ("ぴゅ", "pyu"), ("ぴょ", "pyo"),
+];
+
+/// Converts the hiragana of `text` to katakana. Other characters are kept as is.
+pub fn hiragana_to_katakana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' | 'ゝ' | 'ゞ' => {
+                char::from_u32(c as u32 + KATAKANA_OFFSET).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+/// Converts the katakana of `text` to hiragana. Other characters are kept as is.
+pub fn katakana_to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ァ'..='ヶ' | 'ヽ' | 'ヾ' => {
+                char::from_u32(c as u32 - KATAKANA_OFFSET).unwrap_or(c)
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+/// Converts the romaji of `text` to hiragana. Characters that aren't part of
+/// a romaji spelling are kept as is.
+///
+/// Hepburn (`shi`, `tsu`, `fu`), Kunrei-shiki (`si`, `tu`, `hu`), and IME
+/// (`nn`, `xtsu`) spellings are understood. Doubled consonants become a sokuon
+/// (`kitte` → きって), `n` before a consonant or `m` before a labial become ん,
+/// and long vowels written with macrons or circumflexes are spelled out
+/// (`tōkyō` → とうきょう). Hyphens become the long vowel mark.
+pub fn romaji_to_hiragana(text: &str) -> String {
+    let chars: Vec<char> = text
+        .to_lowercase()
+        .chars()
+        .flat_map(expand_long_vowel)
+        .collect();
+    let mut res = String::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let after_next = chars.get(i + 2).copied();
+
+        if c == 'n' {
+            match next {
+                Some('\'') => {
+                    res.push('ん');
+                    i += 2;
+                    continue;
+                }
+                // A doubled n followed by a syllable is ん plus that syllable,
+                // e.g. konnichiwa, so only the first n is consumed.
+                Some('n') if !after_next.is_some_and(|c| is_vowel(c) || c == 'y') => {
+                    res.push('ん');
+                    i += 2;
+                    continue;
+                }
+                Some(n) if is_vowel(n) || n == 'y' => {}
+                _ => {
+                    res.push('ん');
+                    i += 1;
+                    continue;
+                }
+            }
+        }
+
+        // Hepburn writes ん as m before labials, e.g. shimbun.
+        if c == 'm' && matches!(next, Some('b' | 'p')) {
+            res.push('ん');
+            i += 1;
+            continue;
+        }
+
+        // Doubled consonants, and Hepburn's tch, e.g. matcha.
+        if is_consonant(c) && (next == Some(c) || (c == 't' && next == Some('c'))) {
+            res.push('っ');
+            i += 1;
+            continue;
+        }
+
+        let kana = (1..=MAX_ROMAJI_LEN.min(chars.len() - i))
+            .rev()
+            .find_map(|len| {
+                let romaji: String = chars[i..i + len].iter().collect();
+                ROMAJI
+                    .iter()
+                    .find(|(r, _)| *r == romaji)
+                    .map(|(_, kana)| (len, *kana))
+            });
+
+        match kana {
+            Some((len, kana)) => {
+                res.push_str(kana);
+                i += len;
+            }
+            None => {
+                res.push(if c == '-' { 'ー' } else { c });
+                i += 1;
+            }
+        }
+    }
+
+    res
+}
+
+/// Converts the katakana and romaji of `text` to hiragana.
+pub fn to_hiragana(text: &str) -> String {
+    romaji_to_hiragana(&katakana_to_hiragana(text))
+}
+
+/// Scripts Japanese is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Hiragana,
+    /// Also includes the middle dot ・ separating the words of loanwords.
+    Katakana,
+    /// Also includes the repetition mark 々.
+    Kanji,
+}
+
+impl Script {
+    /// Gets the script `c` is written in, or [`None`] if it isn't a Japanese
+    /// letter. The long vowel mark ー is written in both kana scripts, so it has none.
+    pub fn of(c: char) -> Option<Script> {
+        match c {
+            'ぁ'..='ゖ' | 'ゝ'..='ゟ' => Some(Script::Hiragana),
+            'ァ'..='ヺ' | '・' | 'ヽ'..='ヿ' => Some(Script::Katakana),
+            '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々' => Some(Script::Kanji),
+            _ => None,
+        }
+    }
+}
+
+/// Gets the script all of `text` is written in, along with the long vowel mark,
+/// e.g. [`Script::Katakana`] for ラーメン.
+///
+/// Returns [`None`] if `text` mixes scripts, has characters of none, or is empty.
+pub fn script(text: &str) -> Option<Script> {
+    let mut script = None;
+    for c in text.chars().filter(|&c| c != 'ー') {
+        let of = Script::of(c)?;
+        if *script.get_or_insert(of) != of {
+            return None;
+        }
+    }
+
+    script
+}
+
+/// Determines whether `text` has any kanji.
+pub fn has_kanji(text: &str) -> bool {
+    text.chars().any(|c| Script::of(c) == Some(Script::Kanji))
+}
+
+/// Determines whether `a` and `b` are written with the same kanji in the same
+/// order, so they differ only in their kana, e.g. in the okurigana of 受け付け
+/// and 受付. Text without kanji never is.
+pub fn same_kanji(a: &str, b: &str) -> bool {
+    let kanji = |text: &str| -> Vec<char> {
+        text.chars()
+            .filter(|&c| Script::of(c) == Some(Script::Kanji))
+            .collect()
+    };
+    let a = kanji(a);
+
+    !a.is_empty() && a == kanji(b)
+}
+
+/// Determines whether `text` is written only in katakana, along with the long vowel mark.
+pub fn is_katakana(text: &str) -> bool {
+    script(text) == Some(Script::Katakana)
+}
+
+/// Determines whether `text` is written only in one of hiragana or katakana,
+/// along with the long vowel mark.
+pub fn is_kana(text: &str) -> bool {
+    matches!(script(text), Some(Script::Hiragana | Script::Katakana))
+}
+
+/// Determines whether `text` is written only in hiragana, along with the long vowel mark.
+pub fn is_hiragana(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| matches!(c, 'ぁ'..='ゖ' | 'ゝ' | 'ゞ' | 'ー'))
+}
+
+/// Normalizes a typed answer for comparison: whitespace is removed and katakana
+/// and romaji are converted to hiragana.
+pub fn normalize(text: &str) -> String {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    to_hiragana(&text)
+}
+
+/// How closely a typed answer has to match the answer. Each tolerance also
+/// accepts what the stricter ones do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Tolerance {
+    /// The kana must match exactly.
+    #[default]
+    Exact,
+    /// Long vowels may be typed short, e.g. とうきょう as ときょ or ラーメン as らめん.
+    LongVowels,
+    /// One kana may be wrong, missing, extra, or swapped with the next, e.g.
+    /// がっこう as がつこう, unless the answer is shorter than
+    /// [`Self::MIN_TYPO_LENGTH`].
+    OneTypo,
+}
+
+impl Tolerance {
+    /// Answers shorter than this many kana can't be typed with a typo, since
+    /// a typo of them is as likely another word.
+    pub const MIN_TYPO_LENGTH: usize = 3;
+
+    /// Determines whether `guess` is close enough to `answer`. Both should be
+    /// [normalized](normalize).
+    pub fn accepts(self, guess: &str, answer: &str) -> bool {
+        if guess == answer {
+            return true;
+        }
+
+        match self {
+            Tolerance::Exact => false,
+            Tolerance::LongVowels => shorten_long_vowels(guess) == shorten_long_vowels(answer),
+            Tolerance::OneTypo => {
+                let chars: Vec<_> = answer.chars().collect();
+                let typo = chars.len() >= Self::MIN_TYPO_LENGTH
+                    && edit_distance(&guess.chars().collect::<Vec<_>>(), &chars) <= 1;
+
+                typo || Tolerance::LongVowels.accepts(guess, answer)
+            }
+        }
+    }
+}
+
+/// Writes the long vowels of `text`, in hiragana, short: long vowel marks are
+/// removed, as are vowels lengthening the one before them, e.g. the う of
+/// こう or the い of せい.
+fn shorten_long_vowels(text: &str) -> String {
+    let mut shortened = String::new();
+    let mut last_vowel = None;
+
+    for c in text.chars() {
+        if c == 'ー' {
+            continue;
+        }
+        let lengthens = match (last_vowel, c) {
+            (Some(last), 'あ' | 'い' | 'う' | 'え' | 'お') => {
+                vowel_of(c) == Some(last) || matches!((last, c), ('o', 'う') | ('e', 'い'))
+            }
+            _ => false,
+        };
+        if !lengthens {
+            shortened.push(c);
+            last_vowel = vowel_of(c);
+        }
+    }
+
+    shortened
+}
+
+/// Gets the vowel `c`, a hiragana, ends in, e.g. `a` for か or ゃ. [`None`]
+/// for ん and っ, which have none.
+fn vowel_of(c: char) -> Option<char> {
+    if matches!(c, 'ん' | 'っ') {
+        return None;
+    }
+
+    let kana = c.to_string();
+    ROMAJI
+        .iter()
+        .find(|&&(_, k)| k == kana)
+        .and_then(|(romaji, _)| romaji.chars().last())
+        .filter(|&c| is_vowel(c))
+}
+
+/// Gets how many kana have to be changed, removed, added, or swapped with the
+/// next to turn `a` into `b`, each kana changed at most once (the optimal
+/// string alignment distance, a restricted Damerau-Levenshtein distance).
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    // Distances from the prefixes of `a` to the prefixes of `b`, by row of `a`.
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in rows[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// Splits `text` into its morae, e.g. きょうと into きょ, う, and と. Small kana
+/// other than っ are joined to the kana before them.
+pub fn morae(text: &str) -> Vec<&str> {
+    let mut morae = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in text.char_indices().skip(1) {
+        if !is_small_glide(c) {
+            morae.push(&text[start..i]);
+            start = i;
+        }
+    }
+    if start < text.len() {
+        morae.push(&text[start..]);
+    }
+
+    morae
+}
+
+/// Marks where the pitch of `reading` drops with ↓, e.g. は↓し for a `pitch`
+/// of 1, as numbered in pitch-accent dictionaries: the mora after which the
+/// pitch drops, or 0 if it never does. Readings that never drop, or whose
+/// `pitch` is past their end, are kept as is.
+pub fn mark_downstep(reading: &str, pitch: u8) -> String {
+    let morae = morae(reading);
+    let pitch = usize::from(pitch);
+    if pitch == 0 || pitch > morae.len() {
+        return reading.to_string();
+    }
+
+    format!("{}↓{}", morae[..pitch].concat(), morae[pitch..].concat())
+}
+
+/// Determines whether `c` is a small kana that is pronounced together with
+/// the kana before it, e.g. ゃ of きゃ.
+fn is_small_glide(c: char) -> bool {
+    matches!(
+        c,
+        'ぁ' | 'ぃ'
+            | 'ぅ'
+            | 'ぇ'
+            | 'ぉ'
+            | 'ゃ'
+            | 'ゅ'
+            | 'ょ'
+            | 'ゎ'
+            | 'ァ'
+            | 'ィ'
+            | 'ゥ'
+            | 'ェ'
+            | 'ォ'
+            | 'ャ'
+            | 'ュ'
+            | 'ョ'
+            | 'ヮ'
+    )
+}
+
+/// Spells out a vowel written long with a macron or circumflex, e.g. ō → ou.
+fn expand_long_vowel(c: char) -> Vec<char> {
+    match c {
+        'ā' | 'â' => vec!['a', 'a'],
+        'ī' | 'î' => vec!['i', 'i'],
+        'ū' | 'û' => vec!['u', 'u'],
+        'ē' | 'ê' => vec!['e', 'e'],
+        'ō' | 'ô' => vec!['o', 'u'],
+        c => vec![c],
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+/// Determines whether `c` is a consonant that can be doubled into a sokuon.
+fn is_consonant(c: char) -> bool {
+    c.is_ascii_lowercase() && !is_vowel(c) && c != 'n'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_katakana() {
+        assert_eq!(katakana_to_hiragana("カタカナ"), "かたかな");
+        assert_eq!(katakana_to_hiragana("ラーメン"), "らーめん");
+        assert_eq!(katakana_to_hiragana("ヴァイオリン"), "ゔぁいおりん");
+        assert_eq!(
+            katakana_to_hiragana("ひらがな and 漢字"),
+            "ひらがな and 漢字"
+        );
+    }
+
+    #[test]
+    fn converts_hiragana() {
+        assert_eq!(hiragana_to_katakana("ひらがな"), "ヒラガナ");
+        assert_eq!(hiragana_to_katakana("きゃ"), "キャ");
+        assert_eq!(
+            hiragana_to_katakana("らーめん and 漢字"),
+            "ラーメン and 漢字"
+        );
+    }
+
+    #[test]
+    fn spells_the_chart_as_it_is_typed() {
+        for (kana, romaji) in CHART {
+            assert_eq!(
+                romaji_to_hiragana(romaji),
+                kana.replace('ぢ', "じ").replace('づ', "ず")
+            );
+        }
+    }
+
+    #[test]
+    fn converts_basic_romaji() {
+        assert_eq!(romaji_to_hiragana("sakura"), "さくら");
+        assert_eq!(romaji_to_hiragana("arigatou"), "ありがとう");
+        assert_eq!(romaji_to_hiragana("Neko"), "ねこ");
+        assert_eq!(romaji_to_hiragana("kyou"), "きょう");
+    }
+
+    #[test]
+    fn converts_hepburn_and_kunrei_alike() {
+        for (hepburn, kunrei) in [
+            ("shi", "si"),
+            ("chi", "ti"),
+            ("tsu", "tu"),
+            ("fu", "hu"),
+            ("ji", "zi"),
+            ("sha", "sya"),
+            ("cho", "tyo"),
+            ("ju", "zyu"),
+        ] {
+            assert_eq!(romaji_to_hiragana(hepburn), romaji_to_hiragana(kunrei));
+        }
+
+        assert_eq!(romaji_to_hiragana("shinjitsu"), "しんじつ");
+        assert_eq!(romaji_to_hiragana("sinzitu"), "しんじつ");
+    }
+
+    #[test]
+    fn converts_sokuon() {
+        assert_eq!(romaji_to_hiragana("kitte"), "きって");
+        assert_eq!(romaji_to_hiragana("zasshi"), "ざっし");
+        assert_eq!(romaji_to_hiragana("matcha"), "まっちゃ");
+        assert_eq!(romaji_to_hiragana("kippu"), "きっぷ");
+        assert_eq!(romaji_to_hiragana("xtsu"), "っ");
+    }
+
+    #[test]
+    fn converts_syllabic_n() {
+        assert_eq!(romaji_to_hiragana("hon"), "ほん");
+        assert_eq!(romaji_to_hiragana("kanji"), "かんじ");
+        assert_eq!(romaji_to_hiragana("konnichiwa"), "こんにちわ");
+        assert_eq!(romaji_to_hiragana("konnnichiwa"), "こんにちわ");
+        assert_eq!(romaji_to_hiragana("kan'i"), "かんい");
+        assert_eq!(romaji_to_hiragana("kani"), "かに");
+        assert_eq!(romaji_to_hiragana("hannya"), "はんにゃ");
+        assert_eq!(romaji_to_hiragana("shinbun"), "しんぶん");
+        assert_eq!(romaji_to_hiragana("shimbun"), "しんぶん");
+        assert_eq!(romaji_to_hiragana("sanpo"), "さんぽ");
+    }
+
+    #[test]
+    fn converts_long_vowels() {
+        assert_eq!(romaji_to_hiragana("tōkyō"), "とうきょう");
+        assert_eq!(romaji_to_hiragana("tôkyô"), "とうきょう");
+        assert_eq!(romaji_to_hiragana("toukyou"), "とうきょう");
+        assert_eq!(romaji_to_hiragana("ōkii"), "おうきい");
+        assert_eq!(romaji_to_hiragana("okāsan"), "おかあさん");
+        assert_eq!(romaji_to_hiragana("ra-men"), "らーめん");
+    }
+
+    #[test]
+    fn keeps_unknown_characters() {
+        assert_eq!(romaji_to_hiragana("ねこ"), "ねこ");
+        assert_eq!(romaji_to_hiragana("neko!"), "ねこ!");
+        assert_eq!(romaji_to_hiragana("q"), "q");
+    }
+
+    #[test]
+    fn converts_mixed_text() {
+        assert_eq!(to_hiragana("ラーmen"), "らーめん");
+        assert_eq!(to_hiragana("カタkana"), "かたかな");
+    }
+
+    #[test]
+    fn detects_hiragana() {
+        assert!(is_hiragana("ひらがな"));
+        assert!(is_hiragana("らーめん"));
+        assert!(!is_hiragana("カタカナ"));
+        assert!(!is_hiragana("漢字"));
+        assert!(!is_hiragana("hiragana"));
+        assert!(!is_hiragana(""));
+    }
+
+    #[test]
+    fn classifies_scripts() {
+        assert_eq!(script("ひらがな"), Some(Script::Hiragana));
+        assert_eq!(script("ラーメン"), Some(Script::Katakana));
+        assert_eq!(script("コーヒー・カップ"), Some(Script::Katakana));
+        assert_eq!(script("人々"), Some(Script::Kanji));
+        assert_eq!(script("食べる"), None);
+        assert_eq!(script("ー"), None);
+        assert_eq!(script("kana"), None);
+        assert_eq!(script(""), None);
+
+        assert!(is_katakana("テレビ"));
+        assert!(!is_katakana("てれび"));
+        assert!(is_kana("てれび"));
+        assert!(!is_kana("テレびデオ"));
+    }
+
+    #[test]
+    fn compares_kanji() {
+        assert!(same_kanji("受け付け", "受付"));
+        assert!(same_kanji("受付け", "受け付け"));
+        assert!(same_kanji("行き届く", "行届く"));
+        assert!(!same_kanji("受付", "受取"));
+        assert!(!same_kanji("付け受け", "受け付け"));
+        assert!(!same_kanji("うけつけ", "うけつけ"));
+        assert!(has_kanji("受け付け"));
+        assert!(!has_kanji("うけつけ"));
+    }
+
+    #[test]
+    fn normalizes_answers() {
+        assert_eq!(normalize(" Ne Ko "), "ねこ");
+        assert_eq!(normalize("ネコ"), "ねこ");
+        assert_eq!(normalize("ね こ"), "ねこ");
+        assert_eq!(normalize("toukyou"), normalize("とうきょう"));
+    }
+
+    #[test]
+    fn accepts_exact_answers_only() {
+        assert!(Tolerance::Exact.accepts("がっこう", "がっこう"));
+        assert!(!Tolerance::Exact.accepts("がつこう", "がっこう"));
+        assert!(!Tolerance::Exact.accepts("がこう", "がっこう"));
+        assert!(!Tolerance::Exact.accepts("ときょ", "とうきょう"));
+    }
+
+    #[test]
+    fn accepts_short_long_vowels() {
+        let long_vowels = Tolerance::LongVowels;
+
+        assert!(long_vowels.accepts("ときょ", "とうきょう"));
+        assert!(long_vowels.accepts(&normalize("tokyo"), &normalize("とうきょう")));
+        assert!(long_vowels.accepts("らめん", "らーめん"));
+        assert!(long_vowels.accepts("せんせ", "せんせい"));
+        assert!(long_vowels.accepts("おばさん", "おばあさん"));
+        assert!(long_vowels.accepts("おきい", "おおきい"));
+        // A sokuon isn't a long vowel, and doesn't lengthen the vowel after it.
+        assert!(!long_vowels.accepts("がつこう", "がっこう"));
+        assert!(!long_vowels.accepts("がこう", "がっこう"));
+        assert!(long_vowels.accepts("きって", "きって"));
+        assert!(!long_vowels.accepts("きて", "きって"));
+        // ん ends no vowel, so the vowel after it is kept.
+        assert!(!long_vowels.accepts("きん", "きんい"));
+        assert!(!long_vowels.accepts("ねこ", "ねく"));
+    }
+
+    #[test]
+    fn accepts_one_typo() {
+        let one_typo = Tolerance::OneTypo;
+
+        assert!(one_typo.accepts("がつこう", "がっこう"));
+        assert!(one_typo.accepts("がこう", "がっこう"));
+        assert!(one_typo.accepts("がっっこう", "がっこう"));
+        assert!(one_typo.accepts("たべるる", "たべる"));
+        assert!(one_typo.accepts("たるべ", "たべる"));
+        assert!(one_typo.accepts("ときょ", "とうきょう"));
+        assert!(!one_typo.accepts("がつこお", "がっこう"));
+        assert!(!one_typo.accepts("るべた", "たべる"));
+        // Answers this short are another word with any typo.
+        assert!(!one_typo.accepts("ねく", "ねこ"));
+        assert!(!one_typo.accepts("か", "き"));
+    }
+
+    #[test]
+    fn measures_edit_distance() {
+        let distance = |a: &str, b: &str| {
+            edit_distance(
+                &a.chars().collect::<Vec<_>>(),
+                &b.chars().collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("", "ねこ"), 2);
+        assert_eq!(distance("ねこ", "ねこ"), 0);
+        assert_eq!(distance("っ", "つ"), 1);
+        assert_eq!(distance("たべる", "たるべ"), 1);
+        assert_eq!(distance("かきくけこ", "かくきけこ"), 1);
+        assert_eq!(distance("がっこう", "がこお"), 2);
+    }
+
+    #[test]
+    fn splits_morae() {
+        assert_eq!(morae("きょうと"), ["きょ", "う", "と"]);
+        assert_eq!(morae("がっこう"), ["が", "っ", "こ", "う"]);
+        assert_eq!(morae("ヴァイオリン"), ["ヴァ", "イ", "オ", "リ", "ン"]);
+        assert!(morae("").is_empty());
+    }
+
+    #[test]
+    fn marks_downstep() {
+        assert_eq!(mark_downstep("はし", 1), "は↓し");
+        assert_eq!(mark_downstep("アクセント", 1), "ア↓クセント");
+        assert_eq!(mark_downstep("きょうと", 1), "きょ↓うと");
+        assert_eq!(mark_downstep("はし", 2), "はし↓");
+        assert_eq!(mark_downstep("はし", 0), "はし");
+        assert_eq!(mark_downstep("はし", 3), "はし");
+    }
+}
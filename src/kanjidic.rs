@@ -0,0 +1,236 @@
+use std::io::BufRead;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dictionary::NLevel;
+
+/// A kanji of the KANJIDIC2 dictionary, e.g. 亜.
+///
+/// # See also
+/// <https://www.edrdg.org/wiki/index.php/KANJIDIC_Project>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KanjiCharacter {
+    /// The kanji itself.
+    pub literal: String,
+    /// On'yomi, in katakana.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on: Vec<String>,
+    /// Kun'yomi, in hiragana. Okurigana follow a `.` and a `-` marks where a
+    /// reading used as a prefix or suffix attaches, e.g. `つ.ぐ` or `-がた`.
+    /// See [`kun_label`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kun: Vec<String>,
+    /// Meanings, in English.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub meanings: Vec<String>,
+    /// School grade the kanji is taught in: 1 to 6 for elementary school, 8 for
+    /// the rest of the jōyō kanji, and 9 or 10 for kanji used in names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grade: Option<u8>,
+    /// The level the kanji is tested at, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<NLevel>,
+    /// Number of strokes the kanji is written with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strokes: Option<u8>,
+    /// Rank among the 2,500 kanji used most in newspapers, 1 being the most used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<u16>,
+}
+
+/// Gets the level of a kanji from its level of the JLPT before 2010, which
+/// KANJIDIC2 lists, e.g. 4 for N5. The old level 2 spans N3 and N2, and is
+/// taken as N2.
+pub fn level_of_old_jlpt(level: u8) -> Option<NLevel> {
+    match level {
+        1 => Some(NLevel::N1),
+        2 => Some(NLevel::N2),
+        3 => Some(NLevel::N4),
+        4 => Some(NLevel::N5),
+        _ => None,
+    }
+}
+
+/// Writes a kun'yomi with its okurigana in parentheses and its prefix or
+/// suffix mark as 〜, e.g. `つ(ぐ)` for `つ.ぐ` or `〜がた` for `-がた`.
+pub fn kun_label(reading: &str) -> String {
+    let reading = reading.replace('-', "〜");
+
+    match reading.split_once('.') {
+        Some((stem, okurigana)) => match okurigana.strip_suffix('〜') {
+            Some(okurigana) => format!("{stem}({okurigana})〜"),
+            None => format!("{stem}({okurigana})"),
+        },
+        None => reading,
+    }
+}
+
+/// Parses the kanji of `reader`, the KANJIDIC2 XML file. Only the elements
+/// of [`KanjiCharacter`] are read, each of which KANJIDIC2 puts on its own line.
+///
+/// # Errors
+/// Fails if `reader` can't be read, or ends inside a character.
+pub fn parse_kanjidic(reader: impl BufRead) -> crate::Result<Vec<KanjiCharacter>> {
+    let mut characters = Vec::new();
+    let mut current: Option<KanjiCharacter> = None;
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.starts_with("<character>") {
+            current = Some(KanjiCharacter::default());
+            continue;
+        }
+        if line.starts_with("</character>") {
+            characters.extend(current.take().filter(|c| !c.literal.is_empty()));
+            continue;
+        }
+        let (Some(character), Some((tag, attributes, text))) = (&mut current, element(line)) else {
+            continue;
+        };
+
+        match tag {
+            "literal" => character.literal = text,
+            "grade" => character.grade = text.parse().ok(),
+            // The first count is the accepted one, the rest common miscounts.
+            "stroke_count" if character.strokes.is_none() => {
+                character.strokes = text.parse().ok();
+            }
+            "freq" => character.frequency = text.parse().ok(),
+            "jlpt" => character.level = text.parse().ok().and_then(level_of_old_jlpt),
+            "reading" if attributes.contains(r#"r_type="ja_on""#) => character.on.push(text),
+            "reading" if attributes.contains(r#"r_type="ja_kun""#) => character.kun.push(text),
+            // Meanings in other languages are labeled with theirs.
+            "meaning" if !attributes.contains("m_lang") => character.meanings.push(text),
+            _ => {}
+        }
+    }
+    if current.is_some() {
+        return Err(crate::Error::DictionaryFormat(
+            "KANJIDIC2 ended inside a character".to_string(),
+        ));
+    }
+
+    Ok(characters)
+}
+
+/// Reads kanji from `reader`, a JSON object per line, e.g. the `kanji.jsonl`
+/// generated by dict_combine.
+///
+/// # Errors
+/// Fails if `reader` can't be read or a kanji can't be parsed.
+pub fn read_kanji(reader: impl BufRead) -> crate::Result<Vec<KanjiCharacter>> {
+    serde_json::Deserializer::from_reader(reader)
+        .into_iter()
+        .collect::<serde_json::Result<_>>()
+        .map_err(crate::Error::from)
+}
+
+/// Splits an element written on one line, e.g. `<reading r_type="ja_on">ア</reading>`,
+/// into its tag, attributes, and unescaped text.
+fn element(line: &str) -> Option<(&str, &str, String)> {
+    let rest = line.strip_prefix('<')?;
+    let (head, rest) = rest.split_once('>')?;
+    let (tag, attributes) = head.split_once(' ').unwrap_or((head, ""));
+    let text = rest
+        .strip_suffix('>')?
+        .strip_suffix(tag)?
+        .strip_suffix("</")?;
+
+    Some((tag, attributes, unescape(text)))
+}
+
+/// Replaces the predefined XML entities of `text`, e.g. `&amp;`.
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KANJIDIC: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kanjidic2>
+<character>
+<literal>亜</literal>
+<misc>
+<grade>8</grade>
+<stroke_count>7</stroke_count>
+<stroke_count>8</stroke_count>
+<freq>1509</freq>
+<jlpt>1</jlpt>
+</misc>
+<reading_meaning>
+<rmgroup>
+<reading r_type="pinyin">ya4</reading>
+<reading r_type="ja_on">ア</reading>
+<reading r_type="ja_kun">つ.ぐ</reading>
+<meaning>Asia</meaning>
+<meaning>rank next</meaning>
+<meaning m_lang="fr">Asie</meaning>
+</rmgroup>
+</reading_meaning>
+</character>
+<character>
+<literal>方</literal>
+<misc>
+<grade>2</grade>
+<stroke_count>4</stroke_count>
+<jlpt>4</jlpt>
+</misc>
+<reading_meaning>
+<rmgroup>
+<reading r_type="ja_on">ホウ</reading>
+<reading r_type="ja_kun">-かた</reading>
+<meaning>direction &amp; person</meaning>
+</rmgroup>
+</reading_meaning>
+</character>
+</kanjidic2>
+"#;
+
+    #[test]
+    fn parses_characters() {
+        let characters = parse_kanjidic(KANJIDIC.as_bytes()).unwrap();
+
+        assert_eq!(
+            characters[0],
+            KanjiCharacter {
+                literal: "亜".to_string(),
+                on: vec!["ア".to_string()],
+                kun: vec!["つ.ぐ".to_string()],
+                meanings: vec!["Asia".to_string(), "rank next".to_string()],
+                grade: Some(8),
+                level: Some(NLevel::N1),
+                strokes: Some(7),
+                frequency: Some(1509),
+            }
+        );
+        assert_eq!(characters[1].level, Some(NLevel::N5));
+        assert_eq!(characters[1].meanings, ["direction & person"]);
+        assert_eq!(characters.len(), 2);
+    }
+
+    #[test]
+    fn rejects_cut_off_files() {
+        let cut_off = &KANJIDIC[..KANJIDIC.rfind("</character>").unwrap()];
+
+        assert!(matches!(
+            parse_kanjidic(cut_off.as_bytes()),
+            Err(crate::Error::DictionaryFormat(_))
+        ));
+    }
+
+    #[test]
+    fn labels_kun_readings() {
+        assert_eq!(kun_label("つ.ぐ"), "つ(ぐ)");
+        assert_eq!(kun_label("-かた"), "〜かた");
+        assert_eq!(kun_label("あ.げる-"), "あ(げる)〜");
+        assert_eq!(kun_label("やま"), "やま");
+    }
+}
@@ -1,16 +1,40 @@
-use std::{fs::File, io::BufReader, path::Path, process};
+#[cfg(feature = "fs")]
+use std::{fs::File, io::BufReader, path::Path};
 
+pub mod conjugation;
+pub mod counter;
 pub mod dictionary;
+pub mod distractor;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod grammar;
+pub mod heap_size;
+pub mod kana;
+pub mod kanjidic;
+pub mod number;
+pub mod particle;
+pub mod question;
+pub mod quiz;
+pub mod rating;
+pub mod scoring;
+pub mod srs;
+#[cfg(feature = "bin")]
+pub mod tts;
+pub mod wordlist;
+
+pub use error::{Error, Result};
 
 /// Opens a reader for a file.
 ///
-/// # Panics
-/// Will panic if there is an error opening the file.
-pub fn open_reader(path: &Path) -> BufReader<File> {
-    let file = File::open(path).unwrap_or_else(|e| {
-        eprintln!("Failed to open file at {}:\n\t{}", path.display(), e);
-        process::exit(-1);
-    });
+/// # Errors
+/// Fails with [`Error::File`] if the file can't be opened.
+#[cfg(feature = "fs")]
+pub fn open_reader(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path).map_err(|source| Error::File {
+        path: path.to_path_buf(),
+        source,
+    })?;
 
-    BufReader::new(file)
+    Ok(BufReader::new(file))
 }
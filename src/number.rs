@@ -0,0 +1,140 @@
+/// Largest number that can be read by [`reading`] and [`kanji`].
+pub const MAX: u32 = 99_999_999;
+
+const DIGIT_READINGS: [&str; 10] = [
+    "ゼロ",
+    "いち",
+    "に",
+    "さん",
+    "よん",
+    "ご",
+    "ろく",
+    "なな",
+    "はち",
+    "きゅう",
+];
+
+const DIGIT_KANJI: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+/// Reads `n` out in hiragana, applying the sound changes of
+/// 百 (ひゃく) and 千 (せん), e.g. 3800 is さんぜんはっぴゃく.
+///
+/// # Panics
+/// Will panic if `n` is greater than [`MAX`].
+pub fn reading(n: u32) -> String {
+    assert!(n <= MAX, "{n} is too large to read");
+
+    if n == 0 {
+        return DIGIT_READINGS[0].to_string();
+    }
+
+    let (man, rest) = (n / 10_000, n % 10_000);
+
+    let mut res = String::new();
+    if man > 0 {
+        // 1000万 is read いっせんまん rather than せんまん.
+        if man / 1000 == 1 {
+            res.push_str("いっ");
+        }
+        res.push_str(&group_reading(man));
+        res.push_str("まん");
+    }
+    res.push_str(&group_reading(rest));
+
+    res
+}
+
+/// Reads a group of up to four digits. Zero is read as nothing.
+fn group_reading(n: u32) -> String {
+    let (thousands, hundreds, tens, ones) = (
+        (n / 1000) as usize,
+        (n / 100 % 10) as usize,
+        (n / 10 % 10) as usize,
+        (n % 10) as usize,
+    );
+
+    let mut res = String::new();
+
+    match thousands {
+        0 => {}
+        1 => res.push_str("せん"),
+        3 => res.push_str("さんぜん"),
+        8 => res.push_str("はっせん"),
+        d => {
+            res.push_str(DIGIT_READINGS[d]);
+            res.push_str("せん");
+        }
+    }
+
+    match hundreds {
+        0 => {}
+        1 => res.push_str("ひゃく"),
+        3 => res.push_str("さんびゃく"),
+        6 => res.push_str("ろっぴゃく"),
+        8 => res.push_str("はっぴゃく"),
+        d => {
+            res.push_str(DIGIT_READINGS[d]);
+            res.push_str("ひゃく");
+        }
+    }
+
+    match tens {
+        0 => {}
+        1 => res.push_str("じゅう"),
+        d => {
+            res.push_str(DIGIT_READINGS[d]);
+            res.push_str("じゅう");
+        }
+    }
+
+    if ones != 0 {
+        res.push_str(DIGIT_READINGS[ones]);
+    }
+
+    res
+}
+
+/// Writes `n` out in kanji numerals, e.g. 3800 is 三千八百.
+///
+/// # Panics
+/// Will panic if `n` is greater than [`MAX`].
+pub fn kanji(n: u32) -> String {
+    assert!(n <= MAX, "{n} is too large to write");
+
+    if n == 0 {
+        return DIGIT_KANJI[0].to_string();
+    }
+
+    let (man, rest) = (n / 10_000, n % 10_000);
+
+    let mut res = String::new();
+    if man > 0 {
+        res.push_str(&group_kanji(man));
+        res.push('万');
+    }
+    res.push_str(&group_kanji(rest));
+
+    res
+}
+
+/// Writes a group of up to four digits. Zero is written as nothing.
+fn group_kanji(n: u32) -> String {
+    let mut res = String::new();
+
+    for (unit, place) in [("千", 1000), ("百", 100), ("十", 10)] {
+        match n / place % 10 {
+            0 => {}
+            1 => res.push_str(unit),
+            d => {
+                res.push_str(DIGIT_KANJI[d as usize]);
+                res.push_str(unit);
+            }
+        }
+    }
+
+    if !n.is_multiple_of(10) {
+        res.push_str(DIGIT_KANJI[(n % 10) as usize]);
+    }
+
+    res
+}
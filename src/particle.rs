@@ -0,0 +1,102 @@
+//! Finds the particles of sentences that can be blanked out for players to
+//! fill in.
+//!
+//! Without a tokenizer, particles are told apart from the same kana inside
+//! words by a small set of rules: a particle follows a word written in kanji or
+//! katakana, and isn't followed by kana that would make it part of a verb or
+//! ending, e.g. the が of 上がる or the で of です. Particles after kana are
+//! skipped, since they can't be told apart from the end of a word, so a
+//! sentence may have fewer blanks than particles but its blanks are particles.
+
+use crate::kana::Script;
+
+/// Particles that are blanked, which are also the options of every question.
+pub const PARTICLES: [char; 5] = ['は', 'が', 'を', 'に', 'で'];
+
+/// The placeholder a blanked particle is replaced with.
+pub const BLANK: &str = "（　）";
+
+/// Kana that continue a word when they follow one of the [`PARTICLES`], e.g.
+/// the る of 上がる, the す of です, or the く of 見にくい.
+const CONTINUATIONS: [char; 11] = [
+    'る', 'り', 'っ', 'ら', 'れ', 'ろ', 'す', 'し', 'く', 'き', 'ず',
+];
+
+/// A sentence with one of its particles blanked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blank {
+    /// The sentence with the particle replaced by [`BLANK`].
+    pub prompt: String,
+    /// The blanked particle, one of [`PARTICLES`].
+    pub particle: char,
+}
+
+/// Finds the particles of `sentence` that can be blanked out, in order.
+pub fn blanks(sentence: &str) -> Vec<Blank> {
+    let chars: Vec<_> = sentence.chars().collect();
+
+    (1..chars.len().saturating_sub(1))
+        .filter(|&i| is_particle(chars[i - 1], chars[i], chars[i + 1]))
+        .map(|i| Blank {
+            prompt: format!(
+                "{}{BLANK}{}",
+                String::from_iter(&chars[..i]),
+                String::from_iter(&chars[i + 1..])
+            ),
+            particle: chars[i],
+        })
+        .collect()
+}
+
+/// Determines whether `c`, between `prev` and `next`, is a particle by the
+/// rules of this module.
+fn is_particle(prev: char, c: char, next: char) -> bool {
+    if !PARTICLES.contains(&c) || CONTINUATIONS.contains(&next) {
+        return false;
+    }
+
+    // を is only ever a particle. The long vowel mark mostly ends katakana words.
+    c == 'を' || prev == 'ー' || matches!(Script::of(prev), Some(Script::Kanji | Script::Katakana))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn particles(sentence: &str) -> Vec<char> {
+        blanks(sentence).into_iter().map(|b| b.particle).collect()
+    }
+
+    #[test]
+    fn blanks_particles_after_kanji_and_katakana() {
+        assert_eq!(
+            particles("私は学校で日本語を勉強します。"),
+            ['は', 'で', 'を']
+        );
+        assert_eq!(particles("新しいパソコンがほしいです。"), ['が']);
+        assert_eq!(
+            blanks("駅に着いた。")[0],
+            Blank {
+                prompt: "駅（　）着いた。".to_string(),
+                particle: 'に',
+            }
+        );
+    }
+
+    #[test]
+    fn skips_kana_inside_words() {
+        // The が of 上がる, the で of です, and the に of 見にくい.
+        assert_eq!(particles("物価が上がる一方だ。"), ['が']);
+        assert!(particles("学生です。").is_empty());
+        assert_eq!(particles("字が見にくい。"), ['が']);
+        // Particles after kana can't be told apart from the ends of words.
+        assert!(particles("これはペンだ。").is_empty());
+    }
+
+    #[test]
+    fn skips_the_ends_of_sentences() {
+        assert!(particles("は").is_empty());
+        assert!(particles("本で").is_empty());
+        assert!(particles("").is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+use rand::{Rng, seq::SliceRandom};
+use strum_macros::EnumIter;
+
+use crate::{
+    dictionary::{EntryRef, KanjiRef, Pos, ReadingRef, SenseRef},
+    heap_size::no_heap,
+    kana,
+};
+
+/// Options of a multiple choice question, including the answer.
+pub const OPTIONS: usize = 5;
+
+/// Fewest questions [`is_position_biased`] judges, since chance alone often
+/// places the answers of fewer unevenly.
+pub const MIN_AUDITED: u64 = 100;
+
+/// Critical value of the chi-squared distribution with `OPTIONS - 1` degrees
+/// of freedom at p = 0.001.
+const CHI_SQUARED_CRITICAL: f64 = 18.467;
+
+/// The longest gloss that can be an option. Options are shown as buttons,
+/// whose labels Discord limits to 80 characters.
+pub const MAX_GLOSS_LEN: usize = 80;
+
+/// Pairs of an entry's spellings and meanings that questions ask about. Each is
+/// asked about in both directions, e.g. by reading and by meaning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+pub enum Pairing {
+    /// See [`reading_sense_pair`].
+    ReadingSense,
+    /// See [`kanji_reading_pair`].
+    KanjiReading,
+    /// See [`kanji_sense_pair`].
+    KanjiSense,
+    /// See [`katakana_sense_pair`].
+    KatakanaSense,
+}
+
+impl Pairing {
+    /// Determines whether this pair can be extracted from `entry` with the `pos` tag.
+    pub fn has_pair(self, entry: EntryRef<'_>, pos: Pos) -> bool {
+        match self {
+            Pairing::ReadingSense => reading_sense_pair(entry, pos).is_some(),
+            Pairing::KanjiReading => kanji_reading_pair(entry, pos).is_some(),
+            Pairing::KanjiSense => kanji_sense_pair(entry, pos).is_some(),
+            Pairing::KatakanaSense => katakana_sense_pair(entry, pos).is_some(),
+        }
+    }
+}
+
+no_heap!(Pairing);
+
+/// Determines whether `sense` has the `pos` tag and a first gloss short enough
+/// to be an option.
+fn is_askable(sense: SenseRef<'_>, pos: Pos) -> bool {
+    sense.pos().contains(&pos)
+        && sense
+            .glosses()
+            .next()
+            .is_some_and(|g| g.chars().count() <= MAX_GLOSS_LEN)
+}
+
+/// Conventiently extracts a [`ReadingRef`] and correlated [`SenseRef`] from an [`EntryRef`] where
+/// the sense has the `pos` tag and is guaranteed to have at least one gloss.
+///
+/// Returns [`None`] if no possible extraction.
+pub fn reading_sense_pair<'a>(
+    entry: EntryRef<'a>,
+    pos: Pos,
+) -> Option<(ReadingRef<'a>, SenseRef<'a>)> {
+    let sense = entry.senses().find(|&s| is_askable(s, pos))?;
+
+    let reading = entry
+        .readings()
+        .find(|r| sense.applies_to_reading(r.text()))?;
+
+    Some((reading, sense))
+}
+
+/// Conveniently extracts a [`KanjiRef`] and correlated [`ReadingRef`] from an [`EntryRef`] where
+/// the reading has the `pos` tag.
+///
+/// Returns [`None`] if no possible extraction.
+pub fn kanji_reading_pair<'a>(
+    entry: EntryRef<'a>,
+    pos: Pos,
+) -> Option<(KanjiRef<'a>, ReadingRef<'a>)> {
+    let sense = entry.senses().find(|s| s.pos().contains(&pos))?;
+
+    let kanji = entry.kanjis().next()?;
+
+    let reading = entry
+        .readings()
+        .find(|r| r.applies_to(kanji.text()) && sense.applies_to_reading(r.text()))?;
+
+    Some((kanji, reading))
+}
+
+/// Conventiently extracts a [`KanjiRef`] and correlated [`SenseRef`] from an [`EntryRef`] where
+/// the sense has the `pos` tag and is guaranteed to have at least one gloss.
+///
+/// Returns [`None`] if no possible extraction.
+pub fn kanji_sense_pair<'a>(entry: EntryRef<'a>, pos: Pos) -> Option<(KanjiRef<'a>, SenseRef<'a>)> {
+    let sense = entry.senses().find(|&s| is_askable(s, pos))?;
+
+    let kanji = entry.kanjis().next()?;
+
+    Some((kanji, sense))
+}
+
+/// Conveniently extracts a [`ReadingRef`] written in katakana, e.g. of a loanword,
+/// and correlated [`SenseRef`] from an [`EntryRef`] where the sense has the `pos`
+/// tag and is guaranteed to have at least one gloss.
+///
+/// Returns [`None`] if no possible extraction.
+pub fn katakana_sense_pair<'a>(
+    entry: EntryRef<'a>,
+    pos: Pos,
+) -> Option<(ReadingRef<'a>, SenseRef<'a>)> {
+    entry
+        .senses()
+        .filter(|&s| is_askable(s, pos))
+        .find_map(|sense| {
+            let reading = entry
+                .readings()
+                .find(|r| kana::is_katakana(r.text()) && sense.applies_to_reading(r.text()))?;
+
+            Some((reading, sense))
+        })
+}
+
+/// Shuffles `options`, the first of which is the answer, and gets the index the
+/// answer was moved to. Every index is as likely as the others.
+///
+/// The answer is tracked through the shuffle rather than looked up by its text
+/// after it, which would favor the first positions whenever a distractor has
+/// the same text, e.g. when there are too few distractors to fill every option.
+///
+/// # Panics
+/// Will panic if `options` is empty.
+pub fn shuffle_options<T, R: Rng + ?Sized>(options: &mut [T], rng: &mut R) -> usize {
+    let (_, distractors) = options
+        .split_first_mut()
+        .expect("Options should include the answer");
+    distractors.shuffle(rng);
+
+    let answer = rng.random_range(0..options.len());
+    options.swap(0, answer);
+
+    answer
+}
+
+/// Determines whether the answers of questions, `counts[i]` of which were at
+/// the `i`th option, were placed too unevenly for chance to explain, by a
+/// chi-squared test at p = 0.001. Fewer than [`MIN_AUDITED`] questions are
+/// never judged biased.
+pub fn is_position_biased(counts: &[u64; OPTIONS]) -> bool {
+    let total: u64 = counts.iter().sum();
+    if total < MIN_AUDITED {
+        return false;
+    }
+
+    let expected = total as f64 / OPTIONS as f64;
+    let chi_squared: f64 = counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum();
+
+    chi_squared > CHI_SQUARED_CRITICAL
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Tallies where the answer lands over `rounds` shuffles of `options`.
+    fn positions(options: [&str; OPTIONS], rounds: usize) -> [u64; OPTIONS] {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut counts = [0; OPTIONS];
+        for _ in 0..rounds {
+            let mut options = options;
+            counts[shuffle_options(&mut options, &mut rng)] += 1;
+        }
+
+        counts
+    }
+
+    #[test]
+    fn places_answers_evenly() {
+        let counts = positions(["a", "b", "c", "d", "e"], 10_000);
+
+        assert!(!is_position_biased(&counts), "{counts:?}");
+    }
+
+    #[test]
+    fn places_answers_evenly_among_duplicates() {
+        // Too few distractors leave the answer in the remaining options.
+        let counts = positions(["a", "b", "a", "a", "a"], 10_000);
+
+        assert!(!is_position_biased(&counts), "{counts:?}");
+    }
+
+    #[test]
+    fn keeps_the_answer_at_its_index() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let mut options = ["answer", "b", "c", "d", "e"];
+            let answer = shuffle_options(&mut options, &mut rng);
+
+            assert_eq!(options[answer], "answer");
+        }
+    }
+
+    #[test]
+    fn detects_biased_positions() {
+        // Looking the answer up by its text finds the first of its duplicates.
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut counts = [0; OPTIONS];
+        for _ in 0..10_000 {
+            let mut options = ["a", "b", "a", "a", "a"];
+            options.shuffle(&mut rng);
+            counts[options.iter().position(|&o| o == "a").unwrap()] += 1;
+        }
+
+        assert!(is_position_biased(&counts), "{counts:?}");
+        assert!(!is_position_biased(&[MIN_AUDITED - 1, 0, 0, 0, 0]));
+    }
+}
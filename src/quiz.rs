@@ -0,0 +1,548 @@
+//! Generates multiple choice questions about the words of a [`Dictionary`].
+//!
+//! ```no_run
+//! use jplearnbot::{
+//!     dictionary::{Dictionary, NLevel, Pos},
+//!     distractor::Difficulty,
+//!     quiz::{Mode, QuestionBuilder},
+//! };
+//! use rand::{SeedableRng, rngs::StdRng};
+//!
+//! let bytes = std::fs::read("dictionary.jsonl").unwrap();
+//! let dictionary = Dictionary::from_bytes(&bytes).unwrap();
+//!
+//! let questions = QuestionBuilder::with_rng(&dictionary, StdRng::seed_from_u64(7))
+//!     .mode(Mode::KanToHir)
+//!     .levels([NLevel::N5, NLevel::N4])
+//!     .pos([Pos::N, Pos::V1])
+//!     .distractors(Difficulty::Hard)
+//!     .build();
+//!
+//! for question in questions.take(10) {
+//!     println!("{}: {:?}", question.prompt, question.options);
+//! }
+//! ```
+
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::Deref,
+};
+
+use rand::{Rng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{
+    dictionary::{Dictionary, EntryRef, NLevel, Pos, SenseRef},
+    distractor::{self, Difficulty, Resemblance},
+    question::{
+        OPTIONS, Pairing, kanji_reading_pair, kanji_sense_pair, katakana_sense_pair,
+        reading_sense_pair, shuffle_options,
+    },
+};
+
+/// Ways of asking about a word, named as what is shown ▶ what is picked.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, strum_macros::Display,
+)]
+pub enum Mode {
+    #[strum(to_string = "English ▶ ひらがな")]
+    EngToHir,
+    #[strum(to_string = "ひらがな ▶ English")]
+    HirToEng,
+    #[strum(to_string = "ひらがな ▶ 漢字")]
+    HirToKan,
+    #[strum(to_string = "漢字 ▶ ひらがな")]
+    KanToHir,
+    #[strum(to_string = "漢字 ▶ English")]
+    KanToEng,
+    #[strum(to_string = "English ▶ 漢字")]
+    EngToKan,
+    /// Picks one of the other modes for every question.
+    #[strum(to_string = "Mixed ▶ ミックス")]
+    Mixed,
+    /// Asks about a reading read aloud. See [`Mode::is_spoken`].
+    #[strum(to_string = "🔊 ▶ ひらがな")]
+    ListenToHir,
+    /// Asks about the meaning of a reading read aloud. See [`Mode::is_spoken`].
+    #[strum(to_string = "🔊 ▶ English")]
+    ListenToEng,
+    /// Asks only about words read in katakana, mostly loanwords.
+    #[strum(to_string = "English ▶ カタカナ")]
+    EngToKat,
+    /// Asks only about words read in katakana, mostly loanwords.
+    #[strum(to_string = "カタカナ ▶ English")]
+    KatToEng,
+}
+
+impl Mode {
+    /// Modes [`Mode::Mixed`] alternates between.
+    pub const MIXABLE: [Mode; 6] = [
+        Mode::EngToHir,
+        Mode::HirToEng,
+        Mode::HirToKan,
+        Mode::KanToHir,
+        Mode::KanToEng,
+        Mode::EngToKan,
+    ];
+
+    /// Determines whether a question in this mode can be made from `entry`
+    /// with at least one of `pos`, e.g. kana-only words aren't compatible with kanji modes.
+    pub fn is_compatible(self, entry: EntryRef<'_>, pos: &[Pos]) -> bool {
+        match self {
+            Mode::EngToHir | Mode::HirToEng | Mode::ListenToHir | Mode::ListenToEng => {
+                pos.iter().any(|&p| reading_sense_pair(entry, p).is_some())
+            }
+            Mode::HirToKan | Mode::KanToHir => {
+                pos.iter().any(|&p| kanji_reading_pair(entry, p).is_some())
+            }
+            Mode::KanToEng | Mode::EngToKan => {
+                pos.iter().any(|&p| kanji_sense_pair(entry, p).is_some())
+            }
+            Mode::EngToKat | Mode::KatToEng => {
+                pos.iter().any(|&p| katakana_sense_pair(entry, p).is_some())
+            }
+            Mode::Mixed => Mode::MIXABLE.iter().any(|m| m.is_compatible(entry, pos)),
+        }
+    }
+
+    /// Determines whether the prompt of questions in this mode is a reading
+    /// meant to be synthesized into speech and heard rather than shown.
+    /// These modes aren't mixed, since they need a text-to-speech service.
+    pub fn is_spoken(self) -> bool {
+        matches!(self, Mode::ListenToHir | Mode::ListenToEng)
+    }
+}
+
+/// A multiple choice question about an entry of a [`Dictionary`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Question {
+    /// The word to translate.
+    pub prompt: String,
+    /// Possible translations of [`Self::prompt`], in the order they should be shown.
+    pub options: [String; OPTIONS],
+    /// The index of the correct translation of [`Self::prompt`].
+    pub answer: usize,
+    /// The [id](`crate::dictionary::DictEntry::id`) of the entry the question is about.
+    pub entry: u32,
+    /// The [number](`crate::dictionary::Sense::number`) of the sense of the
+    /// entry the question asks about, if it asks about a meaning.
+    pub sense: Option<u32>,
+    /// The mode the question is asked in, never [`Mode::Mixed`].
+    pub mode: Mode,
+}
+
+/// Configures and creates [`Question`]s about the entries of a [`Dictionary`].
+///
+/// `D` is any pointer to the dictionary, e.g. `&Dictionary` or `Arc<Dictionary>`
+/// for questions that outlive the borrow. By default, questions are asked in
+/// [`Mode::Mixed`] about words of every level and part of speech, with
+/// [`Difficulty::Normal`] distractors and randomness seeded by the thread's
+/// generator, or from the one given to [`QuestionBuilder::with_rng`].
+pub struct QuestionBuilder<D, R = StdRng> {
+    dictionary: D,
+    mode: Mode,
+    levels: Option<Vec<NLevel>>,
+    pos: Vec<Pos>,
+    entries: Option<HashSet<u32>>,
+    excluded: HashSet<u32>,
+    distractors: Difficulty,
+    rng: R,
+}
+
+impl<D: Deref<Target = Dictionary>> QuestionBuilder<D> {
+    /// Creates a builder of questions about the entries of `dictionary`.
+    #[cfg(feature = "os-rng")]
+    pub fn new(dictionary: D) -> Self {
+        QuestionBuilder::with_rng(dictionary, rand::SeedableRng::from_rng(&mut rand::rng()))
+    }
+}
+
+impl<D: Deref<Target = Dictionary>, R: Rng> QuestionBuilder<D, R> {
+    /// Creates a builder of questions about the entries of `dictionary` that
+    /// draws all randomness from `rng`. Unlike `new`, it doesn't need the OS
+    /// to seed a generator, e.g. in a browser.
+    pub fn with_rng(dictionary: D, rng: R) -> Self {
+        QuestionBuilder {
+            dictionary,
+            mode: Mode::Mixed,
+            levels: None,
+            pos: Pos::ALL.to_vec(),
+            entries: None,
+            excluded: HashSet::new(),
+            distractors: Difficulty::default(),
+            rng,
+        }
+    }
+
+    /// Sets the mode questions are asked in.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the difficulty of the words asked about, by asking only about words
+    /// tagged with at least one of `levels`.
+    pub fn levels(mut self, levels: impl IntoIterator<Item = NLevel>) -> Self {
+        self.levels = Some(levels.into_iter().collect());
+        self
+    }
+
+    /// Asks only about words with a sense tagged with at least one of `pos`.
+    /// Distractors share the part of speech of the sense asked about.
+    pub fn pos(mut self, pos: impl IntoIterator<Item = Pos>) -> Self {
+        self.pos = pos.into_iter().collect();
+        self
+    }
+
+    /// Asks only about the entries with the ids `ids`, e.g. a study list.
+    pub fn entries(mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        self.entries = Some(ids.into_iter().collect());
+        self
+    }
+
+    /// Never asks about the entries with the ids `ids`, e.g. ones asked about recently.
+    pub fn exclude(mut self, ids: impl IntoIterator<Item = u32>) -> Self {
+        self.excluded.extend(ids);
+        self
+    }
+
+    /// Sets how closely the wrong options resemble the answer.
+    pub fn distractors(mut self, difficulty: Difficulty) -> Self {
+        self.distractors = difficulty;
+        self
+    }
+
+    /// Draws all randomness from `rng`, e.g. a seeded one for reproducible questions.
+    pub fn rng<R2: Rng>(self, rng: R2) -> QuestionBuilder<D, R2> {
+        QuestionBuilder {
+            dictionary: self.dictionary,
+            mode: self.mode,
+            levels: self.levels,
+            pos: self.pos,
+            entries: self.entries,
+            excluded: self.excluded,
+            distractors: self.distractors,
+            rng,
+        }
+    }
+
+    /// Creates a question about the entry with the id `id`, regardless of the
+    /// levels and entries set.
+    ///
+    /// Returns [`None`] if there is no such entry or no question can be made
+    /// from it with the mode and parts of speech set.
+    pub fn question(&mut self, id: u32) -> Option<Question> {
+        let dictionary = &*self.dictionary;
+        let entry = dictionary.entry(id)?;
+
+        self.pos.shuffle(&mut self.rng);
+        self.pos.iter().find_map(|&pos| {
+            let draw = Draw {
+                dictionary,
+                pos,
+                difficulty: self.distractors,
+            };
+            draw.question(entry, self.mode, &mut self.rng)
+        })
+    }
+
+    /// Samples the entries that can be asked about in a random order, and creates
+    /// an iterator of a question about each of them.
+    pub fn build(mut self) -> Questions<D, R> {
+        let levels = self
+            .levels
+            .take()
+            .unwrap_or_else(|| self.dictionary.levels().to_vec());
+
+        // Exclude entries that can't be asked in this mode up front,
+        // so every entry left in the pool makes a question.
+        let pool: Vec<_> = self
+            .dictionary
+            .sample(&levels, &self.pos, &mut self.rng)
+            .into_iter()
+            .filter(|&entry| {
+                self.mode.is_compatible(entry, &self.pos)
+                    && self
+                        .entries
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(&entry.id()))
+                    && !self.excluded.contains(&entry.id())
+            })
+            .map(|entry| entry.id())
+            .collect();
+
+        Questions {
+            pool: pool.into(),
+            builder: self,
+        }
+    }
+}
+
+/// Iterator of the [`Question`]s of a [`QuestionBuilder`], one per entry of its pool.
+pub struct Questions<D, R> {
+    pool: VecDeque<u32>,
+    builder: QuestionBuilder<D, R>,
+}
+
+impl<D: Deref<Target = Dictionary>, R: Rng> Questions<D, R> {
+    /// Creates a question about an entry drawn from the rest of the pool with
+    /// a chance proportional to its `weight`, instead of the next one, e.g. to
+    /// prefer words fitting a player's [rating](crate::rating). Entries are
+    /// drawn in order if none has a positive weight.
+    pub fn next_weighted(&mut self, weight: impl Fn(EntryRef<'_>) -> f64) -> Option<Question> {
+        let dictionary = &*self.builder.dictionary;
+        let weights: Vec<_> = self
+            .pool
+            .iter()
+            .map(|&id| {
+                dictionary
+                    .entry(id)
+                    .map_or(0.0, |entry| weight(entry).max(0.0))
+            })
+            .collect();
+
+        let total: f64 = weights.iter().sum();
+        let index = if total > 0.0 && total.is_finite() {
+            let mut drawn = self.builder.rng.random_range(0.0..total);
+            weights
+                .iter()
+                .position(|&w| {
+                    drawn -= w;
+                    drawn < 0.0
+                })
+                .unwrap_or(weights.len() - 1)
+        } else {
+            0
+        };
+
+        let id = self.pool.remove(index)?;
+        let question = self
+            .builder
+            .question(id)
+            .expect("Entry should be compatible with the mode");
+
+        Some(question)
+    }
+}
+
+impl<D: Deref<Target = Dictionary>, R: Rng> Iterator for Questions<D, R> {
+    type Item = Question;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.pool.pop_front()?;
+        let question = self
+            .builder
+            .question(id)
+            .expect("Entry should be compatible with the mode");
+
+        Some(question)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.pool.len(), Some(self.pool.len()))
+    }
+}
+
+impl<D: Deref<Target = Dictionary>, R: Rng> ExactSizeIterator for Questions<D, R> {}
+
+/// What the options of a question are drawn from, besides its entry.
+struct Draw<'d> {
+    dictionary: &'d Dictionary,
+    /// Part of speech the question asks about, which distractors share.
+    pos: Pos,
+    /// How closely distractors resemble the answer.
+    difficulty: Difficulty,
+}
+
+impl<'d> Draw<'d> {
+    /// Creates a question about `entry` in `mode`, drawing distractors and the
+    /// option order from `rng`.
+    fn question<R: Rng + ?Sized>(
+        &self,
+        entry: EntryRef<'_>,
+        mode: Mode,
+        rng: &mut R,
+    ) -> Option<Question> {
+        let pos = self.pos;
+
+        let (prompt, (options, answer), sense) = match mode {
+            Mode::EngToHir => {
+                let (reading, sense) = reading_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::ReadingSense,
+                    reading.text(),
+                    Resemblance::Sound,
+                    |e| reading_sense_pair(e, pos).map(|(reading, _)| reading.text()),
+                    rng,
+                );
+                (first_gloss(sense), options, Some(sense))
+            }
+            Mode::HirToEng | Mode::ListenToEng => {
+                let (reading, sense) = reading_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::ReadingSense,
+                    first_gloss(sense),
+                    Resemblance::Meaning,
+                    |e| reading_sense_pair(e, pos).map(|(_, sense)| first_gloss(sense)),
+                    rng,
+                );
+                (reading.text(), options, Some(sense))
+            }
+            Mode::ListenToHir => {
+                let (reading, _) = reading_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::ReadingSense,
+                    reading.text(),
+                    Resemblance::Sound,
+                    |e| reading_sense_pair(e, pos).map(|(reading, _)| reading.text()),
+                    rng,
+                );
+                (reading.text(), options, None)
+            }
+            Mode::HirToKan => {
+                let (kanji, reading) = kanji_reading_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::KanjiReading,
+                    kanji.text(),
+                    Resemblance::Look,
+                    |e| kanji_reading_pair(e, pos).map(|(kanji, _)| kanji.text()),
+                    rng,
+                );
+                (reading.text(), options, None)
+            }
+            Mode::KanToHir => {
+                let (kanji, reading) = kanji_reading_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::KanjiReading,
+                    reading.text(),
+                    Resemblance::Sound,
+                    |e| kanji_reading_pair(e, pos).map(|(_, reading)| reading.text()),
+                    rng,
+                );
+                (kanji.text(), options, None)
+            }
+            Mode::KanToEng => {
+                let (kanji, sense) = kanji_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::KanjiSense,
+                    first_gloss(sense),
+                    Resemblance::Meaning,
+                    |e| kanji_sense_pair(e, pos).map(|(_, sense)| first_gloss(sense)),
+                    rng,
+                );
+                (kanji.text(), options, Some(sense))
+            }
+            Mode::EngToKan => {
+                let (kanji, sense) = kanji_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::KanjiSense,
+                    kanji.text(),
+                    Resemblance::Look,
+                    |e| kanji_sense_pair(e, pos).map(|(kanji, _)| kanji.text()),
+                    rng,
+                );
+                (first_gloss(sense), options, Some(sense))
+            }
+            Mode::EngToKat => {
+                let (reading, sense) = katakana_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::KatakanaSense,
+                    reading.text(),
+                    Resemblance::Sound,
+                    |e| katakana_sense_pair(e, pos).map(|(reading, _)| reading.text()),
+                    rng,
+                );
+                (first_gloss(sense), options, Some(sense))
+            }
+            Mode::KatToEng => {
+                let (reading, sense) = katakana_sense_pair(entry, pos)?;
+                let options = self.options(
+                    entry,
+                    Pairing::KatakanaSense,
+                    first_gloss(sense),
+                    Resemblance::Meaning,
+                    |e| katakana_sense_pair(e, pos).map(|(_, sense)| first_gloss(sense)),
+                    rng,
+                );
+                (reading.text(), options, Some(sense))
+            }
+            Mode::Mixed => {
+                // Kanji modes are skipped for kana-only words and so on.
+                let mut modes = Mode::MIXABLE;
+                modes.shuffle(rng);
+                return modes
+                    .into_iter()
+                    .find_map(|mode| self.question(entry, mode, rng));
+            }
+        };
+
+        Some(Question {
+            prompt: prompt.to_string(),
+            options,
+            answer,
+            entry: entry.id(),
+            sense: sense.map(|s| s.number()),
+            mode,
+        })
+    }
+
+    /// Creates the shuffled options of a question about `entry` along with the index
+    /// of `answer` among them. Distractors are the `option`s of other entries that
+    /// `pairing` can be extracted from, picked by how they resemble `answer`.
+    fn options<R: Rng + ?Sized>(
+        &self,
+        entry: EntryRef<'_>,
+        pairing: Pairing,
+        answer: &str,
+        resemblance: Resemblance,
+        option: impl Fn(EntryRef<'d>) -> Option<&'d str>,
+        rng: &mut R,
+    ) -> ([String; OPTIONS], usize) {
+        let amount = OPTIONS - 1;
+        let candidates = self.dictionary.random_paired(
+            pairing,
+            self.pos,
+            entry.id(),
+            self.difficulty.candidates(amount),
+            rng,
+        );
+        let candidates = candidates
+            .into_iter()
+            .map(|e| option(e).expect("Indexed entry should have the pair"));
+        let distractors = distractor::pick(
+            answer,
+            candidates,
+            resemblance,
+            self.difficulty,
+            amount,
+            rng,
+        );
+
+        let mut options = std::array::from_fn(|_| "".to_string());
+        options[0] = answer.to_string();
+        for (option, distractor) in options[1..].iter_mut().zip(distractors) {
+            *option = distractor.to_string();
+        }
+
+        let answer = shuffle_options(&mut options, rng);
+
+        (options, answer)
+    }
+}
+
+/// Gets the first gloss of `sense`, which every sense of a pair has.
+fn first_gloss(sense: SenseRef<'_>) -> &str {
+    sense
+        .glosses()
+        .next()
+        .expect("Paired sense should have a gloss")
+}
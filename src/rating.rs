@@ -0,0 +1,187 @@
+use crate::distractor::Difficulty;
+
+/// Rating of a player who hasn't answered any questions yet.
+pub const INITIAL_RATING: f64 = 1200.0;
+
+/// Answers a player's rating moves faster for, so new players quickly reach
+/// a rating that fits them.
+pub const PROVISIONAL_ANSWERS: u32 = 20;
+
+/// Most a rating changes by in one answer.
+const K: f64 = 24.0;
+
+/// Most a [provisional](Rating::is_provisional) rating changes by in one answer.
+const PROVISIONAL_K: f64 = 48.0;
+
+/// Answers the [`question_rating`] of a question is worth in [`estimate_rating`].
+const PRIOR_ANSWERS: f64 = 10.0;
+
+/// How far a question's rating is from a player's when it [`fit`]s them
+/// half as well as a question of their rating.
+const FIT_SPREAD: f64 = 200.0;
+
+/// Gets the rating of a question, as if it were a player, from the difficulty
+/// in `stars` of the word it asks about (see [`DictEntry::difficulty`]) and how
+/// closely its wrong options resemble the answer.
+///
+/// Questions not made from a word, or about a word of no level, are rated
+/// [`INITIAL_RATING`].
+///
+/// [`DictEntry::difficulty`]: crate::dictionary::DictEntry::difficulty
+pub fn question_rating(stars: Option<u8>, distractors: Difficulty) -> f64 {
+    let Some(stars) = stars else {
+        return INITIAL_RATING;
+    };
+
+    let similarity = match distractors {
+        Difficulty::Easy => -100.0,
+        Difficulty::Normal => 0.0,
+        Difficulty::Hard => 100.0,
+    };
+
+    // One star is rated 900, five stars 1500.
+    750.0 + 150.0 * f64::from(stars.clamp(1, 5)) + similarity
+}
+
+/// Gets the chance of a player rated `player` answering a question rated
+/// `question` correctly.
+pub fn expected_score(player: f64, question: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((question - player) / 400.0))
+}
+
+/// Estimates the rating of a question from its `prior`, as rated by
+/// [`question_rating`], and how many of the `answers` players made to it were
+/// `correct`, as if they were all rated [`INITIAL_RATING`]. The fewer the answers,
+/// the closer the estimate stays to `prior`.
+pub fn estimate_rating(prior: f64, correct: u32, answers: u32) -> f64 {
+    let prior_correct = PRIOR_ANSWERS * expected_score(INITIAL_RATING, prior);
+    let accuracy =
+        (f64::from(correct.min(answers)) + prior_correct) / (f64::from(answers) + PRIOR_ANSWERS);
+
+    // The rating an average player answers correctly this often, the inverse
+    // of `expected_score`.
+    INITIAL_RATING + 400.0 * ((1.0 - accuracy) / accuracy).log10()
+}
+
+/// Gets how well a question rated `question` fits a player rated `player`, from
+/// 1 for a question of their rating down towards 0 the further it is from it.
+pub fn fit(player: f64, question: f64) -> f64 {
+    0.5f64.powf(((question - player) / FIT_SPREAD).powi(2))
+}
+
+/// Elo-like rating of a player, adjusted after every answer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    pub value: f64,
+    /// Number of answers the rating was adjusted for.
+    pub answers: u32,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            value: INITIAL_RATING,
+            answers: 0,
+        }
+    }
+}
+
+impl Rating {
+    /// Whether the rating is based on too few answers to be reliable.
+    pub fn is_provisional(&self) -> bool {
+        self.answers < PROVISIONAL_ANSWERS
+    }
+
+    /// Gets the rating after answering a question rated `question`.
+    pub fn answer(self, question: f64, correct: bool) -> Rating {
+        let k = if self.is_provisional() {
+            PROVISIONAL_K
+        } else {
+            K
+        };
+        let actual = if correct { 1.0 } else { 0.0 };
+
+        Rating {
+            value: self.value + k * (actual - expected_score(self.value, question)),
+            answers: self.answers + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settled(value: f64) -> Rating {
+        Rating {
+            value,
+            answers: PROVISIONAL_ANSWERS,
+        }
+    }
+
+    #[test]
+    fn expects_even_odds_against_an_equal_rating() {
+        assert_eq!(expected_score(1300.0, 1300.0), 0.5);
+        assert!(expected_score(1500.0, 1100.0) > 0.9);
+        assert!(
+            (expected_score(1500.0, 1100.0) + expected_score(1100.0, 1500.0) - 1.0).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn rates_harder_questions_higher() {
+        let normal = |stars| question_rating(Some(stars), Difficulty::Normal);
+
+        assert!(normal(1) < normal(3) && normal(3) < normal(5));
+        assert!(
+            question_rating(Some(3), Difficulty::Easy) < normal(3)
+                && normal(3) < question_rating(Some(3), Difficulty::Hard)
+        );
+        assert_eq!(question_rating(None, Difficulty::Hard), INITIAL_RATING);
+    }
+
+    #[test]
+    fn estimates_ratings_from_accuracy() {
+        assert!((estimate_rating(1350.0, 0, 0) - 1350.0).abs() < 1e-9);
+        assert!(estimate_rating(1350.0, 90, 100) < 1350.0);
+        assert!(estimate_rating(1350.0, 10, 100) > 1350.0);
+        assert!(estimate_rating(1350.0, 9, 10) > estimate_rating(1350.0, 90, 100));
+    }
+
+    #[test]
+    fn fits_questions_near_the_player() {
+        assert_eq!(fit(1200.0, 1200.0), 1.0);
+        assert!((fit(1200.0, 1200.0 + FIT_SPREAD) - 0.5).abs() < 1e-9);
+        assert_eq!(fit(1200.0, 1000.0), fit(1200.0, 1400.0));
+        assert!(fit(1200.0, 1300.0) > fit(1200.0, 1600.0));
+    }
+
+    #[test]
+    fn moves_towards_the_result() {
+        let rating = settled(1200.0);
+
+        assert!(rating.answer(1200.0, true).value > rating.value);
+        assert!(rating.answer(1200.0, false).value < rating.value);
+        assert_eq!(rating.answer(1200.0, true).answers, PROVISIONAL_ANSWERS + 1);
+    }
+
+    #[test]
+    fn rewards_upsets_more() {
+        let rating = settled(1200.0);
+        let gain = |question| rating.answer(question, true).value - rating.value;
+        let loss = |question| rating.value - rating.answer(question, false).value;
+
+        assert!(gain(1500.0) > gain(900.0));
+        assert!(loss(900.0) > loss(1500.0));
+    }
+
+    #[test]
+    fn settles_after_provisional_answers() {
+        let new = Rating::default();
+        assert!(new.is_provisional());
+        assert!(!settled(1200.0).is_provisional());
+
+        let change = |rating: Rating| rating.answer(1200.0, true).value - rating.value;
+        assert!(change(new) > change(settled(1200.0)));
+    }
+}
@@ -0,0 +1,112 @@
+use std::time::{Duration, SystemTime};
+
+use crate::dictionary::{DictEntry, NLevel};
+
+/// Points of a correct answer before any weighting.
+pub const BASE_POINTS: f64 = 100.0;
+
+/// Answers made within this long of a question being asked earn a speed bonus.
+pub const SPEED_WINDOW: Duration = Duration::from_secs(10);
+
+/// The most an answer's points are multiplied by for being fast.
+pub const MAX_SPEED_BONUS: f64 = 0.5;
+
+/// Gets the multiplier of the points of a question about a word of `level`.
+pub fn level_weight(level: NLevel) -> f64 {
+    match level {
+        NLevel::N5 => 0.75,
+        NLevel::N4 => 1.0,
+        NLevel::N3 => 1.25,
+        NLevel::N2 => 1.5,
+        NLevel::N1 => 2.0,
+    }
+}
+
+/// Gets the multiplier of the points of a question with a difficulty of `stars`.
+/// See [`DictEntry::difficulty`].
+pub fn difficulty_weight(stars: u8) -> f64 {
+    1.0 + 0.1 * f64::from(stars.saturating_sub(1))
+}
+
+/// Gets the multiplier of the points of an answer made `response_time` after
+/// the question was asked. Decreases linearly to none over [`SPEED_WINDOW`].
+pub fn speed_weight(response_time: Duration) -> f64 {
+    let remaining = 1.0 - (response_time.as_secs_f64() / SPEED_WINDOW.as_secs_f64()).min(1.0);
+
+    1.0 + MAX_SPEED_BONUS * remaining
+}
+
+/// Gets how long an answer took from when Discord says the question was posted,
+/// `asked_at`, to when it says the answer was made, `answered_at`, so the time
+/// the answer took to reach the bot, which varies with the player's connection
+/// and Discord's load, isn't counted against the player.
+///
+/// `received` is how long after the question was posted the answer reached the
+/// bot. Answers are never scored as slower than that, which is also used as is
+/// if Discord's times are out of order.
+pub fn compensated_response_time(
+    asked_at: SystemTime,
+    answered_at: SystemTime,
+    received: Duration,
+) -> Duration {
+    answered_at
+        .duration_since(asked_at)
+        .map_or(received, |taken| taken.min(received))
+}
+
+/// Scores an answer to a question about `entry` made `response_time` after the
+/// question was asked, so players of harder levels are ranked fairly against
+/// players of easier ones.
+///
+/// Incorrect answers are worth nothing. Questions not made from an entry
+/// are only weighted by speed.
+pub fn points(entry: Option<&DictEntry>, response_time: Duration, correct: bool) -> u32 {
+    if !correct {
+        return 0;
+    }
+
+    // Words of several levels are weighted by the easiest one.
+    let level = entry
+        .and_then(|e| e.levels().last().copied())
+        .map_or(1.0, level_weight);
+    let difficulty = entry
+        .and_then(|e| e.difficulty())
+        .map_or(1.0, difficulty_weight);
+
+    (BASE_POINTS * level * difficulty * speed_weight(response_time)).round() as u32
+}
+
+/// A handicap given to a player of a game, so players of different levels can
+/// compete, e.g. an advanced player against beginners.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Handicap {
+    /// The player plays as usual.
+    #[default]
+    None,
+    /// Correct answers made later than this after the question was asked count
+    /// for nothing.
+    Deadline(Duration),
+    /// The points of correct answers are multiplied by this.
+    Weight(f64),
+}
+
+impl Handicap {
+    /// Determines whether a correct answer made `response_time` after the
+    /// question was asked still counts.
+    pub fn counts(self, response_time: Duration) -> bool {
+        match self {
+            Handicap::Deadline(deadline) => response_time <= deadline,
+            Handicap::None | Handicap::Weight(_) => true,
+        }
+    }
+
+    /// Applies this handicap to the `points` of an answer made `response_time`
+    /// after the question was asked. See [`points`].
+    pub fn apply(self, points: u32, response_time: Duration) -> u32 {
+        match self {
+            _ if !self.counts(response_time) => 0,
+            Handicap::Weight(weight) => (f64::from(points) * weight).round() as u32,
+            Handicap::None | Handicap::Deadline(_) => points,
+        }
+    }
+}
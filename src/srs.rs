@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+/// How well a card was remembered when reviewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+    /// The card was forgotten.
+    Again,
+    /// The card was remembered.
+    Good,
+    /// The card was remembered without effort.
+    Easy,
+}
+
+/// Scheduling state of a card in a simplified SM-2 spaced repetition scheduler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Card {
+    /// Days until the card should be reviewed again. Zero if the card
+    /// should be reviewed again shortly.
+    pub interval: u32,
+    /// Factor the interval grows by on each successful review.
+    pub ease: f64,
+    /// Number of successful reviews in a row.
+    pub reps: u32,
+}
+
+impl Default for Card {
+    fn default() -> Self {
+        Card {
+            interval: 0,
+            ease: Card::INITIAL_EASE,
+            reps: 0,
+        }
+    }
+}
+
+impl Card {
+    const INITIAL_EASE: f64 = 2.5;
+    const MIN_EASE: f64 = 1.3;
+
+    /// How long until a forgotten card is due again.
+    const RELEARN_DELAY: Duration = Duration::from_secs(10 * 60);
+
+    /// Gets the state of the card after being reviewed with `grade`.
+    pub fn review(self, grade: Grade) -> Card {
+        match grade {
+            Grade::Again => Card {
+                interval: 0,
+                ease: (self.ease - 0.2).max(Card::MIN_EASE),
+                reps: 0,
+            },
+            Grade::Good => {
+                let reps = self.reps + 1;
+                let interval = match reps {
+                    1 => 1,
+                    2 => 3,
+                    _ => (f64::from(self.interval) * self.ease).round() as u32,
+                };
+
+                Card {
+                    interval,
+                    ease: self.ease,
+                    reps,
+                }
+            }
+            Grade::Easy => {
+                let reps = self.reps + 1;
+                let interval = match reps {
+                    1 => 4,
+                    _ => (f64::from(self.interval.max(1)) * self.ease * 1.3).round() as u32,
+                };
+
+                Card {
+                    interval,
+                    ease: self.ease + 0.15,
+                    reps,
+                }
+            }
+        }
+    }
+
+    /// Gets how long after a review the card is due again.
+    pub fn due_after(&self) -> Duration {
+        if self.interval == 0 {
+            Card::RELEARN_DELAY
+        } else {
+            Duration::from_secs(u64::from(self.interval) * 24 * 60 * 60)
+        }
+    }
+}
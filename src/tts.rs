@@ -0,0 +1,399 @@
+use std::{
+    fs::{self, File},
+    future::Future,
+    io,
+    path::PathBuf,
+    pin::Pin,
+    sync::Arc,
+    time::SystemTime,
+};
+
+use reqwest::{Client, header::CONTENT_TYPE};
+use serde_json::Value;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Speaker VOICEVOX reads prompts as if `VOICEVOX_SPEAKER` isn't set.
+const DEFAULT_SPEAKER: u32 = 1;
+
+/// Credit shown for VOICEVOX voices if `TTS_ATTRIBUTION` isn't set, as its terms require.
+const VOICEVOX_ATTRIBUTION: &str = "Voice: VOICEVOX";
+
+/// Speech synthesized from text, as an audio file.
+pub struct Speech {
+    pub bytes: Vec<u8>,
+    /// Extension of the file's format, e.g. `wav`, which Discord needs to play it.
+    pub extension: &'static str,
+}
+
+/// A text-to-speech service that reads the prompts of listening questions aloud.
+pub trait TtsBackend: Send + Sync {
+    /// Synthesizes `text`, in Japanese, into speech.
+    fn synthesize<'a>(&'a self, text: &'a str) -> BoxFuture<'a, Result<Speech, Error>>;
+
+    /// Identifies the voice speech is synthesized in, so speech of the same
+    /// text in different voices is cached apart.
+    fn voice(&self) -> String;
+
+    /// Credit to show along with synthesized speech, if the service needs one.
+    fn attribution(&self) -> Option<&str>;
+}
+
+/// Creates the text-to-speech service configured by the environment: the
+/// VOICEVOX engine at `VOICEVOX_URL` speaking as `VOICEVOX_SPEAKER`, or the
+/// default speaker if it isn't a number, or else the HTTP API at `TTS_URL`,
+/// where `{text}` is replaced by the text to read. Either is credited with
+/// `TTS_ATTRIBUTION` if it is set.
+///
+/// Returns [`None`] if neither is set, in which case listening modes can't be played.
+pub fn backend_from_env() -> Option<Arc<dyn TtsBackend>> {
+    let attribution = std::env::var("TTS_ATTRIBUTION").ok();
+
+    if let Ok(url) = std::env::var("VOICEVOX_URL") {
+        let speaker = std::env::var("VOICEVOX_SPEAKER")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SPEAKER);
+        let mut voicevox = Voicevox::new(url, speaker);
+        if let Some(attribution) = attribution {
+            voicevox.attribution = attribution;
+        }
+        return Some(Arc::new(voicevox));
+    }
+
+    std::env::var("TTS_URL").ok().map(|url| {
+        Arc::new(HttpTts {
+            attribution,
+            ..HttpTts::new(url)
+        }) as Arc<dyn TtsBackend>
+    })
+}
+
+/// Synthesizes `text` into speech with `tts`, reusing the speech in `cache`
+/// if it was synthesized before, and storing it there if it wasn't.
+pub async fn synthesize_cached(
+    tts: &dyn TtsBackend,
+    cache: Option<&AudioCache>,
+    text: &str,
+) -> Result<Speech, Error> {
+    let Some(cache) = cache else {
+        return tts.synthesize(text).await;
+    };
+
+    let key = AudioCache::key(&tts.voice(), text);
+    if let Some(speech) = cache.get(&key) {
+        return Ok(speech);
+    }
+
+    let speech = tts.synthesize(text).await?;
+    // Speech that can't be cached can still be played.
+    if let Err(e) = cache.insert(&key, &speech) {
+        eprintln!("Failed to cache speech of {text}: {e}");
+    }
+
+    Ok(speech)
+}
+
+/// A [VOICEVOX](https://voicevox.hiroshiba.jp) engine, e.g. run alongside the
+/// bot in Docker.
+pub struct Voicevox {
+    client: Client,
+    /// Base URL of the engine's API, e.g. `http://localhost:50021`.
+    url: String,
+    /// Id of the voice the engine speaks in.
+    speaker: u32,
+    attribution: String,
+}
+
+impl Voicevox {
+    pub fn new(url: String, speaker: u32) -> Self {
+        Voicevox {
+            client: Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+            speaker,
+            attribution: VOICEVOX_ATTRIBUTION.to_string(),
+        }
+    }
+}
+
+impl TtsBackend for Voicevox {
+    fn synthesize<'a>(&'a self, text: &'a str) -> BoxFuture<'a, Result<Speech, Error>> {
+        Box::pin(async move {
+            let speaker = self.speaker.to_string();
+
+            // The engine plans how to read the text, then reads it as planned.
+            let query: Value = self
+                .client
+                .post(format!("{}/audio_query", self.url))
+                .query(&[("text", text), ("speaker", &speaker)])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let bytes = self
+                .client
+                .post(format!("{}/synthesis", self.url))
+                .query(&[("speaker", &speaker)])
+                .json(&query)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+
+            Ok(Speech {
+                bytes: bytes.to_vec(),
+                extension: "wav",
+            })
+        })
+    }
+
+    fn voice(&self) -> String {
+        format!("voicevox:{}", self.speaker)
+    }
+
+    fn attribution(&self) -> Option<&str> {
+        Some(&self.attribution)
+    }
+}
+
+/// An HTTP API that responds to a GET request with the speech of the text in
+/// its URL, e.g. `https://tts.example.com/speak?lang=ja&text={text}`.
+pub struct HttpTts {
+    client: Client,
+    /// URL of the API, with `{text}` where the text to read goes.
+    url: String,
+    attribution: Option<String>,
+}
+
+impl HttpTts {
+    pub fn new(url: String) -> Self {
+        HttpTts {
+            client: Client::new(),
+            url,
+            attribution: None,
+        }
+    }
+}
+
+impl TtsBackend for HttpTts {
+    fn synthesize<'a>(&'a self, text: &'a str) -> BoxFuture<'a, Result<Speech, Error>> {
+        Box::pin(async move {
+            let url = self.url.replace("{text}", &urlencoding::encode(text));
+            let response = self.client.get(url).send().await?.error_for_status()?;
+
+            let extension = match response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+            {
+                Some(t) if t.starts_with("audio/ogg") || t.starts_with("audio/opus") => "ogg",
+                Some(t) if t.starts_with("audio/mpeg") => "mp3",
+                _ => "wav",
+            };
+
+            Ok(Speech {
+                bytes: response.bytes().await?.to_vec(),
+                extension,
+            })
+        })
+    }
+
+    fn voice(&self) -> String {
+        self.url.clone()
+    }
+
+    fn attribution(&self) -> Option<&str> {
+        self.attribution.as_deref()
+    }
+}
+
+/// Speech stored on disk under a hash of its voice and text, so the same
+/// text isn't synthesized twice. Once the files take up more than the size
+/// limit, the least recently used are removed.
+pub struct AudioCache {
+    dir: PathBuf,
+    /// Most bytes the files can take up.
+    max_size: u64,
+}
+
+impl AudioCache {
+    /// Extensions of the formats speech is cached in. See [`Speech::extension`].
+    const EXTENSIONS: [&str; 3] = ["wav", "ogg", "mp3"];
+
+    /// Size limit of the cache in MiB if `AUDIO_CACHE_MB` isn't set.
+    const DEFAULT_SIZE_MB: u64 = 256;
+
+    /// Opens the cache in `dir`, creating it if needed.
+    ///
+    /// # Errors
+    /// Fails if `dir` can't be created.
+    pub fn new(dir: impl Into<PathBuf>, max_size: u64) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(AudioCache { dir, max_size })
+    }
+
+    /// Opens the cache in the directory at the `AUDIO_CACHE_PATH` env var,
+    /// `content/audio_cache` by default, limited to `AUDIO_CACHE_MB` MiB, or
+    /// the default size if it isn't a number.
+    ///
+    /// Returns [`None`] if the limit is 0 or the directory can't be created.
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::var("AUDIO_CACHE_PATH").unwrap_or("content/audio_cache".to_string());
+        let size_mb = std::env::var("AUDIO_CACHE_MB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(Self::DEFAULT_SIZE_MB);
+        if size_mb == 0 {
+            return None;
+        }
+
+        AudioCache::new(&dir, size_mb * 1024 * 1024)
+            .inspect_err(|e| eprintln!("Failed to open audio cache at {dir}: {e}"))
+            .ok()
+    }
+
+    /// Gets the key speech of `text` in `voice` is cached under: a 64-bit
+    /// FNV-1a hash of both in hex, which doesn't change between builds.
+    pub fn key(voice: &str, text: &str) -> String {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        // The separator keeps e.g. ("ab", "c") and ("a", "bc") apart.
+        let hash = voice
+            .bytes()
+            .chain([0])
+            .chain(text.bytes())
+            .fold(OFFSET, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+            });
+
+        format!("{hash:016x}")
+    }
+
+    /// Gets the speech cached under `key`, marking it as just used.
+    pub fn get(&self, key: &str) -> Option<Speech> {
+        Self::EXTENSIONS.into_iter().find_map(|extension| {
+            let path = self.dir.join(format!("{key}.{extension}"));
+            let bytes = fs::read(&path).ok()?;
+            if let Ok(file) = File::options().write(true).open(&path) {
+                file.set_modified(SystemTime::now()).ok();
+            }
+
+            Some(Speech { bytes, extension })
+        })
+    }
+
+    /// Caches `speech` under `key`, then removes the least recently used
+    /// files until the cache fits in its size limit.
+    ///
+    /// # Errors
+    /// Fails if the file can't be written or the cache can't be listed.
+    pub fn insert(&self, key: &str, speech: &Speech) -> io::Result<()> {
+        // Written to a temporary file first so a partial file is never read.
+        let path = self.dir.join(format!("{key}.{}", speech.extension));
+        let partial = path.with_extension("part");
+        fs::write(&partial, &speech.bytes)?;
+        fs::rename(&partial, &path)?;
+
+        self.evict()
+    }
+
+    /// Removes the least recently used files until the cache fits in its size limit.
+    fn evict(&self) -> io::Result<()> {
+        let mut files = Vec::new();
+        for file in fs::read_dir(&self.dir)? {
+            let file = file?;
+            let metadata = file.metadata()?;
+            if metadata.is_file() {
+                files.push((metadata.modified()?, metadata.len(), file.path()));
+            }
+        }
+
+        let mut size: u64 = files.iter().map(|(_, len, _)| len).sum();
+        files.sort();
+        for (_, len, path) in files {
+            if size <= self.max_size {
+                break;
+            }
+            fs::remove_file(path)?;
+            size -= len;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Opens a cache of `max_size` bytes in an empty directory named `name`.
+    fn cache(name: &str, max_size: u64) -> AudioCache {
+        let dir = std::env::temp_dir().join(format!("jplearnbot-{name}-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+
+        AudioCache::new(dir, max_size).unwrap()
+    }
+
+    fn speech(len: usize) -> Speech {
+        Speech {
+            bytes: vec![0; len],
+            extension: "wav",
+        }
+    }
+
+    #[test]
+    fn keys_by_voice_and_text() {
+        assert_eq!(
+            AudioCache::key("voicevox:1", "ねこ"),
+            AudioCache::key("voicevox:1", "ねこ")
+        );
+        assert_ne!(
+            AudioCache::key("voicevox:1", "ねこ"),
+            AudioCache::key("voicevox:2", "ねこ")
+        );
+        assert_ne!(AudioCache::key("ab", "c"), AudioCache::key("a", "bc"));
+        assert_eq!(AudioCache::key("", "").len(), 16);
+    }
+
+    #[test]
+    fn gets_inserted_speech() {
+        let cache = cache("get", 1024);
+
+        assert!(cache.get("ねこ").is_none());
+        cache.insert("ねこ", &speech(10)).unwrap();
+
+        let cached = cache.get("ねこ").unwrap();
+        assert_eq!(cached.bytes.len(), 10);
+        assert_eq!(cached.extension, "wav");
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = cache("evict", 25);
+
+        cache.insert("old", &speech(10)).unwrap();
+        cache.insert("used", &speech(10)).unwrap();
+        // Make the order of use unambiguous even on coarse file times.
+        for (key, age) in [("old", 20), ("used", 10)] {
+            File::options()
+                .write(true)
+                .open(cache.dir.join(format!("{key}.wav")))
+                .unwrap()
+                .set_modified(SystemTime::now() - Duration::from_secs(age))
+                .unwrap();
+        }
+        cache.insert("new", &speech(10)).unwrap();
+
+        assert!(cache.get("old").is_none());
+        assert!(cache.get("used").is_some());
+        assert!(cache.get("new").is_some());
+    }
+}
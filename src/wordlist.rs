@@ -0,0 +1,395 @@
+//! Imports study lists of words exported from spreadsheets as CSV, or from Anki
+//! as notes in plain text, and matches their rows to the entries of a
+//! [`Dictionary`].
+
+use std::{cmp::Reverse, collections::HashSet};
+
+use crate::{
+    dictionary::{Dictionary, EntryRef},
+    kana::{self, Script},
+};
+
+/// A row of an imported list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The line of the list the row starts at, starting at 1.
+    pub line: usize,
+    /// The fields of the row, without quotes or HTML.
+    pub fields: Vec<String>,
+}
+
+/// The entries a list was matched to.
+#[derive(Debug, Default)]
+pub struct Import {
+    /// Ids of the entries matched, in the order of the list, once each.
+    pub entries: Vec<u32>,
+    /// The rows that didn't match any entry.
+    pub unmatched: Vec<Row>,
+}
+
+/// Parses `text`, a CSV file or an Anki export of notes in plain text, into
+/// its rows. Fields are separated by what Anki's `#separator:` header names,
+/// or by tabs if the first row has any, or else by commas. They may be quoted,
+/// with `""` for a quote inside. Blank lines, lines starting with `#`, and the
+/// guid, notetype, deck, and tags columns Anki's headers name are left out.
+/// HTML tags are removed from fields, e.g. of Anki's `#html:true` exports.
+pub fn parse(text: &str) -> Vec<Row> {
+    let text = text.trim_start_matches('\u{FEFF}');
+
+    let mut separator = None;
+    let mut skipped = HashSet::new();
+    for header in text.lines().map_while(|line| line.strip_prefix('#')) {
+        let Some((key, value)) = header.split_once(':') else {
+            continue;
+        };
+        match key {
+            "separator" => separator = separator_named(value),
+            "guid column" | "notetype column" | "deck column" | "tags column" => {
+                if let Ok(column) = value.trim().parse::<usize>() {
+                    skipped.insert(column.saturating_sub(1));
+                }
+            }
+            _ => {}
+        }
+    }
+    let separator = separator.unwrap_or_else(|| {
+        let first = text.lines().find(|line| !line.starts_with('#'));
+        if first.is_some_and(|line| line.contains('\t')) {
+            '\t'
+        } else {
+            ','
+        }
+    });
+
+    let mut rows = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start = line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+        let comment = chars.peek() == Some(&'#');
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' if quoted => quoted = false,
+                '"' if field.trim().is_empty() => {
+                    field.clear();
+                    quoted = true;
+                }
+                '\n' if !quoted => {
+                    line += 1;
+                    break;
+                }
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                '\r' if !quoted => {}
+                c if c == separator && !quoted => fields.push(std::mem::take(&mut field)),
+                c => field.push(c),
+            }
+        }
+        fields.push(field);
+
+        let fields: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !skipped.contains(i))
+            .map(|(_, field)| strip_html(field))
+            .collect();
+        if !comment && fields.iter().any(|field| !field.is_empty()) {
+            rows.push(Row {
+                line: start,
+                fields,
+            });
+        }
+    }
+
+    rows
+}
+
+/// Parses `text` as in [`parse`], and matches its rows to the entries of
+/// `dictionary` as in [`match_row`].
+pub fn import(dictionary: &Dictionary, text: &str) -> Import {
+    let mut import = Import::default();
+    let mut seen = HashSet::new();
+
+    for row in parse(text) {
+        match match_row(dictionary, &row.fields) {
+            Some(entry) => {
+                if seen.insert(entry.id()) {
+                    import.entries.push(entry.id());
+                }
+            }
+            None => import.unmatched.push(row),
+        }
+    }
+
+    import
+}
+
+/// Finds the entry of `dictionary` a row of a list with the `fields` is about:
+/// the one whose id is its first field, e.g. as the bot shows with answers, or
+/// else one spelled or read as its first field written in Japanese. Entries
+/// read as its other Japanese fields or furigana, e.g. 漢字\[かんじ\], are
+/// preferred, then ones with a gloss among its other fields. If no entry is
+/// spelled exactly so, the field is looked up without parentheses, tildes, or a
+/// trailing する, e.g. 勉強（する）.
+///
+/// Returns [`None`] if no field is an id or written in Japanese, or no entry
+/// matches.
+pub fn match_row<'d>(dictionary: &'d Dictionary, fields: &[String]) -> Option<EntryRef<'d>> {
+    if let Some(entry) = fields
+        .first()
+        .and_then(|field| field.trim().parse().ok())
+        .and_then(|id| dictionary.entry(id))
+    {
+        return Some(entry);
+    }
+
+    let mut words = Vec::new();
+    let mut readings = Vec::new();
+    let mut glosses = Vec::new();
+    for field in fields {
+        if field.chars().any(|c| Script::of(c).is_some()) {
+            let (word, reading) = split_furigana(field);
+            readings.extend(reading.map(|r| kana::to_hiragana(&r)));
+            readings.push(kana::to_hiragana(&word));
+            words.push(word);
+        } else {
+            glosses.extend(
+                field
+                    .split([',', ';', '/'])
+                    .map(|gloss| gloss.trim().trim_start_matches("to ").to_lowercase())
+                    .filter(|gloss| !gloss.is_empty()),
+            );
+        }
+    }
+
+    let word = words.first()?;
+    let candidates = variants(word)
+        .into_iter()
+        .map(|word| spelled_as(dictionary, &word))
+        .find(|candidates| !candidates.is_empty())?;
+
+    let score = |entry: EntryRef<'_>| {
+        let read = entry
+            .readings()
+            .any(|r| readings.contains(&kana::to_hiragana(r.text())));
+        let glossed = entry.senses().flat_map(|s| s.glosses()).any(|g| {
+            let g = g.trim_start_matches("to ").to_lowercase();
+            glosses.contains(&g)
+        });
+        u8::from(read) * 2 + u8::from(glossed)
+    };
+
+    // The first of the best candidates, which are in the order of the index.
+    candidates
+        .into_iter()
+        .enumerate()
+        .max_by_key(|&(i, entry)| (score(entry), Reverse(i)))
+        .map(|(_, entry)| entry)
+}
+
+/// Finds the entries of `dictionary` spelled or read exactly as `word`,
+/// romaji and katakana also looked up as hiragana.
+fn spelled_as<'d>(dictionary: &'d Dictionary, word: &str) -> Vec<EntryRef<'d>> {
+    let mut seen = HashSet::new();
+    dictionary
+        .by_kanji(word)
+        .chain(dictionary.by_reading(word))
+        .chain(dictionary.by_reading(&kana::to_hiragana(word)))
+        .filter(|entry| seen.insert(entry.id()))
+        .collect()
+}
+
+/// Gets the spellings `word` is looked up as, the closest first: as is, then
+/// without parentheses, tildes, and spaces, then also without a trailing する.
+fn variants(word: &str) -> Vec<String> {
+    let mut stripped = String::new();
+    let mut depth = 0usize;
+    for c in word.chars() {
+        match c {
+            '(' | '（' => depth += 1,
+            ')' | '）' => depth = depth.saturating_sub(1),
+            '〜' | '～' | '~' => {}
+            c if c.is_whitespace() => {}
+            c if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    let mut variants = vec![word.to_string()];
+    if stripped != word {
+        variants.push(stripped.clone());
+    }
+    if let Some(stem) = stripped.strip_suffix("する").filter(|s| !s.is_empty()) {
+        variants.push(stem.to_string());
+    }
+
+    variants
+}
+
+/// Splits `field` written with Anki's furigana, e.g. `食[た]べ 物[もの]`, into
+/// the word, e.g. 食べ物, and its reading, e.g. たべもの, if it has furigana.
+/// The reading replaces the kanji before each bracket.
+fn split_furigana(field: &str) -> (String, Option<String>) {
+    if !field.contains('[') {
+        return (field.trim().to_string(), None);
+    }
+
+    let mut word = String::new();
+    let mut reading = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                while reading
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| Script::of(c) == Some(Script::Kanji))
+                {
+                    reading.pop();
+                }
+                reading.extend(chars.by_ref().take_while(|&c| c != ']'));
+            }
+            // Spaces separate the words of Anki's furigana from the kana before them.
+            ' ' => {}
+            c => {
+                word.push(c);
+                reading.push(c);
+            }
+        }
+    }
+
+    (word, Some(reading))
+}
+
+/// Gets the separator named `name` in Anki's `#separator:` header.
+fn separator_named(name: &str) -> Option<char> {
+    match name.trim().to_lowercase().as_str() {
+        "tab" => Some('\t'),
+        "comma" => Some(','),
+        "semicolon" => Some(';'),
+        "pipe" => Some('|'),
+        "space" => Some(' '),
+        "colon" => Some(':'),
+        other => {
+            let mut chars = other.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        }
+    }
+}
+
+/// Removes the HTML tags of `field` and decodes its common entities, and trims it.
+fn strip_html(field: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in field.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(rows: &[Row]) -> Vec<Vec<&str>> {
+        rows.iter()
+            .map(|row| row.fields.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_quoted_csv() {
+        let rows = parse("word,meaning\n\"食べる\",\"to eat, to \"\"consume\"\"\"\r\n\n犬,dog\n");
+
+        assert_eq!(
+            fields(&rows),
+            [
+                vec!["word", "meaning"],
+                vec!["食べる", "to eat, to \"consume\""],
+                vec!["犬", "dog"],
+            ]
+        );
+        assert_eq!(rows.iter().map(|r| r.line).collect::<Vec<_>>(), [1, 2, 4]);
+    }
+
+    #[test]
+    fn parses_anki_exports() {
+        let export = "#separator:tab\n#html:true\n#guid column:1\n\
+                      abc123\t<b>猫</b>\tねこ\tcat&nbsp;\n\
+                      def456\t食[た]べ 物[もの]\t\tfood\n";
+
+        assert_eq!(
+            fields(&parse(export)),
+            [
+                vec!["猫", "ねこ", "cat"],
+                vec!["食[た]べ 物[もの]", "", "food"]
+            ]
+        );
+    }
+
+    #[test]
+    fn guesses_tabs_without_a_header() {
+        assert_eq!(fields(&parse("犬\tdog, hound")), [vec!["犬", "dog, hound"]]);
+    }
+
+    #[test]
+    fn splits_furigana() {
+        assert_eq!(
+            split_furigana("食[た]べ 物[もの]"),
+            ("食べ物".to_string(), Some("たべもの".to_string()))
+        );
+        assert_eq!(
+            split_furigana(" 漢字[かんじ]"),
+            ("漢字".to_string(), Some("かんじ".to_string()))
+        );
+        assert_eq!(split_furigana("ねこ"), ("ねこ".to_string(), None));
+    }
+
+    #[test]
+    fn matches_entry_ids() {
+        let dictionary = Dictionary::from_bytes(
+            r#"{"ent_seq":1467640,"r_ele":[{"reb":"ねこ"}],"sense":[{"gloss":[{"content":"cat"}]}]}"#
+                .as_bytes(),
+        )
+        .unwrap();
+        let id = |fields: &[&str]| {
+            let fields: Vec<_> = fields.iter().map(|f| f.to_string()).collect();
+            match_row(&dictionary, &fields).map(|entry| entry.id())
+        };
+
+        assert_eq!(id(&["1467640"]), Some(1467640));
+        assert_eq!(id(&[" 1467640 ", "dog"]), Some(1467640));
+        // Row numbers aren't ids of any entry.
+        assert_eq!(id(&["1", "ねこ"]), Some(1467640));
+        assert_eq!(id(&["2"]), None);
+    }
+
+    #[test]
+    fn loosens_spellings() {
+        assert_eq!(variants("勉強（する）"), ["勉強（する）", "勉強"]);
+        assert_eq!(variants("〜勉強する"), ["〜勉強する", "勉強する", "勉強"]);
+        assert_eq!(variants("犬"), ["犬"]);
+    }
+}